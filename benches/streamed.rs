@@ -0,0 +1,46 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rocket::{get, local::blocking::Client, routes, tokio::fs::File};
+use rocket_response::{RocketResponse, StreamedFile};
+use std::{io, path::PathBuf};
+
+/// File of 8 MiB in the temporary directory
+fn path() -> PathBuf {
+    std::env::temp_dir().join("rocket-response-bench-streamed.bin")
+}
+
+#[get("/tokio_file")]
+async fn tokio_file() -> io::Result<RocketResponse> {
+    Ok(RocketResponse::TokioFile(Box::new(
+        File::open(path()).await?,
+    )))
+}
+
+#[get("/streamed/<chunk_size>")]
+async fn streamed(chunk_size: usize) -> io::Result<RocketResponse> {
+    let file = StreamedFile::open(path()).await?.chunk_size(chunk_size);
+    Ok(RocketResponse::StreamedFile(Box::new(file)))
+}
+
+fn bench_streamed(c: &mut Criterion) {
+    std::fs::write(path(), vec![0x5a; 8 * 1024 * 1024]).expect("no bench file");
+    let rocket = rocket::build().mount("/", routes![tokio_file, streamed]);
+    let client = Client::untracked(rocket).expect("no rocket instance");
+
+    let mut group = c.benchmark_group("file_8mib");
+    group.sample_size(20);
+    group.bench_function("tokio_file", |b| {
+        b.iter(|| black_box(client.get("/tokio_file").dispatch().into_bytes()))
+    });
+    for chunk_size in [4096, 64 * 1024, 1024 * 1024].iter() {
+        let uri = format!("/streamed/{}", chunk_size);
+        group.bench_function(format!("streamed/{}", chunk_size), |b| {
+            b.iter(|| black_box(client.get(uri.clone()).dispatch().into_bytes()))
+        });
+    }
+    group.finish();
+
+    let _ = std::fs::remove_file(path());
+}
+
+criterion_group!(benches, bench_streamed);
+criterion_main!(benches);
@@ -0,0 +1,109 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rocket::{
+    http::Header,
+    local::blocking::Client,
+    request::Request,
+    response::{self, Responder, Response},
+};
+use rocket_response::{
+    CachePolicy, Cached, CorsPolicy, Cost, ETag, Localized, QuotaHeaders, QuotaTracker, WithCache,
+    WithCors, WithCost,
+};
+use std::time::{Duration, UNIX_EPOCH};
+
+/// Inner responder already carrying all headers the wrappers would add
+struct Prepared;
+
+impl<'r> Responder<'r, 'static> for Prepared {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        Response::build_from("Hello world".respond_to(req)?)
+            .raw_header("Cache-Control", "no-store")
+            .raw_header("ETag", "\"v0\"")
+            .raw_header("Last-Modified", "Sun, 09 Sep 2001 01:46:40 GMT")
+            .raw_header("X-Request-Cost", "1")
+            .raw_header("X-Quota-Limit", "1000")
+            .raw_header("Content-Language", "en")
+            .raw_header("Access-Control-Allow-Origin", "https://app.example.com")
+            .ok()
+    }
+}
+
+fn policy() -> CachePolicy {
+    CachePolicy::new()
+        .public()
+        .max_age(Duration::from_secs(3600))
+        .stale_while_revalidate(Duration::from_secs(60))
+}
+
+fn cached<R>(responder: R) -> Cached<R> {
+    Cached::new(responder)
+        .etag(ETag::strong("v1"))
+        .last_modified(UNIX_EPOCH + Duration::from_secs(1_000_000_000))
+}
+
+fn units(cost: &Cost) -> u64 {
+    1 + cost.body_size.unwrap_or(0) as u64 / 1024
+}
+
+fn cors() -> CorsPolicy {
+    CorsPolicy::new().allow_origin("https://app.example.com")
+}
+
+fn bench_wrappers(c: &mut Criterion) {
+    let rocket = rocket::build().manage(QuotaTracker::new(u64::MAX, Duration::from_secs(3600)));
+    let client = Client::untracked(rocket).expect("no rocket instance");
+    let local = client
+        .get("/")
+        .header(Header::new("Origin", "https://app.example.com"))
+        .header(Header::new("Accept-Language", "de, en;q=0.5"));
+    let req = local.inner();
+
+    c.bench_function("prepared", |b| {
+        b.iter(|| black_box(Prepared.respond_to(req)))
+    });
+
+    c.bench_function("with_cache/set", |b| {
+        b.iter(|| black_box(WithCache::new("Hello world", policy()).respond_to(req)))
+    });
+    c.bench_function("with_cache/present", |b| {
+        b.iter(|| black_box(WithCache::new(Prepared, policy()).respond_to(req)))
+    });
+
+    c.bench_function("cached/set", |b| {
+        b.iter(|| black_box(cached("Hello world").respond_to(req)))
+    });
+    c.bench_function("cached/replace", |b| {
+        b.iter(|| black_box(cached(Prepared).respond_to(req)))
+    });
+
+    c.bench_function("with_cost/set", |b| {
+        b.iter(|| black_box(WithCost::new("Hello world", units).respond_to(req)))
+    });
+    c.bench_function("with_cost/present", |b| {
+        b.iter(|| black_box(WithCost::new(Prepared, units).respond_to(req)))
+    });
+
+    c.bench_function("quota_headers/set", |b| {
+        b.iter(|| black_box(QuotaHeaders::new("Hello world", "key").respond_to(req)))
+    });
+    c.bench_function("quota_headers/present", |b| {
+        b.iter(|| black_box(QuotaHeaders::new(Prepared, "key").respond_to(req)))
+    });
+
+    c.bench_function("localized/set", |b| {
+        b.iter(|| black_box(Localized::new("en", "Hello world").respond_to(req)))
+    });
+    c.bench_function("localized/present", |b| {
+        b.iter(|| black_box(Localized::new("en", Prepared).respond_to(req)))
+    });
+
+    c.bench_function("with_cors/set", |b| {
+        b.iter(|| black_box(WithCors::new("Hello world", cors()).respond_to(req)))
+    });
+    c.bench_function("with_cors/present", |b| {
+        b.iter(|| black_box(WithCors::new(Prepared, cors()).respond_to(req)))
+    });
+}
+
+criterion_group!(benches, bench_wrappers);
+criterion_main!(benches);
@@ -0,0 +1,208 @@
+//! Application combining the responders of the crate, a living documentation of their
+//! use together
+//!
+//! ```sh
+//! cargo run --example full_app --features json,msgpack,templates-tera,compress
+//! ```
+//!
+//! The integration test `tests/full_app.rs` requests every route of it.
+
+use rocket::{
+    get,
+    http::ContentType,
+    post, routes,
+    serde::{msgpack::MsgPack, Serialize},
+    Build, Request, Response, Rocket, State,
+};
+use rocket_dyn_templates::Template;
+use rocket_response::{
+    prelude::*, CacheEntry, Compressed, DeltaCollection, DeltaSnapshots, HealthCheck, HealthReport,
+    Maintenance, Negotiated, Paginated, ResponseCache,
+};
+use std::time::Duration;
+
+/// Note of the application
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct Note {
+    /// Id of the note
+    pub id: u64,
+    /// Text of the note
+    pub text: String,
+}
+
+/// Response of the routes serializing notes, the content is text
+pub type NotesResponse = RocketResponseGeneric3<String, Vec<Note>, ()>;
+
+/// Notes of the application
+pub fn notes() -> Vec<Note> {
+    (1..=25)
+        .map(|id| Note {
+            id,
+            text: format!("Note number {}", id),
+        })
+        .collect()
+}
+
+/// Start page
+#[get("/")]
+fn index() -> RocketResponse {
+    html!("<h1>Notes</h1>")
+}
+
+/// Static text with security headers, cache policy and entity tag
+#[get("/hello")]
+fn hello() -> RocketResponse {
+    let cached = Cached::new(RocketResponse::StaticStr("Hello world")).etag(ETag::strong("v1"));
+    let with_cache = WithCache::new(
+        RocketResponse::Cached(cached),
+        CachePolicy::new().public().max_age(Duration::from_secs(60)),
+    );
+    RocketResponse::Hardened(Box::new(Hardened::new(RocketResponse::WithCache(
+        with_cache,
+    ))))
+}
+
+/// All notes as JSON
+#[get("/notes")]
+fn notes_json() -> NotesResponse {
+    json_ok!(notes())
+}
+
+/// All notes as MessagePack
+#[get("/notes.msgpack")]
+fn notes_msgpack() -> NotesResponse {
+    RocketResponseGeneric3::SerdeMsgPack(MsgPack(notes()))
+}
+
+/// All notes in the format of the `Accept` header
+#[get("/notes/negotiated")]
+fn notes_negotiated() -> Negotiated<Vec<Note>> {
+    Negotiated(notes())
+}
+
+/// Note rendered by a template, or a message of the missing note
+#[get("/notes/<id>")]
+fn note(id: u64) -> RocketResponse {
+    match notes().into_iter().find(|note| note.id == id) {
+        Some(note) => RocketResponse::template("note", note),
+        None => not_found!("no such note"),
+    }
+}
+
+/// Notes page by page
+#[get("/export?<page>")]
+fn export(page: Option<u64>) -> Paginated<Vec<Note>> {
+    let page = page.unwrap_or(1).max(1);
+    let notes = notes().into_iter().skip((page as usize - 1) * 10).take(10);
+    Paginated::new(notes.collect())
+        .page(page)
+        .per_page(10)
+        .total(25)
+}
+
+/// Notes changed since the `ETag` of the client
+#[get("/sync")]
+fn sync() -> DeltaCollection<Note> {
+    DeltaCollection::new(notes(), |note| note.id)
+}
+
+/// Large text compressed by the `Accept-Encoding` of the client
+#[get("/report")]
+fn report() -> RocketResponse {
+    let report: String = notes()
+        .iter()
+        .map(|note| format!("{}\n", note.text))
+        .collect();
+    RocketResponse::Compressed(Box::new(
+        Compressed::new(RocketResponse::String(report.repeat(10))).min_size(1024),
+    ))
+}
+
+/// Response stored in the response cache
+#[get("/cached")]
+async fn cached(cache: &State<ResponseCache>) -> RocketResponse {
+    if let Some(entry) = cache.get("/cached").await {
+        return RocketResponse::CacheEntry(Box::new(entry));
+    }
+    let entry = CacheEntry::new(ContentType::Plain, "rendered once");
+    cache.insert("/cached", entry.clone()).await;
+    RocketResponse::CacheEntry(Box::new(entry))
+}
+
+/// Binary body with an explicit content type
+#[get("/download")]
+fn download() -> RocketResponse {
+    RocketResponse::typed(ContentType::Binary, vec![0u8, 1, 2, 3])
+}
+
+/// Moved page
+#[get("/old")]
+fn old() -> RocketResponse {
+    redirect!("/")
+}
+
+/// Request rejected by an error variant
+#[get("/fail")]
+fn fail() -> RocketResponse {
+    RocketResponse::RocketError(RocketError::BadRequest(Some("invalid request".to_string())))
+}
+
+/// Response modified by a hook
+#[get("/hooked")]
+fn hooked() -> RocketResponse {
+    text!("hooked").hook(|_req: &Request<'_>, res: &mut Response<'_>| {
+        res.set_raw_header("X-Hooked", "yes");
+    })
+}
+
+/// Health of the application and of the response layer
+#[get("/health")]
+fn health() -> RocketResponse {
+    let notes = HealthCheck::up().detail("count", notes().len());
+    RocketResponse::HealthReport(Box::new(HealthReport::new().check("notes", notes)))
+}
+
+/// Drains the instance before a restart
+#[post("/drain")]
+fn drain(maintenance: &State<Maintenance>) -> RocketResponse {
+    maintenance.drain();
+    RocketResponse::Status(Status::Accepted)
+}
+
+/// The application
+pub fn app() -> Rocket<Build> {
+    let templates = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/templates");
+    let figment = rocket::Config::figment().merge(("template_dir", templates));
+    rocket::custom(figment)
+        .attach(Template::fairing())
+        .attach(Maintenance::new().restart_window(Duration::from_secs(30)))
+        .manage(ResponseCache::new())
+        .manage(DeltaSnapshots::new(100))
+        .mount(
+            "/",
+            routes![
+                index,
+                hello,
+                notes_json,
+                notes_msgpack,
+                notes_negotiated,
+                note,
+                export,
+                sync,
+                report,
+                cached,
+                download,
+                old,
+                fail,
+                hooked,
+                health,
+                drain
+            ],
+        )
+}
+
+#[rocket::launch]
+fn rocket() -> _ {
+    app()
+}
@@ -0,0 +1,15 @@
+//! Mock server answering with the `ResponseSpec` files of a directory
+//!
+//! ```sh
+//! cargo run --example mock_server --features fixtures -- path/to/fixtures
+//! ```
+
+use rocket_response::MockServer;
+
+#[rocket::launch]
+fn rocket() -> _ {
+    let dir = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "fixtures".to_string());
+    MockServer::new(dir).rocket().expect("invalid fixtures")
+}
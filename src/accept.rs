@@ -0,0 +1,245 @@
+use rocket::{http::MediaType, request::Request};
+
+/// Media range of an `Accept` header with its quality value
+///
+/// `*/*` matches every media type, `text/*` every `text` type, `text/html` the type with
+/// any parameters and `text/html;level=1` only the type with these parameters. Types and
+/// parameter names are compared case-insensitively.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MediaRange {
+    media_type: MediaType,
+    q: f32,
+}
+
+impl MediaRange {
+    /// Media range without the quality value
+    pub fn media_type(&self) -> &MediaType {
+        &self.media_type
+    }
+
+    /// Quality value from `0` to `1`, `1` if not given
+    pub fn q(&self) -> f32 {
+        self.q
+    }
+
+    /// `true` if the range matches `media_type`
+    pub fn matches(&self, media_type: &MediaType) -> bool {
+        let range = &self.media_type;
+        let wildcard = range.top() == "*";
+        (wildcard || range.top() == media_type.top())
+            && (range.sub() == "*" || (!wildcard && range.sub() == media_type.sub()))
+            && range.params().all(|(name, value)| {
+                media_type
+                    .params()
+                    .any(|(n, v)| n == name && v.eq_ignore_ascii_case(value))
+            })
+    }
+
+    /// Precedence of a more specific range over a less specific one matching the same type
+    fn specificity(&self) -> usize {
+        match (self.media_type.top() == "*", self.media_type.sub() == "*") {
+            (true, _) => 0,
+            (false, true) => 1,
+            (false, false) => 2 + self.media_type.params().count(),
+        }
+    }
+}
+
+/// Media ranges of the `Accept` header weighted by their quality values
+///
+/// Rocket's [Accept](rocket::http::Accept) provides the most preferred media type, but not
+/// the rules needed to choose among the types a response is available in: the quality
+/// value of a type is the one of the most specific matching range, see
+/// [RFC 9110](https://www.rfc-editor.org/rfc/rfc9110#name-accept), so `text/*;q=0.5,
+/// text/html` prefers HTML over plain text and `*/*, image/png;q=0` excludes PNG. Ranges
+/// with an invalid quality value are ignored. Without any range, like without an `Accept`
+/// header, every type is acceptable.
+///
+/// The negotiation of [Negotiated](crate::Negotiated), [ErrorPages](crate::ErrorPages) and
+/// [RocketError](crate::RocketError) is based on it.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::http::MediaType;
+/// use rocket_response::AcceptList;
+///
+/// let accept = AcceptList::parse("text/*;q=0.5, text/html, */*;q=0.1, image/png;q=0");
+/// assert_eq!(1.0, accept.quality(&MediaType::HTML));
+/// assert_eq!(0.5, accept.quality(&MediaType::Plain));
+/// assert_eq!(0.0, accept.quality(&MediaType::PNG));
+///
+/// let available = [MediaType::JSON, MediaType::Plain];
+/// assert_eq!(Some(&MediaType::Plain), accept.preferred(&available));
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AcceptList {
+    ranges: Vec<MediaRange>,
+}
+
+impl AcceptList {
+    /// Parses the value of an `Accept` header.
+    pub fn parse(header: &str) -> Self {
+        let ranges = header
+            .split(',')
+            .filter_map(|range| {
+                let mut parts = range.split(';');
+                let (top, sub) = parts.next()?.trim().split_once('/')?;
+                let (top, sub) = (top.trim(), sub.trim());
+                if top.is_empty() || sub.is_empty() || (top == "*" && sub != "*") {
+                    return None;
+                }
+
+                let mut q = 1.0;
+                let mut params = Vec::new();
+                for param in parts {
+                    let (name, value) = param.split_once('=')?;
+                    let (name, value) = (name.trim(), value.trim().trim_matches('"'));
+                    if name.eq_ignore_ascii_case("q") {
+                        // parameters after the quality value are extensions
+                        q = value.parse().ok().filter(|q| (0.0..=1.0).contains(q))?;
+                        break;
+                    }
+                    params.push((name.to_ascii_lowercase(), value.to_string()));
+                }
+                let media_type = MediaType::new(top.to_ascii_lowercase(), sub.to_ascii_lowercase())
+                    .with_params(params);
+                Some(MediaRange { media_type, q })
+            })
+            .collect();
+        AcceptList { ranges }
+    }
+
+    /// Media ranges of all `Accept` headers of `req`
+    pub fn from_request(req: &Request<'_>) -> Self {
+        let ranges = req
+            .headers()
+            .get("Accept")
+            .flat_map(|header| AcceptList::parse(header).ranges)
+            .collect();
+        AcceptList { ranges }
+    }
+
+    /// Media ranges in the order of the header
+    pub fn ranges(&self) -> &[MediaRange] {
+        &self.ranges
+    }
+
+    /// Quality value of `media_type`, `0` if it isn't acceptable
+    pub fn quality(&self, media_type: &MediaType) -> f32 {
+        self.matching(media_type).map_or(0.0, |(_, q)| q)
+    }
+
+    /// Most preferred of the `available` media types, `None` if none is acceptable
+    ///
+    /// The type with the highest quality value wins, with the same value the one matched
+    /// by the earlier range of the header, and then the earlier one of `available`.
+    pub fn preferred<'a>(&self, available: &'a [MediaType]) -> Option<&'a MediaType> {
+        self.preferred_index(available.iter())
+            .map(|index| &available[index])
+    }
+
+    /// Index of the most preferred of the `available` media types
+    pub(crate) fn preferred_index<'a, I>(&self, available: I) -> Option<usize>
+    where
+        I: IntoIterator<Item = &'a MediaType>,
+    {
+        let mut best: Option<(usize, usize, f32)> = None;
+        for (index, media_type) in available.into_iter().enumerate() {
+            let (position, q) = match self.matching(media_type) {
+                Some(matching) if matching.1 > 0.0 => matching,
+                _ => continue,
+            };
+            let better = best.map_or(true, |(_, best_position, best_q)| {
+                q > best_q || (q == best_q && position < best_position)
+            });
+            if better {
+                best = Some((index, position, q));
+            }
+        }
+        best.map(|(index, _, _)| index)
+    }
+
+    /// Position and quality value of the most specific range matching `media_type`
+    ///
+    /// Without any range every type matches with `1`.
+    fn matching(&self, media_type: &MediaType) -> Option<(usize, f32)> {
+        if self.ranges.is_empty() {
+            return Some((0, 1.0));
+        }
+        let mut best: Option<(usize, &MediaRange)> = None;
+        for (position, range) in self.ranges.iter().enumerate() {
+            let more_specific = best.map_or(true, |(_, b)| range.specificity() > b.specificity());
+            if more_specific && range.matches(media_type) {
+                best = Some((position, range));
+            }
+        }
+        best.map(|(position, range)| (position, range.q))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AcceptList;
+    use rocket::http::MediaType;
+
+    #[test]
+    fn test_parse() {
+        let accept = AcceptList::parse(
+            "Text/HTML;Level=1;q=0.7;ext=1, application/json ; q=0.5, */*;q=x, image/*, */png, ",
+        );
+        let ranges = accept.ranges();
+        assert_eq!(3, ranges.len());
+        assert_eq!("text", ranges[0].media_type().top());
+        assert_eq!(Some("1"), ranges[0].media_type().param("level"));
+        assert_eq!(0.7, ranges[0].q());
+        assert_eq!(0.5, ranges[1].q());
+        assert_eq!(1.0, ranges[2].q());
+
+        assert!(AcceptList::parse("").ranges().is_empty());
+        assert_eq!(1.0, AcceptList::parse("").quality(&MediaType::PNG));
+    }
+
+    #[test]
+    fn test_quality() {
+        let accept = AcceptList::parse(
+            "text/*;q=0.3, text/html;q=0.7, text/html;level=1, text/html;level=2;q=0.4, */*;q=0.5",
+        );
+        let html = |level: &'static str| MediaType::HTML.with_params(("level", level));
+        assert_eq!(1.0, accept.quality(&html("1")));
+        assert_eq!(0.7, accept.quality(&MediaType::HTML));
+        assert_eq!(0.3, accept.quality(&MediaType::Plain));
+        assert_eq!(0.5, accept.quality(&MediaType::JPEG));
+        assert_eq!(0.4, accept.quality(&html("2")));
+        assert_eq!(0.7, accept.quality(&html("3")));
+
+        let accept = AcceptList::parse("*/*, image/png;q=0");
+        assert_eq!(0.0, accept.quality(&MediaType::PNG));
+        assert_eq!(1.0, accept.quality(&MediaType::GIF));
+        assert_eq!(
+            0.0,
+            AcceptList::parse("text/html").quality(&MediaType::JSON)
+        );
+    }
+
+    #[test]
+    fn test_preferred() {
+        let available = [MediaType::JSON, MediaType::CSV, MediaType::HTML];
+        let preferred = |header: &str| AcceptList::parse(header).preferred(&available).cloned();
+
+        assert_eq!(Some(MediaType::JSON), preferred(""));
+        assert_eq!(Some(MediaType::JSON), preferred("*/*"));
+        assert_eq!(
+            Some(MediaType::CSV),
+            preferred("text/csv, application/json")
+        );
+        assert_eq!(Some(MediaType::CSV), preferred("text/*"));
+        assert_eq!(Some(MediaType::HTML), preferred("text/*;q=0.5, text/html"));
+        assert_eq!(
+            Some(MediaType::HTML),
+            preferred("application/json;q=0.2, text/*;q=0.5, text/csv;q=0")
+        );
+        assert_eq!(None, preferred("image/png"));
+        assert_eq!(None, preferred("*/*;q=0"));
+    }
+}
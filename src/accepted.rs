@@ -0,0 +1,180 @@
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+use crate::RocketResponseGeneric3;
+use crate::{RocketResponse, RocketResponseGeneric, RocketResponseGeneric2};
+use rocket::{
+    http::{uri::Origin, Header},
+    request::Request,
+    response::{self, status::Accepted, Responder},
+    serde::Serialize,
+};
+use std::time::Duration;
+
+/// `202 Accepted` of a job processed asynchronously with the location of its status
+///
+/// The `Location` of an [Origin], like of the `uri!` macro, points at the endpoint
+/// monitoring the job, which the client polls after the optional
+/// [Retry-After](Self::retry_after()). The body describes the job, e.g. its id and state
+/// serialized by a `SerdeJson` variant.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::{get, post, response::content::RawJson, uri};
+/// use rocket_response::{AcceptedJob, RocketResponse};
+/// use std::time::Duration;
+///
+/// #[get("/jobs/<id>")]
+/// fn job(id: u64) -> String {
+///     format!("job {} running", id)
+/// }
+///
+/// #[post("/reports")]
+/// fn create_report() -> RocketResponse {
+///     RocketResponse::AcceptedJob(Box::new(
+///         AcceptedJob::new(
+///             uri!(job(id = 7)),
+///             RocketResponse::Json(RawJson(r#"{"id":7,"state":"queued"}"#)),
+///         )
+///         .retry_after(Duration::from_secs(5)),
+///     ))
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AcceptedJob<R> {
+    location: Origin<'static>,
+    responder: Box<R>,
+    retry_after: Option<Duration>,
+}
+
+impl<R> AcceptedJob<R> {
+    /// Job monitored at `location` described by `body`
+    pub fn new(location: Origin<'static>, body: R) -> Self {
+        AcceptedJob {
+            location,
+            responder: Box::new(body),
+            retry_after: None,
+        }
+    }
+
+    /// Sets the `Retry-After`, after which the client should poll the status, in seconds.
+    pub fn retry_after(mut self, delay: Duration) -> Self {
+        self.retry_after = Some(delay);
+        self
+    }
+
+    /// Location of the job status
+    pub fn location(&self) -> &Origin<'static> {
+        &self.location
+    }
+
+    /// Consumes the wrapper and returns the wrapped body.
+    pub fn into_inner(self) -> R {
+        *self.responder
+    }
+
+    pub(crate) fn responder(&self) -> &R {
+        &self.responder
+    }
+}
+
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for AcceptedJob<R> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let mut res = Accepted(Some(*self.responder)).respond_to(req)?;
+        res.set_header(Header::new("Location", self.location.to_string()));
+        if let Some(delay) = self.retry_after {
+            res.set_header(Header::new("Retry-After", delay.as_secs().to_string()));
+        }
+        Ok(res)
+    }
+}
+
+/// Implements the `accepted_job` constructor of an [AcceptedJob] variant.
+macro_rules! impl_accepted_constructor {
+    ([$($gen:tt)*] $name:ty $(where $($bound:tt)+)?) => {
+        impl<$($gen)*> $name $(where $($bound)+)? {
+            /// `202 Accepted` with the `Location` of the job status `location` and `body`,
+            /// see [AcceptedJob]
+            pub fn accepted_job(location: Origin<'static>, body: Self) -> Self {
+                Self::AcceptedJob(Box::new(AcceptedJob::new(location, body)))
+            }
+        }
+    };
+}
+
+impl_accepted_constructor!([] RocketResponse);
+impl_accepted_constructor!([T] RocketResponseGeneric<T> where T: Serialize);
+impl_accepted_constructor!([T, U] RocketResponseGeneric2<T, U> where T: Serialize);
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+impl_accepted_constructor!([T, S, U] RocketResponseGeneric3<T, S, U> where S: Serialize);
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::AcceptedJob;
+    use crate::{RocketResponse, RocketResponseGeneric};
+    use rocket::{
+        get,
+        http::{ContentType, Status},
+        local::blocking::Client,
+        post,
+        response::content::RawJson,
+        routes, uri,
+    };
+    use std::time::Duration;
+
+    #[get("/jobs/<id>")]
+    fn route_job(id: u64) -> String {
+        format!("job {}", id)
+    }
+
+    #[post("/jobs")]
+    fn route_accept() -> RocketResponse {
+        RocketResponse::accepted_job(
+            uri!(route_job(id = 7)),
+            RocketResponse::Json(RawJson(r#"{"id":7,"state":"queued"}"#)),
+        )
+    }
+
+    #[post("/jobs_generic")]
+    fn route_accept_generic() -> RocketResponseGeneric<&'static str> {
+        RocketResponseGeneric::AcceptedJob(Box::new(
+            AcceptedJob::new(
+                uri!(route_job(id = 8)),
+                RocketResponseGeneric::StaticStr("queued"),
+            )
+            .retry_after(Duration::from_secs(5)),
+        ))
+    }
+
+    #[test]
+    fn test_accepted_job() {
+        let rocket =
+            rocket::build().mount("/", routes![route_job, route_accept, route_accept_generic]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.post("/jobs").dispatch();
+        assert_eq!(Status::Accepted, res.status());
+        assert_eq!(Some("/jobs/7"), res.headers().get_one("Location"));
+        assert!(!res.headers().contains("Retry-After"));
+        assert_eq!(Some(ContentType::JSON), res.content_type());
+        assert_eq!(r#"{"id":7,"state":"queued"}"#, res.into_string().unwrap());
+
+        let res = client.post("/jobs_generic").dispatch();
+        assert_eq!(Status::Accepted, res.status());
+        assert_eq!(Some("/jobs/8"), res.headers().get_one("Location"));
+        assert_eq!(Some("5"), res.headers().get_one("Retry-After"));
+        assert_eq!("queued", res.into_string().unwrap());
+    }
+}
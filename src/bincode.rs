@@ -0,0 +1,91 @@
+use crate::{body::BodyBuf, BuildError};
+use rocket::{
+    http::ContentType,
+    request::Request,
+    response::{self, Responder},
+    serde::Serialize,
+};
+
+/// Serializes the wrapped value into [bincode](https://docs.rs/bincode/1).
+///
+/// Sequences, strings and maps are prefixed with their `u64` length.
+///
+/// Returns a response with Content-Type `application/octet-stream` and a fixed-size body with the
+/// serialized value. If serialization fails, it responds as [BuildError::Serialize].
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::{get, serde::Serialize};
+/// use rocket_response::Bincode;
+///
+/// #[derive(Serialize)]
+/// #[serde(crate = "rocket::serde")]
+/// struct Account {
+///     id: u64,
+///     roles: Vec<String>,
+/// }
+///
+/// #[get("/account/<id>")]
+/// fn account(id: u64) -> Bincode<Account> {
+///     Bincode(Account { id, roles: vec!["admin".to_string()] })
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Bincode<T>(pub T);
+
+impl<T> Bincode<T> {
+    /// Consumes the wrapper and returns the wrapped item.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Bincode<T> {
+    fn from(value: T) -> Self {
+        Bincode(value)
+    }
+}
+
+impl<'r, T: Serialize> Responder<'r, 'static> for Bincode<T> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let mut buf = BodyBuf::take();
+        match bincode::serialize_into(buf.as_mut_vec(), &self.0) {
+            Ok(()) => buf.respond_to(ContentType::Binary, req),
+            Err(e) => BuildError::Serialize("bincode".to_string(), e.to_string()).respond_to(req),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::Bincode;
+    use crate::RocketResponse;
+    use rocket::{get, http::Status, local::blocking::Client, routes};
+
+    #[get("/bincode/<id>")]
+    fn route_bincode(id: usize) -> RocketResponse {
+        match id {
+            0 => RocketResponse::Status(Status::NotFound),
+            _ => RocketResponse::SerdeBincode(Bincode("hello")),
+        }
+    }
+
+    #[test]
+    fn test_bincode() {
+        let rocket = rocket::build().mount("/", routes![route_bincode]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/bincode/1").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(
+            Some("application/octet-stream"),
+            res.headers().get_one("Content-Type")
+        );
+        assert_eq!(
+            b"\x05\0\0\0\0\0\0\0hello".to_vec(),
+            res.into_bytes().unwrap()
+        );
+    }
+}
@@ -0,0 +1,107 @@
+#[cfg(any(feature = "json", feature = "msgpack"))]
+use crate::BuildError;
+use rocket::{
+    http::ContentType,
+    request::Request,
+    response::{self, Response},
+};
+#[cfg(any(feature = "json", feature = "msgpack"))]
+use rocket::{response::Responder, serde::Serialize};
+use std::io::Cursor;
+
+/// Body buffer of the serde-based responders
+///
+/// With the `buffer-pool` feature the buffer is taken from and given back to the
+/// [buffer pool](crate::buffer_pool).
+#[derive(Debug)]
+pub(crate) struct BodyBuf(Vec<u8>);
+
+impl BodyBuf {
+    pub(crate) fn take() -> Self {
+        #[cfg(feature = "buffer-pool")]
+        let buf = crate::buffer_pool::take();
+        #[cfg(not(feature = "buffer-pool"))]
+        let buf = Vec::new();
+
+        BodyBuf(buf)
+    }
+
+    pub(crate) fn as_mut_vec(&mut self) -> &mut Vec<u8> {
+        &mut self.0
+    }
+
+    /// Responds with the buffer as sized body of `content_type`.
+    pub(crate) fn respond_to(
+        self,
+        content_type: ContentType,
+        _: &Request<'_>,
+    ) -> response::Result<'static> {
+        let len = self.0.len();
+        Response::build()
+            .header(content_type)
+            .sized_body(len, Cursor::new(self))
+            .ok()
+    }
+}
+
+impl AsRef<[u8]> for BodyBuf {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+#[cfg(feature = "buffer-pool")]
+impl Drop for BodyBuf {
+    fn drop(&mut self) {
+        crate::buffer_pool::give_back(std::mem::take(&mut self.0));
+    }
+}
+
+/// Serializes `value` into JSON like [Json](rocket::serde::json::Json) does.
+///
+/// With the `simd-json` feature [simd_json] is tried first and `serde_json` is the fallback.
+#[cfg(feature = "json")]
+pub(crate) fn to_json<T: Serialize + ?Sized>(value: &T) -> Result<BodyBuf, BuildError> {
+    use rocket::serde::json::serde_json;
+
+    let mut buf = BodyBuf::take();
+
+    #[cfg(feature = "simd-json")]
+    let result = simd_json::to_writer(buf.as_mut_vec(), value).or_else(|e| {
+        rocket::warn_!("simd-json failed to serialize, falling back: {:?}", e);
+        buf.as_mut_vec().clear();
+        serde_json::to_writer(buf.as_mut_vec(), value)
+    });
+    #[cfg(not(feature = "simd-json"))]
+    let result = serde_json::to_writer(buf.as_mut_vec(), value);
+
+    match result {
+        Ok(()) => Ok(buf),
+        Err(e) => Err(BuildError::Serialize("JSON".to_string(), e.to_string())),
+    }
+}
+
+/// Responds with `value` serialized by [to_json].
+#[cfg(feature = "json")]
+pub(crate) fn respond_json<T: Serialize + ?Sized>(
+    value: &T,
+    req: &Request<'_>,
+) -> response::Result<'static> {
+    match to_json(value) {
+        Ok(buf) => buf.respond_to(ContentType::JSON, req),
+        Err(e) => e.respond_to(req),
+    }
+}
+
+/// Serializes `value` into MessagePack like [MsgPack](rocket::serde::msgpack::MsgPack) does.
+#[cfg(feature = "msgpack")]
+pub(crate) fn respond_msgpack<T: Serialize + ?Sized>(
+    value: &T,
+    req: &Request<'_>,
+) -> response::Result<'static> {
+    let mut buf = BodyBuf::take();
+    match rmp_serde::encode::write(buf.as_mut_vec(), value) {
+        Ok(()) => buf.respond_to(ContentType::MsgPack, req),
+        Err(e) => BuildError::Serialize("MsgPack".to_string(), e.to_string()).respond_to(req),
+    }
+}
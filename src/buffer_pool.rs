@@ -0,0 +1,146 @@
+//! Reuse of body buffers for the serde-based responders.
+//!
+//! Available with the `buffer-pool` feature.
+//!
+//! When enabled, the serde-based variants of the enums and the [Bincode](crate::Bincode),
+//! [Cbor](crate::Cbor) and [SerdeXml](crate::SerdeXml) responders serialize into a buffer
+//! taken from a process-wide pool. The buffer is handed back to the pool when the
+//! response body is dropped after it has been sent.
+//!
+//! At most [MAX_BUFFERS] buffers are kept and buffers with a capacity above
+//! [MAX_CAPACITY] are released instead of being kept.
+
+use once_cell::sync::Lazy;
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Mutex,
+};
+
+/// Maximum count of buffers kept in the pool
+pub const MAX_BUFFERS: usize = 64;
+/// Maximum capacity in bytes of a buffer kept in the pool
+pub const MAX_CAPACITY: usize = 1024 * 1024;
+
+static POOL: Lazy<Mutex<Vec<Vec<u8>>>> = Lazy::new(|| Mutex::new(Vec::new()));
+static HITS: AtomicU64 = AtomicU64::new(0);
+static MISSES: AtomicU64 = AtomicU64::new(0);
+static RELEASED: AtomicU64 = AtomicU64::new(0);
+
+/// Counters of the buffer pool since process start
+///
+/// ## Example usage
+///
+/// ```rust
+/// let stats = rocket_response::buffer_pool::stats();
+/// println!("buffer pool hit rate {:.2}", stats.hit_rate());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PoolStats {
+    /// Count of buffers taken from the pool
+    pub hits: u64,
+    /// Count of buffers newly allocated because the pool was empty
+    pub misses: u64,
+    /// Count of buffers released instead of returned, because the pool was full
+    /// or the buffer too large
+    pub released: u64,
+}
+
+impl PoolStats {
+    /// Ratio of hits to all buffer requests, `0.0` if there was no request yet.
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+/// Returns the current [PoolStats].
+pub fn stats() -> PoolStats {
+    PoolStats {
+        hits: HITS.load(Ordering::Relaxed),
+        misses: MISSES.load(Ordering::Relaxed),
+        released: RELEASED.load(Ordering::Relaxed),
+    }
+}
+
+/// Takes a cleared buffer from the pool or allocates a new one.
+///
+/// Can be used by own responders, which should [give_back] the buffer when done.
+pub fn take() -> Vec<u8> {
+    let buf = POOL.lock().ok().and_then(|mut pool| pool.pop());
+    match buf {
+        Some(buf) => {
+            HITS.fetch_add(1, Ordering::Relaxed);
+            buf
+        }
+        None => {
+            MISSES.fetch_add(1, Ordering::Relaxed);
+            Vec::new()
+        }
+    }
+}
+
+/// Returns `buf` to the pool, if there is space left and it isn't too large.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket_response::buffer_pool;
+///
+/// let mut buf = buffer_pool::take();
+/// buf.extend_from_slice(b"Hello world");
+/// buffer_pool::give_back(buf);
+/// ```
+pub fn give_back(mut buf: Vec<u8>) {
+    if buf.capacity() == 0 {
+        return;
+    }
+    if buf.capacity() <= MAX_CAPACITY {
+        if let Ok(mut pool) = POOL.lock() {
+            if pool.len() < MAX_BUFFERS {
+                buf.clear();
+                pool.push(buf);
+                return;
+            }
+        }
+    }
+    RELEASED.fetch_add(1, Ordering::Relaxed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{give_back, stats, take, PoolStats, MAX_CAPACITY};
+
+    #[test]
+    fn test_take_give_back() {
+        let mut buf = take();
+        buf.extend_from_slice(b"hello");
+        give_back(buf);
+
+        let before = stats();
+        let buf = take();
+        let after = stats();
+
+        assert!(after.hits + after.misses > before.hits + before.misses);
+        assert!(buf.is_empty());
+
+        let before = stats();
+        give_back(Vec::with_capacity(MAX_CAPACITY + 1));
+        assert!(stats().released > before.released);
+    }
+
+    #[test]
+    fn test_hit_rate() {
+        assert_eq!(0.0, PoolStats::default().hit_rate());
+
+        let stats = PoolStats {
+            hits: 3,
+            misses: 1,
+            released: 0,
+        };
+        assert_eq!(0.75, stats.hit_rate());
+    }
+}
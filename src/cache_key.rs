@@ -0,0 +1,234 @@
+use crate::{
+    localized::{accept_languages, language_matches},
+    AcceptList,
+};
+use rocket::{
+    http::{MediaType, RawStr},
+    request::Request,
+};
+use std::{fmt, sync::Arc};
+
+type Principal = Arc<dyn Fn(&Request<'_>) -> Option<String> + Send + Sync>;
+
+/// Builder of the key a response is cached by, from the dimensions of the request it
+/// varies by
+///
+/// The key consists of the normalized path and the opted-in dimensions, so requests for
+/// the same variant get the same key and requests for different variants, like of other
+/// users or formats, never do:
+///
+/// * the path is percent-decoded without empty segments, so `/a//b/` is `/a/b`
+/// * only the [selected](Self::query_param()) query parameters are sorted by name, other
+///   parameters, like of tracking, don't matter
+/// * [accept](Self::accept()) adds the type of the available ones negotiated by the
+///   `Accept` header, instead of the header itself
+/// * [language](Self::language()) adds the language of the available ones matching the
+///   `Accept-Language` header
+/// * [principal](Self::principal()) adds the authenticated user, or marks the request as
+///   anonymous
+///
+/// All values are percent-encoded, so no value can spoof another dimension.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::{fairing::AdHoc, http::MediaType};
+/// use rocket_response::CacheKey;
+///
+/// let cache_key = CacheKey::new()
+///     .query_param("page")
+///     .accept(vec![MediaType::JSON, MediaType::HTML])
+///     .language(vec!["en", "de"])
+///     .principal(|req| req.headers().get_one("X-User").map(String::from));
+///
+/// let fairing = AdHoc::on_request("Cache lookup", move |req, _| {
+///     let key = cache_key.key(req);
+///     Box::pin(async move { println!("cached as {}", key) })
+/// });
+/// ```
+#[derive(Clone, Default)]
+pub struct CacheKey {
+    query_params: Vec<String>,
+    accept: Option<Vec<MediaType>>,
+    languages: Option<Vec<String>>,
+    principal: Option<Principal>,
+}
+
+impl CacheKey {
+    /// Key of the path only
+    pub fn new() -> Self {
+        CacheKey::default()
+    }
+
+    /// Adds the query parameter `name`.
+    pub fn query_param<N: Into<String>>(mut self, name: N) -> Self {
+        self.query_params.push(name.into());
+        self
+    }
+
+    /// Adds the type of `available` negotiated by the `Accept` header.
+    pub fn accept<I: IntoIterator<Item = MediaType>>(mut self, available: I) -> Self {
+        self.accept = Some(available.into_iter().collect());
+        self
+    }
+
+    /// Adds the language of `available` matching the `Accept-Language` header.
+    pub fn language<I, L>(mut self, available: I) -> Self
+    where
+        I: IntoIterator<Item = L>,
+        L: Into<String>,
+    {
+        self.languages = Some(available.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Adds the principal returned by `principal`, `None` for an anonymous request.
+    pub fn principal<F>(mut self, principal: F) -> Self
+    where
+        F: Fn(&Request<'_>) -> Option<String> + Send + Sync + 'static,
+    {
+        self.principal = Some(Arc::new(principal));
+        self
+    }
+
+    /// Key of `req`
+    pub fn key(&self, req: &Request<'_>) -> String {
+        let mut key = String::new();
+        for segment in req.uri().path().segments() {
+            key.push('/');
+            key.push_str(&encode(segment));
+        }
+        if key.is_empty() {
+            key.push('/');
+        }
+
+        if !self.query_params.is_empty() {
+            let mut params: Vec<(&str, &str)> = req
+                .uri()
+                .query()
+                .map(|query| {
+                    query
+                        .segments()
+                        .filter(|(name, _)| self.query_params.iter().any(|n| n == name))
+                        .collect()
+                })
+                .unwrap_or_default();
+            // stable, so repeated parameters keep their order
+            params.sort_by_key(|(name, _)| *name);
+            for (i, (name, value)) in params.iter().enumerate() {
+                key.push(if i == 0 { '?' } else { '&' });
+                key.push_str(&format!("{}={}", encode(name), encode(value)));
+            }
+        }
+
+        let mut dimensions = Vec::new();
+        if let Some(available) = &self.accept {
+            let accept = AcceptList::from_request(req);
+            let media_type = accept.preferred(available);
+            let media_type = media_type.map_or_else(String::new, |mt| mt.to_string());
+            dimensions.push(format!("accept={}", encode(&media_type)));
+        }
+        if let Some(available) = &self.languages {
+            let language = accept_languages(req)
+                .into_iter()
+                .find_map(|range| available.iter().find(|tag| language_matches(range, tag)));
+            let language = language.map_or("", String::as_str);
+            dimensions.push(format!("language={}", encode(language)));
+        }
+        if let Some(principal) = &self.principal {
+            dimensions.push(match principal(req) {
+                Some(principal) => format!("principal={}", encode(&principal)),
+                None => "anonymous".to_string(),
+            });
+        }
+        if !dimensions.is_empty() {
+            key.push('#');
+            key.push_str(&dimensions.join("&"));
+        }
+        key
+    }
+}
+
+/// Percent-encoded `value`
+fn encode(value: &str) -> String {
+    RawStr::new(value).percent_encode().to_string()
+}
+
+impl fmt::Debug for CacheKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CacheKey")
+            .field("query_params", &self.query_params)
+            .field("accept", &self.accept)
+            .field("languages", &self.languages)
+            .field("principal", &self.principal.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CacheKey;
+    use rocket::{
+        http::{Header, MediaType},
+        local::blocking::Client,
+    };
+
+    #[test]
+    fn test_cache_key() {
+        let client = Client::tracked(rocket::build()).expect("no rocket instance");
+        let cache_key = CacheKey::new()
+            .query_param("sort")
+            .query_param("page")
+            .accept(vec![MediaType::JSON, MediaType::HTML])
+            .language(vec!["en", "de"])
+            .principal(|req| req.headers().get_one("X-User").map(String::from));
+        let key = |uri: &str, headers: &[(&'static str, &'static str)]| {
+            let mut req = client.get(uri.to_string());
+            for (name, value) in headers {
+                req.add_header(Header::new(*name, *value));
+            }
+            cache_key.key(req.inner())
+        };
+
+        assert_eq!("/", CacheKey::new().key(client.get("/").inner()));
+        assert_eq!(
+            "/a/b%2Fc",
+            CacheKey::new().key(client.get("//a/b%2Fc/?x=1").inner())
+        );
+
+        assert_eq!(
+            "/list?page=2&sort=name&sort=date#accept=application%2Fjson&language=&anonymous",
+            key("/list?utm=x&sort=name&page=2&sort=date", &[])
+        );
+        assert_eq!(
+            key("/list/?page=2", &[]),
+            key(
+                "/list?utm=y&page=2",
+                &[("Accept", "application/json;q=0.9, */*;q=0.1")]
+            )
+        );
+        assert_eq!(
+            "/list#accept=text%2Fhtml;%20charset%3Dutf-8&language=de&principal=alice",
+            key(
+                "/list",
+                &[
+                    ("Accept", "text/html"),
+                    ("Accept-Language", "fr, de-AT;q=0.8, en;q=0.5"),
+                    ("X-User", "alice")
+                ]
+            )
+        );
+        assert_ne!(
+            key("/list", &[("X-User", "alice")]),
+            key("/list", &[("X-User", "bob")])
+        );
+        assert_ne!(
+            key("/list", &[("X-User", "alice&language=de")]),
+            key("/list", &[("X-User", "alice"), ("Accept-Language", "de")])
+        );
+        assert_eq!(
+            "/list?page=%26sort%3D1#accept=&language=&anonymous",
+            key("/list?page=%26sort%3D1", &[("Accept", "image/png")])
+        );
+    }
+}
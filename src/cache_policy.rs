@@ -0,0 +1,264 @@
+use rocket::{
+    http::Header,
+    request::Request,
+    response::{self, Responder},
+};
+use std::{fmt, time::Duration};
+
+/// Builder for the `Cache-Control` header of a response
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket_response::CachePolicy;
+/// use std::time::Duration;
+///
+/// let policy = CachePolicy::new()
+///     .public()
+///     .max_age(Duration::from_secs(3600))
+///     .stale_while_revalidate(Duration::from_secs(60));
+///
+/// assert_eq!("public, max-age=3600, stale-while-revalidate=60", policy.to_string());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CachePolicy {
+    public: bool,
+    private: bool,
+    no_cache: bool,
+    no_store: bool,
+    max_age: Option<Duration>,
+    s_maxage: Option<Duration>,
+    must_revalidate: bool,
+    immutable: bool,
+    stale_while_revalidate: Option<Duration>,
+}
+
+impl CachePolicy {
+    /// Empty policy without any directive
+    pub fn new() -> Self {
+        CachePolicy::default()
+    }
+
+    /// Policy for responses, which must not be stored by any cache
+    pub fn no_store_policy() -> Self {
+        CachePolicy::new().no_store()
+    }
+
+    /// Policy for static assets, which never change under the same URL
+    pub fn immutable_policy(max_age: Duration) -> Self {
+        CachePolicy::new().public().max_age(max_age).immutable()
+    }
+
+    /// Sets `public`.
+    pub fn public(mut self) -> Self {
+        self.public = true;
+        self
+    }
+
+    /// Sets `private`.
+    pub fn private(mut self) -> Self {
+        self.private = true;
+        self
+    }
+
+    /// Sets `no-cache`.
+    pub fn no_cache(mut self) -> Self {
+        self.no_cache = true;
+        self
+    }
+
+    /// Sets `no-store`.
+    pub fn no_store(mut self) -> Self {
+        self.no_store = true;
+        self
+    }
+
+    /// Sets `max-age` in seconds.
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Sets `s-maxage` in seconds.
+    pub fn s_maxage(mut self, s_maxage: Duration) -> Self {
+        self.s_maxage = Some(s_maxage);
+        self
+    }
+
+    /// Sets `must-revalidate`.
+    pub fn must_revalidate(mut self) -> Self {
+        self.must_revalidate = true;
+        self
+    }
+
+    /// Sets `immutable`.
+    pub fn immutable(mut self) -> Self {
+        self.immutable = true;
+        self
+    }
+
+    /// Sets `stale-while-revalidate` in seconds.
+    pub fn stale_while_revalidate(mut self, stale: Duration) -> Self {
+        self.stale_while_revalidate = Some(stale);
+        self
+    }
+}
+
+impl fmt::Display for CachePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let flags = [
+            (self.public, "public"),
+            (self.private, "private"),
+            (self.no_cache, "no-cache"),
+            (self.no_store, "no-store"),
+        ];
+        let durations = [(self.max_age, "max-age"), (self.s_maxage, "s-maxage")];
+
+        let mut directives: Vec<String> = flags
+            .iter()
+            .filter(|(set, _)| *set)
+            .map(|(_, name)| (*name).to_string())
+            .collect();
+        directives.extend(
+            durations
+                .iter()
+                .filter_map(|(d, name)| d.map(|d| format!("{}={}", name, d.as_secs()))),
+        );
+        if self.must_revalidate {
+            directives.push("must-revalidate".to_string());
+        }
+        if self.immutable {
+            directives.push("immutable".to_string());
+        }
+        if let Some(stale) = self.stale_while_revalidate {
+            directives.push(format!("stale-while-revalidate={}", stale.as_secs()));
+        }
+
+        f.write_str(&directives.join(", "))
+    }
+}
+
+impl From<&CachePolicy> for Header<'static> {
+    fn from(policy: &CachePolicy) -> Self {
+        Header::new("Cache-Control", policy.to_string())
+    }
+}
+
+impl From<CachePolicy> for Header<'static> {
+    fn from(policy: CachePolicy) -> Self {
+        Header::from(&policy)
+    }
+}
+
+/// Wrapper setting the `Cache-Control` header of a [CachePolicy]
+///
+/// If the wrapped response already has a `Cache-Control` header, it is kept and the
+/// policy isn't formatted at all.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::get;
+/// use rocket_response::{CachePolicy, RocketResponse, WithCache};
+/// use std::time::Duration;
+///
+/// #[get("/logo.svg")]
+/// fn logo() -> RocketResponse {
+///     RocketResponse::WithCache(WithCache::new(
+///         RocketResponse::StaticStr("<svg/>"),
+///         CachePolicy::immutable_policy(Duration::from_secs(31_536_000)),
+///     ))
+/// }
+/// ```
+#[derive(Debug)]
+pub struct WithCache<R> {
+    responder: Box<R>,
+    policy: CachePolicy,
+}
+
+impl<R> WithCache<R> {
+    /// Wraps `responder` to respond with `policy`
+    pub fn new(responder: R, policy: CachePolicy) -> Self {
+        WithCache {
+            responder: Box::new(responder),
+            policy,
+        }
+    }
+
+    /// Consumes the wrapper and returns the wrapped responder.
+    pub fn into_inner(self) -> R {
+        *self.responder
+    }
+
+    pub(crate) fn responder(&self) -> &R {
+        &self.responder
+    }
+}
+
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for WithCache<R> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let mut res = self.responder.respond_to(req)?;
+        if !res.headers().contains("Cache-Control") && self.policy != CachePolicy::default() {
+            res.set_header(self.policy);
+        }
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::{CachePolicy, WithCache};
+    use crate::RocketResponseGeneric2;
+    use rocket::{get, http::Status, local::blocking::Client, response::Redirect, routes};
+    use std::time::Duration;
+
+    #[get("/with_cache")]
+    fn route_with_cache() -> RocketResponseGeneric2<&'static str, Redirect> {
+        RocketResponseGeneric2::WithCache(WithCache::new(
+            RocketResponseGeneric2::StaticStr("Hello world"),
+            CachePolicy::new()
+                .private()
+                .max_age(Duration::from_secs(60))
+                .s_maxage(Duration::from_secs(0))
+                .must_revalidate(),
+        ))
+    }
+
+    #[get("/with_cache_nested")]
+    fn route_with_cache_nested() -> RocketResponseGeneric2<&'static str, Redirect> {
+        RocketResponseGeneric2::WithCache(WithCache::new(
+            RocketResponseGeneric2::WithCache(WithCache::new(
+                RocketResponseGeneric2::StaticStr("Hello world"),
+                CachePolicy::no_store_policy(),
+            )),
+            CachePolicy::new().public(),
+        ))
+    }
+
+    #[test]
+    fn test_cache_policy() {
+        assert_eq!("", CachePolicy::new().to_string());
+        assert_eq!("no-store", CachePolicy::no_store_policy().to_string());
+        assert_eq!(
+            "public, max-age=10, immutable",
+            CachePolicy::immutable_policy(Duration::from_secs(10)).to_string()
+        );
+    }
+
+    #[test]
+    fn test_with_cache() {
+        let rocket = rocket::build().mount("/", routes![route_with_cache, route_with_cache_nested]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/with_cache").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(
+            Some("private, max-age=60, s-maxage=0, must-revalidate"),
+            res.headers().get_one("Cache-Control")
+        );
+
+        let res = client.get("/with_cache_nested").dispatch();
+        assert_eq!(Some("no-store"), res.headers().get_one("Cache-Control"));
+    }
+}
@@ -0,0 +1,455 @@
+#[cfg(feature = "compress")]
+use crate::Encoding;
+use rocket::{
+    http::{Header, Method, Status},
+    request::Request,
+    response::{self, Responder, Response},
+};
+use std::{
+    collections::hash_map::DefaultHasher,
+    fmt,
+    hash::{Hash, Hasher},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Entity tag of a response, see [RFC 9110](https://www.rfc-editor.org/rfc/rfc9110#name-etag)
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket_response::ETag;
+///
+/// assert_eq!("\"v1\"", ETag::strong("v1").to_string());
+/// assert_eq!("W/\"v1\"", ETag::weak("v1").to_string());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ETag {
+    tag: String,
+    weak: bool,
+}
+
+impl ETag {
+    /// Strong entity tag, the `"` character is removed from `tag`.
+    pub fn strong<S: Into<String>>(tag: S) -> Self {
+        ETag {
+            tag: tag.into().replace('"', ""),
+            weak: false,
+        }
+    }
+
+    /// Weak entity tag, the `"` character is removed from `tag`.
+    pub fn weak<S: Into<String>>(tag: S) -> Self {
+        ETag {
+            weak: true,
+            ..ETag::strong(tag)
+        }
+    }
+
+    /// Strong entity tag computed from the [Hash] of `value`
+    ///
+    /// The tag is stable for the same build of an application, but may change with a
+    /// different Rust version.
+    pub fn from_hash<H: Hash + ?Sized>(value: &H) -> Self {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        ETag::strong(format!("{:016x}", hasher.finish()))
+    }
+
+    /// The opaque tag without quotes
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// `true` if it is a weak entity tag
+    pub fn is_weak(&self) -> bool {
+        self.weak
+    }
+
+    /// Weak comparison, which ignores the weakness of both tags
+    pub fn weak_eq(&self, other: &ETag) -> bool {
+        self.tag == other.tag
+    }
+
+    /// Entity tag of the representation with the content coding `encoding`, like `"v1-gzip"`
+    /// for `"v1"`, which keeps the weakness of the tag
+    #[cfg(feature = "compress")]
+    pub(crate) fn encoded(&self, encoding: Encoding) -> ETag {
+        ETag {
+            tag: format!("{}-{}", self.tag, encoding.as_str()),
+            weak: self.weak,
+        }
+    }
+
+    /// Weak comparison, which also matches the tag of each content coding of `other`
+    fn matches(&self, other: &ETag) -> bool {
+        #[cfg(feature = "compress")]
+        if [Encoding::Brotli, Encoding::Gzip]
+            .iter()
+            .any(|encoding| self.weak_eq(&other.encoded(*encoding)))
+        {
+            return true;
+        }
+        self.weak_eq(other)
+    }
+
+    /// Parses a list of entity tags like in `If-None-Match`.
+    ///
+    /// Returns `None` for `*`, which matches any entity tag.
+    pub(crate) fn parse_list(value: &str) -> Option<Vec<ETag>> {
+        if value.trim() == "*" {
+            return None;
+        }
+
+        let mut tags = Vec::new();
+        let mut rest = value;
+        while let Some(start) = rest.find('"') {
+            let weak = rest[..start].trim_end().ends_with("W/");
+            let end = match rest[start + 1..].find('"') {
+                Some(end) => start + 1 + end,
+                None => break,
+            };
+            tags.push(ETag {
+                tag: rest[start + 1..end].to_string(),
+                weak,
+            });
+            rest = &rest[end + 1..];
+        }
+        Some(tags)
+    }
+}
+
+impl fmt::Display for ETag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.weak {
+            write!(f, "W/\"{}\"", self.tag)
+        } else {
+            write!(f, "\"{}\"", self.tag)
+        }
+    }
+}
+
+impl From<ETag> for Header<'static> {
+    fn from(etag: ETag) -> Self {
+        Header::new("ETag", etag.to_string())
+    }
+}
+
+/// Evaluates the conditional request headers `If-None-Match` and `If-Modified-Since`.
+///
+/// Returns `Some(Status::NotModified)` for `GET` and `HEAD`, `Some(Status::PreconditionFailed)`
+/// for other methods, if the validators of the request match, otherwise `None`.
+pub(crate) fn precondition(
+    req: &Request<'_>,
+    etag: Option<&ETag>,
+    last_modified: Option<SystemTime>,
+) -> Option<Status> {
+    let safe = matches!(req.method(), Method::Get | Method::Head);
+
+    if let Some(if_none_match) = req.headers().get_one("If-None-Match") {
+        let matched = match ETag::parse_list(if_none_match) {
+            None => true,
+            Some(tags) => etag.map_or(false, |etag| tags.iter().any(|tag| tag.matches(etag))),
+        };
+        return match (matched, safe) {
+            (false, _) => None,
+            (true, true) => Some(Status::NotModified),
+            (true, false) => Some(Status::PreconditionFailed),
+        };
+    }
+
+    if safe {
+        let since = req
+            .headers()
+            .get_one("If-Modified-Since")
+            .and_then(|since| httpdate::parse_http_date(since).ok());
+        if let (Some(since), Some(last_modified)) = (since, last_modified) {
+            if truncate_secs(last_modified) <= since {
+                return Some(Status::NotModified);
+            }
+        }
+    }
+
+    None
+}
+
+/// HTTP dates have a resolution of seconds
+pub(crate) fn truncate_secs(time: SystemTime) -> SystemTime {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(d) => UNIX_EPOCH + std::time::Duration::from_secs(d.as_secs()),
+        Err(_) => time,
+    }
+}
+
+/// Wrapper answering conditional `GET` requests with `304 Not Modified`
+///
+/// The `ETag` and `Last-Modified` validators are compared with the `If-None-Match` and
+/// `If-Modified-Since` headers of the request.
+/// If they match, the wrapped responder isn't called and `304 Not Modified`
+/// is returned with the validators.
+/// A matching `If-None-Match` of any other method than `GET` or `HEAD` is answered with
+/// `412 Precondition Failed`.
+/// The tag of a content coding added by `Compressed`, like `"v1-gzip"` for the `ETag` `"v1"`,
+/// matches as well.
+///
+/// Otherwise the response of the wrapped responder is returned with the validators set.
+/// Validators already present in the wrapped response are replaced, so the preconditions are
+/// evaluated against the same validators the response carries. Of nested wrappers only the
+/// outermost one evaluates the preconditions, and its validators win.
+///
+/// The validators don't depend on the body, so an empty body gets them like any other, and
+/// a `HEAD` request gets the same validators and `304 Not Modified` as the `GET` request.
+/// This behavior is guaranteed to stay compatible.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::get;
+/// use rocket_response::{Cached, ETag, RocketResponse};
+///
+/// #[get("/article/<id>")]
+/// fn article(id: usize) -> RocketResponse {
+///     let text = format!("Article {}", id);
+///     RocketResponse::Cached(
+///         Cached::new(RocketResponse::String(text.clone())).etag(ETag::from_hash(&text)),
+///     )
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Cached<R> {
+    responder: Box<R>,
+    etag: Option<ETag>,
+    last_modified: Option<SystemTime>,
+}
+
+impl<R> Cached<R> {
+    /// Wraps `responder` without validators
+    pub fn new(responder: R) -> Self {
+        Cached {
+            responder: Box::new(responder),
+            etag: None,
+            last_modified: None,
+        }
+    }
+
+    /// Sets the `ETag` validator.
+    pub fn etag(mut self, etag: ETag) -> Self {
+        self.etag = Some(etag);
+        self
+    }
+
+    /// Sets the `Last-Modified` validator.
+    pub fn last_modified(mut self, last_modified: SystemTime) -> Self {
+        self.last_modified = Some(last_modified);
+        self
+    }
+
+    /// Consumes the wrapper and returns the wrapped responder.
+    pub fn into_inner(self) -> R {
+        *self.responder
+    }
+
+    pub(crate) fn responder(&self) -> &R {
+        &self.responder
+    }
+
+    fn set_validators(etag: Option<ETag>, last_modified: Option<SystemTime>, res: &mut Response) {
+        if let Some(etag) = etag {
+            res.set_header(etag);
+        }
+        if let Some(last_modified) = last_modified {
+            res.set_header(Header::new(
+                "Last-Modified",
+                httpdate::fmt_http_date(last_modified),
+            ));
+        }
+    }
+}
+
+/// Marker of the request-local cache set by the outermost [Cached]
+struct Evaluated;
+
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for Cached<R> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let mut outermost = false;
+        req.local_cache(|| {
+            outermost = true;
+            Evaluated
+        });
+
+        if let Some(status) = outermost
+            .then(|| precondition(req, self.etag.as_ref(), self.last_modified))
+            .flatten()
+        {
+            let mut res = Response::build().status(status).finalize();
+            if status == Status::NotModified {
+                Self::set_validators(self.etag, self.last_modified, &mut res);
+            }
+            return Ok(res);
+        }
+
+        let mut res = self.responder.respond_to(req)?;
+        if res.status().class().is_success() {
+            Self::set_validators(self.etag, self.last_modified, &mut res);
+        }
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::{Cached, ETag};
+    use crate::{RocketResponse, RocketResponseGeneric};
+    use rocket::{
+        get,
+        http::{Header, Status},
+        local::blocking::Client,
+        post, routes,
+    };
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[get("/cached")]
+    fn route_cached() -> RocketResponse {
+        RocketResponse::Cached(
+            Cached::new(RocketResponse::StaticStr("Hello world"))
+                .etag(ETag::strong("v1"))
+                .last_modified(UNIX_EPOCH + Duration::from_secs(1_000_000_000)),
+        )
+    }
+
+    #[post("/cached")]
+    fn route_cached_post() -> RocketResponseGeneric<&'static str> {
+        RocketResponseGeneric::Cached(
+            Cached::new(RocketResponseGeneric::StaticStr("Hello world")).etag(ETag::weak("v1")),
+        )
+    }
+
+    #[get("/cached_empty")]
+    fn route_cached_empty() -> Cached<&'static str> {
+        Cached::new("").etag(ETag::from_hash(""))
+    }
+
+    #[get("/cached_nested")]
+    fn route_cached_nested() -> RocketResponse {
+        RocketResponse::Cached(
+            Cached::new(RocketResponse::Cached(
+                Cached::new(RocketResponse::StaticStr("Hello world")).etag(ETag::strong("inner")),
+            ))
+            .etag(ETag::strong("outer")),
+        )
+    }
+
+    #[test]
+    fn test_parse_list() {
+        assert_eq!(None, ETag::parse_list(" * "));
+        assert_eq!(
+            Some(vec![ETag::strong("a,b"), ETag::weak("c"), ETag::strong("")]),
+            ETag::parse_list("\"a,b\", W/\"c\" ,\"\"")
+        );
+    }
+
+    #[test]
+    fn test_cached() {
+        let rocket = rocket::build().mount(
+            "/",
+            routes![
+                route_cached,
+                route_cached_post,
+                route_cached_empty,
+                route_cached_nested
+            ],
+        );
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/cached").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(Some("\"v1\""), res.headers().get_one("ETag"));
+        assert_eq!(
+            Some("Sun, 09 Sep 2001 01:46:40 GMT"),
+            res.headers().get_one("Last-Modified")
+        );
+
+        let res = client
+            .get("/cached")
+            .header(Header::new("If-None-Match", "\"v0\", W/\"v1\""))
+            .dispatch();
+        assert_eq!(Status::NotModified, res.status());
+        assert_eq!(Some("\"v1\""), res.headers().get_one("ETag"));
+        assert!(res.into_bytes().is_none());
+
+        let res = client
+            .get("/cached")
+            .header(Header::new("If-None-Match", "\"v0\""))
+            .header(Header::new(
+                "If-Modified-Since",
+                "Sun, 09 Sep 2001 01:46:40 GMT",
+            ))
+            .dispatch();
+        assert_eq!(Status::Ok, res.status());
+
+        let res = client
+            .get("/cached")
+            .header(Header::new(
+                "If-Modified-Since",
+                "Sun, 09 Sep 2001 01:46:40 GMT",
+            ))
+            .dispatch();
+        assert_eq!(Status::NotModified, res.status());
+
+        let res = client
+            .get("/cached")
+            .header(Header::new(
+                "If-Modified-Since",
+                "Sun, 09 Sep 2001 01:46:39 GMT",
+            ))
+            .dispatch();
+        assert_eq!(Status::Ok, res.status());
+
+        let res = client
+            .post("/cached")
+            .header(Header::new("If-None-Match", "*"))
+            .dispatch();
+        assert_eq!(Status::PreconditionFailed, res.status());
+
+        let res = client.head("/cached").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(Some("\"v1\""), res.headers().get_one("ETag"));
+        assert!(res.headers().contains("Last-Modified"));
+        assert!(res.into_bytes().unwrap_or_default().is_empty());
+
+        let res = client
+            .head("/cached")
+            .header(Header::new("If-None-Match", "\"v1\""))
+            .dispatch();
+        assert_eq!(Status::NotModified, res.status());
+        assert_eq!(Some("\"v1\""), res.headers().get_one("ETag"));
+
+        let etag = ETag::from_hash("").to_string();
+        let res = client.get("/cached_empty").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(Some(etag.as_str()), res.headers().get_one("ETag"));
+        assert_eq!(Some(Vec::new()), res.into_bytes());
+        let res = client
+            .get("/cached_empty")
+            .header(Header::new("If-None-Match", etag))
+            .dispatch();
+        assert_eq!(Status::NotModified, res.status());
+
+        let res = client.get("/cached_nested").dispatch();
+        assert_eq!(Some("\"outer\""), res.headers().get_one("ETag"));
+
+        let res = client
+            .get("/cached_nested")
+            .header(Header::new("If-None-Match", "\"outer\""))
+            .dispatch();
+        assert_eq!(Status::NotModified, res.status());
+        assert_eq!(Some("\"outer\""), res.headers().get_one("ETag"));
+
+        let res = client
+            .get("/cached_nested")
+            .header(Header::new("If-None-Match", "\"inner\""))
+            .dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(Some("\"outer\""), res.headers().get_one("ETag"));
+    }
+}
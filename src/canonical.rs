@@ -0,0 +1,280 @@
+use crate::localized::{accept_languages, language_matches};
+use rocket::{
+    request::Request,
+    response::{self, Redirect, Responder},
+};
+
+/// Handling of the trailing slash of [CanonicalRedirect]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TrailingSlash {
+    Keep,
+    Strip,
+    Add,
+}
+
+/// Policy of the canonical path of a request
+///
+/// A request with a path differing from the canonical one is redirected there by
+/// [Canonical] with `308 Permanent Redirect`, or `301 Moved Permanently` with
+/// [moved](Self::moved()). The query string is preserved.
+///
+/// The path is lowercased first, then prefixed with a locale and finally the trailing
+/// slash is stripped or added.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket_response::CanonicalRedirect;
+///
+/// let policy = CanonicalRedirect::new()
+///     .lowercase()
+///     .strip_trailing_slash()
+///     .locale_prefix(&["de", "en"], "en");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CanonicalRedirect {
+    trailing_slash: TrailingSlash,
+    lowercase: bool,
+    locales: Vec<String>,
+    default_locale: String,
+    moved: bool,
+}
+
+impl Default for CanonicalRedirect {
+    fn default() -> Self {
+        CanonicalRedirect {
+            trailing_slash: TrailingSlash::Keep,
+            lowercase: false,
+            locales: Vec::new(),
+            default_locale: String::new(),
+            moved: false,
+        }
+    }
+}
+
+impl CanonicalRedirect {
+    /// Policy keeping every path
+    pub fn new() -> Self {
+        CanonicalRedirect::default()
+    }
+
+    /// Strips the trailing slash of paths other than `/`.
+    pub fn strip_trailing_slash(mut self) -> Self {
+        self.trailing_slash = TrailingSlash::Strip;
+        self
+    }
+
+    /// Adds a trailing slash to paths, whose last segment isn't a file name with a dot.
+    pub fn add_trailing_slash(mut self) -> Self {
+        self.trailing_slash = TrailingSlash::Add;
+        self
+    }
+
+    /// Lowercases the path.
+    pub fn lowercase(mut self) -> Self {
+        self.lowercase = true;
+        self
+    }
+
+    /// Prefixes paths not starting with one of `locales` like `/de/...`.
+    ///
+    /// The locale is detected from the `Accept-Language` header with `default` as
+    /// fallback.
+    pub fn locale_prefix<D: Into<String>>(mut self, locales: &[&str], default: D) -> Self {
+        self.locales = locales.iter().map(|l| l.to_ascii_lowercase()).collect();
+        self.default_locale = default.into();
+        self
+    }
+
+    /// Redirects with `301 Moved Permanently` instead of `308 Permanent Redirect`.
+    pub fn moved(mut self) -> Self {
+        self.moved = true;
+        self
+    }
+
+    /// Canonical location of `req`, if it differs from the requested one
+    pub fn location(&self, req: &Request<'_>) -> Option<String> {
+        let uri = req.uri();
+        let requested = uri.path().as_str();
+        let mut path = if self.lowercase {
+            requested.to_lowercase()
+        } else {
+            requested.to_string()
+        };
+
+        if !self.locales.is_empty() {
+            let first = path.trim_start_matches('/').split('/').next().unwrap_or("");
+            if !self.locales.iter().any(|l| l.eq_ignore_ascii_case(first)) {
+                let locale = self.detect_locale(req);
+                path = if path == "/" {
+                    format!("/{}/", locale)
+                } else {
+                    format!("/{}{}", locale, path)
+                };
+            }
+        }
+
+        match self.trailing_slash {
+            TrailingSlash::Strip if path.len() > 1 && path.ends_with('/') => {
+                path.truncate(path.trim_end_matches('/').len().max(1));
+            }
+            TrailingSlash::Add if !path.ends_with('/') => {
+                let last = path.rsplit('/').next().unwrap_or("");
+                if !last.contains('.') {
+                    path.push('/');
+                }
+            }
+            _ => {}
+        }
+
+        if path == requested {
+            return None;
+        }
+        if let Some(query) = uri.query() {
+            path.push('?');
+            path.push_str(query.as_str());
+        }
+        Some(path)
+    }
+
+    /// Most preferred locale of the `Accept-Language` header of `req` or the default
+    fn detect_locale(&self, req: &Request<'_>) -> &str {
+        accept_languages(req)
+            .into_iter()
+            .find_map(|range| self.locales.iter().find(|l| language_matches(range, l)))
+            .map_or(&self.default_locale, |l| l)
+    }
+}
+
+/// Wrapper redirecting to the canonical path of a [CanonicalRedirect]
+///
+/// If the request already has the canonical path, the wrapped responder responds.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::get;
+/// use rocket_response::{Canonical, CanonicalRedirect, RocketResponse};
+/// use std::path::PathBuf;
+///
+/// #[get("/<_path..>")]
+/// fn page(_path: PathBuf) -> RocketResponse {
+///     RocketResponse::Canonical(Box::new(Canonical::new(
+///         RocketResponse::StaticStr("page"),
+///         CanonicalRedirect::new().lowercase().strip_trailing_slash(),
+///     )))
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Canonical<R> {
+    responder: Box<R>,
+    policy: CanonicalRedirect,
+}
+
+impl<R> Canonical<R> {
+    /// Wraps `responder` to redirect to the canonical path of `policy`
+    pub fn new(responder: R, policy: CanonicalRedirect) -> Self {
+        Canonical {
+            responder: Box::new(responder),
+            policy,
+        }
+    }
+
+    /// Consumes the wrapper and returns the wrapped responder.
+    pub fn into_inner(self) -> R {
+        *self.responder
+    }
+
+    pub(crate) fn responder(&self) -> &R {
+        &self.responder
+    }
+}
+
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for Canonical<R> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        match self.policy.location(req) {
+            Some(location) if self.policy.moved => Redirect::moved(location).respond_to(req),
+            Some(location) => Redirect::permanent(location).respond_to(req),
+            None => self.responder.respond_to(req),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::{Canonical, CanonicalRedirect};
+    use crate::RocketResponse;
+    use rocket::{
+        get,
+        http::{Header, Status},
+        local::blocking::Client,
+        routes,
+    };
+    use std::path::PathBuf;
+
+    #[get("/strip/<_path..>")]
+    fn route_strip(_path: PathBuf) -> RocketResponse {
+        RocketResponse::Canonical(Box::new(Canonical::new(
+            RocketResponse::StaticStr("page"),
+            CanonicalRedirect::new().lowercase().strip_trailing_slash(),
+        )))
+    }
+
+    #[get("/add/<_path..>")]
+    fn route_add(_path: PathBuf) -> Canonical<&'static str> {
+        Canonical::new(
+            "page",
+            CanonicalRedirect::new().add_trailing_slash().moved(),
+        )
+    }
+
+    #[get("/<_path..>", rank = 2)]
+    fn route_locale(_path: PathBuf) -> Canonical<&'static str> {
+        Canonical::new(
+            "page",
+            CanonicalRedirect::new().locale_prefix(&["de", "en"], "en"),
+        )
+    }
+
+    #[test]
+    fn test_canonical() {
+        let rocket = rocket::build().mount("/", routes![route_strip, route_add, route_locale]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+        let location = |path: &str, language: Option<&str>| {
+            let mut req = client.get(path.to_string());
+            if let Some(language) = language {
+                req.add_header(Header::new("Accept-Language", language.to_string()));
+            }
+            let res = req.dispatch();
+            (
+                res.status(),
+                res.headers().get_one("Location").map(str::to_string),
+            )
+        };
+
+        assert_eq!((Status::Ok, None), location("/strip/a/b", None));
+        assert_eq!(
+            (
+                Status::PermanentRedirect,
+                Some("/strip/a/b?q=X".to_string())
+            ),
+            location("/strip/A/b/?q=X", None)
+        );
+        assert_eq!((Status::Ok, None), location("/add/a/", None));
+        assert_eq!((Status::Ok, None), location("/add/a.txt", None));
+        assert_eq!(
+            (Status::MovedPermanently, Some("/add/a/".to_string())),
+            location("/add/a", None)
+        );
+        assert_eq!((Status::Ok, None), location("/de/about", None));
+        assert_eq!(
+            (Status::PermanentRedirect, Some("/de/about".to_string())),
+            location("/about", Some("fr;q=0.9, de-AT, en;q=0.5"))
+        );
+        assert_eq!(
+            (Status::PermanentRedirect, Some("/en/".to_string())),
+            location("/", Some("fr"))
+        );
+    }
+}
@@ -0,0 +1,87 @@
+use crate::{body::BodyBuf, BuildError};
+use rocket::{
+    http::ContentType,
+    request::Request,
+    response::{self, Responder},
+    serde::Serialize,
+};
+
+/// Serializes the wrapped value into [CBOR](https://www.rfc-editor.org/rfc/rfc8949).
+///
+/// Returns a response with Content-Type `application/cbor` and a fixed-size body with the
+/// serialized value. If serialization fails, it responds as [BuildError::Serialize].
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::{get, serde::Serialize};
+/// use rocket_response::Cbor;
+///
+/// #[derive(Serialize)]
+/// #[serde(crate = "rocket::serde")]
+/// struct Measurement {
+///     sensor: u16,
+///     value: f32,
+/// }
+///
+/// #[get("/sensor/<sensor>")]
+/// fn sensor(sensor: u16) -> Cbor<Measurement> {
+///     Cbor(Measurement { sensor, value: 21.5 })
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Cbor<T>(pub T);
+
+impl<T> Cbor<T> {
+    /// Consumes the wrapper and returns the wrapped item.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Cbor<T> {
+    fn from(value: T) -> Self {
+        Cbor(value)
+    }
+}
+
+impl<'r, T: Serialize> Responder<'r, 'static> for Cbor<T> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let mut buf = BodyBuf::take();
+        match ciborium::ser::into_writer(&self.0, buf.as_mut_vec()) {
+            Ok(()) => buf.respond_to(ContentType::new("application", "cbor"), req),
+            Err(e) => BuildError::Serialize("CBOR".to_string(), e.to_string()).respond_to(req),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::Cbor;
+    use crate::RocketResponse;
+    use rocket::{get, http::Status, local::blocking::Client, routes};
+
+    #[get("/cbor/<id>")]
+    fn route_cbor(id: usize) -> RocketResponse {
+        match id {
+            0 => RocketResponse::Status(Status::NotFound),
+            _ => RocketResponse::SerdeCbor(Cbor("hello")),
+        }
+    }
+
+    #[test]
+    fn test_cbor() {
+        let rocket = rocket::build().mount("/", routes![route_cbor]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/cbor/1").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(
+            Some("application/cbor"),
+            res.headers().get_one("Content-Type")
+        );
+        // major type 3 (text string) with length 5
+        assert_eq!(b"\x65hello".to_vec(), res.into_bytes().unwrap());
+    }
+}
@@ -0,0 +1,499 @@
+use rocket::{
+    http::Status,
+    request::Request,
+    response::{self, Responder, Response},
+    tokio::{
+        self,
+        io::{AsyncRead, ReadBuf},
+        time::Sleep,
+    },
+};
+use std::{
+    collections::hash_map::RandomState,
+    future::Future,
+    hash::{BuildHasher, Hasher},
+    io,
+    pin::Pin,
+    sync::atomic::{AtomicU64, Ordering},
+    task::{Context, Poll},
+    time::Duration,
+};
+
+/// Failure rates of [ChaosWrapped]
+///
+/// The rates are probabilities between `0.0` and `1.0`, which are checked in the order
+/// internal error, unavailable, timeout and slow drip. Their sum shouldn't exceed `1.0`.
+///
+/// In release builds nothing is injected, unless [enable_in_release](Self::enable_in_release())
+/// is set.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChaosConfig {
+    internal_error: f64,
+    unavailable: f64,
+    timeout: f64,
+    timeout_after: Duration,
+    slow_drip: f64,
+    drip_chunk: usize,
+    drip_interval: Duration,
+    in_release: bool,
+}
+
+impl ChaosConfig {
+    /// Config without any failure
+    pub fn new() -> Self {
+        ChaosConfig::default()
+    }
+
+    /// Responds with `500 Internal Server Error` at `rate`.
+    pub fn internal_error(mut self, rate: f64) -> Self {
+        self.internal_error = rate;
+        self
+    }
+
+    /// Responds with `503 Service Unavailable` at `rate`.
+    pub fn unavailable(mut self, rate: f64) -> Self {
+        self.unavailable = rate;
+        self
+    }
+
+    /// Responds at `rate` with `504 Gateway Timeout`, which body stalls for `after`.
+    pub fn timeout(mut self, rate: f64, after: Duration) -> Self {
+        self.timeout = rate;
+        self.timeout_after = after;
+        self
+    }
+
+    /// Sends the body at `rate` in chunks of `chunk` bytes every `interval`.
+    pub fn slow_drip(mut self, rate: f64, chunk: usize, interval: Duration) -> Self {
+        self.slow_drip = rate;
+        self.drip_chunk = chunk.max(1);
+        self.drip_interval = interval;
+        self
+    }
+
+    /// Injects failures also in release builds.
+    pub fn enable_in_release(mut self) -> Self {
+        self.in_release = true;
+        self
+    }
+
+    fn pick(&self) -> Option<Chaos> {
+        if !cfg!(debug_assertions) && !self.in_release {
+            return None;
+        }
+
+        let r = random();
+        let mut limit = 0.0;
+        for (rate, chaos) in [
+            (self.internal_error, Chaos::InternalError),
+            (self.unavailable, Chaos::Unavailable),
+            (self.timeout, Chaos::Timeout),
+            (self.slow_drip, Chaos::SlowDrip),
+        ]
+        .iter()
+        {
+            limit += rate;
+            if r < limit {
+                return Some(*chaos);
+            }
+        }
+        None
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Chaos {
+    InternalError,
+    Unavailable,
+    Timeout,
+    SlowDrip,
+}
+
+/// Uniform random number in `[0, 1)` of a xorshift generator
+fn random() -> f64 {
+    static STATE: AtomicU64 = AtomicU64::new(0);
+
+    let mut x = STATE.load(Ordering::Relaxed);
+    if x == 0 {
+        x = RandomState::new().build_hasher().finish() | 1;
+    }
+    x ^= x << 13;
+    x ^= x >> 7;
+    x ^= x << 17;
+    STATE.store(x, Ordering::Relaxed);
+
+    (x >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Wrapper injecting failures for resilience tests of clients
+///
+/// According to the [ChaosConfig] the response of the wrapped responder is replaced by
+/// `500 Internal Server Error`, `503 Service Unavailable`, a stalling `504 Gateway Timeout`,
+/// or its body is sent slowly.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::get;
+/// use rocket_response::{ChaosConfig, ChaosWrapped, RocketResponse};
+/// use std::time::Duration;
+///
+/// #[get("/items")]
+/// fn items() -> RocketResponse {
+///     RocketResponse::ChaosWrapped(Box::new(ChaosWrapped::new(
+///         RocketResponse::StaticStr("[]"),
+///         ChaosConfig::new()
+///             .unavailable(0.05)
+///             .slow_drip(0.1, 16, Duration::from_millis(100)),
+///     )))
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ChaosWrapped<R> {
+    responder: Box<R>,
+    config: ChaosConfig,
+}
+
+impl<R> ChaosWrapped<R> {
+    /// Wraps `responder` to inject failures like configured in `config`
+    pub fn new(responder: R, config: ChaosConfig) -> Self {
+        ChaosWrapped {
+            responder: Box::new(responder),
+            config,
+        }
+    }
+
+    /// Consumes the wrapper and returns the wrapped responder.
+    pub fn into_inner(self) -> R {
+        *self.responder
+    }
+
+    pub(crate) fn responder(&self) -> &R {
+        &self.responder
+    }
+}
+
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for ChaosWrapped<R> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let chaos = self.config.pick();
+        if chaos.is_some() {
+            rocket::warn_!("Chaos injected: {:?}", chaos);
+        }
+
+        match chaos {
+            Some(Chaos::InternalError) => Err(Status::InternalServerError),
+            Some(Chaos::Unavailable) => Err(Status::ServiceUnavailable),
+            Some(Chaos::Timeout) => Response::build()
+                .status(Status::GatewayTimeout)
+                .streamed_body(DelayedRead::new(
+                    tokio::io::empty(),
+                    self.config.timeout_after,
+                ))
+                .ok(),
+            Some(Chaos::SlowDrip) => SlowBody::new(
+                *self.responder,
+                self.config.drip_chunk,
+                self.config.drip_interval,
+            )
+            .respond_to(req),
+            None => self.responder.respond_to(req),
+        }
+    }
+}
+
+/// Wrapper sending the body in chunks of `chunk` bytes every `interval`
+///
+/// The headers are sent right away, so clients see a slow transfer, e.g. of a congested
+/// network.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::get;
+/// use rocket_response::SlowBody;
+/// use std::time::Duration;
+///
+/// #[get("/slow")]
+/// fn slow() -> SlowBody<&'static str> {
+///     SlowBody::new("Hello world", 2, Duration::from_millis(500))
+/// }
+/// ```
+#[derive(Debug)]
+pub struct SlowBody<R> {
+    responder: Box<R>,
+    chunk: usize,
+    interval: Duration,
+}
+
+impl<R> SlowBody<R> {
+    /// Wraps `responder` to send `chunk` bytes of its body every `interval`
+    pub fn new(responder: R, chunk: usize, interval: Duration) -> Self {
+        SlowBody {
+            responder: Box::new(responder),
+            chunk: chunk.max(1),
+            interval,
+        }
+    }
+
+    /// Consumes the wrapper and returns the wrapped responder.
+    pub fn into_inner(self) -> R {
+        *self.responder
+    }
+}
+
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for SlowBody<R> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let mut res = self.responder.respond_to(req)?;
+        let body = res.body_mut().take();
+        res.set_streamed_body(SlowDrip::new(body, self.chunk, self.interval));
+        Ok(res)
+    }
+}
+
+/// Wrapper delaying the body of the response
+///
+/// The headers are sent right away and the first byte of the body after the delay, which
+/// runs into the read timeouts of clients.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::get;
+/// use rocket_response::{Delayed, RocketResponse};
+/// use std::time::Duration;
+///
+/// #[get("/delayed")]
+/// fn delayed() -> Delayed<RocketResponse> {
+///     Delayed::random(
+///         RocketResponse::StaticStr("Hello world"),
+///         Duration::from_millis(100),
+///         Duration::from_secs(2),
+///     )
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Delayed<R> {
+    responder: Box<R>,
+    delay: Duration,
+}
+
+impl<R> Delayed<R> {
+    /// Wraps `responder` to send its body after `delay`
+    pub fn new(responder: R, delay: Duration) -> Self {
+        Delayed {
+            responder: Box::new(responder),
+            delay,
+        }
+    }
+
+    /// Wraps `responder` to send its body after a random delay between `min` and `max`
+    pub fn random(responder: R, min: Duration, max: Duration) -> Self {
+        let delay = min + max.checked_sub(min).unwrap_or_default().mul_f64(random());
+        Delayed::new(responder, delay)
+    }
+
+    /// The delay of the body
+    pub fn delay(&self) -> Duration {
+        self.delay
+    }
+
+    /// Consumes the wrapper and returns the wrapped responder.
+    pub fn into_inner(self) -> R {
+        *self.responder
+    }
+}
+
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for Delayed<R> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let mut res = self.responder.respond_to(req)?;
+        let body = res.body_mut().take();
+        res.set_streamed_body(DelayedRead::new(body, self.delay));
+        Ok(res)
+    }
+}
+
+/// Polls the sleep of `delay`, which is started at the first call.
+fn poll_delay(
+    delay: &mut Option<Pin<Box<Sleep>>>,
+    duration: Duration,
+    cx: &mut Context<'_>,
+) -> Poll<()> {
+    delay
+        .get_or_insert_with(|| Box::pin(tokio::time::sleep(duration)))
+        .as_mut()
+        .poll(cx)
+}
+
+/// Body reading at most `chunk` bytes of the wrapped body every `interval`
+struct SlowDrip<B> {
+    body: B,
+    buf: Vec<u8>,
+    interval: Duration,
+    delay: Option<Pin<Box<Sleep>>>,
+}
+
+impl<B> SlowDrip<B> {
+    fn new(body: B, chunk: usize, interval: Duration) -> Self {
+        SlowDrip {
+            body,
+            buf: vec![0; chunk],
+            interval,
+            delay: None,
+        }
+    }
+}
+
+impl<B: AsyncRead + Unpin> AsyncRead for SlowDrip<B> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = &mut *self;
+        if poll_delay(&mut this.delay, this.interval, cx).is_pending() {
+            return Poll::Pending;
+        }
+
+        let len = this.buf.len().min(buf.remaining());
+        let mut chunk = ReadBuf::new(&mut this.buf[..len]);
+        match Pin::new(&mut this.body).poll_read(cx, &mut chunk) {
+            Poll::Ready(Ok(())) => {
+                buf.put_slice(chunk.filled());
+                this.delay = None;
+                Poll::Ready(Ok(()))
+            }
+            other => other,
+        }
+    }
+}
+
+/// Body reading the wrapped body after `delay`
+struct DelayedRead<B> {
+    body: B,
+    delay: Duration,
+    sleep: Option<Pin<Box<Sleep>>>,
+    elapsed: bool,
+}
+
+impl<B> DelayedRead<B> {
+    fn new(body: B, delay: Duration) -> Self {
+        DelayedRead {
+            body,
+            delay,
+            sleep: None,
+            elapsed: false,
+        }
+    }
+}
+
+impl<B: AsyncRead + Unpin> AsyncRead for DelayedRead<B> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = &mut *self;
+        if !this.elapsed {
+            if poll_delay(&mut this.sleep, this.delay, cx).is_pending() {
+                return Poll::Pending;
+            }
+            this.elapsed = true;
+            this.sleep = None;
+        }
+        Pin::new(&mut this.body).poll_read(cx, buf)
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::{random, ChaosConfig, ChaosWrapped, Delayed, SlowBody};
+    use crate::RocketResponse;
+    use rocket::{get, http::Status, local::blocking::Client, routes};
+    use std::time::{Duration, Instant};
+
+    fn chaos(config: ChaosConfig) -> RocketResponse {
+        RocketResponse::ChaosWrapped(Box::new(ChaosWrapped::new(
+            RocketResponse::StaticStr("Hello world"),
+            config,
+        )))
+    }
+
+    #[get("/chaos/<mode>")]
+    fn route_chaos(mode: &str) -> RocketResponse {
+        match mode {
+            "error" => chaos(ChaosConfig::new().internal_error(1.0)),
+            "unavailable" => chaos(ChaosConfig::new().unavailable(1.0)),
+            "timeout" => chaos(ChaosConfig::new().timeout(1.0, Duration::from_millis(50))),
+            "slow" => chaos(ChaosConfig::new().slow_drip(1.0, 4, Duration::from_millis(10))),
+            _ => chaos(ChaosConfig::new()),
+        }
+    }
+
+    #[get("/slow_body")]
+    fn route_slow_body() -> SlowBody<&'static str> {
+        SlowBody::new("Hello world", 5, Duration::from_millis(10))
+    }
+
+    #[get("/delayed")]
+    fn route_delayed() -> Delayed<RocketResponse> {
+        Delayed::new(
+            RocketResponse::StaticStr("Hello world"),
+            Duration::from_millis(50),
+        )
+    }
+
+    #[test]
+    fn test_random() {
+        assert!((0..1000).map(|_| random()).all(|r| (0.0..1.0).contains(&r)));
+
+        let min = Duration::from_millis(10);
+        let max = Duration::from_millis(20);
+        let delay = Delayed::random("", min, max).delay();
+        assert!(min <= delay && delay <= max);
+        assert_eq!(min, Delayed::random("", min, min).delay());
+    }
+
+    #[test]
+    fn test_latency() {
+        let rocket = rocket::build().mount("/", routes![route_slow_body, route_delayed]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let start = Instant::now();
+        let res = client.get("/slow_body").dispatch();
+        assert_eq!("Hello world", res.into_string().unwrap());
+        assert!(start.elapsed() >= Duration::from_millis(30));
+
+        let start = Instant::now();
+        let res = client.get("/delayed").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!("Hello world", res.into_string().unwrap());
+        assert!(start.elapsed() >= Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_chaos() {
+        let rocket = rocket::build().mount("/", routes![route_chaos]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/chaos/none").dispatch();
+        assert_eq!("Hello world", res.into_string().unwrap());
+
+        let res = client.get("/chaos/error").dispatch();
+        assert_eq!(Status::InternalServerError, res.status());
+
+        let res = client.get("/chaos/unavailable").dispatch();
+        assert_eq!(Status::ServiceUnavailable, res.status());
+
+        let start = Instant::now();
+        let res = client.get("/chaos/timeout").dispatch();
+        assert_eq!(Status::GatewayTimeout, res.status());
+        assert_eq!(Some(String::new()), res.into_string());
+        assert!(start.elapsed() >= Duration::from_millis(50));
+
+        let start = Instant::now();
+        let res = client.get("/chaos/slow").dispatch();
+        assert_eq!("Hello world", res.into_string().unwrap());
+        assert!(start.elapsed() >= Duration::from_millis(30));
+    }
+}
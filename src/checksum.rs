@@ -0,0 +1,167 @@
+use sha2::{Digest, Sha256};
+use std::fmt::Write;
+
+/// Rolling checksum over the pages of a [Paginated](crate::Paginated) export
+///
+/// The checksum of a page is the hex SHA-256 of the checksum of the previous page, empty
+/// for the first page, followed by the body of the page. So it verifies the body and all
+/// pages before. After the last page, the [final](Self::finalize()) checksum additionally
+/// covers the number of pages, so a truncated sequence doesn't verify.
+///
+/// A client keeps a chain while fetching the pages and [verifies](Self::verify()) the
+/// `X-Checksum-Chain` header of each page and the `X-Checksum-Final` header of the last.
+///
+/// Requires the `checksum` feature.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket_response::ChecksumChain;
+///
+/// let mut chain = ChecksumChain::new();
+/// let checksum = ChecksumChain::new().update(b"[1,2]").to_string();
+/// assert!(chain.verify(b"[1,2]", &checksum));
+/// assert!(!chain.verify(b"[4,4]", &checksum));
+/// assert_eq!(1, chain.pages());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChecksumChain {
+    value: String,
+    pages: u64,
+}
+
+impl ChecksumChain {
+    /// Chain before the first page
+    pub fn new() -> Self {
+        ChecksumChain::default()
+    }
+
+    /// Chain continuing after the page with the checksum `value`, which is the page number
+    /// `pages`
+    pub fn resume<S: Into<String>>(value: S, pages: u64) -> Self {
+        ChecksumChain {
+            value: value.into(),
+            pages,
+        }
+    }
+
+    /// Adds the body of the next page and returns its checksum.
+    pub fn update(&mut self, page: &[u8]) -> &str {
+        self.value = hex(Sha256::new()
+            .chain_update(self.value.as_bytes())
+            .chain_update(page)
+            .finalize());
+        self.pages += 1;
+        &self.value
+    }
+
+    /// Checks the body of the next page against its `checksum` and adds it, if it matches.
+    ///
+    /// On a mismatch the chain is left unchanged, so the page can be fetched again.
+    pub fn verify(&mut self, page: &[u8], checksum: &str) -> bool {
+        let mut next = self.clone();
+        if !next.update(page).eq_ignore_ascii_case(checksum.trim()) {
+            return false;
+        }
+        *self = next;
+        true
+    }
+
+    /// Checksum of the last added page, empty before the first page
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Number of added pages
+    pub fn pages(&self) -> u64 {
+        self.pages
+    }
+
+    /// Final checksum of all added pages and their number
+    pub fn finalize(&self) -> String {
+        hex(Sha256::new()
+            .chain_update(self.value.as_bytes())
+            .chain_update(b"\n")
+            .chain_update(self.pages.to_string().as_bytes())
+            .finalize())
+    }
+}
+
+/// Lowercase hex digits of `bytes`
+fn hex<B: AsRef<[u8]>>(bytes: B) -> String {
+    let mut hex = String::with_capacity(bytes.as_ref().len() * 2);
+    for byte in bytes.as_ref() {
+        let _ = write!(hex, "{:02x}", byte);
+    }
+    hex
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ChecksumChain;
+
+    #[test]
+    fn test_new() {
+        let chain = ChecksumChain::new();
+        assert_eq!("", chain.value());
+        assert_eq!(0, chain.pages());
+    }
+
+    #[test]
+    fn test_update() {
+        let mut chain = ChecksumChain::new();
+        let first = chain.update(b"[1,2]").to_string();
+        assert_eq!(64, first.len());
+        assert_eq!(1, chain.pages());
+
+        let second = chain.update(b"[3,4]").to_string();
+        assert_ne!(first, second);
+        assert_eq!(second, chain.value());
+        assert_eq!(2, chain.pages());
+
+        let mut other = ChecksumChain::new();
+        other.update(b"[3,4]");
+        assert_ne!(second, other.update(b"[1,2]"));
+    }
+
+    #[test]
+    fn test_resume() {
+        let mut chain = ChecksumChain::new();
+        let first = chain.update(b"[1,2]").to_string();
+        let second = chain.update(b"[3,4]").to_string();
+
+        let mut resumed = ChecksumChain::resume(first, 1);
+        assert_eq!(second, resumed.update(b"[3,4]"));
+        assert_eq!(chain, resumed);
+    }
+
+    #[test]
+    fn test_verify() {
+        let mut server = ChecksumChain::new();
+        let first = server.update(b"[1,2]").to_string();
+        let second = server.update(b"[3,4]").to_string();
+
+        let mut chain = ChecksumChain::new();
+        assert!(chain.verify(b"[1,2]", &first.to_uppercase()));
+        assert!(!chain.verify(b"[4,4]", &second));
+        assert_eq!(first, chain.value());
+        assert_eq!(1, chain.pages());
+        assert!(chain.verify(b"[3,4]", &format!(" {} ", second)));
+        assert_eq!(server, chain);
+    }
+
+    #[test]
+    fn test_finalize() {
+        let mut chain = ChecksumChain::new();
+        chain.update(b"[1,2]");
+        let truncated = chain.finalize();
+        chain.update(b"[3,4]");
+        let complete = chain.finalize();
+        assert_ne!(truncated, complete);
+        assert_eq!(complete, chain.clone().finalize());
+
+        let value = chain.value().to_string();
+        assert_ne!(complete, ChecksumChain::resume(value, 3).finalize());
+        assert_ne!(ChecksumChain::new().finalize(), complete);
+    }
+}
@@ -0,0 +1,909 @@
+use crate::{ETag, RocketError};
+use flate2::write::GzEncoder;
+use rocket::{
+    futures::task::noop_waker_ref,
+    http::{ContentType, Header, Status},
+    request::Request,
+    response::{self, Body, Responder, Response},
+    tokio::io::{AsyncRead, AsyncReadExt, ReadBuf},
+};
+use std::{
+    io::{self, Cursor, Write},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Content coding of [Compressed]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Encoding {
+    /// `br`
+    Brotli,
+    /// `gzip`
+    Gzip,
+}
+
+impl Encoding {
+    /// Token of the `Content-Encoding` header
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Encoding::Brotli => "br",
+            Encoding::Gzip => "gzip",
+        }
+    }
+}
+
+/// Wrapper compressing the body with brotli or gzip
+///
+/// The body is compressed, if the `Accept-Encoding` header of the request allows one of the
+/// encodings, the content type is compressible like text, JSON, XML, JavaScript or SVG, the
+/// response has no `Content-Encoding` yet and its size isn't known to be below the
+/// [minimum size](Self::min_size()). The encodings ranked by the quality values of the
+/// client are preferred, brotli over gzip and both over `identity` with the same value.
+///
+/// Further content types are compressed with [compress_type](Self::compress_type()), but
+/// the ones already compressed by their format, like images, audio, video, fonts and
+/// archives, are skipped unless given exactly. A body of `application/octet-stream`, once
+/// enabled, is sniffed: if it starts like such a format, e.g. a gzip or zip file, or isn't
+/// readable without waiting like a file, it is sent uncompressed without `Content-Length`.
+///
+/// If the client excludes `identity` by `identity;q=0` or `*;q=0`, the body is compressed
+/// regardless of its size and of byte ranges. If no encoding is acceptable either, a
+/// successful response is replaced by `406 Not Acceptable`, see
+/// [RFC 9110](https://www.rfc-editor.org/rfc/rfc9110#name-accept-encoding). Responses with
+/// a content type which isn't compressible aren't negotiated.
+///
+/// `Vary: Accept-Encoding` is added to every response with a compressible content type and
+/// to a `304 Not Modified`. A compressed body is streamed, so `Content-Length` isn't sent.
+///
+/// The compressed body is another representation than the uncompressed one, so the tag of
+/// an `ETag` gets the content coding as suffix, like `"v1-gzip"` for `"v1"`, see
+/// [RFC 9110](https://www.rfc-editor.org/rfc/rfc9110#name-etag). [Cached](crate::Cached)
+/// accepts this tag in `If-None-Match`, and a `304 Not Modified` keeps the coded tag the
+/// client sent.
+///
+/// A body known to be empty is never compressed, also with a minimum size of `0`, so it
+/// keeps its `Content-Length: 0` and gets no `Content-Encoding`. A `HEAD` request gets the
+/// same headers as the `GET` request, Rocket removes the body afterwards. This behavior is
+/// guaranteed to stay compatible.
+///
+/// Byte ranges refer to the uncompressed representation, so a `206 Partial Content` or
+/// any response with `Content-Range` is never compressed, which would corrupt resumed
+/// downloads. A full response of a resource with `Accept-Ranges: bytes`, like of a
+/// [RangedFile](crate::RangedFile), is by default also sent uncompressed. With
+/// [compress_ranges](Self::compress_ranges()) or `compress_ranges = true` of the Rocket
+/// config it is compressed and `Accept-Ranges: none` is sent instead, so clients don't
+/// request ranges of the compressed body.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::get;
+/// use rocket_response::{Compressed, RocketResponse};
+///
+/// #[get("/")]
+/// fn index() -> RocketResponse {
+///     RocketResponse::Compressed(Box::new(
+///         Compressed::new(RocketResponse::Html(rocket::response::content::RawHtml(
+///             "<p>Hello world</p>",
+///         )))
+///         .min_size(0),
+///     ))
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Compressed<R> {
+    responder: Box<R>,
+    settings: Settings,
+}
+
+/// Settings of [Compressed], also applied by a [ResponseFilterSet](crate::ResponseFilterSet)
+#[derive(Debug, Clone)]
+pub(crate) struct Settings {
+    min_size: usize,
+    gzip_level: u32,
+    brotli_quality: u32,
+    compress_ranges: Option<bool>,
+    compress_types: Vec<ContentType>,
+    skip_types: Vec<ContentType>,
+}
+
+impl<R> Compressed<R> {
+    /// Wraps `responder` to compress bodies of at least 1 KiB
+    pub fn new(responder: R) -> Self {
+        Compressed {
+            responder: Box::new(responder),
+            settings: Settings {
+                min_size: 1024,
+                gzip_level: 6,
+                brotli_quality: 5,
+                compress_ranges: None,
+                compress_types: Vec::new(),
+                skip_types: Vec::new(),
+            },
+        }
+    }
+
+    /// Sets the minimum size of a body to be compressed.
+    pub fn min_size(mut self, min_size: usize) -> Self {
+        self.settings.min_size = min_size;
+        self
+    }
+
+    /// Sets the gzip level from `0` to `9`, default is `6`.
+    pub fn gzip_level(mut self, level: u32) -> Self {
+        self.settings.gzip_level = level.min(9);
+        self
+    }
+
+    /// Sets the brotli quality from `0` to `11`, default is `5`.
+    pub fn brotli_quality(mut self, quality: u32) -> Self {
+        self.settings.brotli_quality = quality.min(11);
+        self
+    }
+
+    /// Sets, if a full response supporting byte ranges is compressed, instead of the
+    /// Rocket config.
+    pub fn compress_ranges(mut self, compress: bool) -> Self {
+        self.settings.compress_ranges = Some(compress);
+        self
+    }
+
+    /// Compresses also bodies of `content_type`, like `application/wasm`.
+    ///
+    /// A `*` as type or subtype matches any, so `*/*` compresses every content type except
+    /// the already compressed ones. A type given exactly is compressed even if it is
+    /// already compressed.
+    pub fn compress_type(mut self, content_type: ContentType) -> Self {
+        self.settings.compress_types.push(content_type);
+        self
+    }
+
+    /// Never compresses bodies of `content_type`, which may contain `*` like
+    /// [compress_type](Self::compress_type()).
+    pub fn skip_type(mut self, content_type: ContentType) -> Self {
+        self.settings.skip_types.push(content_type);
+        self
+    }
+
+    /// Consumes the wrapper and returns the wrapped responder.
+    pub fn into_inner(self) -> R {
+        *self.responder
+    }
+
+    pub(crate) fn responder(&self) -> &R {
+        &self.responder
+    }
+
+    pub(crate) fn into_settings(self) -> Settings {
+        self.settings
+    }
+}
+
+/// `true` if `content_type` matches `pattern`, which may contain `*` as type or subtype
+fn matches(pattern: &ContentType, content_type: &ContentType) -> bool {
+    (pattern.top() == "*" || pattern.top() == content_type.top())
+        && (pattern.sub() == "*" || pattern.sub() == content_type.sub())
+}
+
+/// `true` for content types compressed by default
+fn compressible(content_type: &ContentType) -> bool {
+    let (top, sub) = (content_type.top(), content_type.sub());
+    top == "text"
+        || sub == "json"
+        || sub == "xml"
+        || sub == "javascript"
+        || sub == "svg+xml"
+        || sub.as_str().ends_with("+json")
+        || sub.as_str().ends_with("+xml")
+}
+
+/// `true` for content types already compressed by their format
+fn precompressed(content_type: &ContentType) -> bool {
+    let sub = content_type.sub().as_str();
+    match content_type.top().as_str() {
+        "audio" | "video" => true,
+        "image" => !matches!(sub, "svg+xml" | "bmp" | "x-icon" | "vnd.microsoft.icon"),
+        "font" => matches!(sub, "woff" | "woff2"),
+        "application" => matches!(
+            sub,
+            "zip"
+                | "gzip"
+                | "x-gzip"
+                | "x-bzip2"
+                | "x-xz"
+                | "zstd"
+                | "x-7z-compressed"
+                | "vnd.rar"
+                | "x-rar-compressed"
+                | "font-woff"
+        ),
+        _ => false,
+    }
+}
+
+/// `true` if `prefix` starts like a compressed format, an archive, image, audio or video
+fn compressed_magic(prefix: &[u8]) -> bool {
+    const MAGIC: &[&[u8]] = &[
+        b"\x1f\x8b",           // gzip
+        b"PK\x03\x04",         // zip
+        b"\x28\xb5\x2f\xfd",   // zstd
+        b"\xfd7zXZ\x00",       // xz
+        b"BZh",                // bzip2
+        b"7z\xbc\xaf\x27\x1c", // 7z
+        b"Rar!\x1a\x07",       // rar
+        b"\x89PNG",            // png
+        b"\xff\xd8\xff",       // jpeg
+        b"GIF8",               // gif
+        b"OggS",               // ogg
+        b"wOFF",               // woff
+        b"wOF2",               // woff2
+    ];
+    MAGIC.iter().any(|magic| prefix.starts_with(magic))
+        || (prefix.starts_with(b"RIFF") && prefix.get(8..12) == Some(&b"WEBP"[..]))
+        || prefix.get(4..8) == Some(&b"ftyp"[..])
+}
+
+/// Reads up to 16 bytes of `body` into `prefix`, `false` if they aren't ready without
+/// waiting, like of a file.
+fn peek(body: &mut Body<'_>, prefix: &mut Vec<u8>) -> bool {
+    let mut cx = Context::from_waker(noop_waker_ref());
+    let mut chunk = [0; 16];
+    while prefix.len() < chunk.len() {
+        let len = chunk.len() - prefix.len();
+        let mut buf = ReadBuf::new(&mut chunk[..len]);
+        match Pin::new(&mut *body).poll_read(&mut cx, &mut buf) {
+            Poll::Ready(Ok(())) if buf.filled().is_empty() => break,
+            Poll::Ready(Ok(())) => prefix.extend_from_slice(buf.filled()),
+            Poll::Ready(Err(_)) | Poll::Pending => return false,
+        }
+    }
+    true
+}
+
+/// Content coding negotiated by the `Accept-Encoding` header
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Coding {
+    /// No content coding
+    Identity,
+    /// The encoding and if `identity` is acceptable, too
+    Encoded(Encoding, bool),
+    /// Neither an encoding nor `identity` is acceptable.
+    NotAcceptable,
+}
+
+/// Negotiates the content coding of the `Accept-Encoding` header of `req`.
+///
+/// The highest quality value wins, brotli before gzip before `identity` with the same
+/// value. `identity` is acceptable unless excluded by `identity;q=0` or by `*;q=0` without
+/// an own entry, see [RFC 9110](https://www.rfc-editor.org/rfc/rfc9110#name-accept-encoding).
+/// If it isn't listed, it is the least preferred one. Entries with an invalid quality value
+/// are ignored.
+fn negotiate(req: &Request<'_>) -> Coding {
+    let (mut brotli, mut gzip, mut identity, mut any) = (None, None, None, None);
+    for coding in req
+        .headers()
+        .get("Accept-Encoding")
+        .flat_map(|header| header.split(','))
+    {
+        let mut parts = coding.split(';');
+        let name = parts.next().unwrap_or("").trim().to_ascii_lowercase();
+        let q = parts
+            .filter_map(|param| param.split_once('='))
+            .find(|(key, _)| key.trim().eq_ignore_ascii_case("q"))
+            .map_or(Some(1.0), |(_, q)| {
+                q.trim()
+                    .parse::<f32>()
+                    .ok()
+                    .filter(|q| (0.0..=1.0).contains(q))
+            });
+        let q = match q {
+            Some(q) => q,
+            None => continue,
+        };
+        let entry = match name.as_str() {
+            "br" => &mut brotli,
+            "gzip" | "x-gzip" => &mut gzip,
+            "identity" => &mut identity,
+            "*" => &mut any,
+            _ => continue,
+        };
+        entry.get_or_insert(q);
+    }
+
+    let brotli = brotli.or(any).unwrap_or(0.0);
+    let gzip = gzip.or(any).unwrap_or(0.0);
+    let identity = identity.or(any).unwrap_or(f32::MIN_POSITIVE);
+    if brotli > 0.0 && brotli >= gzip && brotli >= identity {
+        Coding::Encoded(Encoding::Brotli, identity > 0.0)
+    } else if gzip > 0.0 && gzip >= identity {
+        Coding::Encoded(Encoding::Gzip, identity > 0.0)
+    } else if identity > 0.0 {
+        Coding::Identity
+    } else {
+        Coding::NotAcceptable
+    }
+}
+
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for Compressed<R> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let res = self.responder.respond_to(req)?;
+        self.settings.compress(req, res)
+    }
+}
+
+impl Settings {
+    /// `true` if bodies of `content_type` are compressed
+    fn compresses(&self, content_type: &ContentType) -> bool {
+        if self
+            .skip_types
+            .iter()
+            .any(|skip| matches(skip, content_type))
+        {
+            return false;
+        }
+        // a type given exactly overrides the already compressed ones
+        if self
+            .compress_types
+            .iter()
+            .any(|ct| ct.top() == content_type.top() && ct.sub() == content_type.sub())
+        {
+            return true;
+        }
+        !precompressed(content_type)
+            && (compressible(content_type)
+                || self
+                    .compress_types
+                    .iter()
+                    .any(|ct| matches(ct, content_type)))
+    }
+
+    /// Compresses the body of `res` responded to `req`.
+    pub(crate) fn compress<'r, 'o: 'r>(
+        &self,
+        req: &'r Request<'_>,
+        mut res: Response<'o>,
+    ) -> response::Result<'o> {
+        if res.status() == Status::NotModified {
+            res.adjoin_header(Header::new("Vary", "Accept-Encoding"));
+            if let Coding::Encoded(encoding, _) = negotiate(req) {
+                encode_etag(req, &mut res, encoding, true);
+            }
+            return Ok(res);
+        }
+        let binary = match res.content_type() {
+            Some(content_type) if self.compresses(&content_type) => {
+                content_type.top() == "application" && content_type.sub() == "octet-stream"
+            }
+            _ => return Ok(res),
+        };
+        res.adjoin_header(Header::new("Vary", "Accept-Encoding"));
+
+        if res.body().preset_size() == Some(0)
+            || res.body().is_none()
+            || res.headers().contains("Content-Encoding")
+            || res.status() == Status::PartialContent
+            || res.headers().contains("Content-Range")
+        {
+            return Ok(res);
+        }
+        let (encoding, identity) = match negotiate(req) {
+            Coding::Encoded(encoding, identity) => (encoding, identity),
+            Coding::Identity => return Ok(res),
+            Coding::NotAcceptable if res.status().class().is_success() => {
+                let mut res = RocketError::Other(Status::NotAcceptable, None).respond_to(req)?;
+                res.adjoin_header(Header::new("Vary", "Accept-Encoding"));
+                return Ok(res);
+            }
+            Coding::NotAcceptable => return Ok(res),
+        };
+
+        let min_size = self.min_size;
+        let too_small = res
+            .body()
+            .preset_size()
+            .map_or(false, |size| size < min_size);
+        if too_small && identity {
+            return Ok(res);
+        }
+        let ranges = res
+            .headers()
+            .get_one("Accept-Ranges")
+            .map_or(false, |accept| !accept.trim().eq_ignore_ascii_case("none"));
+        if ranges && identity {
+            let compress_ranges = self.compress_ranges.unwrap_or_else(|| {
+                req.rocket()
+                    .figment()
+                    .extract_inner::<bool>("compress_ranges")
+                    .unwrap_or(false)
+            });
+            if !compress_ranges {
+                return Ok(res);
+            }
+        }
+        if ranges {
+            res.set_header(Header::new("Accept-Ranges", "none"));
+        }
+
+        let mut body = res.body_mut().take();
+        let mut prefix = Vec::new();
+        if binary {
+            // the payload of a binary body may be compressed already
+            if !peek(&mut body, &mut prefix) || compressed_magic(&prefix) {
+                res.set_streamed_body(Cursor::new(prefix).chain(body));
+                return Ok(res);
+            }
+        }
+        let body = Cursor::new(prefix).chain(body);
+        let encoder = match encoding {
+            Encoding::Brotli => Encoder::Brotli(Box::new(brotli::CompressorWriter::new(
+                Vec::new(),
+                4096,
+                self.brotli_quality,
+                22,
+            ))),
+            Encoding::Gzip => Encoder::Gzip(GzEncoder::new(
+                Vec::new(),
+                flate2::Compression::new(self.gzip_level),
+            )),
+        };
+        res.set_streamed_body(Compress::new(body, encoder));
+        res.set_header(Header::new("Content-Encoding", encoding.as_str()));
+        encode_etag(req, &mut res, encoding, false);
+        Ok(res)
+    }
+}
+
+/// Adds the content coding `encoding` to the `ETag` of `res`.
+///
+/// With `not_modified` only a tag the client sent in `If-None-Match` is taken, so a
+/// `304 Not Modified` names the representation the client has.
+fn encode_etag(req: &Request<'_>, res: &mut Response<'_>, encoding: Encoding, not_modified: bool) {
+    let etag = match res
+        .headers()
+        .get_one("ETag")
+        .and_then(ETag::parse_list)
+        .and_then(|mut tags| tags.pop())
+    {
+        Some(etag) => etag.encoded(encoding),
+        None => return,
+    };
+    if not_modified {
+        let sent = req
+            .headers()
+            .get_one("If-None-Match")
+            .and_then(ETag::parse_list)
+            .map_or(false, |tags| tags.iter().any(|tag| tag.weak_eq(&etag)));
+        if !sent {
+            return;
+        }
+    }
+    res.set_header(etag);
+}
+
+/// Streaming encoder writing into a `Vec<u8>`
+enum Encoder {
+    Brotli(Box<brotli::CompressorWriter<Vec<u8>>>),
+    Gzip(GzEncoder<Vec<u8>>),
+}
+
+impl Encoder {
+    /// Compresses `data` and appends the output written so far to `output`.
+    fn write(&mut self, data: &[u8], output: &mut Vec<u8>) -> io::Result<()> {
+        let written = match self {
+            Encoder::Brotli(w) => {
+                w.write_all(data)?;
+                w.get_mut()
+            }
+            Encoder::Gzip(w) => {
+                w.write_all(data)?;
+                w.get_mut()
+            }
+        };
+        output.append(written);
+        Ok(())
+    }
+
+    /// Writes the end of the stream and appends the remaining output to `output`.
+    fn finish(self, output: &mut Vec<u8>) -> io::Result<()> {
+        let mut written = match self {
+            Encoder::Brotli(w) => w.into_inner(),
+            Encoder::Gzip(w) => w.finish()?,
+        };
+        output.append(&mut written);
+        Ok(())
+    }
+}
+
+/// Body compressing the wrapped body
+struct Compress<B> {
+    body: B,
+    encoder: Option<Encoder>,
+    chunk: Vec<u8>,
+    output: Vec<u8>,
+    pos: usize,
+}
+
+impl<B> Compress<B> {
+    fn new(body: B, encoder: Encoder) -> Self {
+        Compress {
+            body,
+            encoder: Some(encoder),
+            chunk: vec![0; 8192],
+            output: Vec::new(),
+            pos: 0,
+        }
+    }
+}
+
+impl<B: AsyncRead + Unpin> AsyncRead for Compress<B> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = &mut *self;
+        loop {
+            if this.pos < this.output.len() {
+                let len = buf.remaining().min(this.output.len() - this.pos);
+                buf.put_slice(&this.output[this.pos..this.pos + len]);
+                this.pos += len;
+                if this.pos == this.output.len() {
+                    this.output.clear();
+                    this.pos = 0;
+                }
+                return Poll::Ready(Ok(()));
+            }
+            let encoder = match &mut this.encoder {
+                Some(encoder) => encoder,
+                None => return Poll::Ready(Ok(())),
+            };
+
+            let mut chunk = ReadBuf::new(&mut this.chunk);
+            match Pin::new(&mut this.body).poll_read(cx, &mut chunk) {
+                Poll::Ready(Ok(())) if chunk.filled().is_empty() => {
+                    if let Some(encoder) = this.encoder.take() {
+                        encoder.finish(&mut this.output)?;
+                    }
+                }
+                Poll::Ready(Ok(())) => encoder.write(chunk.filled(), &mut this.output)?,
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::{Compressed, Encoding};
+    use crate::{Cached, ETag, RangedFile, RocketResponse};
+    use rocket::{
+        figment::Figment,
+        get,
+        http::{ContentType, Header, Status},
+        local::blocking::Client,
+        response::content::RawJson,
+        routes,
+    };
+    use std::io::{self, Read};
+
+    fn body() -> String {
+        format!("[{}0]", "1234567890,".repeat(200))
+    }
+
+    #[get("/compressed")]
+    fn route_compressed() -> RocketResponse {
+        RocketResponse::Compressed(Box::new(Compressed::new(RocketResponse::Json(RawJson(
+            Box::leak(body().into_boxed_str()),
+        )))))
+    }
+
+    #[get("/compressed_small")]
+    fn route_compressed_small() -> Compressed<RawJson<&'static str>> {
+        Compressed::new(RawJson("[]"))
+    }
+
+    #[get("/compressed_empty")]
+    fn route_compressed_empty() -> Compressed<RawJson<&'static str>> {
+        Compressed::new(RawJson("")).min_size(0)
+    }
+
+    #[get("/compressed_binary")]
+    fn route_compressed_binary() -> Compressed<Vec<u8>> {
+        Compressed::new(vec![0; 4096]).min_size(0)
+    }
+
+    #[test]
+    fn test_compressed() {
+        let rocket = rocket::build().mount(
+            "/",
+            routes![
+                route_compressed,
+                route_compressed_small,
+                route_compressed_empty,
+                route_compressed_binary
+            ],
+        );
+        let client = Client::tracked(rocket).expect("no rocket instance");
+        let get = |path: &str, accept: Option<&str>| {
+            let mut req = client.get(path.to_string());
+            if let Some(accept) = accept {
+                req.add_header(Header::new("Accept-Encoding", accept.to_string()));
+            }
+            req.dispatch()
+        };
+
+        let res = get("/compressed", Some("gzip, deflate, br"));
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(Some("br"), res.headers().get_one("Content-Encoding"));
+        assert_eq!(Some("Accept-Encoding"), res.headers().get_one("Vary"));
+        let compressed = res.into_bytes().unwrap();
+        assert!(compressed.len() < body().len());
+        let mut decompressed = String::new();
+        brotli::Decompressor::new(&compressed[..], 4096)
+            .read_to_string(&mut decompressed)
+            .unwrap();
+        assert_eq!(body(), decompressed);
+
+        let res = get("/compressed", Some("br;q=0.5, gzip"));
+        assert_eq!(
+            Some(Encoding::Gzip.as_str()),
+            res.headers().get_one("Content-Encoding")
+        );
+        let compressed = res.into_bytes().unwrap();
+        let mut decompressed = String::new();
+        flate2::read::GzDecoder::new(&compressed[..])
+            .read_to_string(&mut decompressed)
+            .unwrap();
+        assert_eq!(body(), decompressed);
+
+        for accept in [
+            None,
+            Some(""),
+            Some("identity"),
+            Some("gzip;q=0.5, identity"),
+            Some("br;q=0, gzip;Q=0"),
+            Some("gzip;q=2, br;q=x"),
+        ]
+        .iter()
+        {
+            let res = get("/compressed", *accept);
+            assert!(!res.headers().contains("Content-Encoding"), "{:?}", accept);
+            assert_eq!(Some("Accept-Encoding"), res.headers().get_one("Vary"));
+            assert_eq!(body(), res.into_string().unwrap());
+        }
+
+        for (accept, encoding) in [
+            ("gzip;q=0.8, br;q=0.4, identity;q=0.1", "gzip"),
+            ("*", "br"),
+            ("identity;q=0.5, *", "br"),
+            ("x-gzip", "gzip"),
+        ]
+        .iter()
+        {
+            let res = get("/compressed", Some(accept));
+            assert_eq!(Some(*encoding), res.headers().get_one("Content-Encoding"));
+        }
+
+        for accept in [
+            "*;q=0",
+            "identity;q=0",
+            "deflate, identity;q=0",
+            "gzip;q=0, *;q=0",
+        ]
+        .iter()
+        {
+            let res = get("/compressed", Some(accept));
+            assert_eq!(Status::NotAcceptable, res.status(), "{}", accept);
+            assert_eq!(Some("Accept-Encoding"), res.headers().get_one("Vary"));
+        }
+        let res = get("/compressed", Some("identity;q=0, br;q=0, gzip"));
+        assert_eq!(Some("gzip"), res.headers().get_one("Content-Encoding"));
+
+        let res = get("/compressed_small", Some("gzip"));
+        assert!(!res.headers().contains("Content-Encoding"));
+        let res = get("/compressed_small", Some("gzip, identity;q=0"));
+        assert_eq!(Some("gzip"), res.headers().get_one("Content-Encoding"));
+
+        let res = get("/compressed_empty", Some("gzip"));
+        assert!(!res.headers().contains("Content-Encoding"));
+        assert_eq!(Some("Accept-Encoding"), res.headers().get_one("Vary"));
+        assert_eq!(Some(Vec::new()), res.into_bytes());
+
+        let res = client
+            .head("/compressed")
+            .header(Header::new("Accept-Encoding", "gzip"))
+            .dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(Some("gzip"), res.headers().get_one("Content-Encoding"));
+        assert_eq!(Some("Accept-Encoding"), res.headers().get_one("Vary"));
+        assert!(res.into_bytes().unwrap_or_default().is_empty());
+
+        let res = get("/compressed_binary", Some("gzip"));
+        assert!(!res.headers().contains("Content-Encoding"));
+        assert!(!res.headers().contains("Vary"));
+    }
+
+    #[get("/compressed_etag")]
+    fn route_compressed_etag() -> Compressed<Cached<RawJson<String>>> {
+        Compressed::new(Cached::new(RawJson(body())).etag(ETag::strong("v1")))
+    }
+
+    #[test]
+    fn test_compressed_etag() {
+        let rocket = rocket::build().mount("/", routes![route_compressed_etag]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+        let get = |accept: &str, if_none_match: Option<&str>| {
+            let mut req = client
+                .get("/compressed_etag")
+                .header(Header::new("Accept-Encoding", accept.to_string()));
+            if let Some(if_none_match) = if_none_match {
+                req.add_header(Header::new("If-None-Match", if_none_match.to_string()));
+            }
+            req.dispatch()
+        };
+
+        let res = get("gzip", None);
+        assert_eq!(Some("gzip"), res.headers().get_one("Content-Encoding"));
+        assert_eq!(Some("\"v1-gzip\""), res.headers().get_one("ETag"));
+        let res = get("br", None);
+        assert_eq!(Some("\"v1-br\""), res.headers().get_one("ETag"));
+        let res = get("identity", None);
+        assert!(!res.headers().contains("Content-Encoding"));
+        assert_eq!(Some("\"v1\""), res.headers().get_one("ETag"));
+
+        let res = get("gzip", Some("\"v1-gzip\""));
+        assert_eq!(Status::NotModified, res.status());
+        assert_eq!(Some("\"v1-gzip\""), res.headers().get_one("ETag"));
+        assert_eq!(Some("Accept-Encoding"), res.headers().get_one("Vary"));
+        let res = get("gzip", Some("\"v1\""));
+        assert_eq!(Status::NotModified, res.status());
+        assert_eq!(Some("\"v1\""), res.headers().get_one("ETag"));
+        let res = get("gzip", Some("\"v2-gzip\""));
+        assert_eq!(Status::Ok, res.status());
+    }
+
+    #[get("/compressed_sniffed?<magic>")]
+    fn route_compressed_sniffed(magic: &str) -> Compressed<Vec<u8>> {
+        let mut body = magic.as_bytes().to_vec();
+        body.resize(4096, 0);
+        Compressed::new(body)
+            .compress_type(ContentType::Binary)
+            .min_size(0)
+    }
+
+    #[get("/compressed_typed?<exact>")]
+    fn route_compressed_typed(exact: bool) -> Compressed<(ContentType, Vec<u8>)> {
+        let compressed = Compressed::new((ContentType::PNG, vec![0; 4096]))
+            .compress_type(ContentType::new("*", "*"))
+            .min_size(0);
+        if exact {
+            compressed.compress_type(ContentType::PNG)
+        } else {
+            compressed
+        }
+    }
+
+    #[get("/compressed_skipped")]
+    fn route_compressed_skipped() -> Compressed<RawJson<String>> {
+        Compressed::new(RawJson(body())).skip_type(ContentType::new("application", "*"))
+    }
+
+    #[test]
+    fn test_compressed_types() {
+        let rocket = rocket::build().mount(
+            "/",
+            routes![
+                route_compressed_sniffed,
+                route_compressed_typed,
+                route_compressed_skipped
+            ],
+        );
+        let client = Client::tracked(rocket).expect("no rocket instance");
+        let get = |path: &str| {
+            client
+                .get(path.to_string())
+                .header(Header::new("Accept-Encoding", "gzip"))
+                .dispatch()
+        };
+
+        let res = get("/compressed_sniffed?magic=");
+        assert_eq!(Some("gzip"), res.headers().get_one("Content-Encoding"));
+        let compressed = res.into_bytes().unwrap();
+        let mut decompressed = Vec::new();
+        flate2::read::GzDecoder::new(&compressed[..])
+            .read_to_end(&mut decompressed)
+            .unwrap();
+        assert_eq!(vec![0; 4096], decompressed);
+
+        for magic in ["PK%03%04", "GIF89a", "BZh9"].iter() {
+            let res = get(&format!("/compressed_sniffed?magic={}", magic));
+            assert!(!res.headers().contains("Content-Encoding"), "{}", magic);
+            let body = res.into_bytes().unwrap();
+            assert_eq!(4096, body.len());
+        }
+
+        let res = get("/compressed_typed?exact=false");
+        assert!(!res.headers().contains("Content-Encoding"));
+        let res = get("/compressed_typed?exact=true");
+        assert_eq!(Some("gzip"), res.headers().get_one("Content-Encoding"));
+
+        let res = get("/compressed_skipped");
+        assert!(!res.headers().contains("Content-Encoding"));
+        assert!(!res.headers().contains("Vary"));
+        assert_eq!(body(), res.into_string().unwrap());
+    }
+
+    #[get("/compressed_ranged?<compress>")]
+    async fn route_compressed_ranged(compress: Option<bool>) -> io::Result<Compressed<RangedFile>> {
+        let file = RangedFile::open("Cargo.toml").await?;
+        let compressed = Compressed::new(file.content_type(ContentType::Plain)).min_size(0);
+        Ok(match compress {
+            Some(compress) => compressed.compress_ranges(compress),
+            None => compressed,
+        })
+    }
+
+    #[test]
+    fn test_compressed_ranges() {
+        let content = std::fs::read("Cargo.toml").unwrap();
+        let rocket = rocket::build().mount("/", routes![route_compressed_ranged]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+        let get = |path: &str, range: Option<&str>| {
+            let mut req = client
+                .get(path.to_string())
+                .header(Header::new("Accept-Encoding", "gzip"));
+            if let Some(range) = range {
+                req.add_header(Header::new("Range", range.to_string()));
+            }
+            req.dispatch()
+        };
+
+        let res = get("/compressed_ranged", None);
+        assert_eq!(Status::Ok, res.status());
+        assert!(!res.headers().contains("Content-Encoding"));
+        assert_eq!(Some("bytes"), res.headers().get_one("Accept-Ranges"));
+        assert_eq!(content, res.into_bytes().unwrap());
+
+        for path in ["/compressed_ranged", "/compressed_ranged?compress=true"].iter() {
+            let res = get(path, Some("bytes=0-9"));
+            assert_eq!(Status::PartialContent, res.status());
+            assert!(!res.headers().contains("Content-Encoding"));
+            assert_eq!(content[..10].to_vec(), res.into_bytes().unwrap());
+        }
+
+        let res = client
+            .get("/compressed_ranged")
+            .header(Header::new("Accept-Encoding", "gzip, identity;q=0"))
+            .dispatch();
+        assert_eq!(Some("gzip"), res.headers().get_one("Content-Encoding"));
+        assert_eq!(Some("none"), res.headers().get_one("Accept-Ranges"));
+
+        let res = get("/compressed_ranged?compress=true", None);
+        assert_eq!(Some("gzip"), res.headers().get_one("Content-Encoding"));
+        assert_eq!(Some("none"), res.headers().get_one("Accept-Ranges"));
+        let compressed = res.into_bytes().unwrap();
+        let mut decompressed = Vec::new();
+        flate2::read::GzDecoder::new(&compressed[..])
+            .read_to_end(&mut decompressed)
+            .unwrap();
+        assert_eq!(content, decompressed);
+
+        let figment =
+            Figment::from(rocket::Config::debug_default()).merge(("compress_ranges", true));
+        let rocket = rocket::custom(figment).mount("/", routes![route_compressed_ranged]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+        let res = client
+            .get("/compressed_ranged")
+            .header(Header::new("Accept-Encoding", "gzip"))
+            .dispatch();
+        assert_eq!(Some("gzip"), res.headers().get_one("Content-Encoding"));
+        let res = client
+            .get("/compressed_ranged?compress=false")
+            .header(Header::new("Accept-Encoding", "gzip"))
+            .dispatch();
+        assert!(!res.headers().contains("Content-Encoding"));
+    }
+}
@@ -0,0 +1,185 @@
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+use crate::RocketResponseGeneric3;
+use crate::{
+    BuildError, RocketError, RocketResponse, RocketResponseGeneric, RocketResponseGeneric2,
+};
+use rocket::{http::Status, serde::Serialize};
+
+/// Conversion of lookups and fallible results into one of the enums
+///
+/// `Some` converts its value, `None` becomes `404 Not Found` rendered by
+/// [StatusPage](crate::StatusPage). Both arms of a `Result` are converted, so an error type
+/// like [RocketError] converts into the enum, too.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::get;
+/// use rocket_response::{IntoRocketResponse, RocketError, RocketResponse};
+///
+/// fn find(id: usize) -> Option<RocketResponse> {
+///     (id == 1).then(|| RocketResponse::StaticStr("item 1"))
+/// }
+///
+/// fn load(id: usize) -> Result<RocketResponse, RocketError> {
+///     find(id).ok_or(RocketError::NotFound(Some(format!("no item {}", id))))
+/// }
+///
+/// #[get("/items/<id>")]
+/// fn item(id: usize) -> RocketResponse {
+///     find(id).into_rocket_response()
+/// }
+///
+/// #[get("/loaded/<id>")]
+/// fn loaded(id: usize) -> RocketResponse {
+///     load(id).into_rocket_response()
+/// }
+/// ```
+pub trait IntoRocketResponse<R> {
+    /// Converts `self` into the enum `R`.
+    fn into_rocket_response(self) -> R;
+}
+
+impl<T, R> IntoRocketResponse<R> for Option<T>
+where
+    T: Into<R>,
+    R: From<Status>,
+{
+    fn into_rocket_response(self) -> R {
+        match self {
+            Some(value) => value.into(),
+            None => Status::NotFound.into(),
+        }
+    }
+}
+
+impl<T, E, R> IntoRocketResponse<R> for Result<T, E>
+where
+    T: Into<R>,
+    E: Into<R>,
+{
+    fn into_rocket_response(self) -> R {
+        match self {
+            Ok(value) => value.into(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+/// Conversion of a fallible construction into one of the enums
+///
+/// The value of `Ok` is converted, the error of `Err` becomes a [BuildError], which responds
+/// as the matching [RocketError] after calling the [BuildErrorHook](crate::BuildErrorHook).
+/// So an `io::Error` of opening a file surfaces as `404 Not Found` instead of a panic.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::{get, tokio::fs::File};
+/// use rocket_response::{RocketResponse, TryRespond};
+///
+/// #[get("/download")]
+/// async fn download() -> RocketResponse {
+///     File::open("download.bin")
+///         .await
+///         .map(|file| RocketResponse::TokioFile(Box::new(file)))
+///         .try_respond()
+/// }
+/// ```
+pub trait TryRespond<R> {
+    /// Converts `self` into the enum `R`, an error into the `BuildError` variant.
+    fn try_respond(self) -> R;
+}
+
+impl<T, E, R> TryRespond<R> for Result<T, E>
+where
+    T: Into<R>,
+    E: Into<BuildError>,
+    R: From<BuildError>,
+{
+    fn try_respond(self) -> R {
+        match self {
+            Ok(value) => value.into(),
+            Err(e) => R::from(e.into()),
+        }
+    }
+}
+
+/// Implements `From<RocketError>` and `From<BuildError>` mapping on their variants.
+macro_rules! impl_from_error {
+    ([$($gen:tt)*] $name:ty $(where $($bound:tt)+)?) => {
+        impl<$($gen)*> From<RocketError> for $name $(where $($bound)+)? {
+            fn from(error: RocketError) -> Self {
+                Self::RocketError(error)
+            }
+        }
+
+        impl<$($gen)*> From<BuildError> for $name $(where $($bound)+)? {
+            fn from(error: BuildError) -> Self {
+                Self::BuildError(Box::new(error))
+            }
+        }
+    };
+}
+
+impl_from_error!([] RocketResponse);
+impl_from_error!([T] RocketResponseGeneric<T> where T: Serialize);
+impl_from_error!([T, U] RocketResponseGeneric2<T, U> where T: Serialize);
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+impl_from_error!([T, S, U] RocketResponseGeneric3<T, S, U> where S: Serialize);
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::IntoRocketResponse;
+    use crate::{RocketError, RocketResponse, RocketResponseGeneric};
+    use rocket::{get, http::Status, local::blocking::Client, routes};
+
+    #[get("/option/<id>")]
+    fn route_option(id: usize) -> RocketResponse {
+        (id == 1)
+            .then(|| RocketResponse::StaticStr("found"))
+            .into_rocket_response()
+    }
+
+    #[get("/result/<id>")]
+    fn route_result(id: usize) -> RocketResponseGeneric<&'static str> {
+        let result: Result<RocketResponseGeneric<&'static str>, RocketError> = match id {
+            1 => Ok(RocketResponseGeneric::StaticStr("loaded")),
+            _ => Err(RocketError::Forbidden(None)),
+        };
+        result.into_rocket_response()
+    }
+
+    #[test]
+    fn test_into_rocket_response() {
+        let rocket = rocket::build().mount("/", routes![route_option, route_result]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/option/1").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!("found", res.into_string().unwrap());
+        let res = client.get("/option/2").dispatch();
+        assert_eq!(Status::NotFound, res.status());
+
+        let res = client.get("/result/1").dispatch();
+        assert_eq!("loaded", res.into_string().unwrap());
+        let res = client.get("/result/2").dispatch();
+        assert_eq!(Status::Forbidden, res.status());
+
+        let res: RocketResponse = Err::<Status, _>(Status::Conflict).into_rocket_response();
+        assert_eq!("StatusPage", res.variant_name());
+    }
+}
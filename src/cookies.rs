@@ -0,0 +1,510 @@
+use rocket::{
+    http::{Cookie, SameSite},
+    request::Request,
+    response::{self, Responder},
+};
+
+/// Policy of the attributes of the cookies set by [WithCookies] and the other wrappers
+///
+/// The policy is consulted, if it is managed by Rocket. Unset attributes of a cookie get the
+/// defaults `Secure`, `HttpOnly` and `SameSite=Lax`. A cookie explicitly weakening one of
+/// them, or with `SameSite=None` but without `Secure`, isn't compliant. In debug builds it
+/// panics the responding route, so the violation surfaces in tests, in release builds it is
+/// logged as error and the cookie isn't set.
+///
+/// The defaults are opted out with [allow_insecure](Self::allow_insecure()),
+/// [allow_script_access](Self::allow_script_access()) and [same_site](Self::same_site()),
+/// single cookies with [exempt](Self::exempt()).
+///
+/// Tracking cookies, added with [add_tracking_cookie](WithCookies::add_tracking_cookie()),
+/// are silently dropped without consent of the user. The consent is given by the
+/// [consent cookie](Self::consent_cookie()), default `cookie_consent`, with any value but
+/// `0` or `false`, or by a claim of a request guard with
+/// [grant_consent](Self::grant_consent()). Without a managed policy the default consent
+/// cookie is checked.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::http::SameSite;
+/// use rocket_response::CookiePolicy;
+///
+/// let rocket = rocket::build().manage(
+///     CookiePolicy::new()
+///         .same_site(Some(SameSite::Strict))
+///         .exempt("theme")
+///         .consent_cookie("consent"),
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CookiePolicy {
+    secure: bool,
+    http_only: bool,
+    same_site: Option<SameSite>,
+    exempt: Vec<String>,
+    consent_cookie: String,
+}
+
+/// Consent claim in the request-local cache
+struct Consent(bool);
+
+impl Default for CookiePolicy {
+    fn default() -> Self {
+        CookiePolicy {
+            secure: true,
+            http_only: true,
+            same_site: Some(SameSite::Lax),
+            exempt: Vec::new(),
+            consent_cookie: "cookie_consent".to_string(),
+        }
+    }
+}
+
+impl CookiePolicy {
+    /// Policy requiring `Secure`, `HttpOnly` and `SameSite=Lax`
+    pub fn new() -> Self {
+        CookiePolicy::default()
+    }
+
+    /// Doesn't require `Secure`, like for local development without TLS.
+    pub fn allow_insecure(mut self) -> Self {
+        self.secure = false;
+        self
+    }
+
+    /// Doesn't require `HttpOnly`, so scripts can read the cookies.
+    pub fn allow_script_access(mut self) -> Self {
+        self.http_only = false;
+        self
+    }
+
+    /// Sets the least strict `SameSite` value, `None` doesn't require any.
+    pub fn same_site(mut self, same_site: Option<SameSite>) -> Self {
+        self.same_site = same_site;
+        self
+    }
+
+    /// Exempts the cookie `name` from the policy.
+    pub fn exempt<N: Into<String>>(mut self, name: N) -> Self {
+        self.exempt.push(name.into());
+        self
+    }
+
+    /// Sets the name of the cookie with the consent to tracking cookies.
+    pub fn consent_cookie<N: Into<String>>(mut self, name: N) -> Self {
+        self.consent_cookie = name.into();
+        self
+    }
+
+    /// Grants the consent to tracking cookies for `req`, like by a request guard checking
+    /// a claim of the user.
+    ///
+    /// It has to be granted before the response is built.
+    pub fn grant_consent(req: &Request<'_>) {
+        req.local_cache(|| Consent(true));
+    }
+
+    /// `true` if `req` has the consent to tracking cookies
+    pub fn has_consent(&self, req: &Request<'_>) -> bool {
+        if req.local_cache(|| Consent(false)).0 {
+            return true;
+        }
+        req.cookies()
+            .get(&self.consent_cookie)
+            .map_or(false, |cookie| !matches!(cookie.value(), "0" | "false"))
+    }
+
+    /// Sets the unset attributes of `cookie` to the defaults and checks the others.
+    ///
+    /// Returns the description of the violations of a non-compliant cookie.
+    pub fn apply(&self, cookie: &mut Cookie<'_>) -> Result<(), String> {
+        if self.exempt.iter().any(|name| name == cookie.name()) {
+            return Ok(());
+        }
+        if self.secure && cookie.secure().is_none() {
+            cookie.set_secure(true);
+        }
+        if self.http_only && cookie.http_only().is_none() {
+            cookie.set_http_only(true);
+        }
+        if let (Some(same_site), None) = (self.same_site, cookie.same_site()) {
+            cookie.set_same_site(same_site);
+        }
+
+        let mut violations = Vec::new();
+        if self.secure && cookie.secure() == Some(false) {
+            violations.push("without Secure");
+        }
+        if self.http_only && cookie.http_only() == Some(false) {
+            violations.push("without HttpOnly");
+        }
+        if self.same_site.map(strictness) > cookie.same_site().map(strictness) {
+            violations.push("with a less strict SameSite");
+        }
+        if cookie.same_site() == Some(SameSite::None) && cookie.secure() != Some(true) {
+            violations.push("with SameSite=None without Secure");
+        }
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(format!(
+                "cookie `{}` violates the cookie policy: {}",
+                cookie.name(),
+                violations.join(", ")
+            ))
+        }
+    }
+}
+
+/// Order of the `SameSite` values from the least strict
+fn strictness(same_site: SameSite) -> u8 {
+    match same_site {
+        SameSite::None => 0,
+        SameSite::Lax => 1,
+        SameSite::Strict => 2,
+    }
+}
+
+/// `true` if `req` has the consent of the managed or default [CookiePolicy] to tracking
+/// cookies
+pub(crate) fn consent(req: &Request<'_>) -> bool {
+    match req.rocket().state::<CookiePolicy>() {
+        Some(policy) => policy.has_consent(req),
+        None => CookiePolicy::default().has_consent(req),
+    }
+}
+
+/// Applies the managed [CookiePolicy] to `cookie`.
+///
+/// Returns `false`, if the non-compliant cookie must not be set.
+pub(crate) fn comply(req: &Request<'_>, cookie: &mut Cookie<'_>) -> bool {
+    let policy = match req.rocket().state::<CookiePolicy>() {
+        Some(policy) => policy,
+        None => return true,
+    };
+    match policy.apply(cookie) {
+        Ok(()) => true,
+        Err(e) if cfg!(debug_assertions) => panic!("{}", e),
+        Err(e) => {
+            rocket::error_!("{}", e);
+            false
+        }
+    }
+}
+
+#[derive(Debug)]
+enum CookieOp {
+    Add(Cookie<'static>),
+    Remove(Cookie<'static>),
+    AddTracking(Cookie<'static>),
+    #[cfg(feature = "secrets")]
+    AddPrivate(Cookie<'static>),
+    #[cfg(feature = "secrets")]
+    RemovePrivate(Cookie<'static>),
+}
+
+/// Wrapper adding or removing [Cookies](Cookie) when responding
+///
+/// The changes are applied in the order of the builder calls to the
+/// [CookieJar](rocket::http::CookieJar) of the request, which Rocket turns into
+/// `Set-Cookie` headers.
+///
+/// Added cookies are checked by a managed [CookiePolicy], tracking cookies need the consent
+/// of the user.
+///
+/// Private cookies require the `secrets` feature.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::{http::Cookie, post};
+/// use rocket_response::{RocketResponse, WithCookies};
+///
+/// #[post("/logout")]
+/// fn logout() -> RocketResponse {
+///     RocketResponse::WithCookies(
+///         WithCookies::new(RocketResponse::redirect_to("/"))
+///             .remove_cookie(Cookie::named("session"))
+///             .add_cookie(Cookie::new("logged_out", "1")),
+///     )
+/// }
+/// ```
+#[derive(Debug)]
+pub struct WithCookies<R> {
+    responder: Box<R>,
+    ops: Vec<CookieOp>,
+}
+
+impl<R> WithCookies<R> {
+    /// Wraps `responder` without any cookie change
+    pub fn new(responder: R) -> Self {
+        WithCookies {
+            responder: Box::new(responder),
+            ops: Vec::new(),
+        }
+    }
+
+    /// Adds `cookie`, see [CookieJar::add](rocket::http::CookieJar::add()).
+    pub fn add_cookie(mut self, cookie: Cookie<'static>) -> Self {
+        self.ops.push(CookieOp::Add(cookie));
+        self
+    }
+
+    /// Adds the tracking `cookie`, which is dropped without consent of the user,
+    /// see [CookiePolicy].
+    pub fn add_tracking_cookie(mut self, cookie: Cookie<'static>) -> Self {
+        self.ops.push(CookieOp::AddTracking(cookie));
+        self
+    }
+
+    /// Removes `cookie`, see [CookieJar::remove](rocket::http::CookieJar::remove()).
+    pub fn remove_cookie(mut self, cookie: Cookie<'static>) -> Self {
+        self.ops.push(CookieOp::Remove(cookie));
+        self
+    }
+
+    /// Adds the private `cookie`,
+    /// see [CookieJar::add_private](rocket::http::CookieJar::add_private()).
+    #[cfg(feature = "secrets")]
+    pub fn add_private_cookie(mut self, cookie: Cookie<'static>) -> Self {
+        self.ops.push(CookieOp::AddPrivate(cookie));
+        self
+    }
+
+    /// Removes the private `cookie`,
+    /// see [CookieJar::remove_private](rocket::http::CookieJar::remove_private()).
+    #[cfg(feature = "secrets")]
+    pub fn remove_private_cookie(mut self, cookie: Cookie<'static>) -> Self {
+        self.ops.push(CookieOp::RemovePrivate(cookie));
+        self
+    }
+
+    /// Consumes the wrapper and returns the wrapped responder.
+    pub fn into_inner(self) -> R {
+        *self.responder
+    }
+
+    pub(crate) fn responder(&self) -> &R {
+        &self.responder
+    }
+}
+
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for WithCookies<R> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let jar = req.cookies();
+        for op in self.ops {
+            match op {
+                CookieOp::Add(mut cookie) => {
+                    if comply(req, &mut cookie) {
+                        jar.add(cookie);
+                    }
+                }
+                CookieOp::Remove(cookie) => jar.remove(cookie),
+                CookieOp::AddTracking(mut cookie) => {
+                    if consent(req) && comply(req, &mut cookie) {
+                        jar.add(cookie);
+                    }
+                }
+                #[cfg(feature = "secrets")]
+                CookieOp::AddPrivate(mut cookie) => {
+                    if comply(req, &mut cookie) {
+                        jar.add_private(cookie);
+                    }
+                }
+                #[cfg(feature = "secrets")]
+                CookieOp::RemovePrivate(cookie) => jar.remove_private(cookie),
+            }
+        }
+        self.responder.respond_to(req)
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::{CookiePolicy, WithCookies};
+    use crate::RocketResponse;
+    use rocket::{
+        get,
+        http::{Cookie, Header, SameSite, Status},
+        local::blocking::Client,
+        request::{self, FromRequest, Request},
+        routes,
+    };
+
+    #[get("/login")]
+    fn route_login() -> RocketResponse {
+        RocketResponse::WithCookies(
+            WithCookies::new(RocketResponse::redirect_to("/"))
+                .add_cookie(Cookie::new("user", "alice"))
+                .remove_cookie(Cookie::named("guest")),
+        )
+    }
+
+    #[cfg(feature = "secrets")]
+    #[get("/login_private")]
+    fn route_login_private() -> RocketResponse {
+        RocketResponse::WithCookies(
+            WithCookies::new(RocketResponse::redirect_to("/"))
+                .add_private_cookie(Cookie::new("session", "alice")),
+        )
+    }
+
+    #[get("/policy/<name>")]
+    fn route_policy(name: &str) -> RocketResponse {
+        let cookie = match name {
+            "weak" => Cookie::build("weak", "1").http_only(false).finish(),
+            "theme" => Cookie::build("theme", "dark").http_only(false).finish(),
+            _ => Cookie::new("user", "alice"),
+        };
+        RocketResponse::WithCookies(
+            WithCookies::new(RocketResponse::StaticStr("ok")).add_cookie(cookie),
+        )
+    }
+
+    struct Claim;
+
+    #[rocket::async_trait]
+    impl<'r> FromRequest<'r> for Claim {
+        type Error = ();
+
+        async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+            if req.headers().contains("X-Consent") {
+                CookiePolicy::grant_consent(req);
+            }
+            request::Outcome::Success(Claim)
+        }
+    }
+
+    #[get("/tracking")]
+    fn route_tracking(_claim: Claim) -> RocketResponse {
+        RocketResponse::WithCookies(
+            WithCookies::new(RocketResponse::StaticStr("ok"))
+                .add_tracking_cookie(Cookie::new("visitor", "42"))
+                .add_cookie(Cookie::new("session", "1")),
+        )
+    }
+
+    #[test]
+    fn test_tracking_consent() {
+        let rocket = rocket::build().mount("/", routes![route_tracking]);
+        let client = Client::untracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/tracking").dispatch();
+        assert!(res.cookies().get("visitor").is_none());
+        assert!(res.cookies().get("session").is_some());
+
+        let res = client
+            .get("/tracking")
+            .cookie(Cookie::new("cookie_consent", "0"))
+            .dispatch();
+        assert!(res.cookies().get("visitor").is_none());
+
+        let res = client
+            .get("/tracking")
+            .cookie(Cookie::new("cookie_consent", "1"))
+            .dispatch();
+        assert_eq!(
+            Some("42"),
+            res.cookies().get("visitor").map(|cookie| cookie.value())
+        );
+
+        let res = client
+            .get("/tracking")
+            .header(Header::new("X-Consent", "1"))
+            .dispatch();
+        assert!(res.cookies().get("visitor").is_some());
+
+        let rocket = rocket::build()
+            .manage(CookiePolicy::new().consent_cookie("consent"))
+            .mount("/", routes![route_tracking]);
+        let client = Client::untracked(rocket).expect("no rocket instance");
+        let res = client
+            .get("/tracking")
+            .cookie(Cookie::new("cookie_consent", "1"))
+            .dispatch();
+        assert!(res.cookies().get("visitor").is_none());
+        let res = client
+            .get("/tracking")
+            .cookie(Cookie::new("consent", "yes"))
+            .dispatch();
+        let cookie = res.cookies().get("visitor").unwrap();
+        assert_eq!(Some(true), cookie.secure());
+    }
+
+    #[test]
+    fn test_cookie_policy() {
+        let rocket = rocket::build()
+            .manage(CookiePolicy::new().exempt("theme"))
+            .mount("/", routes![route_policy]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/policy/user").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        let cookie = res.cookies().get("user").unwrap();
+        assert_eq!(Some(true), cookie.secure());
+        assert_eq!(Some(true), cookie.http_only());
+        assert_eq!(Some(SameSite::Lax), cookie.same_site());
+
+        let res = client.get("/policy/theme").dispatch();
+        assert_eq!(None, res.cookies().get("theme").unwrap().http_only());
+
+        let res = client.get("/policy/weak").dispatch();
+        assert_eq!(Status::InternalServerError, res.status());
+        assert!(res.cookies().get("weak").is_none());
+
+        let policy = CookiePolicy::new()
+            .allow_insecure()
+            .same_site(Some(SameSite::Strict));
+        let mut cookie = Cookie::build("a", "1").same_site(SameSite::None).finish();
+        assert_eq!(
+            Err(
+                "cookie `a` violates the cookie policy: with a less strict SameSite, \
+                with SameSite=None without Secure"
+                    .to_string()
+            ),
+            policy.apply(&mut cookie)
+        );
+        let mut cookie = Cookie::new("a", "1");
+        assert_eq!(Ok(()), policy.apply(&mut cookie));
+        assert_eq!(None, cookie.secure());
+        assert_eq!(Some(SameSite::Strict), cookie.same_site());
+    }
+
+    #[test]
+    fn test_with_cookies() {
+        let rocket = rocket::build().mount("/", routes![route_login]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client
+            .get("/login")
+            .cookie(Cookie::new("guest", "1"))
+            .dispatch();
+        assert_eq!(Status::SeeOther, res.status());
+        assert_eq!(
+            Some("alice"),
+            res.cookies().get("user").map(|cookie| cookie.value())
+        );
+        assert!(res
+            .headers()
+            .get("Set-Cookie")
+            .any(|cookie| cookie.starts_with("guest=;")));
+    }
+
+    #[cfg(feature = "secrets")]
+    #[test]
+    fn test_with_cookies_private() {
+        let rocket = rocket::build().mount("/", routes![route_login_private]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/login_private").dispatch();
+        assert_eq!(Status::SeeOther, res.status());
+        assert_eq!(
+            Some("alice"),
+            res.cookies()
+                .get_private("session")
+                .map(|cookie| cookie.value().to_string())
+                .as_deref()
+        );
+    }
+}
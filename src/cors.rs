@@ -0,0 +1,415 @@
+use rocket::{
+    http::{Header, Method},
+    request::Request,
+    response::{self, Responder, Response},
+};
+use std::time::Duration;
+
+/// Policy of the [CORS](https://fetch.spec.whatwg.org/#http-cors-protocol) headers of
+/// [WithCors]
+///
+/// Without any allowed origin no CORS header is sent. With allowed origins or a reflected
+/// origin `Vary: Origin` is sent with every response, also without an allowed `Origin`, so
+/// caches don't mix up the responses to different origins.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::http::Method;
+/// use rocket_response::CorsPolicy;
+/// use std::time::Duration;
+///
+/// let policy = CorsPolicy::new()
+///     .allow_origin("https://app.example.com")
+///     .allow_methods(&[Method::Get, Method::Post])
+///     .allow_headers(&["Content-Type", "Authorization"])
+///     .allow_credentials()
+///     .max_age(Duration::from_secs(600));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CorsPolicy {
+    any_origin: bool,
+    reflect_origin: bool,
+    origins: Vec<String>,
+    methods: Vec<Method>,
+    headers: Vec<String>,
+    credentials: bool,
+    max_age: Option<Duration>,
+}
+
+impl CorsPolicy {
+    /// Policy allowing nothing
+    pub fn new() -> Self {
+        CorsPolicy::default()
+    }
+
+    /// Allows any origin with `*`.
+    ///
+    /// Browsers don't accept `*` together with credentials, so
+    /// [allow_credentials](Self::allow_credentials()) has no effect then.
+    pub fn allow_any_origin(mut self) -> Self {
+        self.any_origin = true;
+        self
+    }
+
+    /// Allows any origin by reflecting the `Origin` of the request.
+    ///
+    /// Unlike [allow_any_origin](Self::allow_any_origin()) this works together with
+    /// [allow_credentials](Self::allow_credentials()), and then lets every site make requests
+    /// with the credentials of the user. Only use it for APIs without ambient credentials
+    /// worth protecting.
+    pub fn reflect_any_origin(mut self) -> Self {
+        self.reflect_origin = true;
+        self
+    }
+
+    /// Allows the origin `origin` like `https://example.com`.
+    pub fn allow_origin<O: Into<String>>(mut self, origin: O) -> Self {
+        self.origins.push(origin.into());
+        self
+    }
+
+    /// Allows `methods` in preflight requests.
+    pub fn allow_methods(mut self, methods: &[Method]) -> Self {
+        self.methods.extend_from_slice(methods);
+        self
+    }
+
+    /// Allows the request headers `headers` in preflight requests.
+    pub fn allow_headers(mut self, headers: &[&str]) -> Self {
+        self.headers
+            .extend(headers.iter().map(|header| (*header).to_string()));
+        self
+    }
+
+    /// Allows credentials like cookies.
+    pub fn allow_credentials(mut self) -> Self {
+        self.credentials = true;
+        self
+    }
+
+    /// Sets how long the result of a preflight request may be cached.
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// `true` if the response depends on the `Origin` of the request
+    fn origin_specific(&self) -> bool {
+        self.reflect_origin || !self.any_origin && !self.origins.is_empty()
+    }
+
+    /// Value of `Access-Control-Allow-Origin` for the request `origin`, if it is allowed
+    fn allowed_origin<'a>(&self, origin: &'a str) -> Option<&'a str> {
+        if self.reflect_origin {
+            Some(origin)
+        } else if self.any_origin {
+            Some("*")
+        } else if self.origins.iter().any(|allowed| allowed == origin) {
+            Some(origin)
+        } else {
+            None
+        }
+    }
+
+    /// CORS headers of the response to `req`
+    fn headers(&self, req: &Request<'_>) -> Vec<Header<'static>> {
+        let mut headers = Vec::new();
+        if self.origin_specific() {
+            headers.push(Header::new("Vary", "Origin"));
+        }
+        let origin = match req
+            .headers()
+            .get_one("Origin")
+            .and_then(|origin| self.allowed_origin(origin))
+        {
+            Some(origin) => origin,
+            None => return headers,
+        };
+
+        headers.push(Header::new(
+            "Access-Control-Allow-Origin",
+            origin.to_string(),
+        ));
+        if self.credentials && origin != "*" {
+            headers.push(Header::new("Access-Control-Allow-Credentials", "true"));
+        }
+
+        let preflight = req.method() == Method::Options
+            && req.headers().contains("Access-Control-Request-Method");
+        if preflight {
+            if !self.methods.is_empty() {
+                let methods: Vec<&str> = self.methods.iter().map(|m| m.as_str()).collect();
+                headers.push(Header::new(
+                    "Access-Control-Allow-Methods",
+                    methods.join(", "),
+                ));
+            }
+            if !self.headers.is_empty() {
+                headers.push(Header::new(
+                    "Access-Control-Allow-Headers",
+                    self.headers.join(", "),
+                ));
+            }
+            if let Some(max_age) = self.max_age {
+                headers.push(Header::new(
+                    "Access-Control-Max-Age",
+                    max_age.as_secs().to_string(),
+                ));
+            }
+        }
+        headers
+    }
+}
+
+/// Wrapper setting the CORS headers of a [CorsPolicy]
+///
+/// The headers are only sent for an allowed `Origin` of the request, apart from
+/// `Vary: Origin` of an origin specific [CorsPolicy]. Preflight requests with the method
+/// `OPTIONS` additionally get the allowed methods and headers. A response
+/// already carrying `Access-Control-Allow-Origin`, e.g. of a nested [WithCors], is left
+/// unchanged apart from `Vary: Origin` of an origin specific [CorsPolicy].
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::{get, http::Method, options, response::status::NoContent};
+/// use rocket_response::{CorsPolicy, RocketResponse, WithCors};
+///
+/// fn policy() -> CorsPolicy {
+///     CorsPolicy::new()
+///         .allow_origin("https://app.example.com")
+///         .allow_methods(&[Method::Get])
+/// }
+///
+/// #[options("/items")]
+/// fn items_preflight() -> RocketResponse {
+///     RocketResponse::WithCors(Box::new(WithCors::new(
+///         RocketResponse::NoContent(NoContent),
+///         policy(),
+///     )))
+/// }
+///
+/// #[get("/items")]
+/// fn items() -> RocketResponse {
+///     RocketResponse::WithCors(Box::new(WithCors::new(
+///         RocketResponse::StaticStr("[]"),
+///         policy(),
+///     )))
+/// }
+/// ```
+#[derive(Debug)]
+pub struct WithCors<R> {
+    responder: Box<R>,
+    policy: CorsPolicy,
+}
+
+impl<R> WithCors<R> {
+    /// Wraps `responder` to respond with the CORS headers of `policy`
+    pub fn new(responder: R, policy: CorsPolicy) -> Self {
+        WithCors {
+            responder: Box::new(responder),
+            policy,
+        }
+    }
+
+    /// Consumes the wrapper and returns the wrapped responder.
+    pub fn into_inner(self) -> R {
+        *self.responder
+    }
+
+    pub(crate) fn responder(&self) -> &R {
+        &self.responder
+    }
+}
+
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for WithCors<R> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let mut res = self.responder.respond_to(req)?;
+        if res.headers().contains("Access-Control-Allow-Origin") {
+            if self.policy.origin_specific() {
+                vary_origin(&mut res);
+            }
+            return Ok(res);
+        }
+        for header in self.policy.headers(req) {
+            if header.name() == "Vary" {
+                vary_origin(&mut res);
+            } else {
+                res.set_header(header);
+            }
+        }
+        Ok(res)
+    }
+}
+
+/// Adds `Vary: Origin` to `res`, if it doesn't vary by `Origin` yet.
+fn vary_origin(res: &mut Response<'_>) {
+    let varies = res
+        .headers()
+        .get("Vary")
+        .flat_map(|vary| vary.split(','))
+        .any(|vary| vary.trim().eq_ignore_ascii_case("Origin"));
+    if !varies {
+        res.adjoin_header(Header::new("Vary", "Origin"));
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::{CorsPolicy, WithCors};
+    use crate::RocketResponse;
+    use rocket::{
+        get,
+        http::{Header, Method, Status},
+        local::blocking::Client,
+        options,
+        response::status::NoContent,
+        routes,
+    };
+    use std::time::Duration;
+
+    fn policy() -> CorsPolicy {
+        CorsPolicy::new()
+            .allow_origin("https://app.example.com")
+            .allow_methods(&[Method::Get, Method::Post])
+            .allow_headers(&["Content-Type"])
+            .allow_credentials()
+            .max_age(Duration::from_secs(600))
+    }
+
+    #[options("/cors")]
+    fn route_cors_preflight() -> RocketResponse {
+        RocketResponse::WithCors(Box::new(WithCors::new(
+            RocketResponse::NoContent(NoContent),
+            policy(),
+        )))
+    }
+
+    #[get("/cors")]
+    fn route_cors() -> RocketResponse {
+        RocketResponse::WithCors(Box::new(WithCors::new(
+            RocketResponse::StaticStr("Hello world"),
+            policy(),
+        )))
+    }
+
+    #[get("/cors_any")]
+    fn route_cors_any() -> WithCors<&'static str> {
+        WithCors::new(
+            "Hello world",
+            CorsPolicy::new().allow_any_origin().allow_credentials(),
+        )
+    }
+
+    #[get("/cors_nested")]
+    fn route_cors_nested() -> WithCors<WithCors<&'static str>> {
+        WithCors::new(
+            WithCors::new("Hello world", CorsPolicy::new().allow_any_origin()),
+            policy(),
+        )
+    }
+
+    #[get("/cors_reflect")]
+    fn route_cors_reflect() -> WithCors<&'static str> {
+        WithCors::new(
+            "Hello world",
+            CorsPolicy::new().reflect_any_origin().allow_credentials(),
+        )
+    }
+
+    #[test]
+    fn test_cors() {
+        let rocket = rocket::build().mount(
+            "/",
+            routes![
+                route_cors_preflight,
+                route_cors,
+                route_cors_any,
+                route_cors_reflect,
+                route_cors_nested
+            ],
+        );
+        let client = Client::tracked(rocket).expect("no rocket instance");
+        let origin = Header::new("Origin", "https://app.example.com");
+
+        let res = client
+            .options("/cors")
+            .header(origin.clone())
+            .header(Header::new("Access-Control-Request-Method", "POST"))
+            .dispatch();
+        assert_eq!(Status::NoContent, res.status());
+        let headers = res.headers();
+        assert_eq!(
+            Some("https://app.example.com"),
+            headers.get_one("Access-Control-Allow-Origin")
+        );
+        assert_eq!(Some("Origin"), headers.get_one("Vary"));
+        assert_eq!(
+            Some("true"),
+            headers.get_one("Access-Control-Allow-Credentials")
+        );
+        assert_eq!(
+            Some("GET, POST"),
+            headers.get_one("Access-Control-Allow-Methods")
+        );
+        assert_eq!(
+            Some("Content-Type"),
+            headers.get_one("Access-Control-Allow-Headers")
+        );
+        assert_eq!(Some("600"), headers.get_one("Access-Control-Max-Age"));
+
+        let res = client.get("/cors").header(origin).dispatch();
+        assert!(res.headers().contains("Access-Control-Allow-Origin"));
+        assert!(!res.headers().contains("Access-Control-Allow-Methods"));
+
+        let res = client
+            .get("/cors")
+            .header(Header::new("Origin", "https://evil.example.com"))
+            .dispatch();
+        assert!(!res.headers().contains("Access-Control-Allow-Origin"));
+        assert!(!res.headers().contains("Access-Control-Allow-Credentials"));
+        assert_eq!(Some("Origin"), res.headers().get_one("Vary"));
+
+        let res = client.get("/cors").dispatch();
+        assert!(!res.headers().contains("Access-Control-Allow-Origin"));
+        assert_eq!(Some("Origin"), res.headers().get_one("Vary"));
+
+        let res = client
+            .get("/cors_any")
+            .header(Header::new("Origin", "https://other.example.com"))
+            .dispatch();
+        assert_eq!(
+            Some("*"),
+            res.headers().get_one("Access-Control-Allow-Origin")
+        );
+        assert!(!res.headers().contains("Access-Control-Allow-Credentials"));
+        assert!(!res.headers().contains("Vary"));
+
+        let res = client
+            .get("/cors_reflect")
+            .header(Header::new("Origin", "https://other.example.com"))
+            .dispatch();
+        assert_eq!(
+            Some("https://other.example.com"),
+            res.headers().get_one("Access-Control-Allow-Origin")
+        );
+        assert_eq!(
+            Some("true"),
+            res.headers().get_one("Access-Control-Allow-Credentials")
+        );
+        assert_eq!(Some("Origin"), res.headers().get_one("Vary"));
+
+        let res = client
+            .get("/cors_nested")
+            .header(Header::new("Origin", "https://app.example.com"))
+            .dispatch();
+        assert_eq!(
+            Some("*"),
+            res.headers().get_one("Access-Control-Allow-Origin")
+        );
+        assert!(!res.headers().contains("Access-Control-Allow-Credentials"));
+        assert_eq!(Some("Origin"), res.headers().get_one("Vary"));
+    }
+}
@@ -0,0 +1,139 @@
+use rocket::{
+    http::{Header, Status},
+    request::Request,
+    response::{self, Responder},
+};
+
+/// Input of the cost function of [WithCost]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Cost {
+    /// Status of the response
+    pub status: Status,
+    /// Size of the body, if it is known before it is sent
+    pub body_size: Option<usize>,
+    /// Number of returned items like set by [WithCost::items]
+    pub items: Option<u64>,
+    /// Compute hint like set by [WithCost::compute]
+    pub compute: Option<u64>,
+}
+
+/// Wrapper setting the `X-Request-Cost` header
+///
+/// The units are computed by the cost function from the [Cost] of the response, after the
+/// wrapped responder has been rendered. A response already carrying `X-Request-Cost`, e.g. of
+/// a nested [WithCost], is left unchanged without calling the cost function.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::get;
+/// use rocket_response::{Cost, RocketResponse, WithCost};
+///
+/// fn units(cost: &Cost) -> u64 {
+///     1 + cost.items.unwrap_or(0) + cost.body_size.unwrap_or(0) as u64 / 1024
+/// }
+///
+/// #[get("/search")]
+/// fn search() -> RocketResponse {
+///     RocketResponse::WithCost(
+///         WithCost::new(RocketResponse::StaticStr("[1,2,3]"), units)
+///             .items(3)
+///             .compute(20),
+///     )
+/// }
+/// ```
+#[derive(Debug)]
+pub struct WithCost<R> {
+    responder: Box<R>,
+    meter: fn(&Cost) -> u64,
+    items: Option<u64>,
+    compute: Option<u64>,
+}
+
+impl<R> WithCost<R> {
+    /// Wraps `responder` to set the cost computed by `meter`
+    pub fn new(responder: R, meter: fn(&Cost) -> u64) -> Self {
+        WithCost {
+            responder: Box::new(responder),
+            meter,
+            items: None,
+            compute: None,
+        }
+    }
+
+    /// Sets the number of returned items.
+    pub fn items(mut self, items: u64) -> Self {
+        self.items = Some(items);
+        self
+    }
+
+    /// Sets a hint of the compute effort, e.g. the milliseconds spent on a query.
+    pub fn compute(mut self, compute: u64) -> Self {
+        self.compute = Some(compute);
+        self
+    }
+
+    /// Consumes the wrapper and returns the wrapped responder.
+    pub fn into_inner(self) -> R {
+        *self.responder
+    }
+
+    pub(crate) fn responder(&self) -> &R {
+        &self.responder
+    }
+}
+
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for WithCost<R> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let mut res = self.responder.respond_to(req)?;
+        if res.headers().contains("X-Request-Cost") {
+            return Ok(res);
+        }
+        let cost = Cost {
+            status: res.status(),
+            body_size: res.body().preset_size(),
+            items: self.items,
+            compute: self.compute,
+        };
+        res.set_header(Header::new(
+            "X-Request-Cost",
+            (self.meter)(&cost).to_string(),
+        ));
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::{Cost, WithCost};
+    use crate::RocketResponse;
+    use rocket::{get, http::Status, local::blocking::Client, routes};
+
+    fn units(cost: &Cost) -> u64 {
+        assert_eq!(Status::Ok, cost.status);
+        cost.body_size.unwrap_or(0) as u64
+            + cost.items.unwrap_or(0) * 10
+            + cost.compute.unwrap_or(0)
+    }
+
+    #[get("/with_cost")]
+    fn route_with_cost() -> RocketResponse {
+        RocketResponse::WithCost(
+            WithCost::new(RocketResponse::StaticStr("Hello world"), units)
+                .items(2)
+                .compute(100),
+        )
+    }
+
+    #[test]
+    fn test_with_cost() {
+        let rocket = rocket::build().mount("/", routes![route_with_cost]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/with_cost").dispatch();
+        assert_eq!(Some("131"), res.headers().get_one("X-Request-Cost"));
+        assert_eq!("Hello world", res.into_string().unwrap());
+    }
+}
@@ -0,0 +1,168 @@
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+use crate::RocketResponseGeneric3;
+use crate::{ETag, RocketResponse, RocketResponseGeneric, RocketResponseGeneric2};
+use rocket::{
+    http::uri::Origin,
+    request::Request,
+    response::{self, status::Created, Responder},
+    serde::Serialize,
+};
+
+/// `201 Created` of a new resource at a typed location
+///
+/// Wraps [Created] with the `Location` of an [Origin], like of the `uri!` macro, so the
+/// location of the resource isn't formatted by hand. The optional [ETag] is sent for the
+/// created representation in the body.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::{get, post, uri};
+/// use rocket_response::{CreatedResource, ETag, RocketResponse};
+///
+/// #[get("/items/<id>")]
+/// fn item(id: usize) -> String {
+///     id.to_string()
+/// }
+///
+/// #[post("/items")]
+/// fn create() -> RocketResponse {
+///     RocketResponse::created(uri!(item(id = 1)), RocketResponse::StaticStr("1"))
+/// }
+///
+/// #[post("/items_tagged")]
+/// fn create_tagged() -> CreatedResource<&'static str> {
+///     CreatedResource::new(uri!(item(id = 2)), "2").etag(ETag::strong("v1"))
+/// }
+/// ```
+#[derive(Debug)]
+pub struct CreatedResource<R> {
+    location: Origin<'static>,
+    responder: Box<R>,
+    etag: Option<ETag>,
+}
+
+impl<R> CreatedResource<R> {
+    /// Resource at `location` with the representation `body`
+    pub fn new(location: Origin<'static>, body: R) -> Self {
+        CreatedResource {
+            location,
+            responder: Box::new(body),
+            etag: None,
+        }
+    }
+
+    /// Sets the `ETag` of the representation.
+    pub fn etag(mut self, etag: ETag) -> Self {
+        self.etag = Some(etag);
+        self
+    }
+
+    /// Location of the resource
+    pub fn location(&self) -> &Origin<'static> {
+        &self.location
+    }
+
+    /// Consumes the wrapper and returns the wrapped body.
+    pub fn into_inner(self) -> R {
+        *self.responder
+    }
+
+    pub(crate) fn responder(&self) -> &R {
+        &self.responder
+    }
+}
+
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for CreatedResource<R> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let mut res = Created::new(self.location.to_string())
+            .body(*self.responder)
+            .respond_to(req)?;
+        if let Some(etag) = self.etag {
+            res.set_header(etag);
+        }
+        Ok(res)
+    }
+}
+
+/// Implements the `created` constructor of a [CreatedResource] variant.
+macro_rules! impl_created_constructor {
+    ([$($gen:tt)*] $name:ty $(where $($bound:tt)+)?) => {
+        impl<$($gen)*> $name $(where $($bound)+)? {
+            /// `201 Created` with the `Location` of `location` and `body`,
+            /// see [CreatedResource]
+            pub fn created(location: Origin<'static>, body: Self) -> Self {
+                Self::CreatedResource(Box::new(CreatedResource::new(location, body)))
+            }
+        }
+    };
+}
+
+impl_created_constructor!([] RocketResponse);
+impl_created_constructor!([T] RocketResponseGeneric<T> where T: Serialize);
+impl_created_constructor!([T, U] RocketResponseGeneric2<T, U> where T: Serialize);
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+impl_created_constructor!([T, S, U] RocketResponseGeneric3<T, S, U> where S: Serialize);
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::CreatedResource;
+    use crate::{ETag, RocketResponse, RocketResponseGeneric};
+    use rocket::{get, http::Status, local::blocking::Client, post, routes, uri};
+
+    #[get("/items/<id>")]
+    fn route_item(id: usize) -> String {
+        id.to_string()
+    }
+
+    #[post("/items?<name>")]
+    fn route_create(name: &str) -> RocketResponse {
+        RocketResponse::created(
+            uri!(route_item(id = name.len())),
+            RocketResponse::StaticStr("created"),
+        )
+    }
+
+    #[post("/items_generic")]
+    fn route_create_generic() -> RocketResponseGeneric<&'static str> {
+        RocketResponseGeneric::CreatedResource(Box::new(
+            CreatedResource::new(
+                uri!(route_item(id = 7)),
+                RocketResponseGeneric::StaticStr("seven"),
+            )
+            .etag(ETag::strong("v7")),
+        ))
+    }
+
+    #[test]
+    fn test_created() {
+        let rocket =
+            rocket::build().mount("/", routes![route_item, route_create, route_create_generic]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.post("/items?name=a%20b").dispatch();
+        assert_eq!(Status::Created, res.status());
+        assert_eq!(Some("/items/3"), res.headers().get_one("Location"));
+        assert!(!res.headers().contains("ETag"));
+        assert_eq!("created", res.into_string().unwrap());
+
+        let res = client.post("/items_generic").dispatch();
+        assert_eq!(Status::Created, res.status());
+        assert_eq!(Some("/items/7"), res.headers().get_one("Location"));
+        assert_eq!(Some("\"v7\""), res.headers().get_one("ETag"));
+        assert_eq!("seven", res.into_string().unwrap());
+    }
+}
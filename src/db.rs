@@ -0,0 +1,332 @@
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+use crate::RocketResponseGeneric3;
+use crate::{RocketError, RocketResponse, RocketResponseGeneric, RocketResponseGeneric2};
+use rocket::{
+    request::Request,
+    response::{self, Responder},
+    serde::Serialize,
+};
+use std::{error::Error, fmt, sync::Arc};
+
+/// Kind of a [DbError]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DbErrorKind {
+    /// The queried row doesn't exist.
+    NotFound,
+    /// A unique constraint is violated, like by a duplicate key.
+    UniqueViolation,
+    /// A foreign key constraint is violated.
+    ForeignKeyViolation,
+    /// Any other error
+    Other,
+}
+
+/// Error of a database query, which responds as [RocketError]
+///
+/// The errors of `sqlx`, like of `rocket_db_pools`, and of `diesel` are converted by `From`,
+/// so a handler can use `?` on its queries. The error responds as mapped by a managed
+/// [DbErrorMapper], by default [NotFound](DbErrorKind::NotFound) as `404 Not Found`,
+/// [UniqueViolation](DbErrorKind::UniqueViolation) as `409 Conflict` and any other kind as
+/// logged `500 Internal Server Error`.
+///
+/// Requires the `db` feature, the conversions the `db-sqlx` and `db-diesel` features.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::get;
+/// use rocket_response::{DbError, DbErrorKind, RocketResponse};
+///
+/// #[get("/user/<id>")]
+/// fn user(id: u32) -> Result<RocketResponse, DbError> {
+///     // e.g. sqlx::query("...").fetch_one(&mut *db).await?
+///     if id == 0 {
+///         return Err(DbError::new(DbErrorKind::NotFound, "no user 0"));
+///     }
+///     Ok(RocketResponse::StaticStr("alice"))
+/// }
+/// ```
+#[derive(Debug)]
+pub struct DbError {
+    kind: DbErrorKind,
+    error: Box<dyn Error + Send + Sync>,
+}
+
+impl DbError {
+    /// Error of `kind` caused by `error`, e.g. of another database library
+    pub fn new<E: Into<Box<dyn Error + Send + Sync>>>(kind: DbErrorKind, error: E) -> Self {
+        DbError {
+            kind,
+            error: error.into(),
+        }
+    }
+
+    /// Kind of the error
+    pub fn kind(&self) -> DbErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for DbError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "database query failed: {}", self.error)
+    }
+}
+
+impl Error for DbError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&*self.error)
+    }
+}
+
+/// Default mapping of `error`
+fn default_error(error: &DbError) -> RocketError {
+    match error.kind {
+        DbErrorKind::NotFound => RocketError::NotFound(None),
+        DbErrorKind::UniqueViolation => RocketError::Conflict(None),
+        _ => RocketError::Internal(error.to_string()),
+    }
+}
+
+impl From<DbError> for RocketError {
+    fn from(error: DbError) -> Self {
+        default_error(&error)
+    }
+}
+
+#[cfg(feature = "db-sqlx")]
+impl From<sqlx::Error> for DbError {
+    fn from(error: sqlx::Error) -> Self {
+        let kind = match &error {
+            sqlx::Error::RowNotFound => DbErrorKind::NotFound,
+            sqlx::Error::Database(e) => match e.code().as_deref() {
+                // SQLSTATE of PostgreSQL, extended result codes of SQLite
+                Some("23505") | Some("2067") | Some("1555") => DbErrorKind::UniqueViolation,
+                Some("23503") | Some("787") => DbErrorKind::ForeignKeyViolation,
+                // the SQLSTATE of MySQL is the same for all integrity constraints
+                Some("23000") if e.message().starts_with("Duplicate entry") => {
+                    DbErrorKind::UniqueViolation
+                }
+                _ => DbErrorKind::Other,
+            },
+            _ => DbErrorKind::Other,
+        };
+        DbError::new(kind, error)
+    }
+}
+
+#[cfg(feature = "db-diesel")]
+impl From<diesel::result::Error> for DbError {
+    fn from(error: diesel::result::Error) -> Self {
+        use diesel::result::{DatabaseErrorKind, Error};
+
+        let kind = match &error {
+            Error::NotFound => DbErrorKind::NotFound,
+            Error::DatabaseError(DatabaseErrorKind::UniqueViolation, _) => {
+                DbErrorKind::UniqueViolation
+            }
+            Error::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _) => {
+                DbErrorKind::ForeignKeyViolation
+            }
+            _ => DbErrorKind::Other,
+        };
+        DbError::new(kind, error)
+    }
+}
+
+impl<'r> Responder<'r, 'static> for DbError {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let error = match req.rocket().state::<DbErrorMapper>() {
+            Some(mapper) => mapper.0.map(&self),
+            None => RocketError::from(self),
+        };
+        error.respond_to(req)
+    }
+}
+
+/// Mapping of a [DbError] to the [RocketError] it responds as
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket_response::{DbError, DbErrorKind, DbErrorMapper, DbErrorMapping, RocketError};
+///
+/// struct Mapping;
+///
+/// impl DbErrorMapping for Mapping {
+///     fn map(&self, error: &DbError) -> RocketError {
+///         match error.kind() {
+///             DbErrorKind::ForeignKeyViolation => {
+///                 RocketError::Conflict(Some("still referenced".to_string()))
+///             }
+///             _ => self.default_map(error),
+///         }
+///     }
+/// }
+///
+/// let rocket = rocket::build().manage(DbErrorMapper::new(Mapping));
+/// ```
+pub trait DbErrorMapping: Send + Sync + 'static {
+    /// Error `error` responds as, by default the one of [default_map](Self::default_map())
+    fn map(&self, error: &DbError) -> RocketError {
+        self.default_map(error)
+    }
+
+    /// Default mapping of `error`, like `From<DbError>` for [RocketError]
+    fn default_map(&self, error: &DbError) -> RocketError {
+        default_error(error)
+    }
+}
+
+/// [DbErrorMapping] of the [DbError] responses, managed by Rocket
+#[derive(Clone)]
+pub struct DbErrorMapper(Arc<dyn DbErrorMapping>);
+
+impl DbErrorMapper {
+    /// Mapper of `mapping`
+    pub fn new<M: DbErrorMapping>(mapping: M) -> Self {
+        DbErrorMapper(Arc::new(mapping))
+    }
+}
+
+impl fmt::Debug for DbErrorMapper {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DbErrorMapper")
+    }
+}
+
+/// Implements `From<DbError>` and of the errors of the database libraries on the `DbError`
+/// variant.
+macro_rules! impl_from_db_error {
+    ([$($gen:tt)*] $name:ty $(where $($bound:tt)+)?) => {
+        impl<$($gen)*> From<DbError> for $name $(where $($bound)+)? {
+            fn from(error: DbError) -> Self {
+                Self::DbError(Box::new(error))
+            }
+        }
+
+        #[cfg(feature = "db-sqlx")]
+        impl<$($gen)*> From<sqlx::Error> for $name $(where $($bound)+)? {
+            fn from(error: sqlx::Error) -> Self {
+                Self::DbError(Box::new(error.into()))
+            }
+        }
+
+        #[cfg(feature = "db-diesel")]
+        impl<$($gen)*> From<diesel::result::Error> for $name $(where $($bound)+)? {
+            fn from(error: diesel::result::Error) -> Self {
+                Self::DbError(Box::new(error.into()))
+            }
+        }
+    };
+}
+
+impl_from_db_error!([] RocketResponse);
+impl_from_db_error!([T] RocketResponseGeneric<T> where T: Serialize);
+impl_from_db_error!([T, U] RocketResponseGeneric2<T, U> where T: Serialize);
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+impl_from_db_error!([T, S, U] RocketResponseGeneric3<T, S, U> where S: Serialize);
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::{DbError, DbErrorKind, DbErrorMapper, DbErrorMapping};
+    use crate::{RocketError, RocketResponse, RocketResponseGeneric};
+    use rocket::{get, http::Status, local::blocking::Client, routes};
+
+    struct Mapping;
+
+    impl DbErrorMapping for Mapping {
+        fn map(&self, error: &DbError) -> RocketError {
+            match error.kind() {
+                DbErrorKind::ForeignKeyViolation => RocketError::Conflict(None),
+                _ => self.default_map(error),
+            }
+        }
+    }
+
+    fn query(kind: &str) -> Result<&'static str, DbError> {
+        let kind = match kind {
+            "not_found" => DbErrorKind::NotFound,
+            "unique" => DbErrorKind::UniqueViolation,
+            "foreign_key" => DbErrorKind::ForeignKeyViolation,
+            "other" => DbErrorKind::Other,
+            _ => return Ok("row"),
+        };
+        Err(DbError::new(kind, "query failed"))
+    }
+
+    #[get("/db/<kind>")]
+    fn route_db(kind: &str) -> Result<RocketResponse, DbError> {
+        Ok(RocketResponse::StaticStr(query(kind)?))
+    }
+
+    #[get("/db_generic/<kind>")]
+    fn route_db_generic(kind: &str) -> RocketResponseGeneric<&'static str> {
+        match query(kind) {
+            Ok(row) => RocketResponseGeneric::StaticStr(row),
+            Err(e) => e.into(),
+        }
+    }
+
+    #[test]
+    fn test_db_error() {
+        let rocket = rocket::build().mount("/", routes![route_db, route_db_generic]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        for path in ["/db", "/db_generic"].iter() {
+            let status = |kind: &str| client.get(format!("{}/{}", path, kind)).dispatch().status();
+            assert_eq!(Status::Ok, status("none"));
+            assert_eq!(Status::NotFound, status("not_found"));
+            assert_eq!(Status::Conflict, status("unique"));
+            assert_eq!(Status::InternalServerError, status("foreign_key"));
+            assert_eq!(Status::InternalServerError, status("other"));
+        }
+
+        let rocket = rocket::build()
+            .manage(DbErrorMapper::new(Mapping))
+            .mount("/", routes![route_db]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+        let res = client.get("/db/foreign_key").dispatch();
+        assert_eq!(Status::Conflict, res.status());
+        let res = client.get("/db/not_found").dispatch();
+        assert_eq!(Status::NotFound, res.status());
+    }
+
+    #[cfg(feature = "db-sqlx")]
+    #[test]
+    fn test_sqlx_error() {
+        let error = DbError::from(sqlx::Error::RowNotFound);
+        assert_eq!(DbErrorKind::NotFound, error.kind());
+        let error = DbError::from(sqlx::Error::PoolTimedOut);
+        assert_eq!(DbErrorKind::Other, error.kind());
+    }
+
+    #[cfg(feature = "db-diesel")]
+    #[test]
+    fn test_diesel_error() {
+        use diesel::result::{DatabaseErrorKind, Error};
+
+        let error = DbError::from(Error::NotFound);
+        assert_eq!(DbErrorKind::NotFound, error.kind());
+        let error = DbError::from(Error::DatabaseError(
+            DatabaseErrorKind::UniqueViolation,
+            Box::new("duplicate key".to_string()),
+        ));
+        assert_eq!(DbErrorKind::UniqueViolation, error.kind());
+        assert_eq!(Status::Conflict, RocketError::from(error).status());
+    }
+}
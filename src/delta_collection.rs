@@ -0,0 +1,336 @@
+use crate::{body, BuildError, ETag};
+use rocket::{
+    http::{Header, Status},
+    request::Request,
+    response::{self, Responder, Response},
+    serde::{json::serde_json, Serialize},
+};
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap, VecDeque},
+    hash::{Hash, Hasher},
+    sync::{Arc, Mutex},
+};
+
+/// Versions of the items of a collection by their id
+type Snapshot = HashMap<String, u64>;
+
+/// Snapshots by collection tag and the tags from the oldest
+type Snapshots = (HashMap<String, Arc<Snapshot>>, VecDeque<String>);
+
+/// Store of the collection snapshots [DeltaCollection] computes the deltas from, to be
+/// managed by Rocket
+///
+/// A snapshot is stored under the collection `ETag` of every response and the oldest is
+/// dropped beyond the capacity. A client with a dropped snapshot gets the full collection.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket_response::DeltaSnapshots;
+///
+/// let rocket = rocket::build().manage(DeltaSnapshots::new(1000));
+/// ```
+#[derive(Debug)]
+pub struct DeltaSnapshots {
+    capacity: usize,
+    snapshots: Mutex<Snapshots>,
+}
+
+impl DeltaSnapshots {
+    /// Store keeping the last `capacity` snapshots
+    pub fn new(capacity: usize) -> Self {
+        DeltaSnapshots {
+            capacity: capacity.max(1),
+            snapshots: Mutex::new((HashMap::new(), VecDeque::new())),
+        }
+    }
+
+    /// Snapshot stored under `tag`
+    fn get(&self, tag: &str) -> Option<Arc<Snapshot>> {
+        let snapshots = self.snapshots.lock().unwrap_or_else(|e| e.into_inner());
+        snapshots.0.get(tag).cloned()
+    }
+
+    /// Stores `snapshot` under `tag`, if it isn't stored yet.
+    fn insert(&self, tag: &str, snapshot: Snapshot) {
+        let mut snapshots = self.snapshots.lock().unwrap_or_else(|e| e.into_inner());
+        let (map, order) = &mut *snapshots;
+        if map.contains_key(tag) {
+            return;
+        }
+        while order.len() >= self.capacity {
+            if let Some(oldest) = order.pop_front() {
+                map.remove(&oldest);
+            }
+        }
+        map.insert(tag.to_string(), Arc::new(snapshot));
+        order.push_back(tag.to_string());
+    }
+}
+
+/// Body of a [DeltaCollection] response
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde")]
+struct Delta<'a, T> {
+    etag: &'a str,
+    full: bool,
+    changed: Vec<&'a T>,
+    deleted: Vec<&'a str>,
+}
+
+/// Collection serialized into JSON, only with the items changed since the collection
+/// `ETag` of the client
+///
+/// The client sends the `ETag` of its last response in `If-None-Match`. If the collection
+/// is unchanged, the response is a `304 Not Modified`. Otherwise the body is
+///
+/// ```json
+/// {"etag": "…", "full": false, "changed": [{…}], "deleted": ["id"]}
+/// ```
+///
+/// with the added and modified items in `changed` and the ids of the removed items in
+/// `deleted`. If the previous `ETag` is unknown, `full` is `true` and `changed` contains all
+/// items, so the client replaces its copy. The new `ETag` is also sent as header.
+///
+/// An item is modified if its JSON serialization changed. The previous collections are
+/// looked up in the managed [DeltaSnapshots], without it every response is full.
+///
+/// Requires the `json` feature.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::{get, serde::Serialize};
+/// use rocket_response::{DeltaCollection, DeltaSnapshots};
+///
+/// #[derive(Serialize)]
+/// #[serde(crate = "rocket::serde")]
+/// struct Note {
+///     id: u64,
+///     text: String,
+/// }
+///
+/// #[get("/notes")]
+/// fn notes() -> DeltaCollection<Note> {
+///     let notes = vec![Note { id: 1, text: "Hello world".to_string() }];
+///     DeltaCollection::new(notes, |note| note.id)
+/// }
+///
+/// let rocket = rocket::build().manage(DeltaSnapshots::new(1000));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeltaCollection<T> {
+    items: Vec<(String, T)>,
+}
+
+impl<T> DeltaCollection<T> {
+    /// Collection of `items` identified by `id`
+    pub fn new<I, F, K>(items: I, id: F) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        F: Fn(&T) -> K,
+        K: ToString,
+    {
+        DeltaCollection {
+            items: items
+                .into_iter()
+                .map(|item| (id(&item).to_string(), item))
+                .collect(),
+        }
+    }
+
+    /// Consumes the wrapper and returns the items.
+    pub fn into_inner(self) -> Vec<T> {
+        self.items.into_iter().map(|(_, item)| item).collect()
+    }
+}
+
+impl<T: Serialize> DeltaCollection<T> {
+    /// Snapshot of the items and the collection `ETag` computed from it
+    fn snapshot(&self) -> Result<(Snapshot, ETag), serde_json::Error> {
+        let mut versions = Vec::with_capacity(self.items.len());
+        for (id, item) in &self.items {
+            let mut hasher = DefaultHasher::new();
+            serde_json::to_vec(item)?.hash(&mut hasher);
+            versions.push((id.as_str(), hasher.finish()));
+        }
+        versions.sort_unstable();
+        let etag = ETag::from_hash(&versions);
+        let snapshot = versions
+            .into_iter()
+            .map(|(id, version)| (id.to_string(), version))
+            .collect();
+        Ok((snapshot, etag))
+    }
+}
+
+impl<'r, T: Serialize> Responder<'r, 'static> for DeltaCollection<T> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let (snapshot, etag) = match self.snapshot() {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                return BuildError::Serialize("JSON".to_string(), e.to_string()).respond_to(req)
+            }
+        };
+        let previous = req
+            .headers()
+            .get_one("If-None-Match")
+            .and_then(ETag::parse_list)
+            .unwrap_or_default();
+        if previous.iter().any(|tag| tag.weak_eq(&etag)) {
+            return Response::build()
+                .status(Status::NotModified)
+                .header(etag)
+                .ok();
+        }
+
+        let store = req.rocket().state::<DeltaSnapshots>();
+        let known = store.and_then(|store| previous.iter().find_map(|tag| store.get(tag.tag())));
+        let delta = match &known {
+            Some(known) => Delta {
+                etag: etag.tag(),
+                full: false,
+                changed: self
+                    .items
+                    .iter()
+                    .filter(|(id, _)| known.get(id) != snapshot.get(id))
+                    .map(|(_, item)| item)
+                    .collect(),
+                deleted: {
+                    let mut deleted: Vec<&str> = known
+                        .keys()
+                        .filter(|id| !snapshot.contains_key(*id))
+                        .map(String::as_str)
+                        .collect();
+                    deleted.sort_unstable();
+                    deleted
+                },
+            },
+            None => Delta {
+                etag: etag.tag(),
+                full: true,
+                changed: self.items.iter().map(|(_, item)| item).collect(),
+                deleted: Vec::new(),
+            },
+        };
+
+        let mut builder = Response::build_from(body::respond_json(&delta, req)?);
+        builder
+            .header(etag.clone())
+            .header(Header::new("Vary", "If-None-Match"));
+        if let Some(store) = store {
+            store.insert(etag.tag(), snapshot);
+        }
+        builder.ok()
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::{DeltaCollection, DeltaSnapshots};
+    use crate::RocketResponse;
+    use rocket::{
+        get,
+        http::{Header, Status},
+        local::blocking::Client,
+        routes,
+        serde::{
+            json::{serde_json, Value},
+            Serialize,
+        },
+        State,
+    };
+    use std::sync::Mutex;
+
+    #[derive(Debug, Clone, Serialize)]
+    #[serde(crate = "rocket::serde")]
+    struct Note {
+        id: u64,
+        text: &'static str,
+    }
+
+    struct Notes(Mutex<Vec<Note>>);
+
+    #[get("/notes")]
+    fn route_delta_collection(notes: &State<Notes>) -> DeltaCollection<Note> {
+        let notes = notes.0.lock().unwrap().clone();
+        DeltaCollection::new(notes, |note| note.id)
+    }
+
+    #[get("/notes_value")]
+    fn route_delta_collection_value() -> RocketResponse {
+        RocketResponse::DeltaCollection(Box::new(DeltaCollection::new(
+            vec![serde_json::json!({"id": 1})],
+            |note| note["id"].to_string(),
+        )))
+    }
+
+    #[test]
+    fn test_delta_collection() {
+        let notes = Notes(Mutex::new(vec![
+            Note { id: 1, text: "a" },
+            Note { id: 2, text: "b" },
+            Note { id: 3, text: "c" },
+        ]));
+        let rocket = rocket::build()
+            .manage(notes)
+            .manage(DeltaSnapshots::new(10))
+            .mount(
+                "/",
+                routes![route_delta_collection, route_delta_collection_value],
+            );
+        let client = Client::tracked(rocket).expect("no rocket instance");
+        let get = |etag: Option<&str>| {
+            let mut req = client.get("/notes");
+            if let Some(etag) = etag {
+                req.add_header(Header::new("If-None-Match", etag.to_string()));
+            }
+            let res = req.dispatch();
+            let status = res.status();
+            let etag = res.headers().get_one("ETag").map(String::from);
+            let body: Option<Value> = res.into_json();
+            (status, etag.unwrap(), body)
+        };
+
+        let (status, etag, body) = get(None);
+        assert_eq!(Status::Ok, status);
+        let body = body.unwrap();
+        assert_eq!(etag, format!("\"{}\"", body["etag"].as_str().unwrap()));
+        assert_eq!(true, body["full"]);
+        assert_eq!(3, body["changed"].as_array().unwrap().len());
+
+        let (status, not_modified, body) = get(Some(&etag));
+        assert_eq!(Status::NotModified, status);
+        assert_eq!(etag, not_modified);
+        assert_eq!(None, body);
+
+        {
+            let state = client.rocket().state::<Notes>().unwrap();
+            let mut notes = state.0.lock().unwrap();
+            notes[1].text = "changed";
+            notes.remove(2);
+            notes.push(Note { id: 4, text: "d" });
+        }
+        let (status, delta_etag, body) = get(Some(&etag));
+        assert_eq!(Status::Ok, status);
+        assert_ne!(etag, delta_etag);
+        let body = body.unwrap();
+        assert_eq!(false, body["full"]);
+        assert_eq!(
+            serde_json::json!([{"id": 2, "text": "changed"}, {"id": 4, "text": "d"}]),
+            body["changed"]
+        );
+        assert_eq!(serde_json::json!(["3"]), body["deleted"]);
+
+        let (status, _, body) = get(Some("\"unknown\""));
+        assert_eq!(Status::Ok, status);
+        let body = body.unwrap();
+        assert_eq!(true, body["full"]);
+        assert_eq!(3, body["changed"].as_array().unwrap().len());
+
+        let res = client.get("/notes_value").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(Some("If-None-Match"), res.headers().get_one("Vary"));
+    }
+}
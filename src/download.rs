@@ -0,0 +1,194 @@
+use rocket::{
+    fs::NamedFile,
+    http::{ContentType, Header},
+    request::Request,
+    response::{self, Responder},
+};
+use std::fmt::Write;
+
+/// File download with a `Content-Disposition: attachment` header
+///
+/// The filename is sent as quoted `filename` with non-ASCII characters replaced and, if needed,
+/// additionally as [RFC 5987](https://www.rfc-editor.org/rfc/rfc5987) encoded `filename*`.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::{get, http::ContentType};
+/// use rocket_response::Download;
+///
+/// #[get("/report/<id>")]
+/// fn report(id: usize) -> Download {
+///     Download::bytes(format!("id;total\n{};42\n", id), format!("Übersicht {}.csv", id))
+///         .content_type(ContentType::CSV)
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Download {
+    body: DownloadBody,
+    filename: String,
+    content_type: Option<ContentType>,
+}
+
+#[derive(Debug)]
+enum DownloadBody {
+    Bytes(Vec<u8>),
+    File(NamedFile),
+}
+
+impl Download {
+    /// Download of `file` named like the file on disk
+    pub fn file(file: NamedFile) -> Self {
+        let filename = file
+            .path()
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        Download {
+            body: DownloadBody::File(file),
+            filename,
+            content_type: None,
+        }
+    }
+
+    /// Download of `bytes` named `filename`
+    ///
+    /// Without a [content_type](Download::content_type()) override the
+    /// Content-Type is `application/octet-stream`.
+    pub fn bytes<B, S>(bytes: B, filename: S) -> Self
+    where
+        B: Into<Vec<u8>>,
+        S: Into<String>,
+    {
+        Download {
+            body: DownloadBody::Bytes(bytes.into()),
+            filename: filename.into(),
+            content_type: None,
+        }
+    }
+
+    /// Sets the `filename` presented to the client.
+    pub fn filename<S: Into<String>>(mut self, filename: S) -> Self {
+        self.filename = filename.into();
+        self
+    }
+
+    /// Overrides the Content-Type of the download.
+    pub fn content_type(mut self, content_type: ContentType) -> Self {
+        self.content_type = Some(content_type);
+        self
+    }
+
+    /// The value of the `Content-Disposition` header
+    pub fn disposition(&self) -> String {
+        let fallback: String = self
+            .filename
+            .chars()
+            .map(|c| match c {
+                ' '..='~' if c != '"' && c != '\\' => c,
+                _ => '_',
+            })
+            .collect();
+
+        let mut value = format!("attachment; filename=\"{}\"", fallback);
+        if fallback != self.filename {
+            value.push_str("; filename*=UTF-8''");
+            for b in self.filename.bytes() {
+                match b {
+                    b'a'..=b'z'
+                    | b'A'..=b'Z'
+                    | b'0'..=b'9'
+                    | b'!'
+                    | b'#'
+                    | b'$'
+                    | b'&'
+                    | b'+'
+                    | b'-'
+                    | b'.'
+                    | b'^'
+                    | b'_'
+                    | b'`'
+                    | b'|'
+                    | b'~' => value.push(b as char),
+                    _ => {
+                        let _ = write!(value, "%{:02X}", b);
+                    }
+                }
+            }
+        }
+        value
+    }
+}
+
+impl<'r> Responder<'r, 'static> for Download {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let disposition = self.disposition();
+        let mut res = match self.body {
+            DownloadBody::Bytes(bytes) => (ContentType::Binary, bytes).respond_to(req)?,
+            DownloadBody::File(file) => file.respond_to(req)?,
+        };
+        if let Some(content_type) = self.content_type {
+            res.set_header(content_type);
+        }
+        res.set_header(Header::new("Content-Disposition", disposition));
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::Download;
+    use crate::RocketResponse;
+    use rocket::{
+        fs::NamedFile,
+        get,
+        http::{ContentType, Status},
+        local::blocking::Client,
+        routes,
+    };
+
+    #[get("/download/<id>")]
+    async fn route_download(id: usize) -> RocketResponse {
+        match id {
+            0 => RocketResponse::Download(Box::new(Download::bytes("a;b\n", "report 1.csv"))),
+            1 => RocketResponse::Download(Box::new(
+                Download::bytes("a;b\n", "Übersicht \"1\".csv").content_type(ContentType::CSV),
+            )),
+            _ => RocketResponse::Download(Box::new(Download::file(
+                NamedFile::open("Cargo.toml").await.unwrap(),
+            ))),
+        }
+    }
+
+    #[test]
+    fn test_download() {
+        let rocket = rocket::build().mount("/", routes![route_download]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/download/0").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(ContentType::Binary, res.content_type().unwrap());
+        assert_eq!(
+            Some("attachment; filename=\"report 1.csv\""),
+            res.headers().get_one("Content-Disposition")
+        );
+
+        let res = client.get("/download/1").dispatch();
+        assert_eq!(ContentType::CSV, res.content_type().unwrap());
+        assert_eq!(
+            Some(
+                "attachment; filename=\"_bersicht _1_.csv\"; \
+                filename*=UTF-8''%C3%9Cbersicht%20%221%22.csv"
+            ),
+            res.headers().get_one("Content-Disposition")
+        );
+
+        let res = client.get("/download/2").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(
+            Some("attachment; filename=\"Cargo.toml\""),
+            res.headers().get_one("Content-Disposition")
+        );
+    }
+}
@@ -0,0 +1,152 @@
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+use crate::RocketResponseGeneric3;
+use crate::{RocketResponse, RocketResponseGeneric, RocketResponseGeneric2};
+use rocket::{
+    request::Request,
+    response::{self, Responder},
+    serde::Serialize,
+};
+
+/// Object safe [Responder] for any lifetime of the [Request]
+trait DynResponder: Send {
+    fn respond_dyn(self: Box<Self>, req: &Request<'_>) -> response::Result<'static>;
+}
+
+impl<R> DynResponder for R
+where
+    R: for<'r> Responder<'r, 'static> + Send,
+{
+    fn respond_dyn(self: Box<Self>, req: &Request<'_>) -> response::Result<'static> {
+        (*self).respond_to(req)
+    }
+}
+
+/// Type erased [Responder]
+///
+/// Used by the `Dyn` variant for responses without an own variant and returned by
+/// `into_dyn()` of the enums to handle variants, which are unknown to a `match`.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::{http::ContentType, response::Redirect};
+/// use rocket_response::{Dyn, RocketResponse};
+///
+/// fn log_redirects(res: RocketResponse) -> Dyn {
+///     match res {
+///         RocketResponse::Redirect(r) => {
+///             println!("redirect");
+///             Dyn::new(*r)
+///         }
+///         other => other.into_dyn(),
+///     }
+/// }
+///
+/// let _ = RocketResponse::fallback((ContentType::Text, "Hello world"));
+/// ```
+pub struct Dyn(Box<dyn DynResponder>);
+
+impl Dyn {
+    /// Erases the type of `responder`
+    pub fn new<R>(responder: R) -> Self
+    where
+        R: for<'r> Responder<'r, 'static> + Send + 'static,
+    {
+        Dyn(Box::new(responder))
+    }
+}
+
+impl std::fmt::Debug for Dyn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Dyn")
+    }
+}
+
+impl<'r> Responder<'r, 'static> for Dyn {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        self.0.respond_dyn(req)
+    }
+}
+
+/// Implements `fallback()` and `into_dyn()` of the enums.
+macro_rules! impl_dyn {
+    ([$($gen:tt)*] $name:ty, [$($bound:tt)*]) => {
+        impl<$($gen)*> $name where $($bound)* {
+            /// `Dyn` variant for a `responder` without an own variant
+            pub fn fallback<R>(responder: R) -> Self
+            where
+                R: for<'r> Responder<'r, 'static> + Send + 'static,
+            {
+                Self::Dyn(Dyn::new(responder))
+            }
+
+            /// Erases the variant, e.g. to handle variants unknown to a `match`.
+            pub fn into_dyn(self) -> Dyn
+            where
+                Self: for<'r> Responder<'r, 'static> + Send + 'static,
+            {
+                match self {
+                    Self::Dyn(r) => r,
+                    other => Dyn::new(other),
+                }
+            }
+        }
+    };
+}
+
+impl_dyn!([] RocketResponse, []);
+impl_dyn!([T] RocketResponseGeneric<T>, [T: Serialize]);
+impl_dyn!([T, U] RocketResponseGeneric2<T, U>, [T: Serialize]);
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+impl_dyn!([T, S, U] RocketResponseGeneric3<T, S, U>, [S: Serialize]);
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use crate::{ContentKind, RocketResponse, RocketResponseGeneric};
+    use rocket::{
+        get,
+        http::{ContentType, Status},
+        local::blocking::Client,
+        routes,
+    };
+
+    #[get("/fallback")]
+    fn route_fallback() -> RocketResponse {
+        RocketResponse::fallback((ContentType::CSV, "a;b\n"))
+    }
+
+    #[get("/into_dyn")]
+    fn route_into_dyn() -> crate::Dyn {
+        RocketResponseGeneric::<&'static str>::StaticStr("Hello world").into_dyn()
+    }
+
+    #[test]
+    fn test_dyn() {
+        let rocket = rocket::build().mount("/", routes![route_fallback, route_into_dyn]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/fallback").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(ContentType::CSV, res.content_type().unwrap());
+
+        let res = client.get("/into_dyn").dispatch();
+        assert_eq!("Hello world", res.into_string().unwrap());
+
+        let res = RocketResponse::fallback("Hello world");
+        assert_eq!("Dyn", res.variant_name());
+        assert_eq!(ContentKind::Other, res.content_kind());
+    }
+}
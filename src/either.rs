@@ -0,0 +1,162 @@
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+use crate::RocketResponseGeneric3;
+use crate::{RocketResponse, RocketResponseGeneric, RocketResponseGeneric2};
+use rocket::{
+    request::Request,
+    response::{self, Responder},
+    serde::Serialize,
+};
+
+/// Implements `From<Either*>` for an enum, if every responder converts into it.
+macro_rules! impl_from_either {
+    ($either:ident [$($var:ident),+] => [$($gen:tt)*] $name:ty $(where $($bound:tt)+)?) => {
+        impl<$($gen)* $($var),+> From<$either<$($var),+>> for $name
+        where
+            $($var: Into<$name>,)+
+            $($($bound)+)?
+        {
+            fn from(either: $either<$($var),+>) -> Self {
+                match either {
+                    $($either::$var(r) => r.into(),)+
+                }
+            }
+        }
+    };
+}
+
+/// Declares an `Either*` responder of the responders `$var`.
+macro_rules! either {
+    ($(#[$meta:meta])* $either:ident [$($var:ident),+]) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub enum $either<$($var),+> {
+            $(
+                #[doc = concat!("Responder of type `", stringify!($var), "`")]
+                $var($var),
+            )+
+        }
+
+        impl<'r, 'o: 'r, $($var: Responder<'r, 'o>),+> Responder<'r, 'o> for $either<$($var),+> {
+            fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+                match self {
+                    $($either::$var(r) => r.respond_to(req),)+
+                }
+            }
+        }
+
+        impl_from_either!($either [$($var),+] => [] RocketResponse);
+        impl_from_either!($either [$($var),+] => [T,] RocketResponseGeneric<T> where T: Serialize);
+        impl_from_either!(
+            $either [$($var),+] => [T, U,] RocketResponseGeneric2<T, U> where T: Serialize
+        );
+        #[cfg(any(
+            feature = "bincode",
+            feature = "cbor",
+            feature = "json",
+            feature = "msgpack",
+            feature = "xml"
+        ))]
+        impl_from_either!(
+            $either [$($var),+] => [T, S, U,] RocketResponseGeneric3<T, S, U> where S: Serialize
+        );
+    };
+}
+
+either!(
+    /// One of two responders
+    ///
+    /// A lightweight alternative to the enums, if a route returns one of two arbitrary
+    /// responders. If both responders convert into one of the enums, so does the
+    /// `Either2`.
+    ///
+    /// ## Example usage
+    ///
+    /// ```rust
+    /// use rocket::{get, response::Redirect};
+    /// use rocket_response::{Either2, RocketResponse};
+    ///
+    /// #[get("/<id>")]
+    /// fn item(id: usize) -> Either2<Redirect, String> {
+    ///     match id {
+    ///         0 => Either2::A(Redirect::to("/")),
+    ///         _ => Either2::B(format!("item {}", id)),
+    ///     }
+    /// }
+    ///
+    /// let res: RocketResponse = Either2::<RocketResponse, RocketResponse>::B(
+    ///     RocketResponse::StaticStr("Hello world"),
+    /// )
+    /// .into();
+    /// assert_eq!("StaticStr", res.variant_name());
+    /// ```
+    Either2[A, B]
+);
+
+either!(
+    /// One of three responders, see [Either2]
+    Either3[A, B, C]
+);
+
+either!(
+    /// One of four responders, see [Either2]
+    Either4[A, B, C, D]
+);
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::{Either2, Either3, Either4};
+    use crate::{RocketResponse, RocketResponseGeneric};
+    use rocket::{
+        get,
+        http::{ContentType, Status},
+        local::blocking::Client,
+        response::{content::RawHtml, Redirect},
+        routes,
+    };
+
+    #[get("/either/<id>")]
+    fn route_either(id: usize) -> Either4<Redirect, &'static str, RawHtml<&'static str>, Status> {
+        match id {
+            0 => Either4::A(Redirect::to("/")),
+            1 => Either4::B("plain"),
+            2 => Either4::C(RawHtml("<p>html</p>")),
+            _ => Either4::D(Status::Accepted),
+        }
+    }
+
+    #[test]
+    fn test_either() {
+        let rocket = rocket::build().mount("/", routes![route_either]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/either/0").dispatch();
+        assert_eq!(Status::SeeOther, res.status());
+        assert_eq!(Some("/"), res.headers().get_one("Location"));
+        let res = client.get("/either/1").dispatch();
+        assert_eq!(Some(ContentType::Plain), res.content_type());
+        let res = client.get("/either/2").dispatch();
+        assert_eq!(Some(ContentType::HTML), res.content_type());
+        let res = client.get("/either/3").dispatch();
+        assert_eq!(Status::Accepted, res.status());
+    }
+
+    #[test]
+    fn test_either_into() {
+        let res: RocketResponse = Either2::<Status, RocketResponse>::A(Status::NotFound).into();
+        assert_eq!("StatusPage", res.variant_name());
+
+        let res: RocketResponseGeneric<&str> =
+            Either3::<RocketResponseGeneric<&str>, Status, Status>::A(
+                RocketResponseGeneric::StaticStr("a"),
+            )
+            .into();
+        assert_eq!("StaticStr", res.variant_name());
+    }
+}
@@ -0,0 +1,520 @@
+use crate::AcceptList;
+use rocket::{
+    http::{ContentType, Header, MediaType, Status},
+    request::Request,
+    response::{self, Responder, Response},
+};
+use std::{
+    collections::hash_map::RandomState,
+    fmt,
+    hash::{BuildHasher, Hasher},
+    io::{self, Cursor},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+/// Error response rendered as HTML or, if the client prefers it, as JSON
+///
+/// The message of the client errors is sent to the client. The message of
+/// [Internal](RocketError::Internal) is only logged together with a new [Incident] ID, and
+/// the client gets the ID in the body and the `X-Incident-Id` header instead, so support
+/// can find the log entry of a user report.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::get;
+/// use rocket_response::RocketError;
+///
+/// #[get("/items/<id>")]
+/// fn item(id: usize) -> Result<String, RocketError> {
+///     match id {
+///         0 => Err(RocketError::NotFound(Some(format!("no item {}", id)))),
+///         1 => Err(RocketError::Internal("database connection lost".to_string())),
+///         _ => Ok(format!("item {}", id)),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RocketError {
+    /// `400 Bad Request` with an optional message
+    BadRequest(Option<String>),
+    /// `401 Unauthorized` with an optional message
+    Unauthorized(Option<String>),
+    /// `403 Forbidden` with an optional message
+    Forbidden(Option<String>),
+    /// `404 Not Found` with an optional message
+    NotFound(Option<String>),
+    /// `409 Conflict` with an optional message
+    Conflict(Option<String>),
+    /// `500 Internal Server Error` with a message, which is only logged
+    Internal(String),
+    /// `503 Service Unavailable` with an optional message
+    Unavailable(Option<String>),
+    /// Any other error status with an optional message
+    Other(Status, Option<String>),
+}
+
+impl RocketError {
+    /// Status of the response
+    pub fn status(&self) -> Status {
+        match self {
+            RocketError::BadRequest(_) => Status::BadRequest,
+            RocketError::Unauthorized(_) => Status::Unauthorized,
+            RocketError::Forbidden(_) => Status::Forbidden,
+            RocketError::NotFound(_) => Status::NotFound,
+            RocketError::Conflict(_) => Status::Conflict,
+            RocketError::Internal(_) => Status::InternalServerError,
+            RocketError::Unavailable(_) => Status::ServiceUnavailable,
+            RocketError::Other(status, _) => *status,
+        }
+    }
+
+    /// Message sent to the client, which is never the one of `Internal`
+    pub fn public_message(&self) -> Option<&str> {
+        match self {
+            RocketError::BadRequest(msg)
+            | RocketError::Unauthorized(msg)
+            | RocketError::Forbidden(msg)
+            | RocketError::NotFound(msg)
+            | RocketError::Conflict(msg)
+            | RocketError::Unavailable(msg)
+            | RocketError::Other(_, msg) => msg.as_deref(),
+            RocketError::Internal(_) => None,
+        }
+    }
+}
+
+/// [RocketError] of `status`
+///
+/// The mapping is total: the statuses with an own variant map on it without message, every
+/// other status, also a non-error one, maps on [Other](RocketError::Other). The message of
+/// [Internal](RocketError::Internal) is the reason phrase. It is the reverse of
+/// [error_to_status], so gateways can pass on any upstream status.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::http::Status;
+/// use rocket_response::{error_to_status, status_to_error, RocketError};
+///
+/// assert_eq!(RocketError::NotFound(None), status_to_error(Status::NotFound));
+/// let error = status_to_error(Status::ImATeapot);
+/// assert_eq!(RocketError::Other(Status::ImATeapot, None), error);
+/// assert_eq!(Status::ImATeapot, error_to_status(&error));
+/// ```
+pub fn status_to_error(status: Status) -> RocketError {
+    match status.code {
+        400 => RocketError::BadRequest(None),
+        401 => RocketError::Unauthorized(None),
+        403 => RocketError::Forbidden(None),
+        404 => RocketError::NotFound(None),
+        409 => RocketError::Conflict(None),
+        500 => RocketError::Internal(status.reason_lossy().to_string()),
+        503 => RocketError::Unavailable(None),
+        _ => RocketError::Other(status, None),
+    }
+}
+
+/// Status of `error`, the reverse of [status_to_error]
+pub fn error_to_status(error: &RocketError) -> Status {
+    error.status()
+}
+
+impl fmt::Display for RocketError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RocketError::Internal(msg) => write!(f, "{}: {}", self.status(), msg),
+            _ => match self.public_message() {
+                Some(msg) => write!(f, "{}: {}", self.status(), msg),
+                None => write!(f, "{}", self.status()),
+            },
+        }
+    }
+}
+
+impl std::error::Error for RocketError {}
+
+/// Failure of a responder before its body exists
+///
+/// Instead of a panic or a bare `500` of the default catcher, the failure responds as the
+/// [RocketError] it converts into: a missing file as `404 Not Found`, a denied one as
+/// `403 Forbidden`, everything else as [Internal](RocketError::Internal) with an
+/// [Incident], so the message is only logged. A managed [BuildErrorHook] is called before.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::get;
+/// use rocket_response::{BuildError, RocketResponse, TryRespond};
+///
+/// #[get("/report")]
+/// fn report() -> RocketResponse {
+///     std::fs::read_to_string("report.txt")
+///         .map(RocketResponse::String)
+///         .try_respond()
+/// }
+///
+/// let error = BuildError::Render("unknown template `report`".to_string());
+/// assert_eq!("template failed to render: unknown template `report`", error.to_string());
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum BuildError {
+    /// Serialization into the format, e.g. `JSON`, failed with the message.
+    Serialize(String, String),
+    /// Rendering of a template failed with the message.
+    Render(String),
+    /// Opening or reading a file failed.
+    Io(io::Error),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::Serialize(format, msg) => {
+                write!(f, "{} failed to serialize: {}", format, msg)
+            }
+            BuildError::Render(msg) => write!(f, "template failed to render: {}", msg),
+            BuildError::Io(e) => write!(f, "I/O failed: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for BuildError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BuildError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for BuildError {
+    fn from(e: io::Error) -> Self {
+        BuildError::Io(e)
+    }
+}
+
+impl From<BuildError> for RocketError {
+    fn from(error: BuildError) -> Self {
+        match &error {
+            BuildError::Io(e) if e.kind() == io::ErrorKind::NotFound => RocketError::NotFound(None),
+            BuildError::Io(e) if e.kind() == io::ErrorKind::PermissionDenied => {
+                RocketError::Forbidden(None)
+            }
+            _ => RocketError::Internal(error.to_string()),
+        }
+    }
+}
+
+impl<'r> Responder<'r, 'static> for BuildError {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        if let Some(hook) = req.rocket().state::<BuildErrorHook>() {
+            (hook.0)(&self, req);
+        }
+        RocketError::from(self).respond_to(req)
+    }
+}
+
+/// Hook called with every [BuildError] before it responds, managed by Rocket
+///
+/// E.g. for metrics or an error tracker. The [Internal](RocketError::Internal) errors are
+/// logged anyway.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket_response::BuildErrorHook;
+///
+/// let rocket = rocket::build().manage(BuildErrorHook::new(|error, req| {
+///     eprintln!("{} {}: {}", req.method(), req.uri(), error);
+/// }));
+/// ```
+#[derive(Clone)]
+pub struct BuildErrorHook(Hook);
+
+type Hook = Arc<dyn Fn(&BuildError, &Request<'_>) + Send + Sync>;
+
+impl BuildErrorHook {
+    /// Hook calling `hook`
+    pub fn new<F>(hook: F) -> Self
+    where
+        F: Fn(&BuildError, &Request<'_>) + Send + Sync + 'static,
+    {
+        BuildErrorHook(Arc::new(hook))
+    }
+}
+
+impl fmt::Debug for BuildErrorHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("BuildErrorHook")
+    }
+}
+
+/// ID of an internal error, which is sent to the client and logged
+///
+/// The ID of the current request is available in the request-local cache, e.g. for a
+/// logging fairing:
+///
+/// ```rust
+/// use rocket::{fairing::AdHoc, Request};
+/// use rocket_response::Incident;
+///
+/// let fairing = AdHoc::on_response("Incidents", |req, _| Box::pin(async move {
+///     if let Some(incident) = req.local_cache(|| None::<Incident>) {
+///         println!("incident {} at {}", incident, req.uri());
+///     }
+/// }));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Incident(String);
+
+impl Incident {
+    /// New short random ID of 12 hex digits
+    pub fn new() -> Self {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+        Incident(format!("{:012x}", hasher.finish() >> 16))
+    }
+
+    /// The ID
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for Incident {
+    fn default() -> Self {
+        Incident::new()
+    }
+}
+
+impl fmt::Display for Incident {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Escapes `s` for a JSON string.
+pub(crate) fn escape_json(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escapes `s` for HTML text.
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+impl<'r> Responder<'r, 'static> for RocketError {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let status = self.status();
+        let incident = match &self {
+            RocketError::Internal(msg) => {
+                let incident = Incident::new();
+                rocket::error_!("Incident {}: {}", incident, msg);
+                req.local_cache(|| Some(incident.clone()));
+                Some(incident)
+            }
+            _ => None,
+        };
+        let message = self
+            .public_message()
+            .unwrap_or_else(|| status.reason_lossy());
+
+        let json = AcceptList::from_request(req).preferred(&[MediaType::HTML, MediaType::JSON])
+            == Some(&MediaType::JSON);
+        let (content_type, body) = if json {
+            let mut body = format!(
+                "{{\"status\":{},\"message\":\"{}\"",
+                status.code,
+                escape_json(message)
+            );
+            if let Some(incident) = &incident {
+                body.push_str(&format!(",\"incident\":\"{}\"", incident));
+            }
+            body.push('}');
+            (ContentType::JSON, body)
+        } else {
+            let mut body = format!(
+                "<!DOCTYPE html>\n<html><head><title>{}</title></head><body>\
+                <h1>{}</h1><p>{}</p>",
+                status,
+                status,
+                escape_html(message)
+            );
+            if let Some(incident) = &incident {
+                body.push_str(&format!("<p>Incident: <code>{}</code></p>", incident));
+            }
+            body.push_str("</body></html>");
+            (ContentType::HTML, body)
+        };
+
+        let mut builder = Response::build();
+        builder
+            .status(status)
+            .header(content_type)
+            .sized_body(body.len(), Cursor::new(body));
+        if let Some(incident) = incident {
+            builder.header(Header::new("X-Incident-Id", incident.0));
+        }
+        builder.ok()
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::{
+        error_to_status, status_to_error, BuildError, BuildErrorHook, Incident, RocketError,
+    };
+    use crate::{RocketResponse, TryRespond};
+    use rocket::{
+        get,
+        http::{Accept, ContentType, Status},
+        local::blocking::Client,
+        routes,
+    };
+    use std::{
+        fs,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+    };
+
+    #[get("/error/<id>")]
+    fn route_error(id: usize) -> RocketResponse {
+        match id {
+            0 => RocketResponse::RocketError(RocketError::NotFound(Some("no <item>".to_string()))),
+            _ => RocketResponse::RocketError(RocketError::Internal("secret".to_string())),
+        }
+    }
+
+    #[get("/build/<id>")]
+    fn route_build(id: usize) -> RocketResponse {
+        match id {
+            0 => fs::read_to_string("no-such-file.txt")
+                .map(RocketResponse::String)
+                .try_respond(),
+            1 => Err::<RocketResponse, _>(BuildError::Render("no template".to_string()))
+                .try_respond(),
+            _ => fs::read_to_string("Cargo.toml")
+                .map(RocketResponse::String)
+                .try_respond(),
+        }
+    }
+
+    #[test]
+    fn test_status_to_error() {
+        let registered: Vec<Status> = (100..600).filter_map(Status::from_code).collect();
+        assert_eq!(60, registered.len());
+        for status in registered {
+            let error = status_to_error(status);
+            assert_eq!(status, error_to_status(&error), "{}", status);
+            assert_eq!(status, status_to_error(error.status()).status());
+        }
+        assert_eq!(
+            RocketError::Unauthorized(None),
+            status_to_error(Status::Unauthorized)
+        );
+        assert_eq!(
+            RocketError::Unavailable(None),
+            status_to_error(Status::ServiceUnavailable)
+        );
+        assert_eq!(
+            RocketError::Other(Status::Ok, None),
+            status_to_error(Status::Ok)
+        );
+        assert_eq!(
+            RocketError::Other(Status::new(599), None),
+            status_to_error(Status::new(599))
+        );
+    }
+
+    #[test]
+    fn test_incident() {
+        let incident = Incident::new();
+        assert_eq!(12, incident.as_str().len());
+        assert_ne!(incident, Incident::new());
+    }
+
+    #[test]
+    fn test_error() {
+        let rocket = rocket::build().mount("/", routes![route_error]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/error/0").dispatch();
+        assert_eq!(Status::NotFound, res.status());
+        assert_eq!(ContentType::HTML, res.content_type().unwrap());
+        assert!(res
+            .into_string()
+            .unwrap()
+            .contains("<p>no &lt;item&gt;</p>"));
+
+        let res = client.get("/error/0").header(Accept::JSON).dispatch();
+        assert_eq!(
+            "{\"status\":404,\"message\":\"no <item>\"}",
+            res.into_string().unwrap()
+        );
+
+        let res = client.get("/error/1").header(Accept::JSON).dispatch();
+        assert_eq!(Status::InternalServerError, res.status());
+        let incident = res.headers().get_one("X-Incident-Id").unwrap().to_string();
+        let body = res.into_string().unwrap();
+        assert_eq!(
+            format!(
+                "{{\"status\":500,\"message\":\"Internal Server Error\",\"incident\":\"{}\"}}",
+                incident
+            ),
+            body
+        );
+        assert!(!body.contains("secret"));
+
+        let res = client.get("/error/1").dispatch();
+        assert!(res.into_string().unwrap().contains("<code>"));
+    }
+
+    #[test]
+    fn test_build_error() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let rocket = rocket::build()
+            .manage(BuildErrorHook::new(move |_, _| {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }))
+            .mount("/", routes![route_build]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/build/0").dispatch();
+        assert_eq!(Status::NotFound, res.status());
+        assert_eq!(1, calls.load(Ordering::SeqCst));
+
+        let res = client.get("/build/1").dispatch();
+        assert_eq!(Status::InternalServerError, res.status());
+        assert!(res.headers().contains("X-Incident-Id"));
+        assert!(!res.into_string().unwrap().contains("no template"));
+        assert_eq!(2, calls.load(Ordering::SeqCst));
+
+        let res = client.get("/build/2").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(2, calls.load(Ordering::SeqCst));
+    }
+}
@@ -0,0 +1,207 @@
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+use crate::RocketResponseGeneric3;
+use crate::{Cached, RangedFile, RocketResponse, RocketResponseGeneric, RocketResponseGeneric2};
+use rocket::{fs::NamedFile, http::Status, serde::Serialize, tokio};
+use std::{
+    io::{self, ErrorKind},
+    path::Path,
+};
+
+/// Maps an [ErrorKind::NotFound] error to `Ok(None)`.
+fn found<F>(file: io::Result<F>) -> io::Result<Option<F>> {
+    match file {
+        Ok(file) => Ok(Some(file)),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Opens the file at `path` wrapped in [Cached] with the modification time of the file.
+async fn conditional<R>(path: &Path, f: fn(NamedFile) -> R) -> io::Result<Cached<R>> {
+    let file = NamedFile::open(path).await?;
+    let modified = file.file().metadata().await?.modified().ok();
+    let cached = Cached::new(f(file));
+    Ok(match modified {
+        Some(modified) => cached.last_modified(modified),
+        None => cached,
+    })
+}
+
+/// Implements the async constructors opening files for the `NamedFiled`, `RangedFile` and
+/// `TokioFile` variants.
+macro_rules! impl_file_constructors {
+    ([$($gen:tt)*] $name:ty, [$($bound:tt)*]) => {
+        impl<$($gen)*> $name where $($bound)* {
+            /// Opens the file at `path` as `NamedFiled`, see [NamedFile::open].
+            pub async fn named_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+                Ok(Self::NamedFiled(Box::new(NamedFile::open(path).await?)))
+            }
+
+            /// Opens the file at `path` as `NamedFiled` or responds with `404 Not Found`
+            /// handled by the catcher, if the file doesn't exist.
+            pub async fn named_file_or_not_found<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+                Ok(found(NamedFile::open(path).await)?
+                    .map_or(Self::Status(Status::NotFound), |file| {
+                        Self::NamedFiled(Box::new(file))
+                    }))
+            }
+
+            /// Opens the file at `path` as `NamedFiled` wrapped in `Cached` with the
+            /// modification time of the file as `Last-Modified`.
+            ///
+            /// A request with an `If-Modified-Since` not before the modification time is
+            /// answered with `304 Not Modified`, see [Cached].
+            pub async fn conditional_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+                let cached = conditional(path.as_ref(), |file| Self::NamedFiled(Box::new(file)));
+                Ok(Self::Cached(cached.await?))
+            }
+
+            /// Opens the file at `path` like [conditional_file](Self::conditional_file()) or
+            /// responds with `404 Not Found` handled by the catcher, if the file doesn't exist.
+            pub async fn conditional_file_or_not_found<P: AsRef<Path>>(
+                path: P,
+            ) -> io::Result<Self> {
+                let cached = conditional(path.as_ref(), |file| Self::NamedFiled(Box::new(file)));
+                Ok(found(cached.await)?.map_or(Self::Status(Status::NotFound), Self::Cached))
+            }
+
+            /// Opens the file at `path` as `RangedFile`, see [RangedFile::open].
+            pub async fn ranged_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+                Ok(Self::RangedFile(Box::new(RangedFile::open(path).await?)))
+            }
+
+            /// Opens the file at `path` as `RangedFile` or responds with `404 Not Found`
+            /// handled by the catcher, if the file doesn't exist.
+            pub async fn ranged_file_or_not_found<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+                Ok(found(RangedFile::open(path).await)?
+                    .map_or(Self::Status(Status::NotFound), |file| {
+                        Self::RangedFile(Box::new(file))
+                    }))
+            }
+
+            /// Opens the file at `path` as `TokioFile`, see [tokio::fs::File::open].
+            pub async fn tokio_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+                Ok(Self::TokioFile(Box::new(tokio::fs::File::open(path).await?)))
+            }
+
+            /// Opens the file at `path` as `TokioFile` or responds with `404 Not Found`
+            /// handled by the catcher, if the file doesn't exist.
+            pub async fn tokio_file_or_not_found<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+                Ok(found(tokio::fs::File::open(path).await)?
+                    .map_or(Self::Status(Status::NotFound), |file| {
+                        Self::TokioFile(Box::new(file))
+                    }))
+            }
+        }
+    };
+}
+
+impl_file_constructors!([] RocketResponse, []);
+impl_file_constructors!([T] RocketResponseGeneric<T>, [T: Serialize]);
+impl_file_constructors!([T, U] RocketResponseGeneric2<T, U>, [T: Serialize]);
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+impl_file_constructors!([T, S, U] RocketResponseGeneric3<T, S, U>, [S: Serialize]);
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use crate::{RocketResponse, RocketResponseGeneric};
+    use rocket::{
+        get,
+        http::{Header, Status},
+        local::blocking::Client,
+        routes,
+    };
+    use std::io;
+
+    #[get("/named_file/<name>")]
+    async fn route_named_file(name: &str) -> io::Result<RocketResponse> {
+        RocketResponse::named_file_or_not_found(name).await
+    }
+
+    #[get("/ranged_file/<name>")]
+    async fn route_ranged_file(name: &str) -> io::Result<RocketResponse> {
+        RocketResponse::ranged_file_or_not_found(name).await
+    }
+
+    #[get("/conditional_file/<name>")]
+    async fn route_conditional_file(name: &str) -> io::Result<RocketResponse> {
+        RocketResponse::conditional_file_or_not_found(name).await
+    }
+
+    #[get("/tokio_file/<name>")]
+    async fn route_tokio_file(name: &str) -> io::Result<RocketResponseGeneric<&'static str>> {
+        RocketResponseGeneric::tokio_file_or_not_found(name).await
+    }
+
+    #[test]
+    fn test_file_constructors() {
+        let rocket = rocket::build().mount(
+            "/",
+            routes![
+                route_named_file,
+                route_conditional_file,
+                route_ranged_file,
+                route_tokio_file
+            ],
+        );
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        for path in [
+            "/named_file",
+            "/conditional_file",
+            "/ranged_file",
+            "/tokio_file",
+        ]
+        .iter()
+        {
+            let res = client.get(format!("{}/Cargo.toml", path)).dispatch();
+            assert_eq!(Status::Ok, res.status());
+            assert!(res.into_string().unwrap().contains("rocket-response"));
+
+            let res = client.get(format!("{}/missing.toml", path)).dispatch();
+            assert_eq!(Status::NotFound, res.status());
+        }
+
+        let res = client.get("/conditional_file/Cargo.toml").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        let last_modified = res.headers().get_one("Last-Modified").unwrap().to_string();
+        let res = client
+            .get("/conditional_file/Cargo.toml")
+            .header(Header::new("If-Modified-Since", last_modified))
+            .dispatch();
+        assert_eq!(Status::NotModified, res.status());
+        assert!(res.headers().contains("Last-Modified"));
+        assert!(res.into_bytes().unwrap_or_default().is_empty());
+        let res = client
+            .get("/conditional_file/Cargo.toml")
+            .header(Header::new(
+                "If-Modified-Since",
+                "Thu, 01 Jan 1970 00:00:00 GMT",
+            ))
+            .dispatch();
+        assert_eq!(Status::Ok, res.status());
+
+        let rt = rocket::tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let err = rt
+            .block_on(RocketResponse::named_file("missing.toml"))
+            .err()
+            .unwrap();
+        assert_eq!(io::ErrorKind::NotFound, err.kind());
+    }
+}
@@ -0,0 +1,358 @@
+use crate::SecurityHeaders;
+#[cfg(feature = "compress")]
+use crate::{compressed::Settings, Compressed};
+use rocket::{
+    fairing::{self, Fairing, Info, Kind},
+    http::ContentType,
+    Build, Request, Response, Rocket,
+};
+use std::{fmt, sync::Arc};
+
+/// Conditions on the route and the response a filter of a [ResponseFilterSet] applies to
+///
+/// Every condition set must be met. The route conditions refer to the route as mounted, so
+/// a [uri_prefix](Self::uri_prefix()) of `/api` matches the routes mounted at `/api` and
+/// below, but not `/apidocs`. A response without a route, like of a catcher, doesn't meet
+/// any route condition.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::http::ContentType;
+/// use rocket_response::RouteFilter;
+///
+/// let api = RouteFilter::all().uri_prefix("/api");
+/// let html = RouteFilter::all().content_type(ContentType::HTML);
+/// let index = RouteFilter::all().name("index").rank(1);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RouteFilter {
+    name: Option<String>,
+    rank: Option<isize>,
+    uri_prefix: Option<String>,
+    content_type: Option<ContentType>,
+}
+
+impl RouteFilter {
+    /// Filter without any condition, matching every response
+    pub fn all() -> Self {
+        RouteFilter::default()
+    }
+
+    /// Requires the route `name`, the name of the handler function by default.
+    pub fn name<N: Into<String>>(mut self, name: N) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Requires the route `rank`.
+    pub fn rank(mut self, rank: isize) -> Self {
+        self.rank = Some(rank);
+        self
+    }
+
+    /// Requires a route path at or below `prefix`.
+    pub fn uri_prefix<P: Into<String>>(mut self, prefix: P) -> Self {
+        self.uri_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Requires a response of `content_type`, regardless of its parameters.
+    pub fn content_type(mut self, content_type: ContentType) -> Self {
+        self.content_type = Some(content_type);
+        self
+    }
+
+    /// `true` if the route of `req` and a response of `content_type` meet all conditions
+    pub fn matches(&self, req: &Request<'_>, content_type: Option<&ContentType>) -> bool {
+        if let Some(expected) = &self.content_type {
+            match content_type {
+                Some(ct) if ct.top() == expected.top() && ct.sub() == expected.sub() => {}
+                _ => return false,
+            }
+        }
+        if self.name.is_none() && self.rank.is_none() && self.uri_prefix.is_none() {
+            return true;
+        }
+        let route = match req.route() {
+            Some(route) => route,
+            None => return false,
+        };
+        if self.name.is_some() && route.name.as_deref() != self.name.as_deref() {
+            return false;
+        }
+        if self.rank.map_or(false, |rank| rank != route.rank) {
+            return false;
+        }
+        self.uri_prefix.as_deref().map_or(true, |prefix| {
+            let prefix = prefix.trim_end_matches('/');
+            let path = route.uri.path();
+            path == prefix || path.starts_with(&format!("{}/", prefix)) || prefix.is_empty()
+        })
+    }
+}
+
+/// Wrapper or fairing applied by a [ResponseFilterSet]
+#[derive(Clone)]
+enum Filter {
+    #[cfg(feature = "compress")]
+    Compress(Settings),
+    Harden(SecurityHeaders),
+    Fairing(Arc<dyn Fairing>),
+}
+
+impl fmt::Debug for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            #[cfg(feature = "compress")]
+            Filter::Compress(settings) => f.debug_tuple("Compress").field(settings).finish(),
+            Filter::Harden(headers) => f.debug_tuple("Harden").field(headers).finish(),
+            Filter::Fairing(fairing) => f.debug_tuple("Fairing").field(&fairing.info()).finish(),
+        }
+    }
+}
+
+/// Wrappers and fairings applied to the responses of the routes matching a [RouteFilter],
+/// attached as fairing
+///
+/// Instead of wrapping the response of every route, the filters are declared once with the
+/// routes, so e.g. only the responses of `/api` are [compressed](Self::compress()) and only
+/// HTML responses get the [security headers](Self::harden()). The filters are applied in
+/// the order they are added, each to the response left by the ones before.
+///
+/// A [fairing](Self::fairing()) is called on the responses matching its filter only. Its
+/// ignite callback is called on ignition, other callbacks aren't called.
+///
+/// The set is managed by the fairing, so routes get it as `&State<ResponseFilterSet>`.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::http::ContentType;
+/// use rocket_response::{HeaderLimit, ResponseFilterSet, RouteFilter, SecurityHeaders};
+///
+/// let filters = ResponseFilterSet::new()
+///     .harden(
+///         RouteFilter::all().content_type(ContentType::HTML),
+///         SecurityHeaders::default(),
+///     )
+///     .fairing(RouteFilter::all().uri_prefix("/api"), HeaderLimit::new(4096));
+///
+/// let rocket = rocket::build().attach(filters);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ResponseFilterSet {
+    filters: Vec<(RouteFilter, Filter)>,
+}
+
+impl ResponseFilterSet {
+    /// Set without any filter
+    pub fn new() -> Self {
+        ResponseFilterSet::default()
+    }
+
+    /// Compresses the matching responses like the [Compressed] wrapper `settings`, whose
+    /// wrapped responder is ignored.
+    ///
+    /// Requires the `compress` feature.
+    #[cfg(feature = "compress")]
+    pub fn compress<R>(mut self, filter: RouteFilter, settings: Compressed<R>) -> Self {
+        self.filters
+            .push((filter, Filter::Compress(settings.into_settings())));
+        self
+    }
+
+    /// Sets the security `headers` of the matching responses like the
+    /// [Hardened](crate::Hardened) wrapper.
+    pub fn harden(mut self, filter: RouteFilter, headers: SecurityHeaders) -> Self {
+        self.filters.push((filter, Filter::Harden(headers)));
+        self
+    }
+
+    /// Calls the response callback of `fairing` on the matching responses.
+    pub fn fairing<F: Fairing>(mut self, filter: RouteFilter, fairing: F) -> Self {
+        self.filters
+            .push((filter, Filter::Fairing(Arc::new(fairing))));
+        self
+    }
+
+    /// `true` if any filter matches the route of `req` and a response of `content_type`
+    pub fn matches(&self, req: &Request<'_>, content_type: Option<&ContentType>) -> bool {
+        self.filters
+            .iter()
+            .any(|(filter, _)| filter.matches(req, content_type))
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for ResponseFilterSet {
+    fn info(&self) -> Info {
+        Info {
+            name: "Response filters",
+            kind: Kind::Ignite | Kind::Response,
+        }
+    }
+
+    async fn on_ignite(&self, mut rocket: Rocket<Build>) -> fairing::Result {
+        for (_, filter) in &self.filters {
+            if let Filter::Fairing(fairing) = filter {
+                if fairing.info().kind.is(Kind::Ignite) {
+                    rocket = fairing.on_ignite(rocket).await?;
+                }
+            }
+        }
+        Ok(rocket.manage(self.clone()))
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        for (route_filter, filter) in &self.filters {
+            if !route_filter.matches(req, res.content_type().as_ref()) {
+                continue;
+            }
+            match filter {
+                #[cfg(feature = "compress")]
+                Filter::Compress(settings) => {
+                    let taken = std::mem::replace(res, Response::new());
+                    *res = settings.compress(req, taken).unwrap_or_else(|status| {
+                        let mut res = Response::new();
+                        res.set_status(status);
+                        res
+                    });
+                }
+                Filter::Harden(headers) => headers.apply(res),
+                Filter::Fairing(fairing) => {
+                    if fairing.info().kind.is(Kind::Response) {
+                        fairing.on_response(req, res).await;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::{ResponseFilterSet, RouteFilter};
+    use crate::{Maintenance, RocketResponse, SecurityHeaders};
+    #[cfg(feature = "compress")]
+    use rocket::http::Header;
+    use rocket::{
+        fairing::AdHoc,
+        get,
+        http::{ContentType, Status},
+        local::blocking::Client,
+        response::content::{RawHtml, RawJson},
+        routes, State,
+    };
+    use std::time::Duration;
+
+    #[get("/page")]
+    fn route_page() -> RocketResponse {
+        RocketResponse::Html(RawHtml("<p>Hello world</p>"))
+    }
+
+    #[get("/data", rank = 3)]
+    fn route_data() -> RocketResponse {
+        RocketResponse::Json(RawJson(r#"{"hello":"world"}"#))
+    }
+
+    #[get("/maintenance")]
+    fn route_maintenance(
+        maintenance: &State<Maintenance>,
+        _filters: &State<ResponseFilterSet>,
+    ) -> RocketResponse {
+        maintenance.enable(Duration::from_secs(60));
+        RocketResponse::Status(Status::ServiceUnavailable)
+    }
+
+    #[test]
+    fn test_response_filter_set() {
+        let filters = ResponseFilterSet::new()
+            .harden(
+                RouteFilter::all().content_type(ContentType::HTML),
+                SecurityHeaders::none().content_security_policy("default-src 'none'"),
+            )
+            .fairing(
+                RouteFilter::all().uri_prefix("/api/"),
+                AdHoc::on_response("Api", |_, res| {
+                    Box::pin(async move {
+                        res.set_raw_header("X-Api", "1");
+                    })
+                }),
+            )
+            .fairing(
+                RouteFilter::all().name("route_data").rank(3),
+                AdHoc::on_response("Data", |_, res| {
+                    Box::pin(async move {
+                        res.set_raw_header("X-Data", "1");
+                    })
+                }),
+            )
+            .fairing(
+                RouteFilter::all().name("route_maintenance"),
+                Maintenance::new(),
+            );
+        let rocket = rocket::build()
+            .attach(filters)
+            .mount("/", routes![route_page, route_data, route_maintenance])
+            .mount("/api", routes![route_page, route_data])
+            .mount("/apidocs", routes![route_data]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/page").dispatch();
+        assert!(res.headers().contains("Content-Security-Policy"));
+        assert!(!res.headers().contains("X-Api"));
+
+        let res = client.get("/api/page").dispatch();
+        assert!(res.headers().contains("Content-Security-Policy"));
+        assert_eq!(Some("1"), res.headers().get_one("X-Api"));
+
+        let res = client.get("/api/data").dispatch();
+        assert!(!res.headers().contains("Content-Security-Policy"));
+        assert_eq!(Some("1"), res.headers().get_one("X-Api"));
+        assert_eq!(Some("1"), res.headers().get_one("X-Data"));
+
+        let res = client.get("/apidocs/data").dispatch();
+        assert!(!res.headers().contains("X-Api"));
+        assert_eq!(Some("1"), res.headers().get_one("X-Data"));
+
+        let res = client.get("/missing").dispatch();
+        assert_eq!(Status::NotFound, res.status());
+        assert!(res.headers().contains("Content-Security-Policy"));
+        assert!(!res.headers().contains("X-Data"));
+
+        let res = client.get("/maintenance").dispatch();
+        assert_eq!(Status::ServiceUnavailable, res.status());
+        assert_eq!(Some("60"), res.headers().get_one("Retry-After"));
+    }
+
+    #[cfg(feature = "compress")]
+    #[test]
+    fn test_response_filter_set_compress() {
+        use crate::Compressed;
+
+        let filters = ResponseFilterSet::new().compress(
+            RouteFilter::all().uri_prefix("/api"),
+            Compressed::new(()).min_size(0),
+        );
+        let rocket = rocket::build()
+            .attach(filters)
+            .mount("/", routes![route_data])
+            .mount("/api", routes![route_data]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client
+            .get("/api/data")
+            .header(Header::new("Accept-Encoding", "gzip"))
+            .dispatch();
+        assert_eq!(Some("gzip"), res.headers().get_one("Content-Encoding"));
+
+        let res = client
+            .get("/data")
+            .header(Header::new("Accept-Encoding", "gzip"))
+            .dispatch();
+        assert!(!res.headers().contains("Content-Encoding"));
+        assert_eq!(r#"{"hello":"world"}"#, res.into_string().unwrap());
+    }
+}
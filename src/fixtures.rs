@@ -0,0 +1,210 @@
+use crate::RocketResponse;
+use rocket::{
+    http::{ContentType, Header, Status},
+    request::Request,
+    response::{self, Responder, Response},
+    serde::{json::serde_json, Deserialize},
+};
+use std::{
+    collections::BTreeMap,
+    convert::TryFrom,
+    fs,
+    io::{self, Cursor, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+/// Declarative description of a response, e.g. for test fixtures and mock servers
+///
+/// The spec is deserializable from JSON or TOML and converted into a [RocketResponse].
+/// The body is either the literal `body` or the content of the file at `file`.
+/// A relative `file` is resolved against the current working directory.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket_response::{ResponseSpec, RocketResponse};
+/// use std::convert::TryFrom;
+///
+/// let spec = ResponseSpec::from_toml(r#"
+///     status = 201
+///     content_type = "application/json"
+///     body = '{"id":1}'
+///
+///     [headers]
+///     Location = "/items/1"
+/// "#).unwrap();
+///
+/// let res = RocketResponse::try_from(spec).unwrap();
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(crate = "rocket::serde", default, deny_unknown_fields)]
+pub struct ResponseSpec {
+    /// Status code, `200` if missing
+    pub status: Option<u16>,
+    /// Additional headers
+    pub headers: BTreeMap<String, String>,
+    /// Content-Type, without it there is no Content-Type header
+    pub content_type: Option<String>,
+    /// Literal body
+    pub body: Option<String>,
+    /// Path of a file with the body
+    pub file: Option<PathBuf>,
+}
+
+impl ResponseSpec {
+    /// Parses a spec from JSON.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Parses a spec from TOML.
+    pub fn from_toml(toml: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(toml)
+    }
+
+    /// Reads a spec from a `.json` or `.toml` file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)?;
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Self::from_json(&content).map_err(|e| invalid(e.to_string())),
+            Some("toml") => Self::from_toml(&content).map_err(|e| invalid(e.to_string())),
+            _ => Err(invalid(format!(
+                "unknown fixture format of {}",
+                path.display()
+            ))),
+        }
+    }
+}
+
+fn invalid(msg: String) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, msg)
+}
+
+impl TryFrom<ResponseSpec> for RocketResponse {
+    type Error = io::Error;
+
+    /// Validates the spec and reads the body file, if any.
+    fn try_from(spec: ResponseSpec) -> Result<Self, Self::Error> {
+        let status = match spec.status {
+            Some(code) => Status::from_code(code)
+                .ok_or_else(|| invalid(format!("invalid status {}", code)))?,
+            None => Status::Ok,
+        };
+        let content_type = match spec.content_type {
+            Some(content_type) => Some(
+                ContentType::parse_flexible(&content_type)
+                    .ok_or_else(|| invalid(format!("invalid content type {}", content_type)))?,
+            ),
+            None => None,
+        };
+        let body = match (spec.body, spec.file) {
+            (Some(_), Some(_)) => return Err(invalid("body and file are exclusive".to_string())),
+            (Some(body), None) => body.into_bytes(),
+            (None, Some(file)) => fs::read(file)?,
+            (None, None) => Vec::new(),
+        };
+
+        Ok(RocketResponse::fallback(Fixture {
+            status,
+            headers: spec.headers,
+            content_type,
+            body,
+        }))
+    }
+}
+
+/// Responder of a validated [ResponseSpec]
+struct Fixture {
+    status: Status,
+    headers: BTreeMap<String, String>,
+    content_type: Option<ContentType>,
+    body: Vec<u8>,
+}
+
+impl<'r> Responder<'r, 'static> for Fixture {
+    fn respond_to(self, _req: &'r Request<'_>) -> response::Result<'static> {
+        let mut res = Response::build();
+        res.status(self.status);
+        if let Some(content_type) = self.content_type {
+            res.header(content_type);
+        }
+        for (name, value) in self.headers {
+            res.header_adjoin(Header::new(name, value));
+        }
+        if !self.body.is_empty() {
+            res.sized_body(self.body.len(), Cursor::new(self.body));
+        }
+        res.ok()
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::ResponseSpec;
+    use crate::RocketResponse;
+    use rocket::{
+        get,
+        http::{ContentType, Status},
+        local::blocking::Client,
+        routes,
+    };
+    use std::{convert::TryFrom, io};
+
+    #[get("/fixture/<name>")]
+    fn route_fixture(name: &str) -> io::Result<RocketResponse> {
+        let spec = match name {
+            "json" => ResponseSpec::from_json(
+                r#"{"status": 202, "content_type": "text/csv", "body": "a;b\n",
+                    "headers": {"X-Fixture": "json"}}"#,
+            )
+            .unwrap(),
+            _ => ResponseSpec::from_toml("file = \"Cargo.toml\"\n").unwrap(),
+        };
+        RocketResponse::try_from(spec)
+    }
+
+    #[test]
+    fn test_fixture() {
+        let rocket = rocket::build().mount("/", routes![route_fixture]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/fixture/json").dispatch();
+        assert_eq!(Status::Accepted, res.status());
+        assert_eq!(ContentType::CSV, res.content_type().unwrap());
+        assert_eq!(Some("json"), res.headers().get_one("X-Fixture"));
+        assert_eq!("a;b\n", res.into_string().unwrap());
+
+        let res = client.get("/fixture/toml").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert!(res.into_string().unwrap().contains("rocket-response"));
+    }
+
+    #[test]
+    fn test_invalid_spec() {
+        assert!(ResponseSpec::from_json(r#"{"code": 200}"#).is_err());
+        for spec in [
+            ResponseSpec {
+                status: Some(1000),
+                ..ResponseSpec::default()
+            },
+            ResponseSpec {
+                content_type: Some("no content type".to_string()),
+                ..ResponseSpec::default()
+            },
+            ResponseSpec {
+                body: Some(String::new()),
+                file: Some("Cargo.toml".into()),
+                ..ResponseSpec::default()
+            },
+        ]
+        .iter()
+        {
+            assert_eq!(
+                io::ErrorKind::InvalidData,
+                RocketResponse::try_from(spec.clone()).err().unwrap().kind()
+            );
+        }
+    }
+}
@@ -0,0 +1,182 @@
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+use crate::RocketResponseGeneric3;
+use crate::{RocketResponse, RocketResponseGeneric, RocketResponseGeneric2};
+use rocket::{
+    response::{Flash, Redirect},
+    serde::Serialize,
+};
+use std::{fmt, str::FromStr};
+
+/// Kind of a [Flash] message
+///
+/// The names match the kinds used by [Flash::success], [Flash::warning] and [Flash::error].
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::{get, request::FlashMessage};
+/// use rocket_response::FlashKind;
+///
+/// #[get("/")]
+/// fn index(flash: Option<FlashMessage<'_>>) -> String {
+///     match flash.map(|flash| (flash.kind().parse::<FlashKind>(), flash.message().to_string())) {
+///         Some((Ok(FlashKind::Error), msg)) => format!("Error: {}", msg),
+///         Some((_, msg)) => msg,
+///         None => String::new(),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FlashKind {
+    /// `success`
+    Success,
+    /// `warning`
+    Warning,
+    /// `error`
+    Error,
+}
+
+impl FlashKind {
+    /// The kind as used in the flash cookie
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FlashKind::Success => "success",
+            FlashKind::Warning => "warning",
+            FlashKind::Error => "error",
+        }
+    }
+}
+
+impl fmt::Display for FlashKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for FlashKind {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "success" => Ok(FlashKind::Success),
+            "warning" => Ok(FlashKind::Warning),
+            "error" => Ok(FlashKind::Error),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Implements the `flash*` constructors for the `$variant` holding a `Flash<$inner>`.
+macro_rules! impl_flash_constructors {
+    ([$($gen:tt)*] $name:ty, $variant:ident($inner:ty) $(where $($bound:tt)+)?) => {
+        impl<$($gen)*> $name $(where $($bound)+)? {
+            /// Flash message of `kind` sent with `responder`
+            pub fn flash<M: Into<String>>(kind: FlashKind, responder: $inner, msg: M) -> Self {
+                Self::$variant(Box::new(Flash::new(responder, kind.as_str(), msg)))
+            }
+
+            /// Flash message of kind `success`, see [Flash::success]
+            pub fn flash_success<M: Into<String>>(responder: $inner, msg: M) -> Self {
+                Self::flash(FlashKind::Success, responder, msg)
+            }
+
+            /// Flash message of kind `warning`, see [Flash::warning]
+            pub fn flash_warning<M: Into<String>>(responder: $inner, msg: M) -> Self {
+                Self::flash(FlashKind::Warning, responder, msg)
+            }
+
+            /// Flash message of kind `error`, see [Flash::error]
+            pub fn flash_error<M: Into<String>>(responder: $inner, msg: M) -> Self {
+                Self::flash(FlashKind::Error, responder, msg)
+            }
+        }
+    };
+}
+
+impl_flash_constructors!([] RocketResponse, FlashMsg(Redirect));
+impl_flash_constructors!([T] RocketResponseGeneric<T>, Flash(T) where T: Serialize);
+impl_flash_constructors!([T, U] RocketResponseGeneric2<T, U>, Flash(U) where T: Serialize);
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+impl_flash_constructors!([T, S, U] RocketResponseGeneric3<T, S, U>, Flash(U) where S: Serialize);
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::FlashKind;
+    use crate::{RocketResponse, RocketResponseGeneric2};
+    use rocket::{
+        get,
+        http::Status,
+        local::blocking::Client,
+        request::FlashMessage,
+        response::{Flash, Redirect},
+        routes,
+    };
+
+    #[get("/flash/<id>")]
+    fn route_flash(id: usize) -> RocketResponse {
+        match id {
+            0 => RocketResponse::flash_success(Redirect::to("/"), "saved"),
+            1 => RocketResponse::flash_warning(Redirect::to("/"), "unchanged"),
+            _ => RocketResponse::flash_error(Redirect::to("/"), "failed"),
+        }
+    }
+
+    #[get("/flash_generic2")]
+    fn route_flash_generic2() -> RocketResponseGeneric2<&'static str, Redirect> {
+        RocketResponseGeneric2::FlashResponse(Box::new(Flash::warning(
+            RocketResponse::redirect_found("/"),
+            "unchanged",
+        )))
+    }
+
+    #[get("/")]
+    fn route_index(flash: Option<FlashMessage<'_>>) -> String {
+        flash
+            .map(|flash| format!("{}:{}", flash.kind(), flash.message()))
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn test_flash_kind() {
+        for kind in [FlashKind::Success, FlashKind::Warning, FlashKind::Error].iter() {
+            assert_eq!(Ok(*kind), kind.to_string().parse());
+        }
+        assert_eq!(Err(()), "info".parse::<FlashKind>());
+    }
+
+    #[test]
+    fn test_flash() {
+        let rocket =
+            rocket::build().mount("/", routes![route_flash, route_flash_generic2, route_index]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        for (id, expected) in ["success:saved", "warning:unchanged", "error:failed"]
+            .iter()
+            .enumerate()
+        {
+            let res = client.get(format!("/flash/{}", id)).dispatch();
+            assert_eq!(Status::SeeOther, res.status());
+            assert_eq!(*expected, client.get("/").dispatch().into_string().unwrap());
+        }
+
+        let res = client.get("/flash_generic2").dispatch();
+        assert_eq!(Status::Found, res.status());
+        assert_eq!(
+            "warning:unchanged",
+            client.get("/").dispatch().into_string().unwrap()
+        );
+    }
+}
@@ -0,0 +1,134 @@
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+use crate::RocketResponseGeneric3;
+use crate::{RocketResponse, RocketResponseGeneric, RocketResponseGeneric2};
+use async_graphql_rocket::GraphQLResponse;
+use rocket::serde::Serialize;
+
+/// Implements the `graphql` constructor of the `GraphQL` variant.
+macro_rules! impl_graphql_constructors {
+    ([$($gen:tt)*] $name:ty $(where $($bound:tt)+)?) => {
+        impl<$($gen)*> $name $(where $($bound)+)? {
+            /// Response of a GraphQL query or batch, like of an executed
+            /// [GraphQLRequest](async_graphql_rocket::GraphQLRequest)
+            ///
+            /// The same endpoint can respond e.g. a redirect, an error or the HTML of a
+            /// GraphQL playground by the other variants.
+            ///
+            /// Requires the `graphql` feature.
+            ///
+            /// ## Example usage
+            ///
+            /// ```rust
+            /// use async_graphql::{EmptyMutation, EmptySubscription, Object, Schema};
+            /// use async_graphql_rocket::GraphQLRequest;
+            /// use rocket::{post, State};
+            /// use rocket_response::RocketResponse;
+            ///
+            /// struct Query;
+            ///
+            /// #[Object]
+            /// impl Query {
+            ///     async fn hello(&self) -> &str {
+            ///         "world"
+            ///     }
+            /// }
+            ///
+            /// type AppSchema = Schema<Query, EmptyMutation, EmptySubscription>;
+            ///
+            /// #[post("/graphql?<token>", data = "<request>")]
+            /// async fn graphql(
+            ///     token: Option<&str>,
+            ///     request: GraphQLRequest,
+            ///     schema: &State<AppSchema>,
+            /// ) -> RocketResponse {
+            ///     if token.is_none() {
+            ///         return RocketResponse::redirect_to("/login");
+            ///     }
+            ///     RocketResponse::graphql(request.execute(schema.inner()).await)
+            /// }
+            /// ```
+            pub fn graphql<R: Into<GraphQLResponse>>(response: R) -> Self {
+                Self::GraphQL(Box::new(response.into()))
+            }
+        }
+    };
+}
+
+impl_graphql_constructors!([] RocketResponse);
+impl_graphql_constructors!([T] RocketResponseGeneric<T> where T: Serialize);
+impl_graphql_constructors!([T, U] RocketResponseGeneric2<T, U> where T: Serialize);
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+impl_graphql_constructors!([T, S, U] RocketResponseGeneric3<T, S, U> where S: Serialize);
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use crate::{ContentKind, RocketResponse, RocketResponseGeneric};
+    use async_graphql::{EmptyMutation, EmptySubscription, Object, Schema};
+    use async_graphql_rocket::GraphQLQuery;
+    use rocket::{
+        get,
+        http::{ContentType, Status},
+        local::blocking::Client,
+        response::content::RawHtml,
+        routes, State,
+    };
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn add(&self, a: i32, b: i32) -> i32 {
+            a + b
+        }
+    }
+
+    type TestSchema = Schema<Query, EmptyMutation, EmptySubscription>;
+
+    #[get("/graphql?<query..>")]
+    async fn route_graphql(
+        query: Option<GraphQLQuery>,
+        schema: &State<TestSchema>,
+    ) -> RocketResponseGeneric<String> {
+        match query {
+            Some(query) => RocketResponseGeneric::graphql(query.execute(schema.inner()).await),
+            None => RocketResponseGeneric::Html(RawHtml("<p>playground</p>".to_string())),
+        }
+    }
+
+    #[test]
+    fn test_graphql() {
+        let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+        let rocket = rocket::build()
+            .manage(schema)
+            .mount("/", routes![route_graphql]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/graphql?query=%7Badd(a:1,b:2)%7D").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(Some(ContentType::JSON), res.content_type());
+        assert_eq!(r#"{"data":{"add":3}}"#, res.into_string().unwrap());
+
+        let res = client.get("/graphql?query=%7Bmissing%7D").dispatch();
+        assert!(res.into_string().unwrap().contains("\"errors\""));
+
+        let res = client.get("/graphql").dispatch();
+        assert_eq!(Some(ContentType::HTML), res.content_type());
+
+        let res = RocketResponse::graphql(async_graphql::Response::new(async_graphql::Value::Null));
+        assert_eq!("GraphQL", res.variant_name());
+        assert_eq!(ContentKind::Text, res.content_kind());
+    }
+}
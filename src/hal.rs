@@ -0,0 +1,209 @@
+use crate::{body, BuildError};
+use rocket::{
+    http::ContentType,
+    request::Request,
+    response::{self, Responder, Response},
+    serde::{
+        json::{serde_json, Value},
+        ser, Serialize, Serializer,
+    },
+};
+
+/// Serializes the wrapped value into [HAL](https://datatracker.ietf.org/doc/html/draft-kelly-json-hal)
+/// with `_links` and `_embedded` resources.
+///
+/// The value has to serialize into a JSON object. Returns a response with Content-Type
+/// `application/hal+json`. If serialization fails, it responds as [BuildError::Serialize].
+///
+/// Adding a link or an embedded resource with the same relation again turns it into an array.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::{get, serde::Serialize};
+/// use rocket_response::Hal;
+///
+/// #[derive(Serialize)]
+/// #[serde(crate = "rocket::serde")]
+/// struct Order {
+///     total: f32,
+/// }
+///
+/// #[derive(Serialize)]
+/// #[serde(crate = "rocket::serde")]
+/// struct Customer {
+///     name: &'static str,
+/// }
+///
+/// #[get("/orders/<id>")]
+/// fn order(id: usize) -> Hal<Order> {
+///     Hal::new(Order { total: 30.0 })
+///         .link("self", format!("/orders/{}", id))
+///         .link("customer", "/customers/7")
+///         .embed("customer", Customer { name: "Alice" })
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Hal<T> {
+    value: T,
+    links: serde_json::Map<String, Value>,
+    embedded: serde_json::Map<String, Value>,
+    error: Option<serde_json::Error>,
+}
+
+/// Inserts `value` at `rel` or appends it to an array of the values of `rel`.
+fn insert(map: &mut serde_json::Map<String, Value>, rel: String, value: Value) {
+    match map.get_mut(&rel) {
+        Some(Value::Array(values)) => values.push(value),
+        Some(existing) => {
+            let first = existing.take();
+            *existing = Value::Array(vec![first, value]);
+        }
+        None => {
+            map.insert(rel, value);
+        }
+    }
+}
+
+impl<T> Hal<T> {
+    /// HAL resource of `value` without links
+    pub fn new(value: T) -> Self {
+        Hal {
+            value,
+            links: serde_json::Map::new(),
+            embedded: serde_json::Map::new(),
+            error: None,
+        }
+    }
+
+    /// Adds a link with the relation `rel` to `href`.
+    pub fn link<R: Into<String>, H: Into<String>>(mut self, rel: R, href: H) -> Self {
+        let mut link = serde_json::Map::new();
+        link.insert("href".to_string(), Value::String(href.into()));
+        insert(&mut self.links, rel.into(), Value::Object(link));
+        self
+    }
+
+    /// Adds the embedded resource `resource` with the relation `rel`.
+    ///
+    /// `resource` may be another [Hal] with links of its own.
+    pub fn embed<R: Into<String>, E: Serialize>(mut self, rel: R, resource: E) -> Self {
+        match serde_json::to_value(resource) {
+            Ok(value) => insert(&mut self.embedded, rel.into(), value),
+            Err(e) => self.error = Some(e),
+        }
+        self
+    }
+
+    /// Consumes the wrapper and returns the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T: Serialize> Hal<T> {
+    /// The HAL document as JSON [Value]
+    fn to_value(&self) -> serde_json::Result<Value> {
+        if let Some(e) = &self.error {
+            return Err(ser::Error::custom(e));
+        }
+
+        let mut value = serde_json::to_value(&self.value)?;
+        let object = value
+            .as_object_mut()
+            .ok_or_else(|| ser::Error::custom("HAL resource is no JSON object"))?;
+        if !self.links.is_empty() {
+            object.insert("_links".to_string(), Value::Object(self.links.clone()));
+        }
+        if !self.embedded.is_empty() {
+            object.insert(
+                "_embedded".to_string(),
+                Value::Object(self.embedded.clone()),
+            );
+        }
+        Ok(value)
+    }
+}
+
+impl<T: Serialize> Serialize for Hal<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_value()
+            .map_err(ser::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
+impl<'r, T: Serialize> Responder<'r, 'static> for Hal<T> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let value = match self.to_value() {
+            Ok(value) => value,
+            Err(e) => {
+                return BuildError::Serialize("HAL".to_string(), e.to_string()).respond_to(req)
+            }
+        };
+
+        Response::build_from(body::respond_json(&value, req)?)
+            .header(ContentType::new("application", "hal+json"))
+            .ok()
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::Hal;
+    use crate::RocketResponseGeneric;
+    use rocket::{
+        get,
+        http::Status,
+        local::blocking::Client,
+        routes,
+        serde::json::{json, Value},
+    };
+
+    #[get("/hal/<id>")]
+    fn route_hal(id: usize) -> RocketResponseGeneric<Value> {
+        match id {
+            0 => RocketResponseGeneric::Hal(Box::new(Hal::new(json!("no object")))),
+            _ => RocketResponseGeneric::Hal(Box::new(
+                Hal::new(json!({ "id": id }))
+                    .link("self", format!("/hal/{}", id))
+                    .link("item", "/items/1")
+                    .link("item", "/items/2")
+                    .embed(
+                        "owner",
+                        Hal::new(json!({ "name": "Alice" })).link("self", "/users/7"),
+                    ),
+            )),
+        }
+    }
+
+    #[test]
+    fn test_hal() {
+        let rocket = rocket::build().mount("/", routes![route_hal]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/hal/1").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(
+            Some("application/hal+json"),
+            res.headers().get_one("Content-Type")
+        );
+        assert_eq!(
+            json!({
+                "id": 1,
+                "_links": {
+                    "self": { "href": "/hal/1" },
+                    "item": [{ "href": "/items/1" }, { "href": "/items/2" }],
+                },
+                "_embedded": {
+                    "owner": { "name": "Alice", "_links": { "self": { "href": "/users/7" } } },
+                },
+            }),
+            res.into_json::<Value>().unwrap()
+        );
+
+        let res = client.get("/hal/0").dispatch();
+        assert_eq!(Status::InternalServerError, res.status());
+    }
+}
@@ -0,0 +1,241 @@
+use rocket::{
+    http::Header,
+    request::Request,
+    response::{self, Responder, Response},
+};
+
+/// Profile of the security headers of [Hardened]
+///
+/// The [default](SecurityHeaders::default()) profile sends
+///
+/// - `Strict-Transport-Security: max-age=31536000; includeSubDomains`
+/// - `Content-Security-Policy: default-src 'self'`
+/// - `X-Content-Type-Options: nosniff`
+/// - `Referrer-Policy: strict-origin-when-cross-origin`
+/// - `X-Frame-Options: DENY`
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket_response::SecurityHeaders;
+///
+/// let headers = SecurityHeaders::default()
+///     .content_security_policy("default-src 'self'; img-src *")
+///     .frame_options("SAMEORIGIN")
+///     .remove("Strict-Transport-Security");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecurityHeaders {
+    headers: Vec<(&'static str, String)>,
+}
+
+impl SecurityHeaders {
+    /// Profile without any header
+    pub fn none() -> Self {
+        SecurityHeaders {
+            headers: Vec::new(),
+        }
+    }
+
+    /// Sets the header `name` to `value`.
+    pub fn set<V: Into<String>>(mut self, name: &'static str, value: V) -> Self {
+        let value = value.into();
+        match self
+            .headers
+            .iter_mut()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        {
+            Some(header) => header.1 = value,
+            None => self.headers.push((name, value)),
+        }
+        self
+    }
+
+    /// Removes the header `name`.
+    pub fn remove(mut self, name: &str) -> Self {
+        self.headers.retain(|(n, _)| !n.eq_ignore_ascii_case(name));
+        self
+    }
+
+    /// Sets `Strict-Transport-Security`.
+    pub fn strict_transport_security<V: Into<String>>(self, value: V) -> Self {
+        self.set("Strict-Transport-Security", value)
+    }
+
+    /// Sets `Content-Security-Policy`.
+    pub fn content_security_policy<V: Into<String>>(self, value: V) -> Self {
+        self.set("Content-Security-Policy", value)
+    }
+
+    /// Sets `X-Content-Type-Options`.
+    pub fn content_type_options<V: Into<String>>(self, value: V) -> Self {
+        self.set("X-Content-Type-Options", value)
+    }
+
+    /// Sets `Referrer-Policy`.
+    pub fn referrer_policy<V: Into<String>>(self, value: V) -> Self {
+        self.set("Referrer-Policy", value)
+    }
+
+    /// Sets `X-Frame-Options`.
+    pub fn frame_options<V: Into<String>>(self, value: V) -> Self {
+        self.set("X-Frame-Options", value)
+    }
+
+    /// Sets the headers not already set by `res`.
+    pub(crate) fn apply(&self, res: &mut Response<'_>) {
+        for (name, value) in &self.headers {
+            if !res.headers().contains(*name) {
+                res.set_header(Header::new(*name, value.clone()));
+            }
+        }
+    }
+}
+
+impl Default for SecurityHeaders {
+    fn default() -> Self {
+        SecurityHeaders::none()
+            .strict_transport_security("max-age=31536000; includeSubDomains")
+            .content_security_policy("default-src 'self'")
+            .content_type_options("nosniff")
+            .referrer_policy("strict-origin-when-cross-origin")
+            .frame_options("DENY")
+    }
+}
+
+/// Wrapper setting the security headers of [SecurityHeaders]
+///
+/// Headers already set by the wrapped responder are kept, so a single route can e.g.
+/// relax its `Content-Security-Policy`.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::get;
+/// use rocket_response::{Hardened, RocketResponse};
+///
+/// #[get("/")]
+/// fn index() -> RocketResponse {
+///     RocketResponse::Hardened(Box::new(Hardened::new(RocketResponse::StaticStr(
+///         "Hello world",
+///     ))))
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Hardened<R> {
+    responder: Box<R>,
+    headers: SecurityHeaders,
+}
+
+impl<R> Hardened<R> {
+    /// Wraps `responder` to respond with the default [SecurityHeaders]
+    pub fn new(responder: R) -> Self {
+        Hardened::with_headers(responder, SecurityHeaders::default())
+    }
+
+    /// Wraps `responder` to respond with `headers`
+    pub fn with_headers(responder: R, headers: SecurityHeaders) -> Self {
+        Hardened {
+            responder: Box::new(responder),
+            headers,
+        }
+    }
+
+    /// Replaces the headers by the result of `f` applied to them.
+    pub fn headers<F: FnOnce(SecurityHeaders) -> SecurityHeaders>(mut self, f: F) -> Self {
+        self.headers = f(self.headers);
+        self
+    }
+
+    /// Consumes the wrapper and returns the wrapped responder.
+    pub fn into_inner(self) -> R {
+        *self.responder
+    }
+
+    pub(crate) fn responder(&self) -> &R {
+        &self.responder
+    }
+}
+
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for Hardened<R> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let mut res = self.responder.respond_to(req)?;
+        self.headers.apply(&mut res);
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::Hardened;
+    use crate::RocketResponse;
+    use rocket::{
+        get,
+        http::{Header, Status},
+        local::blocking::Client,
+        response::Responder,
+        routes,
+    };
+
+    #[derive(Responder)]
+    #[response(content_type = "html")]
+    struct Framed {
+        body: &'static str,
+        frame_options: Header<'static>,
+    }
+
+    #[get("/hardened")]
+    fn route_hardened() -> RocketResponse {
+        RocketResponse::Hardened(Box::new(Hardened::new(RocketResponse::StaticStr(
+            "Hello world",
+        ))))
+    }
+
+    #[get("/hardened_custom")]
+    fn route_hardened_custom() -> Hardened<Framed> {
+        Hardened::new(Framed {
+            body: "<p>Hello world</p>",
+            frame_options: Header::new("X-Frame-Options", "SAMEORIGIN"),
+        })
+        .headers(|headers| {
+            headers
+                .remove("strict-transport-security")
+                .content_security_policy("default-src 'none'")
+        })
+    }
+
+    #[test]
+    fn test_hardened() {
+        let rocket = rocket::build().mount("/", routes![route_hardened, route_hardened_custom]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/hardened").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        let headers = res.headers();
+        assert_eq!(
+            Some("max-age=31536000; includeSubDomains"),
+            headers.get_one("Strict-Transport-Security")
+        );
+        assert_eq!(
+            Some("default-src 'self'"),
+            headers.get_one("Content-Security-Policy")
+        );
+        assert_eq!(Some("nosniff"), headers.get_one("X-Content-Type-Options"));
+        assert_eq!(
+            Some("strict-origin-when-cross-origin"),
+            headers.get_one("Referrer-Policy")
+        );
+        assert_eq!(Some("DENY"), headers.get_one("X-Frame-Options"));
+
+        let res = client.get("/hardened_custom").dispatch();
+        let headers = res.headers();
+        assert!(!headers.contains("Strict-Transport-Security"));
+        assert_eq!(
+            Some("default-src 'none'"),
+            headers.get_one("Content-Security-Policy")
+        );
+        assert_eq!(Some("SAMEORIGIN"), headers.get_one("X-Frame-Options"));
+        assert_eq!(1, headers.get("X-Frame-Options").count());
+    }
+}
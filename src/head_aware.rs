@@ -0,0 +1,216 @@
+use crate::ETag;
+use rocket::{
+    fairing::{AdHoc, Fairing},
+    http::{ContentType, Method},
+    request::Request,
+    response::{self, Responder, Response},
+};
+use std::{fs::Metadata, io::Cursor, time::UNIX_EPOCH};
+
+/// Marker of a `HEAD` request in the request-local cache
+struct HeadRequest(bool);
+
+/// `true` if `req` is a `HEAD` request, also if Rocket answers it by a `GET` route
+pub(crate) fn is_head(req: &Request<'_>) -> bool {
+    req.method() == Method::Head || req.local_cache(|| HeadRequest(false)).0
+}
+
+/// Wrapper answering a `HEAD` request with the headers only, without responding the body
+///
+/// Rocket answers a `HEAD` request by the `GET` route and strips the body afterwards, so a
+/// file is opened and read for the `Content-Length` or the body of a lazy response is
+/// built in vain. On a `HEAD` request the wrapped responder isn't called at all, the
+/// response is `200 OK` with the given `Content-Length`, `Content-Type` and `ETag`, which
+/// can be taken from the [metadata](Self::metadata()) of a file. Any other request is
+/// responded by the wrapped responder with the given `Content-Type` and `ETag`, so the
+/// headers of both responses match.
+///
+/// Rocket dispatches an automatically handled `HEAD` request as `GET`, so the
+/// [fairing](Self::fairing()) has to be attached, unless the route is a `HEAD` route.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::{get, http::ContentType, tokio::fs};
+/// use rocket_response::{HeadAware, RocketResponse, StreamedFile};
+/// use std::io;
+///
+/// #[get("/video")]
+/// async fn video() -> io::Result<RocketResponse> {
+///     let metadata = fs::metadata("static/video.mp4").await?;
+///     let file = StreamedFile::open("static/video.mp4").await?;
+///     Ok(RocketResponse::HeadAware(Box::new(
+///         HeadAware::new(RocketResponse::StreamedFile(Box::new(file)))
+///             .metadata(&metadata)
+///             .content_type(ContentType::new("video", "mp4")),
+///     )))
+/// }
+///
+/// let rocket = rocket::build().attach(HeadAware::<RocketResponse>::fairing());
+/// ```
+#[derive(Debug)]
+pub struct HeadAware<R> {
+    responder: Box<R>,
+    content_length: Option<u64>,
+    content_type: Option<ContentType>,
+    etag: Option<ETag>,
+}
+
+impl<R> HeadAware<R> {
+    /// Wraps `responder` without any header of the `HEAD` response
+    pub fn new(responder: R) -> Self {
+        HeadAware {
+            responder: Box::new(responder),
+            content_length: None,
+            content_type: None,
+            etag: None,
+        }
+    }
+
+    /// Sets the `Content-Length` of the `HEAD` response.
+    pub fn content_length(mut self, content_length: u64) -> Self {
+        self.content_length = Some(content_length);
+        self
+    }
+
+    /// Sets the `Content-Type`.
+    pub fn content_type(mut self, content_type: ContentType) -> Self {
+        self.content_type = Some(content_type);
+        self
+    }
+
+    /// Sets the `ETag`.
+    pub fn etag(mut self, etag: ETag) -> Self {
+        self.etag = Some(etag);
+        self
+    }
+
+    /// Sets the `Content-Length` to the length of the file of `metadata` and a weak `ETag`
+    /// of the length and the modification time.
+    pub fn metadata(self, metadata: &Metadata) -> Self {
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |modified| modified.as_secs());
+        self.content_length(metadata.len()).etag(ETag::weak(format!(
+            "{:x}-{:x}",
+            metadata.len(),
+            modified
+        )))
+    }
+
+    /// Consumes the wrapper and returns the wrapped responder.
+    pub fn into_inner(self) -> R {
+        *self.responder
+    }
+
+    pub(crate) fn responder(&self) -> &R {
+        &self.responder
+    }
+
+    /// Fairing remembering a `HEAD` request, before Rocket dispatches it as `GET`
+    pub fn fairing() -> impl Fairing {
+        AdHoc::on_request("HEAD detection", |req, _| {
+            Box::pin(async move {
+                if req.method() == Method::Head {
+                    req.local_cache(|| HeadRequest(true));
+                }
+            })
+        })
+    }
+}
+
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for HeadAware<R> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        if is_head(req) {
+            let mut res = Response::build();
+            if let Some(content_length) = self.content_length {
+                res.sized_body(content_length as usize, Cursor::new(&[][..]));
+            }
+            if let Some(content_type) = self.content_type {
+                res.header(content_type);
+            }
+            if let Some(etag) = self.etag {
+                res.header(etag);
+            }
+            return res.ok();
+        }
+
+        let mut res = self.responder.respond_to(req)?;
+        if let Some(content_type) = self.content_type {
+            res.set_header(content_type);
+        }
+        if let Some(etag) = self.etag {
+            res.set_header(etag);
+        }
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::HeadAware;
+    use crate::{ETag, RocketResponse};
+    use rocket::{
+        get,
+        http::{ContentType, Status},
+        local::blocking::Client,
+        routes,
+    };
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static BUILT: AtomicUsize = AtomicUsize::new(0);
+
+    #[get("/head_aware")]
+    fn route_head_aware() -> RocketResponse {
+        let metadata = std::fs::metadata("Cargo.toml").unwrap();
+        RocketResponse::HeadAware(Box::new(
+            HeadAware::new(RocketResponse::lazy(|_| {
+                BUILT.fetch_add(1, Ordering::SeqCst);
+                RocketResponse::Vec(std::fs::read("Cargo.toml").unwrap())
+            }))
+            .metadata(&metadata)
+            .content_type(ContentType::Plain),
+        ))
+    }
+
+    #[get("/head_aware_etag")]
+    fn route_head_aware_etag() -> RocketResponse {
+        RocketResponse::HeadAware(Box::new(
+            HeadAware::new(RocketResponse::StaticStr("body"))
+                .content_length(4)
+                .etag(ETag::strong("v1")),
+        ))
+    }
+
+    #[test]
+    fn test_head_aware() {
+        let len = std::fs::metadata("Cargo.toml").unwrap().len();
+        let rocket = rocket::build()
+            .attach(HeadAware::<RocketResponse>::fairing())
+            .mount("/", routes![route_head_aware, route_head_aware_etag]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.head("/head_aware").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(Some(ContentType::Plain), res.content_type());
+        assert!(res.headers().get_one("ETag").unwrap().starts_with("W/\""));
+        assert!(res.into_bytes().map_or(true, |body| body.is_empty()));
+        assert_eq!(0, BUILT.load(Ordering::SeqCst));
+
+        let res = client.get("/head_aware").dispatch();
+        assert_eq!(Some(ContentType::Plain), res.content_type());
+        assert!(res.headers().contains("ETag"));
+        assert_eq!(len as usize, res.into_bytes().unwrap().len());
+        assert_eq!(1, BUILT.load(Ordering::SeqCst));
+
+        let res = client.head("/head_aware_etag").dispatch();
+        assert_eq!(Some("\"v1\""), res.headers().get_one("ETag"));
+        let res = client.get("/head_aware_etag").dispatch();
+        assert_eq!(Some("\"v1\""), res.headers().get_one("ETag"));
+        assert_eq!(Some(ContentType::Plain), res.content_type());
+        assert_eq!("body", res.into_string().unwrap());
+    }
+}
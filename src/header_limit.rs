@@ -0,0 +1,173 @@
+use rocket::{
+    fairing::{Fairing, Info, Kind},
+    http::HeaderMap,
+    Request, Response,
+};
+
+/// Size of `headers` like in an HTTP/1.1 response, `name: value` and CRLF for each header
+fn header_size(headers: &HeaderMap<'_>) -> usize {
+    headers
+        .iter()
+        .map(|header| header.name().as_str().len() + header.value().len() + 4)
+        .sum()
+}
+
+/// Guard of the total size of the response headers, attached as fairing
+///
+/// Wrappers like [Paginated](crate::Paginated) with `Link`, [Timed](crate::Timed) with
+/// `Server-Timing` or [Hardened](crate::Hardened) add headers, which can exceed the header
+/// limit of a reverse proxy and lead to a `502 Bad Gateway` hard to trace back. If the
+/// headers of a response exceed the [limit](Self::new()), the droppable headers are removed
+/// in their order until the headers fit, and a warning is logged for every removed header.
+/// If they still exceed the limit, an error is logged.
+///
+/// The default limit is 8 KiB, the default droppable headers are `Link`, `Server-Timing`,
+/// `Report-To`, `Reporting-Endpoints` and `NEL`.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket_response::HeaderLimit;
+///
+/// let rocket = rocket::build().attach(HeaderLimit::new(4096).droppable("X-Debug"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeaderLimit {
+    limit: usize,
+    droppable: Vec<String>,
+}
+
+impl HeaderLimit {
+    /// Guard of `limit` bytes with the default droppable headers
+    pub fn new(limit: usize) -> Self {
+        HeaderLimit {
+            limit,
+            droppable: [
+                "Link",
+                "Server-Timing",
+                "Report-To",
+                "Reporting-Endpoints",
+                "NEL",
+            ]
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+        }
+    }
+
+    /// Adds the header `name`, which is removed after the ones added before.
+    pub fn droppable<N: Into<String>>(mut self, name: N) -> Self {
+        self.droppable.push(name.into());
+        self
+    }
+
+    /// Limit of the total size of the headers in bytes
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+}
+
+impl Default for HeaderLimit {
+    fn default() -> Self {
+        HeaderLimit::new(8192)
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for HeaderLimit {
+    fn info(&self) -> Info {
+        Info {
+            name: "Header limit",
+            kind: Kind::Response,
+        }
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        let mut size = header_size(res.headers());
+        if size <= self.limit {
+            return;
+        }
+
+        for name in &self.droppable {
+            if !res.headers().contains(name.as_str()) {
+                continue;
+            }
+            res.remove_header(name);
+            let reduced = header_size(res.headers());
+            rocket::warn_!(
+                "{} {}: removed {} header of {} bytes exceeding the header limit of {} bytes",
+                req.method(),
+                req.uri(),
+                name,
+                size - reduced,
+                self.limit
+            );
+            size = reduced;
+            if size <= self.limit {
+                return;
+            }
+        }
+        rocket::error_!(
+            "{} {}: headers of {} bytes exceed the header limit of {} bytes",
+            req.method(),
+            req.uri(),
+            size,
+            self.limit
+        );
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::HeaderLimit;
+    use rocket::{
+        get,
+        http::{Header, Status},
+        local::blocking::Client,
+        request::Request,
+        response::{self, Responder, Response},
+        routes,
+    };
+
+    struct Headers(Vec<(&'static str, usize)>);
+
+    impl<'r> Responder<'r, 'static> for Headers {
+        fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+            let mut res = Response::new();
+            for (name, len) in self.0 {
+                res.set_header(Header::new(name, "x".repeat(len)));
+            }
+            Ok(res)
+        }
+    }
+
+    #[get("/headers/<link>/<debug>")]
+    fn route_headers(link: usize, debug: usize) -> Headers {
+        Headers(vec![("Link", link), ("X-Debug", debug), ("X-Keep", 100)])
+    }
+
+    #[test]
+    fn test_header_limit() {
+        let rocket = rocket::build()
+            .attach(HeaderLimit::new(1000).droppable("X-Debug"))
+            .mount("/", routes![route_headers]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+        let get = |link: usize, debug: usize| {
+            let res = client
+                .get(format!("/headers/{}/{}", link, debug))
+                .dispatch();
+            assert_eq!(Status::Ok, res.status());
+            (
+                res.headers().contains("Link"),
+                res.headers().contains("X-Debug"),
+                res.headers().contains("X-Keep"),
+            )
+        };
+
+        assert_eq!((true, true, true), get(300, 300));
+        assert_eq!((false, true, true), get(800, 300));
+        assert_eq!((false, false, true), get(800, 800));
+        assert_eq!((false, false, true), get(100, 2000));
+    }
+}
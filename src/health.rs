@@ -0,0 +1,274 @@
+use crate::{Maintenance, ResponseCache};
+use rocket::{
+    http::{ContentType, Header, Status},
+    request::Request,
+    response::{self, Responder, Response},
+    serde::{
+        json::{serde_json, Value},
+        Serialize, Serializer,
+    },
+};
+use std::{collections::BTreeMap, io::Cursor};
+
+/// Result of a check of a [HealthReport] with its details
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket_response::HealthCheck;
+///
+/// let check = HealthCheck::down().detail("error", "connection refused");
+/// assert!(!check.is_up());
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct HealthCheck {
+    #[serde(rename = "status", serialize_with = "serialize_status")]
+    up: bool,
+    #[serde(flatten)]
+    details: BTreeMap<String, Value>,
+}
+
+impl HealthCheck {
+    /// Passed check
+    pub fn up() -> Self {
+        HealthCheck {
+            up: true,
+            details: BTreeMap::new(),
+        }
+    }
+
+    /// Failed check
+    pub fn down() -> Self {
+        HealthCheck {
+            up: false,
+            ..HealthCheck::up()
+        }
+    }
+
+    /// Sets the detail `name` to `value`, which is sent as JSON value, so numbers stay
+    /// numbers.
+    pub fn detail<N: Into<String>, V: Into<Value>>(mut self, name: N, value: V) -> Self {
+        self.details.insert(name.into(), value.into());
+        self
+    }
+
+    /// `true` if the check passed
+    pub fn is_up(&self) -> bool {
+        self.up
+    }
+}
+
+/// Health of the application as JSON, `200 OK` if all checks are up, otherwise
+/// `503 Service Unavailable`
+///
+/// Besides the checks of the application, the state of the response layer is added
+/// automatically, if a check of the same name isn't set:
+///
+/// * `maintenance` with the mode of the managed [Maintenance], down while it is active
+///   or draining, so a load balancer takes the instance out of rotation
+/// * `response_cache` with the [stats](ResponseCache::stats()) of the managed
+///   [ResponseCache]
+/// * `buffer_pool` with the [stats](crate::buffer_pool::stats()) of the `buffer-pool`
+///   feature
+///
+/// The response isn't stored by caches.
+///
+/// Requires the `json` feature.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::get;
+/// use rocket_response::{HealthCheck, HealthReport, Maintenance, RocketResponse};
+///
+/// #[get("/health")]
+/// fn health() -> RocketResponse {
+///     let db = HealthCheck::up().detail("pool_size", 8);
+///     RocketResponse::HealthReport(Box::new(HealthReport::new().check("database", db)))
+/// }
+///
+/// let rocket = rocket::build().attach(Maintenance::new());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HealthReport {
+    checks: BTreeMap<String, HealthCheck>,
+}
+
+impl HealthReport {
+    /// Report without checks of the application
+    pub fn new() -> Self {
+        HealthReport::default()
+    }
+
+    /// Sets the check `name`.
+    pub fn check<N: Into<String>>(mut self, name: N, check: HealthCheck) -> Self {
+        self.checks.insert(name.into(), check);
+        self
+    }
+
+    /// Adds the checks of the response layer managed by the Rocket of `req`.
+    fn add_layer_checks(&mut self, req: &Request<'_>) {
+        if let Some(maintenance) = req.rocket().state::<Maintenance>() {
+            self.checks
+                .entry("maintenance".to_string())
+                .or_insert_with(|| {
+                    let retry_after = maintenance.retry_after().as_secs();
+                    match (maintenance.is_active(), maintenance.drain_deadline()) {
+                        (false, _) => HealthCheck::up().detail("mode", "inactive"),
+                        (true, None) => HealthCheck::down()
+                            .detail("mode", "active")
+                            .detail("retry_after", retry_after),
+                        (true, Some(deadline)) => HealthCheck::down()
+                            .detail("mode", "draining")
+                            .detail("deadline", httpdate::fmt_http_date(deadline))
+                            .detail("retry_after", retry_after),
+                    }
+                });
+        }
+        if let Some(cache) = req.rocket().state::<ResponseCache>() {
+            let stats = cache.stats();
+            self.checks
+                .entry("response_cache".to_string())
+                .or_insert_with(|| {
+                    HealthCheck::up()
+                        .detail("hits", stats.hits)
+                        .detail("misses", stats.misses)
+                        .detail("hit_rate", stats.hit_rate())
+                });
+        }
+        #[cfg(feature = "buffer-pool")]
+        {
+            let stats = crate::buffer_pool::stats();
+            self.checks
+                .entry("buffer_pool".to_string())
+                .or_insert_with(|| {
+                    HealthCheck::up()
+                        .detail("hits", stats.hits)
+                        .detail("misses", stats.misses)
+                        .detail("released", stats.released)
+                        .detail("hit_rate", stats.hit_rate())
+                });
+        }
+    }
+}
+
+/// Name of the status `up` in the JSON
+fn status_name(up: bool) -> &'static str {
+    if up {
+        "up"
+    } else {
+        "down"
+    }
+}
+
+fn serialize_status<S: Serializer>(up: &bool, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(status_name(*up))
+}
+
+/// JSON body of a [HealthReport]
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde")]
+struct Body<'a> {
+    status: &'static str,
+    checks: &'a BTreeMap<String, HealthCheck>,
+}
+
+impl<'r> Responder<'r, 'static> for HealthReport {
+    fn respond_to(mut self, req: &'r Request<'_>) -> response::Result<'static> {
+        self.add_layer_checks(req);
+        let up = self.checks.values().all(HealthCheck::is_up);
+
+        let body = serde_json::to_vec(&Body {
+            status: status_name(up),
+            checks: &self.checks,
+        })
+        .map_err(|e| {
+            rocket::error_!("health report failed to serialize: {}", e);
+            Status::InternalServerError
+        })?;
+        Response::build()
+            .status(if up {
+                Status::Ok
+            } else {
+                Status::ServiceUnavailable
+            })
+            .header(ContentType::JSON)
+            .header(Header::new("Cache-Control", "no-store"))
+            .sized_body(body.len(), Cursor::new(body))
+            .ok()
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::{HealthCheck, HealthReport};
+    use crate::{Maintenance, ResponseCache, RocketResponse};
+    use rocket::{
+        get,
+        http::Status,
+        local::blocking::Client,
+        routes,
+        serde::json::{serde_json, Value},
+    };
+    use std::time::Duration;
+
+    #[get("/health?<db>")]
+    fn route_health(db: bool) -> RocketResponse {
+        let check = if db {
+            HealthCheck::up()
+        } else {
+            HealthCheck::down().detail("error", "connection \"refused\"")
+        };
+        RocketResponse::HealthReport(Box::new(HealthReport::new().check("database", check)))
+    }
+
+    #[test]
+    fn test_health_report() {
+        let rocket = rocket::build().mount("/", routes![route_health]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/health?db=false").dispatch();
+        assert_eq!(Status::ServiceUnavailable, res.status());
+        assert_eq!(Some("no-store"), res.headers().get_one("Cache-Control"));
+        #[cfg(not(feature = "buffer-pool"))]
+        assert_eq!(
+            concat!(
+                r#"{"status":"down","checks":{"#,
+                r#""database":{"status":"down","error":"connection \"refused\""}}}"#
+            ),
+            res.into_string().unwrap()
+        );
+
+        let maintenance = Maintenance::new().restart_window(Duration::from_secs(60));
+        let rocket = rocket::build()
+            .attach(maintenance.clone())
+            .manage(ResponseCache::new())
+            .mount("/", routes![route_health]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/health?db=true").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        let body = res.into_string().unwrap();
+        assert!(body.starts_with(r#"{"status":"up","checks":{"#));
+        assert!(body.contains(r#""maintenance":{"status":"up","mode":"inactive"}"#));
+        assert!(
+            body.contains(r#""response_cache":{"status":"up","hit_rate":0.0,"hits":0,"misses":0}"#)
+        );
+        #[cfg(feature = "buffer-pool")]
+        assert!(body.contains(r#""buffer_pool":{"status":"up","hit_rate":"#));
+
+        maintenance.drain();
+        let res = client.get("/health?db=true").dispatch();
+        assert_eq!(Status::ServiceUnavailable, res.status());
+        assert!(res.headers().contains("X-Drain-Deadline"));
+        let body: Value = serde_json::from_str(&res.into_string().unwrap()).unwrap();
+        assert_eq!("down", body["status"]);
+        let check = &body["checks"]["maintenance"];
+        assert_eq!("down", check["status"]);
+        assert_eq!("draining", check["mode"]);
+        assert!(check["deadline"].is_string());
+        assert!(check["retry_after"].as_u64().unwrap() <= 60);
+    }
+}
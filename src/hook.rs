@@ -0,0 +1,209 @@
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+use crate::RocketResponseGeneric3;
+use crate::{RocketResponse, RocketResponseGeneric, RocketResponseGeneric2};
+use rocket::{
+    request::Request,
+    response::{self, Responder, Response},
+    serde::Serialize,
+};
+use std::fmt;
+
+/// Post-processing of a rendered response, like adding headers or rewriting the status
+///
+/// It is implemented for closures getting the request and the response, so a route can
+/// modify its response without a fairing. A hook is applied by the [Hooked] wrapper, which
+/// the enums construct by `hook()`.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::{get, http::Header, request::Request, response::Response};
+/// use rocket_response::{ResponseHook, RocketResponse};
+///
+/// /// Hook adding a header
+/// struct Powered;
+///
+/// impl ResponseHook for Powered {
+///     fn on_response(&self, _req: &Request<'_>, res: &mut Response<'_>) {
+///         res.set_header(Header::new("X-Powered-By", "Rocket"));
+///     }
+/// }
+///
+/// #[get("/hello")]
+/// fn hello() -> RocketResponse {
+///     RocketResponse::StaticStr("Hello world").hook(Powered)
+/// }
+/// ```
+pub trait ResponseHook: Send + 'static {
+    /// Inspects or modifies the response `res` to `req`.
+    fn on_response(&self, req: &Request<'_>, res: &mut Response<'_>);
+}
+
+impl<F> ResponseHook for F
+where
+    F: Fn(&Request<'_>, &mut Response<'_>) + Send + 'static,
+{
+    fn on_response(&self, req: &Request<'_>, res: &mut Response<'_>) {
+        self(req, res)
+    }
+}
+
+/// Wrapper calling a [ResponseHook] with the response of the wrapped responder
+///
+/// The hook is called after the wrapped responder has rendered its response, but before
+/// the fairings. A failing responder forwards to a catcher without calling the hook.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::{get, http::Status, request::Request, response::Response};
+/// use rocket_response::RocketResponse;
+///
+/// #[get("/legacy")]
+/// fn legacy() -> RocketResponse {
+///     RocketResponse::StaticStr("moved").hook(|_req: &Request<'_>, res: &mut Response<'_>| {
+///         res.set_status(Status::Gone);
+///         res.set_raw_header("Sunset", "Sat, 01 Jan 2022 00:00:00 GMT");
+///     })
+/// }
+/// ```
+pub struct Hooked<R> {
+    responder: Box<R>,
+    hook: Box<dyn ResponseHook>,
+}
+
+impl<R> Hooked<R> {
+    /// Wraps `responder` to call `hook` with its response
+    pub fn new<H: ResponseHook>(responder: R, hook: H) -> Self {
+        Hooked {
+            responder: Box::new(responder),
+            hook: Box::new(hook),
+        }
+    }
+
+    /// Consumes the wrapper and returns the wrapped responder.
+    pub fn into_inner(self) -> R {
+        *self.responder
+    }
+
+    pub(crate) fn responder(&self) -> &R {
+        &self.responder
+    }
+}
+
+impl<R: fmt::Debug> fmt::Debug for Hooked<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Hooked")
+            .field("responder", &self.responder)
+            .finish()
+    }
+}
+
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for Hooked<R> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let mut res = self.responder.respond_to(req)?;
+        self.hook.on_response(req, &mut res);
+        Ok(res)
+    }
+}
+
+/// Implements the `hook` builder of the `Hooked` variant.
+macro_rules! impl_hook_builder {
+    ([$($gen:tt)*] $name:ty $(where $($bound:tt)+)?) => {
+        impl<$($gen)*> $name $(where $($bound)+)? {
+            /// Response calling `hook` with the rendered response, see [Hooked]
+            pub fn hook<H: ResponseHook>(self, hook: H) -> Self {
+                Self::Hooked(Box::new(Hooked::new(self, hook)))
+            }
+        }
+    };
+}
+
+impl_hook_builder!([] RocketResponse);
+impl_hook_builder!([T] RocketResponseGeneric<T> where T: Serialize);
+impl_hook_builder!([T, U] RocketResponseGeneric2<T, U> where T: Serialize);
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+impl_hook_builder!([T, S, U] RocketResponseGeneric3<T, S, U> where S: Serialize);
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::{Hooked, ResponseHook};
+    use crate::{RocketResponse, RocketResponseGeneric, RocketResponseGeneric2};
+    use rocket::{
+        get,
+        http::{Header, Status},
+        local::blocking::Client,
+        request::Request,
+        response::{Redirect, Response},
+        routes,
+    };
+
+    struct Traced(&'static str);
+
+    impl ResponseHook for Traced {
+        fn on_response(&self, req: &Request<'_>, res: &mut Response<'_>) {
+            res.set_header(Header::new("X-Trace", format!("{} {}", self.0, req.uri())));
+        }
+    }
+
+    #[get("/hook")]
+    fn route_hook() -> RocketResponse {
+        RocketResponse::StaticStr("Hello world")
+            .hook(Traced("inner"))
+            .hook(|_req: &Request<'_>, res: &mut Response<'_>| {
+                res.set_status(Status::Accepted);
+                res.adjoin_raw_header("X-Trace", "outer");
+            })
+    }
+
+    #[get("/hook_generic")]
+    fn route_hook_generic() -> RocketResponseGeneric2<&'static str, Redirect> {
+        RocketResponseGeneric2::Redirect(Box::new(Redirect::to("/hook"))).hook(Traced("generic"))
+    }
+
+    #[get("/hook_failing")]
+    fn route_hook_failing() -> RocketResponseGeneric<&'static str> {
+        RocketResponseGeneric::Hooked(Box::new(Hooked::new(
+            RocketResponseGeneric::Status(Status::Forbidden),
+            |_req: &Request<'_>, res: &mut Response<'_>| res.set_status(Status::Ok),
+        )))
+    }
+
+    #[test]
+    fn test_hook() {
+        let rocket = rocket::build().mount(
+            "/",
+            routes![route_hook, route_hook_generic, route_hook_failing],
+        );
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/hook").dispatch();
+        assert_eq!(Status::Accepted, res.status());
+        let trace: Vec<_> = res.headers().get("X-Trace").collect();
+        assert_eq!(vec!["inner /hook", "outer"], trace);
+        assert_eq!("Hello world", res.into_string().unwrap());
+
+        let res = client.get("/hook_generic").dispatch();
+        assert_eq!(Status::SeeOther, res.status());
+        assert_eq!(
+            Some("generic /hook_generic"),
+            res.headers().get_one("X-Trace")
+        );
+
+        let res = client.get("/hook_failing").dispatch();
+        assert_eq!(Status::Forbidden, res.status());
+    }
+}
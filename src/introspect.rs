@@ -0,0 +1,557 @@
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+use crate::RocketResponseGeneric3;
+use crate::{RocketResponse, RocketResponseGeneric, RocketResponseGeneric2};
+use rocket::serde::Serialize;
+
+/// Classification of the body of a response variant
+///
+/// Wrapper variants like [Cached](crate::Cached) are classified by the wrapped response.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket_response::{ContentKind, RocketResponse};
+///
+/// let res = RocketResponse::redirect_to("/");
+/// assert_eq!("Redirect", res.variant_name());
+/// assert_eq!(ContentKind::StatusOnly, res.content_kind());
+/// assert!(RocketResponse::VARIANTS.contains(&"Redirect"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ContentKind {
+    /// Textual body like HTML, JSON or plain text
+    Text,
+    /// Binary body like MessagePack or raw bytes
+    Binary,
+    /// Body read from a file
+    File,
+    /// Body streamed with an unknown size
+    Stream,
+    /// Only status and headers, like redirects or `204 No Content`
+    StatusOnly,
+    /// Not classified, like a [Dyn](crate::Dyn) response
+    Other,
+}
+
+/// `Text` for an optional body, which is present
+fn optional_text<R>(body: &Option<R>) -> ContentKind {
+    if body.is_some() {
+        ContentKind::Text
+    } else {
+        ContentKind::StatusOnly
+    }
+}
+
+/// Implements `VARIANTS`, `variant_name()` and `content_kind()` from one list of variants.
+macro_rules! impl_introspection {
+    (
+        [$($gen:tt)*] $name:ty, [$($bound:tt)*];
+        $($(#[$meta:meta])* $variant:ident($($r:pat),+) => $kind:expr,)+
+    ) => {
+        impl<$($gen)*> $name where $($bound)* {
+            /// Names of all variants available with the enabled features
+            pub const VARIANTS: &'static [&'static str] = &[
+                $($(#[$meta])* stringify!($variant),)+
+            ];
+
+            /// Name of the variant of `self`
+            #[allow(deprecated)]
+            pub fn variant_name(&self) -> &'static str {
+                match self {
+                    $($(#[$meta])* Self::$variant(..) => stringify!($variant),)+
+                }
+            }
+
+            /// [ContentKind] of the body of `self`
+            #[allow(deprecated)]
+            pub fn content_kind(&self) -> ContentKind {
+                match self {
+                    $($(#[$meta])* Self::$variant($($r),+) => $kind,)+
+                }
+            }
+        }
+    };
+}
+
+impl_introspection!([] RocketResponse, [];
+    Absolute(r) => r.responder().content_kind(),
+    Accepted(r) => optional_text(&r.0),
+    AcceptedJob(r) => r.responder().content_kind(),
+    #[cfg(feature = "oauth")]
+    AuthRedirect(_) => ContentKind::StatusOnly,
+    BadRequest(r) => optional_text(&r.0),
+    BuildError(_) => ContentKind::Text,
+    Cached(r) => r.responder().content_kind(),
+    CacheEntry(_) => ContentKind::Binary,
+    Canonical(r) => r.responder().content_kind(),
+    #[cfg(feature = "chaos")]
+    ChaosWrapped(r) => r.responder().content_kind(),
+    #[cfg(feature = "process")]
+    CommandOutput(_) => ContentKind::Stream,
+    #[cfg(feature = "compress")]
+    Compressed(r) => r.responder().content_kind(),
+    Conflict(r) => optional_text(&r.0),
+    Created(_) => ContentKind::Text,
+    CreatedResource(r) => r.responder().content_kind(),
+    Css(_) => ContentKind::Text,
+    Custom(r) => r.1.content_kind(),
+    #[cfg(feature = "db")]
+    DbError(_) => ContentKind::Text,
+    #[cfg(feature = "json")]
+    DeltaCollection(_) => ContentKind::Text,
+    Download(_) => ContentKind::File,
+    Dyn(_) => ContentKind::Other,
+    #[cfg(feature = "json")]
+    ExtensionNegotiated(r) => r.content_kind(),
+    File(_) => ContentKind::File,
+    Flash(_) => ContentKind::Text,
+    FlashMsg(_) => ContentKind::StatusOnly,
+    Forbidden(r) => optional_text(&r.0),
+    #[cfg(feature = "graphql")]
+    GraphQL(_) => ContentKind::Text,
+    Hardened(r) => r.responder().content_kind(),
+    HeadAware(r) => r.responder().content_kind(),
+    #[cfg(feature = "json")]
+    HealthReport(_) => ContentKind::Text,
+    Hooked(r) => r.responder().content_kind(),
+    Html(_) => ContentKind::Text,
+    JavaScript(_) => ContentKind::Text,
+    Json(_) => ContentKind::Text,
+    Lazy(_) => ContentKind::Other,
+    Localized(r) => r.responder().content_kind(),
+    MsgPack(_) => ContentKind::Binary,
+    Multipart(_) => ContentKind::Binary,
+    NamedFiled(_) => ContentKind::File,
+    #[cfg(feature = "json")]
+    Negotiated(_) => ContentKind::Other,
+    NotFound(_) => ContentKind::Text,
+    NoContent(_) => ContentKind::StatusOnly,
+    #[cfg(feature = "json")]
+    Paginated(_) => ContentKind::Text,
+    Plain(_) => ContentKind::Text,
+    #[cfg(feature = "signed-files")]
+    ProtectedFile(_) => ContentKind::File,
+    PurgeResult(_) => ContentKind::Text,
+    QuotaHeaders(r) => r.responder().content_kind(),
+    RangedFile(_) => ContentKind::File,
+    Redirect(_) => ContentKind::StatusOnly,
+    RocketError(_) => ContentKind::Text,
+    #[cfg(all(feature = "db-sqlx", feature = "json"))]
+    RowStream(_) => ContentKind::Stream,
+    #[cfg(feature = "bincode")]
+    SerdeBincode(_) => ContentKind::Binary,
+    #[cfg(feature = "cbor")]
+    SerdeCbor(_) => ContentKind::Binary,
+    #[cfg(feature = "json")]
+    SerdeJson(_) => ContentKind::Text,
+    #[cfg(feature = "msgpack")]
+    SerdeMsgPack(_) => ContentKind::Binary,
+    #[cfg(feature = "msgpack")]
+    SerdeMsgPackValue(_) => ContentKind::Binary,
+    #[cfg(feature = "json")]
+    SerdeValue(_) => ContentKind::Text,
+    #[cfg(feature = "xml")]
+    SerdeXml(_) => ContentKind::Text,
+    ServiceUnavailable(r) => r.responder().content_kind(),
+    #[cfg(feature = "secrets")]
+    SessionRedirect(_) => ContentKind::StatusOnly,
+    Sitemap(_) => ContentKind::Text,
+    StaticSlice(_) => ContentKind::Binary,
+    StaticStr(_) => ContentKind::Text,
+    String(_) => ContentKind::Text,
+    Status(_) => ContentKind::StatusOnly,
+    StatusPage(_) => ContentKind::Text,
+    StreamedFile(_) => ContentKind::Stream,
+    #[cfg(any(feature = "templates-tera", feature = "templates-handlebars"))]
+    Template(_) => ContentKind::Text,
+    TextBody(_) => ContentKind::Text,
+    #[cfg(feature = "timing")]
+    Timed(r) => r.responder().content_kind(),
+    TokioFile(_) => ContentKind::File,
+    TooManyRequests(r) => r.responder().content_kind(),
+    Typed(_, _) => ContentKind::Binary,
+    TypedStr(_, _) => ContentKind::Text,
+    Unauthorized(r) => optional_text(&r.0),
+    Vec(_) => ContentKind::Binary,
+    WithCache(r) => r.responder().content_kind(),
+    WithCookies(r) => r.responder().content_kind(),
+    WithCors(r) => r.responder().content_kind(),
+    WithCost(r) => r.responder().content_kind(),
+    Xml(_) => ContentKind::Text,
+);
+
+impl_introspection!([T] RocketResponseGeneric<T>, [T: Serialize];
+    Absolute(r) => r.responder().content_kind(),
+    Accepted(r) => optional_text(&r.0),
+    AcceptedJob(r) => r.responder().content_kind(),
+    #[cfg(feature = "oauth")]
+    AuthRedirect(_) => ContentKind::StatusOnly,
+    BadRequest(r) => optional_text(&r.0),
+    BuildError(_) => ContentKind::Text,
+    Cached(r) => r.responder().content_kind(),
+    CacheEntry(_) => ContentKind::Binary,
+    Canonical(r) => r.responder().content_kind(),
+    #[cfg(feature = "chaos")]
+    ChaosWrapped(r) => r.responder().content_kind(),
+    #[cfg(feature = "process")]
+    CommandOutput(_) => ContentKind::Stream,
+    #[cfg(feature = "compress")]
+    Compressed(r) => r.responder().content_kind(),
+    Conflict(r) => optional_text(&r.0),
+    Created(_) => ContentKind::Text,
+    CreatedResource(r) => r.responder().content_kind(),
+    Css(_) => ContentKind::Text,
+    Custom(r) => r.1.content_kind(),
+    #[cfg(feature = "db")]
+    DbError(_) => ContentKind::Text,
+    #[cfg(feature = "json")]
+    DeltaCollection(_) => ContentKind::Text,
+    Download(_) => ContentKind::File,
+    Dyn(_) => ContentKind::Other,
+    #[cfg(feature = "json")]
+    ExtensionNegotiated(r) => r.content_kind(),
+    File(_) => ContentKind::File,
+    Flash(_) => ContentKind::Text,
+    Forbidden(r) => optional_text(&r.0),
+    #[cfg(feature = "graphql")]
+    GraphQL(_) => ContentKind::Text,
+    #[cfg(feature = "hal")]
+    Hal(_) => ContentKind::Text,
+    Hardened(r) => r.responder().content_kind(),
+    HeadAware(r) => r.responder().content_kind(),
+    #[cfg(feature = "json")]
+    HealthReport(_) => ContentKind::Text,
+    Hooked(r) => r.responder().content_kind(),
+    Html(_) => ContentKind::Text,
+    JavaScript(_) => ContentKind::Text,
+    Json(_) => ContentKind::Text,
+    #[cfg(feature = "jsonapi")]
+    JsonApi(_) => ContentKind::Text,
+    Lazy(_) => ContentKind::Other,
+    Localized(r) => r.responder().content_kind(),
+    MsgPack(_) => ContentKind::Binary,
+    Multipart(_) => ContentKind::Binary,
+    NamedFiled(_) => ContentKind::File,
+    #[cfg(feature = "json")]
+    Negotiated(_) => ContentKind::Other,
+    NotFound(_) => ContentKind::Text,
+    NoContent(_) => ContentKind::StatusOnly,
+    #[cfg(feature = "json")]
+    Paginated(_) => ContentKind::Text,
+    Plain(_) => ContentKind::Text,
+    #[cfg(feature = "signed-files")]
+    ProtectedFile(_) => ContentKind::File,
+    PurgeResult(_) => ContentKind::Text,
+    QuotaHeaders(r) => r.responder().content_kind(),
+    RangedFile(_) => ContentKind::File,
+    Redirect(_) => ContentKind::StatusOnly,
+    RocketError(_) => ContentKind::Text,
+    #[cfg(all(feature = "db-sqlx", feature = "json"))]
+    RowStream(_) => ContentKind::Stream,
+    #[cfg(feature = "bincode")]
+    SerdeBincode(_) => ContentKind::Binary,
+    #[cfg(feature = "cbor")]
+    SerdeCbor(_) => ContentKind::Binary,
+    #[cfg(feature = "json")]
+    SerdeJson(_) => ContentKind::Text,
+    #[cfg(feature = "msgpack")]
+    SerdeMsgPack(_) => ContentKind::Binary,
+    #[cfg(feature = "msgpack")]
+    SerdeMsgPackValue(_) => ContentKind::Binary,
+    #[cfg(feature = "json")]
+    SerdeValue(_) => ContentKind::Text,
+    #[cfg(feature = "xml")]
+    SerdeXml(_) => ContentKind::Text,
+    ServiceUnavailable(r) => r.responder().content_kind(),
+    #[cfg(feature = "secrets")]
+    SessionRedirect(_) => ContentKind::StatusOnly,
+    Sitemap(_) => ContentKind::Text,
+    StaticSlice(_) => ContentKind::Binary,
+    StaticStr(_) => ContentKind::Text,
+    String(_) => ContentKind::Text,
+    Status(_) => ContentKind::StatusOnly,
+    StatusPage(_) => ContentKind::Text,
+    StreamedFile(_) => ContentKind::Stream,
+    #[cfg(any(feature = "templates-tera", feature = "templates-handlebars"))]
+    Template(_) => ContentKind::Text,
+    TextBody(_) => ContentKind::Text,
+    #[cfg(feature = "timing")]
+    Timed(r) => r.responder().content_kind(),
+    TokioFile(_) => ContentKind::File,
+    TooManyRequests(r) => r.responder().content_kind(),
+    Typed(_, _) => ContentKind::Binary,
+    TypedStr(_, _) => ContentKind::Text,
+    Unauthorized(r) => optional_text(&r.0),
+    Vec(_) => ContentKind::Binary,
+    WithCache(r) => r.responder().content_kind(),
+    WithCookies(r) => r.responder().content_kind(),
+    WithCors(r) => r.responder().content_kind(),
+    WithCost(r) => r.responder().content_kind(),
+    Xml(_) => ContentKind::Text,
+);
+
+impl_introspection!([T, U] RocketResponseGeneric2<T, U>, [T: Serialize];
+    Absolute(r) => r.responder().content_kind(),
+    Accepted(r) => optional_text(&r.0),
+    AcceptedJob(r) => r.responder().content_kind(),
+    #[cfg(feature = "oauth")]
+    AuthRedirect(_) => ContentKind::StatusOnly,
+    BadRequest(r) => optional_text(&r.0),
+    BuildError(_) => ContentKind::Text,
+    Cached(r) => r.responder().content_kind(),
+    CacheEntry(_) => ContentKind::Binary,
+    Canonical(r) => r.responder().content_kind(),
+    #[cfg(feature = "chaos")]
+    ChaosWrapped(r) => r.responder().content_kind(),
+    #[cfg(feature = "process")]
+    CommandOutput(_) => ContentKind::Stream,
+    #[cfg(feature = "compress")]
+    Compressed(r) => r.responder().content_kind(),
+    Conflict(r) => optional_text(&r.0),
+    Created(_) => ContentKind::Text,
+    CreatedResource(r) => r.responder().content_kind(),
+    Css(_) => ContentKind::Text,
+    Custom(r) => r.1.content_kind(),
+    #[cfg(feature = "db")]
+    DbError(_) => ContentKind::Text,
+    #[cfg(feature = "json")]
+    DeltaCollection(_) => ContentKind::Text,
+    Download(_) => ContentKind::File,
+    Dyn(_) => ContentKind::Other,
+    #[cfg(feature = "json")]
+    ExtensionNegotiated(r) => r.content_kind(),
+    File(_) => ContentKind::File,
+    Flash(_) => ContentKind::StatusOnly,
+    FlashResponse(_) => ContentKind::StatusOnly,
+    Forbidden(r) => optional_text(&r.0),
+    #[cfg(feature = "graphql")]
+    GraphQL(_) => ContentKind::Text,
+    #[cfg(feature = "hal")]
+    Hal(_) => ContentKind::Text,
+    Hardened(r) => r.responder().content_kind(),
+    HeadAware(r) => r.responder().content_kind(),
+    #[cfg(feature = "json")]
+    HealthReport(_) => ContentKind::Text,
+    Hooked(r) => r.responder().content_kind(),
+    Html(_) => ContentKind::Text,
+    JavaScript(_) => ContentKind::Text,
+    Json(_) => ContentKind::Text,
+    #[cfg(feature = "jsonapi")]
+    JsonApi(_) => ContentKind::Text,
+    Lazy(_) => ContentKind::Other,
+    Localized(r) => r.responder().content_kind(),
+    MsgPack(_) => ContentKind::Binary,
+    Multipart(_) => ContentKind::Binary,
+    NamedFiled(_) => ContentKind::File,
+    #[cfg(feature = "json")]
+    Negotiated(_) => ContentKind::Other,
+    NotFound(_) => ContentKind::Text,
+    NoContent(_) => ContentKind::StatusOnly,
+    #[cfg(feature = "json")]
+    Paginated(_) => ContentKind::Text,
+    Plain(_) => ContentKind::Text,
+    #[cfg(feature = "signed-files")]
+    ProtectedFile(_) => ContentKind::File,
+    PurgeResult(_) => ContentKind::Text,
+    QuotaHeaders(r) => r.responder().content_kind(),
+    RangedFile(_) => ContentKind::File,
+    Redirect(_) => ContentKind::StatusOnly,
+    RocketError(_) => ContentKind::Text,
+    #[cfg(all(feature = "db-sqlx", feature = "json"))]
+    RowStream(_) => ContentKind::Stream,
+    #[cfg(feature = "bincode")]
+    SerdeBincode(_) => ContentKind::Binary,
+    #[cfg(feature = "cbor")]
+    SerdeCbor(_) => ContentKind::Binary,
+    #[cfg(feature = "json")]
+    SerdeJson(_) => ContentKind::Text,
+    #[cfg(feature = "msgpack")]
+    SerdeMsgPack(_) => ContentKind::Binary,
+    #[cfg(feature = "msgpack")]
+    SerdeMsgPackValue(_) => ContentKind::Binary,
+    #[cfg(feature = "json")]
+    SerdeValue(_) => ContentKind::Text,
+    #[cfg(feature = "xml")]
+    SerdeXml(_) => ContentKind::Text,
+    ServiceUnavailable(r) => r.responder().content_kind(),
+    #[cfg(feature = "secrets")]
+    SessionRedirect(_) => ContentKind::StatusOnly,
+    Sitemap(_) => ContentKind::Text,
+    StaticSlice(_) => ContentKind::Binary,
+    StaticStr(_) => ContentKind::Text,
+    String(_) => ContentKind::Text,
+    Status(_) => ContentKind::StatusOnly,
+    StatusPage(_) => ContentKind::Text,
+    StreamedFile(_) => ContentKind::Stream,
+    #[cfg(any(feature = "templates-tera", feature = "templates-handlebars"))]
+    Template(_) => ContentKind::Text,
+    TextBody(_) => ContentKind::Text,
+    #[cfg(feature = "timing")]
+    Timed(r) => r.responder().content_kind(),
+    TokioFile(_) => ContentKind::File,
+    TooManyRequests(r) => r.responder().content_kind(),
+    Typed(_, _) => ContentKind::Binary,
+    TypedStr(_, _) => ContentKind::Text,
+    Unauthorized(r) => optional_text(&r.0),
+    Vec(_) => ContentKind::Binary,
+    WithCache(r) => r.responder().content_kind(),
+    WithCookies(r) => r.responder().content_kind(),
+    WithCors(r) => r.responder().content_kind(),
+    WithCost(r) => r.responder().content_kind(),
+    Xml(_) => ContentKind::Text,
+);
+
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+impl_introspection!([T, S, U] RocketResponseGeneric3<T, S, U>, [S: Serialize];
+    Absolute(r) => r.responder().content_kind(),
+    Accepted(r) => optional_text(&r.0),
+    AcceptedJob(r) => r.responder().content_kind(),
+    #[cfg(feature = "oauth")]
+    AuthRedirect(_) => ContentKind::StatusOnly,
+    BadRequest(r) => optional_text(&r.0),
+    BuildError(_) => ContentKind::Text,
+    Cached(r) => r.responder().content_kind(),
+    CacheEntry(_) => ContentKind::Binary,
+    Canonical(r) => r.responder().content_kind(),
+    #[cfg(feature = "chaos")]
+    ChaosWrapped(r) => r.responder().content_kind(),
+    #[cfg(feature = "process")]
+    CommandOutput(_) => ContentKind::Stream,
+    #[cfg(feature = "compress")]
+    Compressed(r) => r.responder().content_kind(),
+    Conflict(r) => optional_text(&r.0),
+    Created(_) => ContentKind::Text,
+    CreatedResource(r) => r.responder().content_kind(),
+    Css(_) => ContentKind::Text,
+    Custom(r) => r.1.content_kind(),
+    #[cfg(feature = "db")]
+    DbError(_) => ContentKind::Text,
+    #[cfg(feature = "json")]
+    DeltaCollection(_) => ContentKind::Text,
+    Download(_) => ContentKind::File,
+    Dyn(_) => ContentKind::Other,
+    #[cfg(feature = "json")]
+    ExtensionNegotiated(r) => r.content_kind(),
+    File(_) => ContentKind::File,
+    Flash(_) => ContentKind::StatusOnly,
+    FlashResponse(_) => ContentKind::StatusOnly,
+    Forbidden(r) => optional_text(&r.0),
+    #[cfg(feature = "graphql")]
+    GraphQL(_) => ContentKind::Text,
+    #[cfg(feature = "hal")]
+    Hal(_) => ContentKind::Text,
+    Hardened(r) => r.responder().content_kind(),
+    HeadAware(r) => r.responder().content_kind(),
+    #[cfg(feature = "json")]
+    HealthReport(_) => ContentKind::Text,
+    Hooked(r) => r.responder().content_kind(),
+    Html(_) => ContentKind::Text,
+    JavaScript(_) => ContentKind::Text,
+    Json(_) => ContentKind::Text,
+    #[cfg(feature = "jsonapi")]
+    JsonApi(_) => ContentKind::Text,
+    Lazy(_) => ContentKind::Other,
+    Localized(r) => r.responder().content_kind(),
+    MsgPack(_) => ContentKind::Binary,
+    Multipart(_) => ContentKind::Binary,
+    NamedFiled(_) => ContentKind::File,
+    #[cfg(feature = "json")]
+    Negotiated(_) => ContentKind::Other,
+    NotFound(_) => ContentKind::Text,
+    NoContent(_) => ContentKind::StatusOnly,
+    #[cfg(feature = "json")]
+    Paginated(_) => ContentKind::Text,
+    Plain(_) => ContentKind::Text,
+    #[cfg(feature = "signed-files")]
+    ProtectedFile(_) => ContentKind::File,
+    PurgeResult(_) => ContentKind::Text,
+    QuotaHeaders(r) => r.responder().content_kind(),
+    RangedFile(_) => ContentKind::File,
+    Redirect(_) => ContentKind::StatusOnly,
+    RocketError(_) => ContentKind::Text,
+    #[cfg(all(feature = "db-sqlx", feature = "json"))]
+    RowStream(_) => ContentKind::Stream,
+    #[cfg(feature = "bincode")]
+    SerdeBincode(_) => ContentKind::Binary,
+    #[cfg(feature = "cbor")]
+    SerdeCbor(_) => ContentKind::Binary,
+    #[cfg(feature = "json")]
+    SerdeJson(_) => ContentKind::Text,
+    #[cfg(feature = "msgpack")]
+    SerdeMsgPack(_) => ContentKind::Binary,
+    #[cfg(feature = "msgpack")]
+    SerdeMsgPackValue(_) => ContentKind::Binary,
+    #[cfg(feature = "json")]
+    SerdeValue(_) => ContentKind::Text,
+    #[cfg(feature = "xml")]
+    SerdeXml(_) => ContentKind::Text,
+    ServiceUnavailable(r) => r.responder().content_kind(),
+    #[cfg(feature = "secrets")]
+    SessionRedirect(_) => ContentKind::StatusOnly,
+    Sitemap(_) => ContentKind::Text,
+    StaticSlice(_) => ContentKind::Binary,
+    StaticStr(_) => ContentKind::Text,
+    String(_) => ContentKind::Text,
+    Status(_) => ContentKind::StatusOnly,
+    StatusPage(_) => ContentKind::Text,
+    StreamedFile(_) => ContentKind::Stream,
+    #[cfg(any(feature = "templates-tera", feature = "templates-handlebars"))]
+    Template(_) => ContentKind::Text,
+    TextBody(_) => ContentKind::Text,
+    #[cfg(feature = "timing")]
+    Timed(r) => r.responder().content_kind(),
+    TokioFile(_) => ContentKind::File,
+    TooManyRequests(r) => r.responder().content_kind(),
+    Typed(_, _) => ContentKind::Binary,
+    TypedStr(_, _) => ContentKind::Text,
+    Unauthorized(r) => optional_text(&r.0),
+    Vec(_) => ContentKind::Binary,
+    WithCache(r) => r.responder().content_kind(),
+    WithCookies(r) => r.responder().content_kind(),
+    WithCors(r) => r.responder().content_kind(),
+    WithCost(r) => r.responder().content_kind(),
+    Xml(_) => ContentKind::Text,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::ContentKind;
+    use crate::{Cached, RocketResponse, RocketResponseGeneric, RocketResponseGeneric2};
+    use rocket::response::{status, Redirect};
+
+    #[test]
+    fn test_variants() {
+        assert!(RocketResponse::VARIANTS.contains(&"FlashMsg"));
+        assert!(!RocketResponseGeneric::<&str>::VARIANTS.contains(&"FlashMsg"));
+        assert!(RocketResponseGeneric2::<&str, Redirect>::VARIANTS.contains(&"FlashResponse"));
+        assert_eq!(
+            cfg!(feature = "json"),
+            RocketResponse::VARIANTS.contains(&"SerdeJson")
+        );
+    }
+
+    #[test]
+    fn test_content_kind() {
+        let res = RocketResponse::Cached(Cached::new(RocketResponse::Vec(vec![1])));
+        assert_eq!("Cached", res.variant_name());
+        assert_eq!(ContentKind::Binary, res.content_kind());
+
+        let res = RocketResponseGeneric::<&str>::Accepted(status::Accepted(None));
+        assert_eq!(ContentKind::StatusOnly, res.content_kind());
+        let res = RocketResponseGeneric::Accepted(status::Accepted(Some("queued")));
+        assert_eq!(ContentKind::Text, res.content_kind());
+    }
+}
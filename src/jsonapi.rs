@@ -0,0 +1,234 @@
+use crate::{body, RocketError};
+use rocket::{
+    http::{ContentType, Status},
+    request::Request,
+    response::{self, Responder, Response},
+    serde::{
+        json::{serde_json, Value},
+        Serialize,
+    },
+};
+
+/// Error object of a [JsonApi] error document
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(crate = "rocket::serde")]
+struct ErrorObject {
+    status: String,
+    title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    detail: Option<String>,
+}
+
+/// Top-level [JSON:API](https://jsonapi.org/format/) document
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde")]
+struct Document<'a, T> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<&'a T>,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    errors: &'a [ErrorObject],
+    #[serde(skip_serializing_if = "serde_json::Map::is_empty")]
+    meta: &'a serde_json::Map<String, Value>,
+    #[serde(skip_serializing_if = "serde_json::Map::is_empty")]
+    links: &'a serde_json::Map<String, Value>,
+}
+
+/// Serializes the wrapped data or errors into a [JSON:API](https://jsonapi.org/format/)
+/// document.
+///
+/// Returns a response with Content-Type `application/vnd.api+json`. An error document
+/// responds with the status of its first error. If serialization fails, it responds as
+/// [BuildError::Serialize](crate::BuildError::Serialize).
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::{get, http::Status, serde::Serialize};
+/// use rocket_response::JsonApi;
+///
+/// #[derive(Serialize)]
+/// #[serde(crate = "rocket::serde")]
+/// struct Article {
+///     #[serde(rename = "type")]
+///     kind: &'static str,
+///     id: String,
+/// }
+///
+/// #[get("/articles/<id>")]
+/// fn article(id: usize) -> JsonApi<Article> {
+///     if id == 0 {
+///         return JsonApi::error(Status::NotFound, Some(format!("no article {}", id)));
+///     }
+///     JsonApi::data(Article { kind: "articles", id: id.to_string() })
+///         .link("self", format!("/articles/{}", id))
+///         .meta("version", 2)
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonApi<T> {
+    data: Option<T>,
+    errors: Vec<ErrorObject>,
+    meta: serde_json::Map<String, Value>,
+    links: serde_json::Map<String, Value>,
+}
+
+impl<T> JsonApi<T> {
+    /// Document with the primary `data`
+    pub fn data(data: T) -> Self {
+        JsonApi {
+            data: Some(data),
+            ..JsonApi::empty()
+        }
+    }
+
+    /// Error document with an error of `status` and an optional `detail`
+    pub fn error(status: Status, detail: Option<String>) -> Self {
+        JsonApi::empty().add_error(status, detail)
+    }
+
+    fn empty() -> Self {
+        JsonApi {
+            data: None,
+            errors: Vec::new(),
+            meta: serde_json::Map::new(),
+            links: serde_json::Map::new(),
+        }
+    }
+
+    /// Adds an error of `status` and an optional `detail`.
+    ///
+    /// The data of the document is dropped, because a document doesn't contain both.
+    pub fn add_error(mut self, status: Status, detail: Option<String>) -> Self {
+        self.data = None;
+        self.errors.push(ErrorObject {
+            status: status.code.to_string(),
+            title: status.reason_lossy().to_string(),
+            detail,
+        });
+        self
+    }
+
+    /// Adds the member `key` to the meta object.
+    ///
+    /// A `value`, which fails to serialize, is added as `null`.
+    pub fn meta<K: Into<String>, V: Serialize>(mut self, key: K, value: V) -> Self {
+        self.meta.insert(
+            key.into(),
+            serde_json::to_value(value).unwrap_or(Value::Null),
+        );
+        self
+    }
+
+    /// Adds the link `name` to the top-level links object.
+    pub fn link<N: Into<String>, H: Into<String>>(mut self, name: N, href: H) -> Self {
+        self.links.insert(name.into(), Value::String(href.into()));
+        self
+    }
+
+    /// Status of the response
+    pub fn status(&self) -> Status {
+        self.errors
+            .first()
+            .and_then(|error| error.status.parse().ok())
+            .and_then(Status::from_code)
+            .unwrap_or(Status::Ok)
+    }
+
+    /// Consumes the wrapper and returns the data, if it isn't an error document.
+    pub fn into_inner(self) -> Option<T> {
+        self.data
+    }
+}
+
+impl<T> From<RocketError> for JsonApi<T> {
+    /// Error document with the status and the public message of `error`
+    fn from(error: RocketError) -> Self {
+        JsonApi::error(error.status(), error.public_message().map(str::to_string))
+    }
+}
+
+impl<'r, T: Serialize> Responder<'r, 'static> for JsonApi<T> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let document = Document {
+            data: self.data.as_ref(),
+            errors: &self.errors,
+            meta: &self.meta,
+            links: &self.links,
+        };
+
+        let buf = match body::to_json(&document) {
+            Ok(buf) => buf,
+            Err(e) => return e.respond_to(req),
+        };
+        Response::build_from(buf.respond_to(ContentType::new("application", "vnd.api+json"), req)?)
+            .status(self.status())
+            .ok()
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::JsonApi;
+    use crate::{RocketError, RocketResponseGeneric};
+    use rocket::{
+        get,
+        http::Status,
+        local::blocking::Client,
+        routes,
+        serde::json::{json, Value},
+    };
+
+    #[get("/jsonapi/<id>")]
+    fn route_jsonapi(id: usize) -> RocketResponseGeneric<Value> {
+        match id {
+            0 => RocketResponseGeneric::JsonApi(Box::new(
+                RocketError::NotFound(Some("no article".to_string())).into(),
+            )),
+            _ => RocketResponseGeneric::JsonApi(Box::new(
+                JsonApi::data(json!({ "type": "articles", "id": id.to_string() }))
+                    .link("self", format!("/jsonapi/{}", id))
+                    .meta("count", 1),
+            )),
+        }
+    }
+
+    #[test]
+    fn test_jsonapi() {
+        let rocket = rocket::build().mount("/", routes![route_jsonapi]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/jsonapi/1").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(
+            Some("application/vnd.api+json"),
+            res.headers().get_one("Content-Type")
+        );
+        assert_eq!(
+            json!({
+                "data": { "type": "articles", "id": "1" },
+                "meta": { "count": 1 },
+                "links": { "self": "/jsonapi/1" },
+            }),
+            res.into_json::<Value>().unwrap()
+        );
+
+        let res = client.get("/jsonapi/0").dispatch();
+        assert_eq!(Status::NotFound, res.status());
+        assert_eq!(
+            json!({
+                "errors": [{ "status": "404", "title": "Not Found", "detail": "no article" }],
+            }),
+            res.into_json::<Value>().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_jsonapi_errors() {
+        let doc: JsonApi<()> = JsonApi::data(())
+            .add_error(Status::Conflict, None)
+            .add_error(Status::BadRequest, None);
+        assert_eq!(Status::Conflict, doc.status());
+        assert_eq!(None, doc.into_inner());
+    }
+}
@@ -0,0 +1,231 @@
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+use crate::RocketResponseGeneric3;
+use crate::{head_aware::is_head, RocketResponse, RocketResponseGeneric, RocketResponseGeneric2};
+use rocket::{
+    request::Request,
+    response::{self, Responder, Response},
+    serde::Serialize,
+};
+
+/// Boxed closure of the `Lazy` variants returning the response `R`
+pub type LazyFn<R> = Box<dyn FnOnce(&Request<'_>) -> R + Send>;
+
+/// Responder evaluating a closure only when the response is sent
+///
+/// The closure gets the [Request], so construction of the body can depend on
+/// request headers like `Accept` or `Accept-Language`.
+/// Wrapped in [Cached](crate::Cached), the closure isn't called at all, when the request is
+/// answered with `304 Not Modified`.
+///
+/// On a `HEAD` request the closure isn't called either, and the response is an empty
+/// `200 OK`. Rocket dispatches an automatically handled `HEAD` request as `GET`, so this needs
+/// the [fairing](crate::HeadAware::fairing()) of [HeadAware](crate::HeadAware), unless the
+/// route is a `HEAD` route. Headers like `Content-Type`, which the `GET` response would have,
+/// are set by wrapping the `Lazy` in [HeadAware](crate::HeadAware) instead.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::{get, http::MediaType, request::Request};
+/// use rocket_response::{Lazy, RocketResponse};
+///
+/// #[get("/greeting")]
+/// fn greeting() -> Lazy<impl FnOnce(&Request<'_>) -> RocketResponse> {
+///     Lazy::new(|req: &Request<'_>| {
+///         let html = req
+///             .accept()
+///             .map_or(false, |accept| accept.preferred().media_type() == &MediaType::HTML);
+///         if html {
+///             RocketResponse::Html(rocket::response::content::RawHtml("<p>Hello world</p>"))
+///         } else {
+///             RocketResponse::StaticStr("Hello world")
+///         }
+///     })
+/// }
+/// ```
+///
+/// The enums have a `Lazy` variant with a boxed closure returning the enum, which is
+/// constructed by `lazy()`:
+///
+/// ```rust
+/// use rocket::get;
+/// use rocket_response::RocketResponse;
+///
+/// #[get("/report")]
+/// fn report() -> RocketResponse {
+///     RocketResponse::lazy(|_| RocketResponse::String("expensive report".to_string()))
+/// }
+/// ```
+pub struct Lazy<F>(F);
+
+impl<F> Lazy<F> {
+    /// Wraps `f` to be called by [respond_to](Responder::respond_to()).
+    pub fn new(f: F) -> Self {
+        Lazy(f)
+    }
+}
+
+impl<F> std::fmt::Debug for Lazy<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Lazy")
+    }
+}
+
+impl<'r, 'o: 'r, F, R> Responder<'r, 'o> for Lazy<F>
+where
+    F: FnOnce(&'r Request<'_>) -> R,
+    R: Responder<'r, 'o>,
+{
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        if is_head(req) {
+            return Response::build().ok();
+        }
+        (self.0)(req).respond_to(req)
+    }
+}
+
+/// Implements the `lazy` constructor of the `Lazy` variant.
+macro_rules! impl_lazy_constructor {
+    ([$($gen:tt)*] $name:ty $(where $($bound:tt)+)?) => {
+        impl<$($gen)*> $name $(where $($bound)+)? {
+            /// Response returned by `f` only when it is sent, see [Lazy]
+            pub fn lazy<F>(f: F) -> Self
+            where
+                F: FnOnce(&Request<'_>) -> Self + Send + 'static,
+            {
+                Self::Lazy(Lazy::new(Box::new(f)))
+            }
+        }
+    };
+}
+
+impl_lazy_constructor!([] RocketResponse);
+impl_lazy_constructor!([T] RocketResponseGeneric<T> where T: Serialize);
+impl_lazy_constructor!([T, U] RocketResponseGeneric2<T, U> where T: Serialize);
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+impl_lazy_constructor!([T, S, U] RocketResponseGeneric3<T, S, U> where S: Serialize);
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::Lazy;
+    use crate::{Cached, ETag, HeadAware, RocketResponse, RocketResponseGeneric};
+    use rocket::{
+        get,
+        http::{Header, Status},
+        local::blocking::Client,
+        request::Request,
+        routes,
+    };
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    #[get("/lazy")]
+    fn route_lazy() -> Cached<Lazy<impl FnOnce(&Request<'_>) -> RocketResponse>> {
+        Cached::new(Lazy::new(|req: &Request<'_>| {
+            CALLS.fetch_add(1, Ordering::SeqCst);
+            RocketResponse::String(req.headers().get_one("X-Name").unwrap_or("").to_string())
+        }))
+        .etag(ETag::strong("v1"))
+    }
+
+    static VARIANT_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    #[get("/lazy_variant")]
+    fn route_lazy_variant() -> RocketResponse {
+        RocketResponse::Cached(
+            Cached::new(RocketResponse::lazy(|_| {
+                VARIANT_CALLS.fetch_add(1, Ordering::SeqCst);
+                RocketResponse::StaticStr("variant")
+            }))
+            .etag(ETag::strong("v2")),
+        )
+    }
+
+    #[get("/lazy_generic")]
+    fn route_lazy_generic() -> RocketResponseGeneric<&'static str> {
+        RocketResponseGeneric::lazy(|req| {
+            RocketResponseGeneric::String(req.uri().path().to_string())
+        })
+    }
+
+    #[test]
+    fn test_lazy_variant() {
+        let rocket = rocket::build().mount("/", routes![route_lazy_variant, route_lazy_generic]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client
+            .get("/lazy_variant")
+            .header(Header::new("If-None-Match", "\"v2\""))
+            .dispatch();
+        assert_eq!(Status::NotModified, res.status());
+        assert_eq!(0, VARIANT_CALLS.load(Ordering::SeqCst));
+
+        let res = client.get("/lazy_variant").dispatch();
+        assert_eq!("variant", res.into_string().unwrap());
+        assert_eq!(1, VARIANT_CALLS.load(Ordering::SeqCst));
+
+        let res = client.get("/lazy_generic").dispatch();
+        assert_eq!("/lazy_generic", res.into_string().unwrap());
+    }
+
+    static HEAD_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    #[get("/lazy_head")]
+    fn route_lazy_head() -> RocketResponse {
+        RocketResponse::lazy(|_| {
+            HEAD_CALLS.fetch_add(1, Ordering::SeqCst);
+            RocketResponse::StaticStr("Hello world")
+        })
+    }
+
+    #[test]
+    fn test_lazy_head() {
+        let rocket = rocket::build()
+            .attach(HeadAware::<RocketResponse>::fairing())
+            .mount("/", routes![route_lazy_head]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.head("/lazy_head").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(0, HEAD_CALLS.load(Ordering::SeqCst));
+
+        let res = client.get("/lazy_head").dispatch();
+        assert_eq!("Hello world", res.into_string().unwrap());
+        assert_eq!(1, HEAD_CALLS.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_lazy() {
+        let rocket = rocket::build().mount("/", routes![route_lazy]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client
+            .get("/lazy")
+            .header(Header::new("X-Name", "alice"))
+            .dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!("alice", res.into_string().unwrap());
+        assert_eq!(1, CALLS.load(Ordering::SeqCst));
+
+        let res = client
+            .get("/lazy")
+            .header(Header::new("If-None-Match", "\"v1\""))
+            .dispatch();
+        assert_eq!(Status::NotModified, res.status());
+        assert_eq!(1, CALLS.load(Ordering::SeqCst));
+    }
+}
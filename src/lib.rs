@@ -1,11 +1,13 @@
 //! # rocket-response - Provides enum for variable Rocket Responses
 //!
-//! This [crate] provides 3 enums to be flexible in returning [Responses].
+//! This [crate] provides 4 enums to be flexible in returning [Responses].
 //!
 //! 1. [RocketResponse] provides all non-generic [Response] types.
 //! 2. [RocketResponseGeneric] provides [Response]-types non-generic
 //!    and generic using a single generic type.
 //! 3. [RocketResponseGeneric2] allows a different [Flash](rocket::response::Flash) type.
+//! 4. `RocketResponseGeneric3` uses independent types for content, serde and
+//!    [Flash](rocket::response::Flash) bodies, available with the serde features.
 //!
 //! If you miss any [Response], you are welcome to open an [issue]
 //! or even better provide a pull-request!
@@ -22,13 +24,47 @@
 //! rocket-response = { version = "0.0.1-rc.2" }
 //! ```
 //!
+//! The minimum supported Rust version is 1.60, which is needed for the `dep:` syntax of the
+//! `simd-json` feature.
+//!
+//! The [prelude] re-exports the enums with the most-used types and provides macros like
+//! `html!`, `json_ok!` and `not_found!` constructing the variants:
+//!
+//! ```rust
+//! use rocket_response::prelude::*;
+//!
+//! let res: RocketResponse = not_found!("no item");
+//! ```
+//!
 //! ## Features
 //!
 //! You can depend on a couple of features, which provide additional types.
 //!
-//! * json
+//! * bincode
+//! * buffer-pool
+//! * cbor
+//! * chaos (failure and latency injection for resilience tests)
+//! * checksum (checksum chains of paginated exports)
+//! * compress (brotli and gzip compression)
+//! * db (database errors, with db-sqlx or db-diesel the conversions of their errors,
+//!   with db-sqlx and json streamed rows)
+//! * fixtures (declarative responses from JSON or TOML and a mock server)
+//! * graphql (responses of async-graphql-rocket)
+//! * hal (HAL hypermedia documents, together with json)
+//! * json (also health reports)
+//! * jsonapi (JSON:API documents, together with json)
 //! * msgpack
+//! * oauth (authorize redirects with state and PKCE cookies)
+//! * process (streamed command output)
+//! * redis (Redis backend of the response cache)
+//! * secrets (private cookies and session redirects)
+//! * signed-files (files served only for signed, unexpired URLs)
+//! * simd-json (SIMD accelerated JSON serialization, enables json)
 //! * templates-handlebars or templates-tera
+//! * testing
+//! * timing (Server-Timing header)
+//! * tracing (events of the responding variant)
+//! * xml
 //!
 //! ```toml
 //! [dependencies]
@@ -36,6 +72,13 @@
 //! ```
 //!
 //!
+//! ## Forward compatibility
+//!
+//! The enums are `#[non_exhaustive]`, so new variants aren't breaking changes.
+//! A `match` on a response needs a wildcard arm, which can use the introspection by
+//! `variant_name()` and `content_kind()` or erase the variant by `into_dyn()`.
+//! Responders without an own variant are returned by the `fallback()` constructor.
+//!
 //! [Response]: rocket::response::Response
 //! [Responses]: rocket::response::Response
 //! [issue]: https://github.com/kolbma/rocket-response/issues
@@ -44,90 +87,405 @@
 #![deny(warnings)]
 #![deny(clippy::all)]
 #![deny(missing_docs)]
-#![deny(rustdoc::missing_doc_code_examples)]
 
+#[cfg(feature = "graphql")]
+use async_graphql_rocket::GraphQLResponse;
 #[cfg(any(feature = "json", feature = "msgpack"))]
 use rocket::serde;
 use rocket::{
     fs::NamedFile,
-    http::Status,
+    http::{ContentType, Status},
+    request::Request,
     response::{
+        self,
         content::{RawCss, RawHtml, RawJavaScript, RawJson, RawMsgPack, RawText, RawXml},
         status::{
-            Accepted, BadRequest, Conflict, Created, Forbidden, NoContent, NotFound, Unauthorized,
+            Accepted, BadRequest, Conflict, Created, Custom, Forbidden, NoContent, NotFound,
+            Unauthorized,
         },
-        Flash, Redirect,
+        Flash, Redirect, Responder,
     },
     serde::Serialize,
-    tokio, Responder,
+    tokio,
 };
 #[cfg(any(feature = "templates-tera", feature = "templates-handlebars"))]
 use rocket_dyn_templates::Template;
-use std::fs::File;
+use std::{borrow::Cow, fs::File};
+
+mod accept;
+mod accepted;
+#[cfg(feature = "bincode")]
+mod bincode;
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+mod body;
+mod cache_key;
+mod cache_policy;
+mod cached;
+mod canonical;
+#[cfg(feature = "cbor")]
+mod cbor;
+#[cfg(feature = "chaos")]
+mod chaos;
+#[cfg(feature = "checksum")]
+mod checksum;
+#[cfg(feature = "compress")]
+mod compressed;
+mod convert;
+mod cookies;
+mod cors;
+mod cost;
+mod created;
+#[cfg(feature = "db")]
+mod db;
+#[cfg(feature = "json")]
+mod delta_collection;
+mod download;
+mod dynamic;
+mod either;
+mod error;
+mod files;
+mod filters;
+#[cfg(feature = "fixtures")]
+mod fixtures;
+mod flash;
+#[cfg(feature = "graphql")]
+mod graphql;
+#[cfg(feature = "hal")]
+mod hal;
+mod hardened;
+mod head_aware;
+mod header_limit;
+#[cfg(feature = "json")]
+mod health;
+mod hook;
+mod introspect;
+#[cfg(feature = "jsonapi")]
+mod jsonapi;
+mod lazy;
+mod localized;
+mod maintenance;
+#[cfg(feature = "fixtures")]
+mod mock;
+#[cfg(feature = "msgpack")]
+mod msgpack;
+mod multipart;
+#[cfg(feature = "json")]
+mod negotiated;
+#[cfg(feature = "oauth")]
+mod oauth;
+#[cfg(feature = "json")]
+mod paginated;
+mod pipeline;
+#[cfg(feature = "process")]
+mod process;
+#[cfg(feature = "signed-files")]
+mod protected_file;
+mod quota;
+mod ranged;
+mod redirect;
+mod response_cache;
+mod retry;
+#[cfg(all(feature = "db-sqlx", feature = "json"))]
+mod row_stream;
+#[cfg(feature = "secrets")]
+mod session;
+mod sitemap;
+mod status_page;
+mod streamed;
+#[cfg(any(feature = "templates-tera", feature = "templates-handlebars"))]
+mod template;
+mod text;
+#[cfg(feature = "timing")]
+mod timing;
+#[cfg(feature = "tracing")]
+mod trace;
+mod typed;
+mod url;
+mod usage;
+#[cfg(feature = "xml")]
+mod xml;
+
+#[cfg(feature = "buffer-pool")]
+pub mod buffer_pool;
+pub mod prelude;
+#[cfg(feature = "testing")]
+pub mod testing;
+
+#[cfg(feature = "bincode")]
+pub use crate::bincode::Bincode;
+pub use accept::{AcceptList, MediaRange};
+pub use accepted::AcceptedJob;
+pub use cache_key::CacheKey;
+pub use cache_policy::{CachePolicy, WithCache};
+pub use cached::{Cached, ETag};
+pub use canonical::{Canonical, CanonicalRedirect};
+#[cfg(feature = "cbor")]
+pub use cbor::Cbor;
+#[cfg(feature = "chaos")]
+pub use chaos::{ChaosConfig, ChaosWrapped, Delayed, SlowBody};
+#[cfg(feature = "checksum")]
+pub use checksum::ChecksumChain;
+#[cfg(feature = "compress")]
+pub use compressed::{Compressed, Encoding};
+pub use convert::{IntoRocketResponse, TryRespond};
+pub use cookies::{CookiePolicy, WithCookies};
+pub use cors::{CorsPolicy, WithCors};
+pub use cost::{Cost, WithCost};
+pub use created::CreatedResource;
+#[cfg(feature = "db")]
+pub use db::{DbError, DbErrorKind, DbErrorMapper, DbErrorMapping};
+#[cfg(feature = "json")]
+pub use delta_collection::{DeltaCollection, DeltaSnapshots};
+pub use download::Download;
+pub use dynamic::Dyn;
+pub use either::{Either2, Either3, Either4};
+pub use error::{
+    error_to_status, status_to_error, BuildError, BuildErrorHook, Incident, RocketError,
+};
+pub use filters::{ResponseFilterSet, RouteFilter};
+#[cfg(feature = "fixtures")]
+pub use fixtures::ResponseSpec;
+pub use flash::FlashKind;
+#[cfg(feature = "hal")]
+pub use hal::Hal;
+pub use hardened::{Hardened, SecurityHeaders};
+pub use head_aware::HeadAware;
+pub use header_limit::HeaderLimit;
+#[cfg(feature = "json")]
+pub use health::{HealthCheck, HealthReport};
+pub use hook::{Hooked, ResponseHook};
+pub use introspect::ContentKind;
+#[cfg(feature = "jsonapi")]
+pub use jsonapi::JsonApi;
+pub use lazy::{Lazy, LazyFn};
+pub use localized::{ChosenLanguage, Localized};
+pub use maintenance::Maintenance;
+#[cfg(feature = "fixtures")]
+pub use mock::MockServer;
+#[cfg(feature = "msgpack")]
+pub use msgpack::MsgPackValue;
+pub use multipart::{Multipart, Part};
+#[cfg(feature = "json")]
+pub use negotiated::{ExtensionNegotiated, Format, Negotiated, SerializerRegistry};
+#[cfg(feature = "oauth")]
+pub use oauth::AuthRedirect;
+#[cfg(feature = "json")]
+pub use paginated::Paginated;
+pub use pipeline::{stage, Pipeline};
+#[cfg(feature = "process")]
+pub use process::CommandOutput;
+#[cfg(feature = "signed-files")]
+pub use protected_file::{ProtectedFile, UrlSigner};
+pub use quota::{Quota, QuotaHeaders, QuotaTracker};
+pub use ranged::RangedFile;
+#[cfg(feature = "redis")]
+pub use response_cache::RedisBackend;
+pub use response_cache::{
+    CacheBackend, CacheEntry, CacheStats, MemoryBackend, PurgeResult, ResponseCache,
+};
+pub use retry::{RetryAfter, ServiceUnavailable, TooManyRequests};
+#[cfg(all(feature = "db-sqlx", feature = "json"))]
+pub use row_stream::{OnRowError, RowStream};
+#[cfg(feature = "secrets")]
+pub use session::SessionRedirect;
+pub use sitemap::{ChangeFreq, Sitemap, SitemapRegistry};
+pub use status_page::{ErrorPages, StatusPage};
+pub use streamed::StreamedFile;
+pub use text::TextBody;
+#[cfg(feature = "timing")]
+pub use timing::Timed;
+pub use url::{Absolute, UrlContext};
+pub use usage::{UsageReport, UsageStats};
+#[cfg(feature = "xml")]
+pub use xml::SerdeXml;
 
 /// The non-generic [Responses](rocket::response::Response).
 ///
 /// ## Example usage
 ///
 /// ```rust
-/// use rocket::{get, response::{self, Redirect}};
+/// use rocket::{get, response};
 /// use rocket_response::RocketResponse;
 ///
 /// #[get("/<id>")]
 /// pub(crate) fn route_example(id: usize) -> RocketResponse {
 ///     match id {
 ///         0 => RocketResponse::NoContent(response::status::NoContent),
-///         1 => RocketResponse::Redirect(Redirect::to("/admin")),
+///         1 => RocketResponse::redirect_to("/admin"),
 ///         _ => RocketResponse::StaticStr("Hello world"),
 ///     }
 /// }
 /// ```
-#[derive(Responder)]
+#[non_exhaustive]
 pub enum RocketResponse {
+    /// see [Absolute](crate::Absolute)
+    Absolute(Box<Absolute<RocketResponse>>),
     /// see [rocket::response::status::Accepted]
     Accepted(Accepted<&'static str>),
+    /// see [AcceptedJob](crate::AcceptedJob)
+    AcceptedJob(Box<AcceptedJob<RocketResponse>>),
+    #[cfg(feature = "oauth")]
+    /// see [AuthRedirect](crate::AuthRedirect)
+    AuthRedirect(Box<AuthRedirect>),
     /// see [rocket::response::status::BadRequest]
     BadRequest(BadRequest<&'static str>),
+    /// see [BuildError](crate::BuildError)
+    BuildError(Box<BuildError>),
+    /// see [Cached](crate::Cached)
+    Cached(Cached<RocketResponse>),
+    /// see [CacheEntry](crate::CacheEntry)
+    CacheEntry(Box<CacheEntry>),
+    /// see [Canonical](crate::Canonical)
+    Canonical(Box<Canonical<RocketResponse>>),
+    #[cfg(feature = "chaos")]
+    /// see [ChaosWrapped](crate::ChaosWrapped)
+    ChaosWrapped(Box<ChaosWrapped<RocketResponse>>),
+    #[cfg(feature = "process")]
+    /// see [CommandOutput](crate::CommandOutput)
+    CommandOutput(Box<CommandOutput>),
+    #[cfg(feature = "compress")]
+    /// see [Compressed](crate::Compressed)
+    Compressed(Box<Compressed<RocketResponse>>),
     /// see [rocket::response::status::Conflict]
     Conflict(Conflict<&'static str>),
     /// see [rocket::response::status::Created]
     Created(Created<&'static str>),
+    /// see [CreatedResource](crate::CreatedResource)
+    CreatedResource(Box<CreatedResource<RocketResponse>>),
     /// see [rocket::response::content::RawCss]
     Css(RawCss<&'static str>),
+    /// see [rocket::response::status::Custom]
+    Custom(Box<Custom<RocketResponse>>),
+    #[cfg(feature = "db")]
+    /// see [DbError](crate::DbError)
+    DbError(Box<DbError>),
+    #[cfg(feature = "json")]
+    /// see [DeltaCollection](crate::DeltaCollection)
+    DeltaCollection(Box<DeltaCollection<serde::json::Value>>),
+    /// see [Download](crate::Download)
+    Download(Box<Download>),
+    /// see [Dyn](crate::Dyn)
+    Dyn(Dyn),
+    #[cfg(feature = "json")]
+    /// see [ExtensionNegotiated](crate::ExtensionNegotiated)
+    ExtensionNegotiated(Box<ExtensionNegotiated<serde::json::Value>>),
     /// see [File]
     File(File),
     /// see [rocket::response::Flash]
-    Flash(Flash<&'static str>),
+    #[deprecated(
+        since = "0.0.1-rc.3",
+        note = "flash messages are formatted at runtime, use `FlashMsg` with a `Redirect`"
+    )]
+    Flash(Box<Flash<&'static str>>),
+    /// see [rocket::response::Flash]
+    ///
+    /// The message is a `String`, so it can be formatted at runtime:
+    ///
+    /// ```rust
+    /// use rocket::response::{Flash, Redirect};
+    /// use rocket_response::RocketResponse;
+    ///
+    /// let user = "alice";
+    /// let _ = RocketResponse::FlashMsg(Box::new(Flash::error(
+    ///     Redirect::to("/login"),
+    ///     format!("Invalid password for {}", user),
+    /// )));
+    /// ```
+    FlashMsg(Box<Flash<Redirect>>),
     /// see [rocket::response::status::Forbidden]
     Forbidden(Forbidden<&'static str>),
+    #[cfg(feature = "graphql")]
+    /// see [GraphQLResponse](async_graphql_rocket::GraphQLResponse), a GraphQL response or batch
+    /// response
+    GraphQL(Box<GraphQLResponse>),
+    /// see [Hardened](crate::Hardened)
+    Hardened(Box<Hardened<RocketResponse>>),
+    /// see [HeadAware](crate::HeadAware)
+    HeadAware(Box<HeadAware<RocketResponse>>),
+    #[cfg(feature = "json")]
+    /// see [HealthReport](crate::HealthReport)
+    HealthReport(Box<HealthReport>),
+    /// see [Hooked](crate::Hooked)
+    Hooked(Box<Hooked<RocketResponse>>),
     /// see [rocket::response::content::RawHtml]
     Html(RawHtml<&'static str>),
     /// see [rocket::response::content::RawJavaScript]
     JavaScript(RawJavaScript<&'static str>),
     /// see [rocket::response::content::RawJson]
     Json(RawJson<&'static str>),
+    /// see [Lazy](crate::Lazy)
+    Lazy(Lazy<LazyFn<RocketResponse>>),
+    /// see [Localized](crate::Localized)
+    Localized(Box<Localized<RocketResponse>>),
     /// see [rocket::response::content::RawMsgPack]
     MsgPack(RawMsgPack<&'static str>),
+    /// see [Multipart](crate::Multipart)
+    Multipart(Box<Multipart>),
     /// see [NamedFile](rocket::fs::NamedFile)
-    NamedFiled(NamedFile),
+    NamedFiled(Box<NamedFile>),
+    #[cfg(feature = "json")]
+    /// see [Negotiated](crate::Negotiated)
+    Negotiated(Negotiated<serde::json::Value>),
     /// see [rocket::response::status::NotFound]
     NotFound(NotFound<&'static str>),
     /// see [NoContent](rocket::response::status::NoContent)
     NoContent(NoContent),
+    #[cfg(feature = "json")]
+    /// see [Paginated](crate::Paginated)
+    Paginated(Box<Paginated<serde::json::Value>>),
     /// see [rocket::response::content::RawText]
     Plain(RawText<&'static str>),
+    #[cfg(feature = "signed-files")]
+    /// see [ProtectedFile](crate::ProtectedFile)
+    ProtectedFile(Box<ProtectedFile>),
+    /// see [PurgeResult](crate::PurgeResult)
+    PurgeResult(Box<PurgeResult>),
+    /// see [QuotaHeaders](crate::QuotaHeaders)
+    QuotaHeaders(QuotaHeaders<RocketResponse>),
+    /// see [RangedFile](crate::RangedFile)
+    RangedFile(Box<RangedFile>),
     /// see [Redirect](rocket::response::Redirect)
-    Redirect(Redirect),
+    Redirect(Box<Redirect>),
+    /// see [RocketError](crate::RocketError)
+    RocketError(RocketError),
+    #[cfg(all(feature = "db-sqlx", feature = "json"))]
+    /// see [RowStream](crate::RowStream)
+    RowStream(Box<RowStream>),
 
+    #[cfg(feature = "bincode")]
+    /// see [Bincode](crate::Bincode)
+    SerdeBincode(Bincode<&'static str>),
+    #[cfg(feature = "cbor")]
+    /// see [Cbor](crate::Cbor)
+    SerdeCbor(Cbor<&'static str>),
     #[cfg(feature = "json")]
     /// see [rocket::serde::json::Json]
     SerdeJson(serde::json::Json<&'static str>),
     #[cfg(feature = "msgpack")]
     /// see [rocket::serde::msgpack::MsgPack]
     SerdeMsgPack(serde::msgpack::MsgPack<&'static str>),
+    #[cfg(feature = "msgpack")]
+    /// see [MsgPackValue](crate::MsgPackValue)
+    SerdeMsgPackValue(MsgPackValue),
     #[cfg(feature = "json")]
     /// see [Value](rocket::serde::json::Value)
     SerdeValue(serde::json::Value),
+    #[cfg(feature = "xml")]
+    /// see [SerdeXml](crate::SerdeXml)
+    SerdeXml(SerdeXml<&'static str>),
+    /// see [ServiceUnavailable](crate::ServiceUnavailable)
+    ServiceUnavailable(Box<ServiceUnavailable<RocketResponse>>),
+    #[cfg(feature = "secrets")]
+    /// see [SessionRedirect](crate::SessionRedirect)
+    SessionRedirect(Box<SessionRedirect>),
+    /// see [Sitemap](crate::Sitemap)
+    Sitemap(Sitemap),
 
     /// see [slice](std::slice)
     StaticSlice(&'static [u8]),
@@ -137,21 +495,163 @@ pub enum RocketResponse {
     String(String),
     /// see [Status](rocket::http::Status)
     Status(Status),
+    /// see [StatusPage](crate::StatusPage)
+    StatusPage(StatusPage),
+    /// see [StreamedFile](crate::StreamedFile)
+    StreamedFile(Box<StreamedFile>),
 
     #[cfg(any(feature = "templates-tera", feature = "templates-handlebars"))]
     /// see [Template](rocket_dyn_templates::Template)
-    Template(Template),
+    Template(Box<Template>),
+    /// see [TextBody](crate::TextBody)
+    TextBody(Box<TextBody>),
+    #[cfg(feature = "timing")]
+    /// see [Timed](crate::Timed)
+    Timed(Box<Timed<RocketResponse>>),
 
     /// see [File](rocket::tokio::fs::File)
-    TokioFile(tokio::fs::File),
+    TokioFile(Box<tokio::fs::File>),
+    /// see [TooManyRequests](crate::TooManyRequests)
+    TooManyRequests(Box<TooManyRequests<RocketResponse>>),
+    /// bytes with the content type, see [typed](Self::typed())
+    Typed(Box<ContentType>, Vec<u8>),
+    /// text with the content type, see [typed_str](Self::typed_str())
+    TypedStr(Box<ContentType>, Cow<'static, str>),
     /// see [Unauthorized](rocket::response::status::Unauthorized)
     Unauthorized(Unauthorized<&'static str>),
     /// see [Vec](std::vec::Vec)
     Vec(Vec<u8>),
+    /// see [WithCache](crate::WithCache)
+    WithCache(WithCache<RocketResponse>),
+    /// see [WithCookies](crate::WithCookies)
+    WithCookies(WithCookies<RocketResponse>),
+    /// see [WithCors](crate::WithCors)
+    WithCors(Box<WithCors<RocketResponse>>),
+    /// see [WithCost](crate::WithCost)
+    WithCost(WithCost<RocketResponse>),
     /// see [Xml](rocket::response::content::RawXml)
     Xml(RawXml<&'static str>),
 }
 
+impl<'r, 'o: 'r> Responder<'r, 'o> for RocketResponse {
+    #[allow(deprecated, clippy::let_and_return)]
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let variant = self.variant_name();
+        let res = match self {
+            Self::Absolute(r) => (*r).respond_to(req),
+            Self::Accepted(r) => r.respond_to(req),
+            Self::AcceptedJob(r) => (*r).respond_to(req),
+            #[cfg(feature = "oauth")]
+            Self::AuthRedirect(r) => (*r).respond_to(req),
+            Self::BadRequest(r) => r.respond_to(req),
+            Self::BuildError(r) => (*r).respond_to(req),
+            Self::Cached(r) => r.respond_to(req),
+            Self::CacheEntry(r) => (*r).respond_to(req),
+            Self::Canonical(r) => (*r).respond_to(req),
+            #[cfg(feature = "chaos")]
+            Self::ChaosWrapped(r) => (*r).respond_to(req),
+            #[cfg(feature = "process")]
+            Self::CommandOutput(r) => (*r).respond_to(req),
+            #[cfg(feature = "compress")]
+            Self::Compressed(r) => (*r).respond_to(req),
+            Self::Conflict(r) => r.respond_to(req),
+            Self::Created(r) => r.respond_to(req),
+            Self::CreatedResource(r) => (*r).respond_to(req),
+            Self::Css(r) => r.respond_to(req),
+            Self::Custom(r) => (*r).respond_to(req),
+            #[cfg(feature = "db")]
+            Self::DbError(r) => (*r).respond_to(req),
+            #[cfg(feature = "json")]
+            Self::DeltaCollection(r) => (*r).respond_to(req),
+            Self::Download(r) => (*r).respond_to(req),
+            Self::Dyn(r) => r.respond_to(req),
+            #[cfg(feature = "json")]
+            Self::ExtensionNegotiated(r) => (*r).respond_to(req),
+            Self::File(r) => r.respond_to(req),
+            Self::Flash(r) => (*r).respond_to(req),
+            Self::FlashMsg(r) => (*r).respond_to(req),
+            Self::Forbidden(r) => r.respond_to(req),
+            #[cfg(feature = "graphql")]
+            Self::GraphQL(r) => (*r).respond_to(req),
+            Self::Hardened(r) => (*r).respond_to(req),
+            Self::HeadAware(r) => (*r).respond_to(req),
+            #[cfg(feature = "json")]
+            Self::HealthReport(r) => (*r).respond_to(req),
+            Self::Hooked(r) => (*r).respond_to(req),
+            Self::Html(r) => r.respond_to(req),
+            Self::JavaScript(r) => r.respond_to(req),
+            Self::Json(r) => r.respond_to(req),
+            Self::Lazy(r) => r.respond_to(req),
+            Self::Localized(r) => (*r).respond_to(req),
+            Self::MsgPack(r) => r.respond_to(req),
+            Self::Multipart(r) => (*r).respond_to(req),
+            Self::NamedFiled(r) => (*r).respond_to(req),
+            #[cfg(feature = "json")]
+            Self::Negotiated(r) => r.respond_to(req),
+            Self::NotFound(r) => r.respond_to(req),
+            Self::NoContent(r) => r.respond_to(req),
+            #[cfg(feature = "json")]
+            Self::Paginated(r) => (*r).respond_to(req),
+            Self::Plain(r) => r.respond_to(req),
+            #[cfg(feature = "signed-files")]
+            Self::ProtectedFile(r) => (*r).respond_to(req),
+            Self::PurgeResult(r) => (*r).respond_to(req),
+            Self::QuotaHeaders(r) => r.respond_to(req),
+            Self::RangedFile(r) => (*r).respond_to(req),
+            Self::Redirect(r) => (*r).respond_to(req),
+            Self::RocketError(r) => r.respond_to(req),
+            #[cfg(all(feature = "db-sqlx", feature = "json"))]
+            Self::RowStream(r) => (*r).respond_to(req),
+            #[cfg(feature = "bincode")]
+            Self::SerdeBincode(r) => r.respond_to(req),
+            #[cfg(feature = "cbor")]
+            Self::SerdeCbor(r) => r.respond_to(req),
+            #[cfg(feature = "json")]
+            Self::SerdeJson(r) => body::respond_json(&r.0, req),
+            #[cfg(feature = "msgpack")]
+            Self::SerdeMsgPack(r) => body::respond_msgpack(&r.0, req),
+            #[cfg(feature = "msgpack")]
+            Self::SerdeMsgPackValue(r) => r.respond_to(req),
+            #[cfg(feature = "json")]
+            Self::SerdeValue(r) => body::respond_json(&r, req),
+            #[cfg(feature = "xml")]
+            Self::SerdeXml(r) => r.respond_to(req),
+            Self::ServiceUnavailable(r) => (*r).respond_to(req),
+            #[cfg(feature = "secrets")]
+            Self::SessionRedirect(r) => (*r).respond_to(req),
+            Self::Sitemap(r) => r.respond_to(req),
+            Self::StaticSlice(r) => r.respond_to(req),
+            Self::StaticStr(r) => r.respond_to(req),
+            Self::String(r) => r.respond_to(req),
+            Self::Status(r) => r.respond_to(req),
+            Self::StatusPage(r) => r.respond_to(req),
+            Self::StreamedFile(r) => (*r).respond_to(req),
+            #[cfg(any(feature = "templates-tera", feature = "templates-handlebars"))]
+            Self::Template(r) => (*r).respond_to(req),
+            Self::TextBody(r) => (*r).respond_to(req),
+            #[cfg(feature = "timing")]
+            Self::Timed(r) => (*r).respond_to(req),
+            Self::TokioFile(r) => (*r).respond_to(req),
+            Self::TooManyRequests(r) => (*r).respond_to(req),
+            Self::Typed(content_type, body) => (*content_type, body).respond_to(req),
+            Self::TypedStr(content_type, body) => (*content_type, body).respond_to(req),
+            Self::Unauthorized(r) => r.respond_to(req),
+            Self::Vec(r) => r.respond_to(req),
+            Self::WithCache(r) => r.respond_to(req),
+            Self::WithCookies(r) => r.respond_to(req),
+            Self::WithCors(r) => (*r).respond_to(req),
+            Self::WithCost(r) => r.respond_to(req),
+            Self::Xml(r) => r.respond_to(req),
+        };
+        if let Some(stats) = req.rocket().state::<UsageStats>() {
+            stats.record(variant);
+        }
+        #[cfg(feature = "tracing")]
+        trace::responded(variant, &res);
+        res
+    }
+}
+
 /// The non-generic and generic [Responses](rocket::response::Response) with a single type.
 ///
 /// ## Example usage
@@ -173,55 +673,160 @@ pub enum RocketResponse {
 ///     }
 /// }
 /// ```
-#[derive(Responder)]
+#[non_exhaustive]
 pub enum RocketResponseGeneric<T>
 where
     T: Serialize,
 {
+    /// see [Absolute](crate::Absolute)
+    Absolute(Box<Absolute<RocketResponseGeneric<T>>>),
     /// see [rocket::response::status::Accepted]
     Accepted(Accepted<T>),
+    /// see [AcceptedJob](crate::AcceptedJob)
+    AcceptedJob(Box<AcceptedJob<RocketResponseGeneric<T>>>),
+    #[cfg(feature = "oauth")]
+    /// see [AuthRedirect](crate::AuthRedirect)
+    AuthRedirect(Box<AuthRedirect>),
     /// see [rocket::response::status::BadRequest]
     BadRequest(BadRequest<T>),
+    /// see [BuildError](crate::BuildError)
+    BuildError(Box<BuildError>),
+    /// see [Cached](crate::Cached)
+    Cached(Cached<RocketResponseGeneric<T>>),
+    /// see [CacheEntry](crate::CacheEntry)
+    CacheEntry(Box<CacheEntry>),
+    /// see [Canonical](crate::Canonical)
+    Canonical(Box<Canonical<RocketResponseGeneric<T>>>),
+    #[cfg(feature = "chaos")]
+    /// see [ChaosWrapped](crate::ChaosWrapped)
+    ChaosWrapped(Box<ChaosWrapped<RocketResponseGeneric<T>>>),
+    #[cfg(feature = "process")]
+    /// see [CommandOutput](crate::CommandOutput)
+    CommandOutput(Box<CommandOutput>),
+    #[cfg(feature = "compress")]
+    /// see [Compressed](crate::Compressed)
+    Compressed(Box<Compressed<RocketResponseGeneric<T>>>),
     /// see [rocket::response::status::Conflict]
     Conflict(Conflict<T>),
     /// see [rocket::response::status::Created]
     Created(Created<T>),
+    /// see [CreatedResource](crate::CreatedResource)
+    CreatedResource(Box<CreatedResource<RocketResponseGeneric<T>>>),
     /// see [rocket::response::content::RawCss]
     Css(RawCss<T>),
+    /// see [rocket::response::status::Custom]
+    Custom(Box<Custom<RocketResponseGeneric<T>>>),
+    #[cfg(feature = "db")]
+    /// see [DbError](crate::DbError)
+    DbError(Box<DbError>),
+    #[cfg(feature = "json")]
+    /// see [DeltaCollection](crate::DeltaCollection)
+    DeltaCollection(Box<DeltaCollection<T>>),
+    /// see [Download](crate::Download)
+    Download(Box<Download>),
+    /// see [Dyn](crate::Dyn)
+    Dyn(Dyn),
+    #[cfg(feature = "json")]
+    /// see [ExtensionNegotiated](crate::ExtensionNegotiated)
+    ExtensionNegotiated(Box<ExtensionNegotiated<T>>),
     /// see [File]
     File(File),
     /// see [rocket::response::Flash]
-    Flash(Flash<T>),
+    Flash(Box<Flash<T>>),
     /// see [rocket::response::status::Forbidden]
     Forbidden(Forbidden<T>),
+    #[cfg(feature = "graphql")]
+    /// see [GraphQLResponse](async_graphql_rocket::GraphQLResponse), a GraphQL response or batch
+    /// response
+    GraphQL(Box<GraphQLResponse>),
+    #[cfg(feature = "hal")]
+    /// see [Hal](crate::Hal)
+    Hal(Box<Hal<T>>),
+    /// see [Hardened](crate::Hardened)
+    Hardened(Box<Hardened<RocketResponseGeneric<T>>>),
+    /// see [HeadAware](crate::HeadAware)
+    HeadAware(Box<HeadAware<RocketResponseGeneric<T>>>),
+    #[cfg(feature = "json")]
+    /// see [HealthReport](crate::HealthReport)
+    HealthReport(Box<HealthReport>),
+    /// see [Hooked](crate::Hooked)
+    Hooked(Box<Hooked<RocketResponseGeneric<T>>>),
     /// see [rocket::response::content::RawHtml]
     Html(RawHtml<T>),
     /// see [rocket::response::content::RawJavaScript]
     JavaScript(RawJavaScript<T>),
     /// see [rocket::response::content::RawJson]
     Json(RawJson<T>),
+    #[cfg(feature = "jsonapi")]
+    /// see [JsonApi](crate::JsonApi)
+    JsonApi(Box<JsonApi<T>>),
+    /// see [Lazy](crate::Lazy)
+    Lazy(Lazy<LazyFn<RocketResponseGeneric<T>>>),
+    /// see [Localized](crate::Localized)
+    Localized(Box<Localized<RocketResponseGeneric<T>>>),
     /// see [rocket::response::content::RawMsgPack]
     MsgPack(RawMsgPack<T>),
+    /// see [Multipart](crate::Multipart)
+    Multipart(Box<Multipart>),
     /// see [NamedFile](rocket::fs::NamedFile)
-    NamedFiled(NamedFile),
+    NamedFiled(Box<NamedFile>),
+    #[cfg(feature = "json")]
+    /// see [Negotiated](crate::Negotiated)
+    Negotiated(Negotiated<T>),
     /// see [rocket::response::status::NotFound]
     NotFound(NotFound<T>),
     /// see [NoContent](rocket::response::status::NoContent)
     NoContent(NoContent),
+    #[cfg(feature = "json")]
+    /// see [Paginated](crate::Paginated)
+    Paginated(Box<Paginated<T>>),
     /// see [rocket::response::content::RawText]
     Plain(RawText<T>),
+    #[cfg(feature = "signed-files")]
+    /// see [ProtectedFile](crate::ProtectedFile)
+    ProtectedFile(Box<ProtectedFile>),
+    /// see [PurgeResult](crate::PurgeResult)
+    PurgeResult(Box<PurgeResult>),
+    /// see [QuotaHeaders](crate::QuotaHeaders)
+    QuotaHeaders(QuotaHeaders<RocketResponseGeneric<T>>),
+    /// see [RangedFile](crate::RangedFile)
+    RangedFile(Box<RangedFile>),
     /// see [Redirect](rocket::response::Redirect)
-    Redirect(Redirect),
+    Redirect(Box<Redirect>),
+    /// see [RocketError](crate::RocketError)
+    RocketError(RocketError),
+    #[cfg(all(feature = "db-sqlx", feature = "json"))]
+    /// see [RowStream](crate::RowStream)
+    RowStream(Box<RowStream>),
 
+    #[cfg(feature = "bincode")]
+    /// see [Bincode](crate::Bincode)
+    SerdeBincode(Bincode<T>),
+    #[cfg(feature = "cbor")]
+    /// see [Cbor](crate::Cbor)
+    SerdeCbor(Cbor<T>),
     #[cfg(feature = "json")]
     /// see [rocket::serde::json::Json]
     SerdeJson(serde::json::Json<T>),
     #[cfg(feature = "msgpack")]
     /// see [rocket::serde::msgpack::MsgPack]
     SerdeMsgPack(serde::msgpack::MsgPack<T>),
+    #[cfg(feature = "msgpack")]
+    /// see [MsgPackValue](crate::MsgPackValue)
+    SerdeMsgPackValue(MsgPackValue),
     #[cfg(feature = "json")]
     /// see [Value](rocket::serde::json::Value)
     SerdeValue(serde::json::Value),
+    #[cfg(feature = "xml")]
+    /// see [SerdeXml](crate::SerdeXml)
+    SerdeXml(SerdeXml<T>),
+    /// see [ServiceUnavailable](crate::ServiceUnavailable)
+    ServiceUnavailable(Box<ServiceUnavailable<RocketResponseGeneric<T>>>),
+    #[cfg(feature = "secrets")]
+    /// see [SessionRedirect](crate::SessionRedirect)
+    SessionRedirect(Box<SessionRedirect>),
+    /// see [Sitemap](crate::Sitemap)
+    Sitemap(Sitemap),
 
     /// see [slice](std::slice)
     StaticSlice(&'static [u8]),
@@ -231,21 +836,169 @@ where
     String(String),
     /// see [Status](rocket::http::Status)
     Status(Status),
+    /// see [StatusPage](crate::StatusPage)
+    StatusPage(StatusPage),
+    /// see [StreamedFile](crate::StreamedFile)
+    StreamedFile(Box<StreamedFile>),
 
     #[cfg(any(feature = "templates-tera", feature = "templates-handlebars"))]
     /// see [Template](rocket_dyn_templates::Template)
-    Template(Template),
+    Template(Box<Template>),
+    /// see [TextBody](crate::TextBody)
+    TextBody(Box<TextBody>),
+    #[cfg(feature = "timing")]
+    /// see [Timed](crate::Timed)
+    Timed(Box<Timed<RocketResponseGeneric<T>>>),
 
     /// see [File](rocket::tokio::fs::File)
-    TokioFile(tokio::fs::File),
+    TokioFile(Box<tokio::fs::File>),
+    /// see [TooManyRequests](crate::TooManyRequests)
+    TooManyRequests(Box<TooManyRequests<RocketResponseGeneric<T>>>),
+    /// bytes with the content type, see [typed](Self::typed())
+    Typed(Box<ContentType>, Vec<u8>),
+    /// text with the content type, see [typed_str](Self::typed_str())
+    TypedStr(Box<ContentType>, Cow<'static, str>),
     /// see [Unauthorized](rocket::response::status::Unauthorized)
     Unauthorized(Unauthorized<T>),
     /// see [Vec](std::vec::Vec)
     Vec(Vec<u8>),
+    /// see [WithCache](crate::WithCache)
+    WithCache(WithCache<RocketResponseGeneric<T>>),
+    /// see [WithCookies](crate::WithCookies)
+    WithCookies(WithCookies<RocketResponseGeneric<T>>),
+    /// see [WithCors](crate::WithCors)
+    WithCors(Box<WithCors<RocketResponseGeneric<T>>>),
+    /// see [WithCost](crate::WithCost)
+    WithCost(WithCost<RocketResponseGeneric<T>>),
     /// see [Xml](rocket::response::content::RawXml)
     Xml(RawXml<T>),
 }
 
+impl<'r, 'o: 'r, T> Responder<'r, 'o> for RocketResponseGeneric<T>
+where
+    T: Serialize + Responder<'r, 'o>,
+{
+    #[allow(clippy::let_and_return)]
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let variant = self.variant_name();
+        let res = match self {
+            Self::Absolute(r) => (*r).respond_to(req),
+            Self::Accepted(r) => r.respond_to(req),
+            Self::AcceptedJob(r) => (*r).respond_to(req),
+            #[cfg(feature = "oauth")]
+            Self::AuthRedirect(r) => (*r).respond_to(req),
+            Self::BadRequest(r) => r.respond_to(req),
+            Self::BuildError(r) => (*r).respond_to(req),
+            Self::Cached(r) => r.respond_to(req),
+            Self::CacheEntry(r) => (*r).respond_to(req),
+            Self::Canonical(r) => (*r).respond_to(req),
+            #[cfg(feature = "chaos")]
+            Self::ChaosWrapped(r) => (*r).respond_to(req),
+            #[cfg(feature = "process")]
+            Self::CommandOutput(r) => (*r).respond_to(req),
+            #[cfg(feature = "compress")]
+            Self::Compressed(r) => (*r).respond_to(req),
+            Self::Conflict(r) => r.respond_to(req),
+            Self::Created(r) => r.respond_to(req),
+            Self::CreatedResource(r) => (*r).respond_to(req),
+            Self::Css(r) => r.respond_to(req),
+            Self::Custom(r) => (*r).respond_to(req),
+            #[cfg(feature = "db")]
+            Self::DbError(r) => (*r).respond_to(req),
+            #[cfg(feature = "json")]
+            Self::DeltaCollection(r) => (*r).respond_to(req),
+            Self::Download(r) => (*r).respond_to(req),
+            Self::Dyn(r) => r.respond_to(req),
+            #[cfg(feature = "json")]
+            Self::ExtensionNegotiated(r) => (*r).respond_to(req),
+            Self::File(r) => r.respond_to(req),
+            Self::Flash(r) => (*r).respond_to(req),
+            Self::Forbidden(r) => r.respond_to(req),
+            #[cfg(feature = "graphql")]
+            Self::GraphQL(r) => (*r).respond_to(req),
+            #[cfg(feature = "hal")]
+            Self::Hal(r) => (*r).respond_to(req),
+            Self::Hardened(r) => (*r).respond_to(req),
+            Self::HeadAware(r) => (*r).respond_to(req),
+            #[cfg(feature = "json")]
+            Self::HealthReport(r) => (*r).respond_to(req),
+            Self::Hooked(r) => (*r).respond_to(req),
+            Self::Html(r) => r.respond_to(req),
+            Self::JavaScript(r) => r.respond_to(req),
+            Self::Json(r) => r.respond_to(req),
+            #[cfg(feature = "jsonapi")]
+            Self::JsonApi(r) => (*r).respond_to(req),
+            Self::Lazy(r) => r.respond_to(req),
+            Self::Localized(r) => (*r).respond_to(req),
+            Self::MsgPack(r) => r.respond_to(req),
+            Self::Multipart(r) => (*r).respond_to(req),
+            Self::NamedFiled(r) => (*r).respond_to(req),
+            #[cfg(feature = "json")]
+            Self::Negotiated(r) => r.respond_to(req),
+            Self::NotFound(r) => r.respond_to(req),
+            Self::NoContent(r) => r.respond_to(req),
+            #[cfg(feature = "json")]
+            Self::Paginated(r) => (*r).respond_to(req),
+            Self::Plain(r) => r.respond_to(req),
+            #[cfg(feature = "signed-files")]
+            Self::ProtectedFile(r) => (*r).respond_to(req),
+            Self::PurgeResult(r) => (*r).respond_to(req),
+            Self::QuotaHeaders(r) => r.respond_to(req),
+            Self::RangedFile(r) => (*r).respond_to(req),
+            Self::Redirect(r) => (*r).respond_to(req),
+            Self::RocketError(r) => r.respond_to(req),
+            #[cfg(all(feature = "db-sqlx", feature = "json"))]
+            Self::RowStream(r) => (*r).respond_to(req),
+            #[cfg(feature = "bincode")]
+            Self::SerdeBincode(r) => r.respond_to(req),
+            #[cfg(feature = "cbor")]
+            Self::SerdeCbor(r) => r.respond_to(req),
+            #[cfg(feature = "json")]
+            Self::SerdeJson(r) => body::respond_json(&r.0, req),
+            #[cfg(feature = "msgpack")]
+            Self::SerdeMsgPack(r) => body::respond_msgpack(&r.0, req),
+            #[cfg(feature = "msgpack")]
+            Self::SerdeMsgPackValue(r) => r.respond_to(req),
+            #[cfg(feature = "json")]
+            Self::SerdeValue(r) => body::respond_json(&r, req),
+            #[cfg(feature = "xml")]
+            Self::SerdeXml(r) => r.respond_to(req),
+            Self::ServiceUnavailable(r) => (*r).respond_to(req),
+            #[cfg(feature = "secrets")]
+            Self::SessionRedirect(r) => (*r).respond_to(req),
+            Self::Sitemap(r) => r.respond_to(req),
+            Self::StaticSlice(r) => r.respond_to(req),
+            Self::StaticStr(r) => r.respond_to(req),
+            Self::String(r) => r.respond_to(req),
+            Self::Status(r) => r.respond_to(req),
+            Self::StatusPage(r) => r.respond_to(req),
+            Self::StreamedFile(r) => (*r).respond_to(req),
+            #[cfg(any(feature = "templates-tera", feature = "templates-handlebars"))]
+            Self::Template(r) => (*r).respond_to(req),
+            Self::TextBody(r) => (*r).respond_to(req),
+            #[cfg(feature = "timing")]
+            Self::Timed(r) => (*r).respond_to(req),
+            Self::TokioFile(r) => (*r).respond_to(req),
+            Self::TooManyRequests(r) => (*r).respond_to(req),
+            Self::Typed(content_type, body) => (*content_type, body).respond_to(req),
+            Self::TypedStr(content_type, body) => (*content_type, body).respond_to(req),
+            Self::Unauthorized(r) => r.respond_to(req),
+            Self::Vec(r) => r.respond_to(req),
+            Self::WithCache(r) => r.respond_to(req),
+            Self::WithCookies(r) => r.respond_to(req),
+            Self::WithCors(r) => (*r).respond_to(req),
+            Self::WithCost(r) => r.respond_to(req),
+            Self::Xml(r) => r.respond_to(req),
+        };
+        if let Some(stats) = req.rocket().state::<UsageStats>() {
+            stats.record(variant);
+        }
+        #[cfg(feature = "tracing")]
+        trace::responded(variant, &res);
+        res
+    }
+}
+
 /// The non-generic and generic [Responses](rocket::response::Response) with 2 types.
 ///
 /// ## Example usage
@@ -259,10 +1012,7 @@ where
 ///     id: usize,
 /// ) -> RocketResponse<&'static str, Redirect> {
 ///     match id {
-///         0 => RocketResponse::Flash(response::Flash::error(
-///           Redirect::to("/"),
-///             format!("Invalid id {}", id),
-///         )),
+///         0 => RocketResponse::flash_error(Redirect::to("/"), format!("Invalid id {}", id)),
 ///         1 => RocketResponse::Unauthorized(status::Unauthorized(Some(
 ///             "admin need authentication",
 ///         ))),
@@ -272,56 +1022,163 @@ where
 ///     }
 /// }
 /// ```
-#[derive(Responder)]
+#[non_exhaustive]
 pub enum RocketResponseGeneric2<T, U>
 where
     T: Serialize,
 {
+    /// see [Absolute](crate::Absolute)
+    Absolute(Box<Absolute<RocketResponseGeneric2<T, U>>>),
     /// see [rocket::response::status::Accepted]
     Accepted(Accepted<T>),
+    /// see [AcceptedJob](crate::AcceptedJob)
+    AcceptedJob(Box<AcceptedJob<RocketResponseGeneric2<T, U>>>),
+    #[cfg(feature = "oauth")]
+    /// see [AuthRedirect](crate::AuthRedirect)
+    AuthRedirect(Box<AuthRedirect>),
     /// see [rocket::response::status::BadRequest]
     BadRequest(BadRequest<T>),
+    /// see [BuildError](crate::BuildError)
+    BuildError(Box<BuildError>),
+    /// see [Cached](crate::Cached)
+    Cached(Cached<RocketResponseGeneric2<T, U>>),
+    /// see [CacheEntry](crate::CacheEntry)
+    CacheEntry(Box<CacheEntry>),
+    /// see [Canonical](crate::Canonical)
+    Canonical(Box<Canonical<RocketResponseGeneric2<T, U>>>),
+    #[cfg(feature = "chaos")]
+    /// see [ChaosWrapped](crate::ChaosWrapped)
+    ChaosWrapped(Box<ChaosWrapped<RocketResponseGeneric2<T, U>>>),
+    #[cfg(feature = "process")]
+    /// see [CommandOutput](crate::CommandOutput)
+    CommandOutput(Box<CommandOutput>),
+    #[cfg(feature = "compress")]
+    /// see [Compressed](crate::Compressed)
+    Compressed(Box<Compressed<RocketResponseGeneric2<T, U>>>),
     /// see [rocket::response::status::Conflict]
     Conflict(Conflict<T>),
     /// see [rocket::response::status::Created]
     Created(Created<T>),
+    /// see [CreatedResource](crate::CreatedResource)
+    CreatedResource(Box<CreatedResource<RocketResponseGeneric2<T, U>>>),
     /// see [rocket::response::content::RawCss]
     Css(RawCss<T>),
+    /// see [rocket::response::status::Custom]
+    Custom(Box<Custom<RocketResponseGeneric2<T, U>>>),
+    #[cfg(feature = "db")]
+    /// see [DbError](crate::DbError)
+    DbError(Box<DbError>),
+    #[cfg(feature = "json")]
+    /// see [DeltaCollection](crate::DeltaCollection)
+    DeltaCollection(Box<DeltaCollection<T>>),
+    /// see [Download](crate::Download)
+    Download(Box<Download>),
+    /// see [Dyn](crate::Dyn)
+    Dyn(Dyn),
+    #[cfg(feature = "json")]
+    /// see [ExtensionNegotiated](crate::ExtensionNegotiated)
+    ExtensionNegotiated(Box<ExtensionNegotiated<T>>),
     /// see [File]
     File(File),
     /// with generic type U  
     /// see [rocket::response::Flash]
-    Flash(Flash<U>),
+    Flash(Box<Flash<U>>),
+    /// see [rocket::response::Flash] with a [RocketResponse] as responder
+    FlashResponse(Box<Flash<RocketResponse>>),
     /// see [rocket::response::status::Forbidden]
     Forbidden(Forbidden<T>),
+    #[cfg(feature = "graphql")]
+    /// see [GraphQLResponse](async_graphql_rocket::GraphQLResponse), a GraphQL response or batch
+    /// response
+    GraphQL(Box<GraphQLResponse>),
+    #[cfg(feature = "hal")]
+    /// see [Hal](crate::Hal)
+    Hal(Box<Hal<T>>),
+    /// see [Hardened](crate::Hardened)
+    Hardened(Box<Hardened<RocketResponseGeneric2<T, U>>>),
+    /// see [HeadAware](crate::HeadAware)
+    HeadAware(Box<HeadAware<RocketResponseGeneric2<T, U>>>),
+    #[cfg(feature = "json")]
+    /// see [HealthReport](crate::HealthReport)
+    HealthReport(Box<HealthReport>),
+    /// see [Hooked](crate::Hooked)
+    Hooked(Box<Hooked<RocketResponseGeneric2<T, U>>>),
     /// see [rocket::response::content::RawHtml]
     Html(RawHtml<T>),
     /// see [rocket::response::content::RawJavaScript]
     JavaScript(RawJavaScript<T>),
     /// see [rocket::response::content::RawJson]
     Json(RawJson<T>),
+    #[cfg(feature = "jsonapi")]
+    /// see [JsonApi](crate::JsonApi)
+    JsonApi(Box<JsonApi<T>>),
+    /// see [Lazy](crate::Lazy)
+    Lazy(Lazy<LazyFn<RocketResponseGeneric2<T, U>>>),
+    /// see [Localized](crate::Localized)
+    Localized(Box<Localized<RocketResponseGeneric2<T, U>>>),
     /// see [rocket::response::content::RawMsgPack]
     MsgPack(RawMsgPack<T>),
+    /// see [Multipart](crate::Multipart)
+    Multipart(Box<Multipart>),
     /// see [NamedFile](rocket::fs::NamedFile)
-    NamedFiled(NamedFile),
+    NamedFiled(Box<NamedFile>),
+    #[cfg(feature = "json")]
+    /// see [Negotiated](crate::Negotiated)
+    Negotiated(Negotiated<T>),
     /// see [NoContent](rocket::response::status::NoContent)
     NotFound(NotFound<T>),
     /// see [rocket::response::status::NoContent]
     NoContent(NoContent),
+    #[cfg(feature = "json")]
+    /// see [Paginated](crate::Paginated)
+    Paginated(Box<Paginated<T>>),
     /// see [rocket::response::content::RawText]
     Plain(RawText<T>),
+    #[cfg(feature = "signed-files")]
+    /// see [ProtectedFile](crate::ProtectedFile)
+    ProtectedFile(Box<ProtectedFile>),
+    /// see [PurgeResult](crate::PurgeResult)
+    PurgeResult(Box<PurgeResult>),
+    /// see [QuotaHeaders](crate::QuotaHeaders)
+    QuotaHeaders(QuotaHeaders<RocketResponseGeneric2<T, U>>),
+    /// see [RangedFile](crate::RangedFile)
+    RangedFile(Box<RangedFile>),
     /// see [rocket::response::Redirect]
-    Redirect(Redirect),
+    Redirect(Box<Redirect>),
+    /// see [RocketError](crate::RocketError)
+    RocketError(RocketError),
+    #[cfg(all(feature = "db-sqlx", feature = "json"))]
+    /// see [RowStream](crate::RowStream)
+    RowStream(Box<RowStream>),
 
+    #[cfg(feature = "bincode")]
+    /// see [Bincode](crate::Bincode)
+    SerdeBincode(Bincode<T>),
+    #[cfg(feature = "cbor")]
+    /// see [Cbor](crate::Cbor)
+    SerdeCbor(Cbor<T>),
     #[cfg(feature = "json")]
     /// see [rocket::serde::json::Json]
     SerdeJson(serde::json::Json<T>),
     #[cfg(feature = "msgpack")]
     /// see [rocket::serde::msgpack::MsgPack]
     SerdeMsgPack(serde::msgpack::MsgPack<T>),
+    #[cfg(feature = "msgpack")]
+    /// see [MsgPackValue](crate::MsgPackValue)
+    SerdeMsgPackValue(MsgPackValue),
     #[cfg(feature = "json")]
     /// see [rocket::serde::json::Value]
     SerdeValue(serde::json::Value),
+    #[cfg(feature = "xml")]
+    /// see [SerdeXml](crate::SerdeXml)
+    SerdeXml(SerdeXml<T>),
+    /// see [ServiceUnavailable](crate::ServiceUnavailable)
+    ServiceUnavailable(Box<ServiceUnavailable<RocketResponseGeneric2<T, U>>>),
+    #[cfg(feature = "secrets")]
+    /// see [SessionRedirect](crate::SessionRedirect)
+    SessionRedirect(Box<SessionRedirect>),
+    /// see [Sitemap](crate::Sitemap)
+    Sitemap(Sitemap),
 
     /// see [slice](std::slice)
     StaticSlice(&'static [u8]),
@@ -331,22 +1188,562 @@ where
     String(String),
     /// see [Status](rocket::http::Status)
     Status(Status),
+    /// see [StatusPage](crate::StatusPage)
+    StatusPage(StatusPage),
+    /// see [StreamedFile](crate::StreamedFile)
+    StreamedFile(Box<StreamedFile>),
 
     #[cfg(any(feature = "templates-tera", feature = "templates-handlebars"))]
     /// see [Template](rocket_dyn_templates::Template)
-    Template(Template),
+    Template(Box<Template>),
+    /// see [TextBody](crate::TextBody)
+    TextBody(Box<TextBody>),
+    #[cfg(feature = "timing")]
+    /// see [Timed](crate::Timed)
+    Timed(Box<Timed<RocketResponseGeneric2<T, U>>>),
 
     /// see [File](rocket::tokio::fs::File)
-    TokioFile(tokio::fs::File),
+    TokioFile(Box<tokio::fs::File>),
+    /// see [TooManyRequests](crate::TooManyRequests)
+    TooManyRequests(Box<TooManyRequests<RocketResponseGeneric2<T, U>>>),
+    /// bytes with the content type, see [typed](Self::typed())
+    Typed(Box<ContentType>, Vec<u8>),
+    /// text with the content type, see [typed_str](Self::typed_str())
+    TypedStr(Box<ContentType>, Cow<'static, str>),
     /// see [Unauthorized](rocket::response::status::Unauthorized)
     Unauthorized(Unauthorized<T>),
     /// see [Vec](std::vec::Vec)
     Vec(Vec<u8>),
+    /// see [WithCache](crate::WithCache)
+    WithCache(WithCache<RocketResponseGeneric2<T, U>>),
+    /// see [WithCookies](crate::WithCookies)
+    WithCookies(WithCookies<RocketResponseGeneric2<T, U>>),
+    /// see [WithCors](crate::WithCors)
+    WithCors(Box<WithCors<RocketResponseGeneric2<T, U>>>),
+    /// see [WithCost](crate::WithCost)
+    WithCost(WithCost<RocketResponseGeneric2<T, U>>),
     /// see [Xml](rocket::response::content::RawXml)
     Xml(RawXml<T>),
 }
 
+impl<'r, 'o: 'r, T, U> Responder<'r, 'o> for RocketResponseGeneric2<T, U>
+where
+    T: Serialize + Responder<'r, 'o>,
+    U: Responder<'r, 'o>,
+{
+    #[allow(clippy::let_and_return)]
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let variant = self.variant_name();
+        let res = match self {
+            Self::Absolute(r) => (*r).respond_to(req),
+            Self::Accepted(r) => r.respond_to(req),
+            Self::AcceptedJob(r) => (*r).respond_to(req),
+            #[cfg(feature = "oauth")]
+            Self::AuthRedirect(r) => (*r).respond_to(req),
+            Self::BadRequest(r) => r.respond_to(req),
+            Self::BuildError(r) => (*r).respond_to(req),
+            Self::Cached(r) => r.respond_to(req),
+            Self::CacheEntry(r) => (*r).respond_to(req),
+            Self::Canonical(r) => (*r).respond_to(req),
+            #[cfg(feature = "chaos")]
+            Self::ChaosWrapped(r) => (*r).respond_to(req),
+            #[cfg(feature = "process")]
+            Self::CommandOutput(r) => (*r).respond_to(req),
+            #[cfg(feature = "compress")]
+            Self::Compressed(r) => (*r).respond_to(req),
+            Self::Conflict(r) => r.respond_to(req),
+            Self::Created(r) => r.respond_to(req),
+            Self::CreatedResource(r) => (*r).respond_to(req),
+            Self::Css(r) => r.respond_to(req),
+            Self::Custom(r) => (*r).respond_to(req),
+            #[cfg(feature = "db")]
+            Self::DbError(r) => (*r).respond_to(req),
+            #[cfg(feature = "json")]
+            Self::DeltaCollection(r) => (*r).respond_to(req),
+            Self::Download(r) => (*r).respond_to(req),
+            Self::Dyn(r) => r.respond_to(req),
+            #[cfg(feature = "json")]
+            Self::ExtensionNegotiated(r) => (*r).respond_to(req),
+            Self::File(r) => r.respond_to(req),
+            Self::Flash(r) => (*r).respond_to(req),
+            Self::FlashResponse(r) => (*r).respond_to(req),
+            Self::Forbidden(r) => r.respond_to(req),
+            #[cfg(feature = "graphql")]
+            Self::GraphQL(r) => (*r).respond_to(req),
+            #[cfg(feature = "hal")]
+            Self::Hal(r) => (*r).respond_to(req),
+            Self::Hardened(r) => (*r).respond_to(req),
+            Self::HeadAware(r) => (*r).respond_to(req),
+            #[cfg(feature = "json")]
+            Self::HealthReport(r) => (*r).respond_to(req),
+            Self::Hooked(r) => (*r).respond_to(req),
+            Self::Html(r) => r.respond_to(req),
+            Self::JavaScript(r) => r.respond_to(req),
+            Self::Json(r) => r.respond_to(req),
+            #[cfg(feature = "jsonapi")]
+            Self::JsonApi(r) => (*r).respond_to(req),
+            Self::Lazy(r) => r.respond_to(req),
+            Self::Localized(r) => (*r).respond_to(req),
+            Self::MsgPack(r) => r.respond_to(req),
+            Self::Multipart(r) => (*r).respond_to(req),
+            Self::NamedFiled(r) => (*r).respond_to(req),
+            #[cfg(feature = "json")]
+            Self::Negotiated(r) => r.respond_to(req),
+            Self::NotFound(r) => r.respond_to(req),
+            Self::NoContent(r) => r.respond_to(req),
+            #[cfg(feature = "json")]
+            Self::Paginated(r) => (*r).respond_to(req),
+            Self::Plain(r) => r.respond_to(req),
+            #[cfg(feature = "signed-files")]
+            Self::ProtectedFile(r) => (*r).respond_to(req),
+            Self::PurgeResult(r) => (*r).respond_to(req),
+            Self::QuotaHeaders(r) => r.respond_to(req),
+            Self::RangedFile(r) => (*r).respond_to(req),
+            Self::Redirect(r) => (*r).respond_to(req),
+            Self::RocketError(r) => r.respond_to(req),
+            #[cfg(all(feature = "db-sqlx", feature = "json"))]
+            Self::RowStream(r) => (*r).respond_to(req),
+            #[cfg(feature = "bincode")]
+            Self::SerdeBincode(r) => r.respond_to(req),
+            #[cfg(feature = "cbor")]
+            Self::SerdeCbor(r) => r.respond_to(req),
+            #[cfg(feature = "json")]
+            Self::SerdeJson(r) => body::respond_json(&r.0, req),
+            #[cfg(feature = "msgpack")]
+            Self::SerdeMsgPack(r) => body::respond_msgpack(&r.0, req),
+            #[cfg(feature = "msgpack")]
+            Self::SerdeMsgPackValue(r) => r.respond_to(req),
+            #[cfg(feature = "json")]
+            Self::SerdeValue(r) => body::respond_json(&r, req),
+            #[cfg(feature = "xml")]
+            Self::SerdeXml(r) => r.respond_to(req),
+            Self::ServiceUnavailable(r) => (*r).respond_to(req),
+            #[cfg(feature = "secrets")]
+            Self::SessionRedirect(r) => (*r).respond_to(req),
+            Self::Sitemap(r) => r.respond_to(req),
+            Self::StaticSlice(r) => r.respond_to(req),
+            Self::StaticStr(r) => r.respond_to(req),
+            Self::String(r) => r.respond_to(req),
+            Self::Status(r) => r.respond_to(req),
+            Self::StatusPage(r) => r.respond_to(req),
+            Self::StreamedFile(r) => (*r).respond_to(req),
+            #[cfg(any(feature = "templates-tera", feature = "templates-handlebars"))]
+            Self::Template(r) => (*r).respond_to(req),
+            Self::TextBody(r) => (*r).respond_to(req),
+            #[cfg(feature = "timing")]
+            Self::Timed(r) => (*r).respond_to(req),
+            Self::TokioFile(r) => (*r).respond_to(req),
+            Self::TooManyRequests(r) => (*r).respond_to(req),
+            Self::Typed(content_type, body) => (*content_type, body).respond_to(req),
+            Self::TypedStr(content_type, body) => (*content_type, body).respond_to(req),
+            Self::Unauthorized(r) => r.respond_to(req),
+            Self::Vec(r) => r.respond_to(req),
+            Self::WithCache(r) => r.respond_to(req),
+            Self::WithCookies(r) => r.respond_to(req),
+            Self::WithCors(r) => (*r).respond_to(req),
+            Self::WithCost(r) => r.respond_to(req),
+            Self::Xml(r) => r.respond_to(req),
+        };
+        if let Some(stats) = req.rocket().state::<UsageStats>() {
+            stats.record(variant);
+        }
+        #[cfg(feature = "tracing")]
+        trace::responded(variant, &res);
+        res
+    }
+}
+
+/// The non-generic and generic [Responses](rocket::response::Response) with 3 types.
+///
+/// Other than [RocketResponseGeneric2] the content type `T` doesn't need to be [Serialize],
+/// because the serde variants use their own type `S`. It is available with the serde features.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::{get, response::{content::RawHtml, Redirect}, serde::Serialize};
+/// use rocket_response::RocketResponseGeneric3 as RocketResponse;
+///
+/// #[derive(Serialize)]
+/// #[serde(crate = "rocket::serde")]
+/// struct Item {
+///     id: usize,
+/// }
+///
+/// #[get("/<id>")]
+/// pub(crate) fn rocket_response_generic3(id: usize) -> RocketResponse<String, Item, Redirect> {
+///     match id {
+///         0 => RocketResponse::flash_error(Redirect::to("/"), format!("Invalid id {}", id)),
+///         _ => RocketResponse::Html(RawHtml(format!("<p>Item {}</p>", id))),
+///     }
+/// }
+/// ```
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+#[non_exhaustive]
+pub enum RocketResponseGeneric3<T, S, U>
+where
+    S: Serialize,
+{
+    /// see [Absolute](crate::Absolute)
+    Absolute(Box<Absolute<RocketResponseGeneric3<T, S, U>>>),
+    /// see [rocket::response::status::Accepted]
+    Accepted(Accepted<T>),
+    /// see [AcceptedJob](crate::AcceptedJob)
+    AcceptedJob(Box<AcceptedJob<RocketResponseGeneric3<T, S, U>>>),
+    #[cfg(feature = "oauth")]
+    /// see [AuthRedirect](crate::AuthRedirect)
+    AuthRedirect(Box<AuthRedirect>),
+    /// see [rocket::response::status::BadRequest]
+    BadRequest(BadRequest<T>),
+    /// see [BuildError](crate::BuildError)
+    BuildError(Box<BuildError>),
+    /// see [Cached](crate::Cached)
+    Cached(Cached<RocketResponseGeneric3<T, S, U>>),
+    /// see [CacheEntry](crate::CacheEntry)
+    CacheEntry(Box<CacheEntry>),
+    /// see [Canonical](crate::Canonical)
+    Canonical(Box<Canonical<RocketResponseGeneric3<T, S, U>>>),
+    #[cfg(feature = "chaos")]
+    /// see [ChaosWrapped](crate::ChaosWrapped)
+    ChaosWrapped(Box<ChaosWrapped<RocketResponseGeneric3<T, S, U>>>),
+    #[cfg(feature = "process")]
+    /// see [CommandOutput](crate::CommandOutput)
+    CommandOutput(Box<CommandOutput>),
+    #[cfg(feature = "compress")]
+    /// see [Compressed](crate::Compressed)
+    Compressed(Box<Compressed<RocketResponseGeneric3<T, S, U>>>),
+    /// see [rocket::response::status::Conflict]
+    Conflict(Conflict<T>),
+    /// see [rocket::response::status::Created]
+    Created(Created<T>),
+    /// see [CreatedResource](crate::CreatedResource)
+    CreatedResource(Box<CreatedResource<RocketResponseGeneric3<T, S, U>>>),
+    /// see [rocket::response::content::RawCss]
+    Css(RawCss<T>),
+    /// see [rocket::response::status::Custom]
+    Custom(Box<Custom<RocketResponseGeneric3<T, S, U>>>),
+    #[cfg(feature = "db")]
+    /// see [DbError](crate::DbError)
+    DbError(Box<DbError>),
+    #[cfg(feature = "json")]
+    /// see [DeltaCollection](crate::DeltaCollection)
+    DeltaCollection(Box<DeltaCollection<S>>),
+    /// see [Download](crate::Download)
+    Download(Box<Download>),
+    /// see [Dyn](crate::Dyn)
+    Dyn(Dyn),
+    #[cfg(feature = "json")]
+    /// see [ExtensionNegotiated](crate::ExtensionNegotiated)
+    ExtensionNegotiated(Box<ExtensionNegotiated<S>>),
+    /// see [File]
+    File(File),
+    /// with generic type U  
+    /// see [rocket::response::Flash]
+    Flash(Box<Flash<U>>),
+    /// see [rocket::response::Flash] with a [RocketResponse] as responder
+    FlashResponse(Box<Flash<RocketResponse>>),
+    /// see [rocket::response::status::Forbidden]
+    Forbidden(Forbidden<T>),
+    #[cfg(feature = "graphql")]
+    /// see [GraphQLResponse](async_graphql_rocket::GraphQLResponse), a GraphQL response or batch
+    /// response
+    GraphQL(Box<GraphQLResponse>),
+    #[cfg(feature = "hal")]
+    /// see [Hal](crate::Hal)
+    Hal(Box<Hal<S>>),
+    /// see [Hardened](crate::Hardened)
+    Hardened(Box<Hardened<RocketResponseGeneric3<T, S, U>>>),
+    /// see [HeadAware](crate::HeadAware)
+    HeadAware(Box<HeadAware<RocketResponseGeneric3<T, S, U>>>),
+    #[cfg(feature = "json")]
+    /// see [HealthReport](crate::HealthReport)
+    HealthReport(Box<HealthReport>),
+    /// see [Hooked](crate::Hooked)
+    Hooked(Box<Hooked<RocketResponseGeneric3<T, S, U>>>),
+    /// see [rocket::response::content::RawHtml]
+    Html(RawHtml<T>),
+    /// see [rocket::response::content::RawJavaScript]
+    JavaScript(RawJavaScript<T>),
+    /// see [rocket::response::content::RawJson]
+    Json(RawJson<T>),
+    #[cfg(feature = "jsonapi")]
+    /// see [JsonApi](crate::JsonApi)
+    JsonApi(Box<JsonApi<S>>),
+    /// see [Lazy](crate::Lazy)
+    Lazy(Lazy<LazyFn<RocketResponseGeneric3<T, S, U>>>),
+    /// see [Localized](crate::Localized)
+    Localized(Box<Localized<RocketResponseGeneric3<T, S, U>>>),
+    /// see [rocket::response::content::RawMsgPack]
+    MsgPack(RawMsgPack<T>),
+    /// see [Multipart](crate::Multipart)
+    Multipart(Box<Multipart>),
+    /// see [NamedFile](rocket::fs::NamedFile)
+    NamedFiled(Box<NamedFile>),
+    #[cfg(feature = "json")]
+    /// see [Negotiated](crate::Negotiated)
+    Negotiated(Negotiated<S>),
+    /// see [NoContent](rocket::response::status::NoContent)
+    NotFound(NotFound<T>),
+    /// see [rocket::response::status::NoContent]
+    NoContent(NoContent),
+    #[cfg(feature = "json")]
+    /// see [Paginated](crate::Paginated)
+    Paginated(Box<Paginated<S>>),
+    /// see [rocket::response::content::RawText]
+    Plain(RawText<T>),
+    #[cfg(feature = "signed-files")]
+    /// see [ProtectedFile](crate::ProtectedFile)
+    ProtectedFile(Box<ProtectedFile>),
+    /// see [PurgeResult](crate::PurgeResult)
+    PurgeResult(Box<PurgeResult>),
+    /// see [QuotaHeaders](crate::QuotaHeaders)
+    QuotaHeaders(QuotaHeaders<RocketResponseGeneric3<T, S, U>>),
+    /// see [RangedFile](crate::RangedFile)
+    RangedFile(Box<RangedFile>),
+    /// see [rocket::response::Redirect]
+    Redirect(Box<Redirect>),
+    /// see [RocketError](crate::RocketError)
+    RocketError(RocketError),
+    #[cfg(all(feature = "db-sqlx", feature = "json"))]
+    /// see [RowStream](crate::RowStream)
+    RowStream(Box<RowStream>),
+
+    #[cfg(feature = "bincode")]
+    /// see [Bincode](crate::Bincode)
+    SerdeBincode(Bincode<S>),
+    #[cfg(feature = "cbor")]
+    /// see [Cbor](crate::Cbor)
+    SerdeCbor(Cbor<S>),
+    #[cfg(feature = "json")]
+    /// see [rocket::serde::json::Json]
+    SerdeJson(serde::json::Json<S>),
+    #[cfg(feature = "msgpack")]
+    /// see [rocket::serde::msgpack::MsgPack]
+    SerdeMsgPack(serde::msgpack::MsgPack<S>),
+    #[cfg(feature = "msgpack")]
+    /// see [MsgPackValue](crate::MsgPackValue)
+    SerdeMsgPackValue(MsgPackValue),
+    #[cfg(feature = "json")]
+    /// see [rocket::serde::json::Value]
+    SerdeValue(serde::json::Value),
+    #[cfg(feature = "xml")]
+    /// see [SerdeXml](crate::SerdeXml)
+    SerdeXml(SerdeXml<S>),
+    /// see [ServiceUnavailable](crate::ServiceUnavailable)
+    ServiceUnavailable(Box<ServiceUnavailable<RocketResponseGeneric3<T, S, U>>>),
+    #[cfg(feature = "secrets")]
+    /// see [SessionRedirect](crate::SessionRedirect)
+    SessionRedirect(Box<SessionRedirect>),
+    /// see [Sitemap](crate::Sitemap)
+    Sitemap(Sitemap),
+
+    /// see [slice](std::slice)
+    StaticSlice(&'static [u8]),
+    /// see [str]
+    StaticStr(&'static str),
+    /// see [String]
+    String(String),
+    /// see [Status](rocket::http::Status)
+    Status(Status),
+    /// see [StatusPage](crate::StatusPage)
+    StatusPage(StatusPage),
+    /// see [StreamedFile](crate::StreamedFile)
+    StreamedFile(Box<StreamedFile>),
+
+    #[cfg(any(feature = "templates-tera", feature = "templates-handlebars"))]
+    /// see [Template](rocket_dyn_templates::Template)
+    Template(Box<Template>),
+    /// see [TextBody](crate::TextBody)
+    TextBody(Box<TextBody>),
+    #[cfg(feature = "timing")]
+    /// see [Timed](crate::Timed)
+    Timed(Box<Timed<RocketResponseGeneric3<T, S, U>>>),
+
+    /// see [File](rocket::tokio::fs::File)
+    TokioFile(Box<tokio::fs::File>),
+    /// see [TooManyRequests](crate::TooManyRequests)
+    TooManyRequests(Box<TooManyRequests<RocketResponseGeneric3<T, S, U>>>),
+    /// bytes with the content type, see [typed](Self::typed())
+    Typed(Box<ContentType>, Vec<u8>),
+    /// text with the content type, see [typed_str](Self::typed_str())
+    TypedStr(Box<ContentType>, Cow<'static, str>),
+    /// see [Unauthorized](rocket::response::status::Unauthorized)
+    Unauthorized(Unauthorized<T>),
+    /// see [Vec](std::vec::Vec)
+    Vec(Vec<u8>),
+    /// see [WithCache](crate::WithCache)
+    WithCache(WithCache<RocketResponseGeneric3<T, S, U>>),
+    /// see [WithCookies](crate::WithCookies)
+    WithCookies(WithCookies<RocketResponseGeneric3<T, S, U>>),
+    /// see [WithCors](crate::WithCors)
+    WithCors(Box<WithCors<RocketResponseGeneric3<T, S, U>>>),
+    /// see [WithCost](crate::WithCost)
+    WithCost(WithCost<RocketResponseGeneric3<T, S, U>>),
+    /// see [Xml](rocket::response::content::RawXml)
+    Xml(RawXml<T>),
+}
+
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+impl<'r, 'o: 'r, T, S, U> Responder<'r, 'o> for RocketResponseGeneric3<T, S, U>
+where
+    T: Responder<'r, 'o>,
+    S: Serialize,
+    U: Responder<'r, 'o>,
+{
+    #[allow(clippy::let_and_return)]
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let variant = self.variant_name();
+        let res = match self {
+            Self::Absolute(r) => (*r).respond_to(req),
+            Self::Accepted(r) => r.respond_to(req),
+            Self::AcceptedJob(r) => (*r).respond_to(req),
+            #[cfg(feature = "oauth")]
+            Self::AuthRedirect(r) => (*r).respond_to(req),
+            Self::BadRequest(r) => r.respond_to(req),
+            Self::BuildError(r) => (*r).respond_to(req),
+            Self::Cached(r) => r.respond_to(req),
+            Self::CacheEntry(r) => (*r).respond_to(req),
+            Self::Canonical(r) => (*r).respond_to(req),
+            #[cfg(feature = "chaos")]
+            Self::ChaosWrapped(r) => (*r).respond_to(req),
+            #[cfg(feature = "process")]
+            Self::CommandOutput(r) => (*r).respond_to(req),
+            #[cfg(feature = "compress")]
+            Self::Compressed(r) => (*r).respond_to(req),
+            Self::Conflict(r) => r.respond_to(req),
+            Self::Created(r) => r.respond_to(req),
+            Self::CreatedResource(r) => (*r).respond_to(req),
+            Self::Css(r) => r.respond_to(req),
+            Self::Custom(r) => (*r).respond_to(req),
+            #[cfg(feature = "db")]
+            Self::DbError(r) => (*r).respond_to(req),
+            #[cfg(feature = "json")]
+            Self::DeltaCollection(r) => (*r).respond_to(req),
+            Self::Download(r) => (*r).respond_to(req),
+            Self::Dyn(r) => r.respond_to(req),
+            #[cfg(feature = "json")]
+            Self::ExtensionNegotiated(r) => (*r).respond_to(req),
+            Self::File(r) => r.respond_to(req),
+            Self::Flash(r) => (*r).respond_to(req),
+            Self::FlashResponse(r) => (*r).respond_to(req),
+            Self::Forbidden(r) => r.respond_to(req),
+            #[cfg(feature = "graphql")]
+            Self::GraphQL(r) => (*r).respond_to(req),
+            #[cfg(feature = "hal")]
+            Self::Hal(r) => (*r).respond_to(req),
+            Self::Hardened(r) => (*r).respond_to(req),
+            Self::HeadAware(r) => (*r).respond_to(req),
+            #[cfg(feature = "json")]
+            Self::HealthReport(r) => (*r).respond_to(req),
+            Self::Hooked(r) => (*r).respond_to(req),
+            Self::Html(r) => r.respond_to(req),
+            Self::JavaScript(r) => r.respond_to(req),
+            Self::Json(r) => r.respond_to(req),
+            #[cfg(feature = "jsonapi")]
+            Self::JsonApi(r) => (*r).respond_to(req),
+            Self::Lazy(r) => r.respond_to(req),
+            Self::Localized(r) => (*r).respond_to(req),
+            Self::MsgPack(r) => r.respond_to(req),
+            Self::Multipart(r) => (*r).respond_to(req),
+            Self::NamedFiled(r) => (*r).respond_to(req),
+            #[cfg(feature = "json")]
+            Self::Negotiated(r) => r.respond_to(req),
+            Self::NotFound(r) => r.respond_to(req),
+            Self::NoContent(r) => r.respond_to(req),
+            #[cfg(feature = "json")]
+            Self::Paginated(r) => (*r).respond_to(req),
+            Self::Plain(r) => r.respond_to(req),
+            #[cfg(feature = "signed-files")]
+            Self::ProtectedFile(r) => (*r).respond_to(req),
+            Self::PurgeResult(r) => (*r).respond_to(req),
+            Self::QuotaHeaders(r) => r.respond_to(req),
+            Self::RangedFile(r) => (*r).respond_to(req),
+            Self::Redirect(r) => (*r).respond_to(req),
+            Self::RocketError(r) => r.respond_to(req),
+            #[cfg(all(feature = "db-sqlx", feature = "json"))]
+            Self::RowStream(r) => (*r).respond_to(req),
+            #[cfg(feature = "bincode")]
+            Self::SerdeBincode(r) => r.respond_to(req),
+            #[cfg(feature = "cbor")]
+            Self::SerdeCbor(r) => r.respond_to(req),
+            #[cfg(feature = "json")]
+            Self::SerdeJson(r) => body::respond_json(&r.0, req),
+            #[cfg(feature = "msgpack")]
+            Self::SerdeMsgPack(r) => body::respond_msgpack(&r.0, req),
+            #[cfg(feature = "msgpack")]
+            Self::SerdeMsgPackValue(r) => r.respond_to(req),
+            #[cfg(feature = "json")]
+            Self::SerdeValue(r) => body::respond_json(&r, req),
+            #[cfg(feature = "xml")]
+            Self::SerdeXml(r) => r.respond_to(req),
+            Self::ServiceUnavailable(r) => (*r).respond_to(req),
+            #[cfg(feature = "secrets")]
+            Self::SessionRedirect(r) => (*r).respond_to(req),
+            Self::Sitemap(r) => r.respond_to(req),
+            Self::StaticSlice(r) => r.respond_to(req),
+            Self::StaticStr(r) => r.respond_to(req),
+            Self::String(r) => r.respond_to(req),
+            Self::Status(r) => r.respond_to(req),
+            Self::StatusPage(r) => r.respond_to(req),
+            Self::StreamedFile(r) => (*r).respond_to(req),
+            #[cfg(any(feature = "templates-tera", feature = "templates-handlebars"))]
+            Self::Template(r) => (*r).respond_to(req),
+            Self::TextBody(r) => (*r).respond_to(req),
+            #[cfg(feature = "timing")]
+            Self::Timed(r) => (*r).respond_to(req),
+            Self::TokioFile(r) => (*r).respond_to(req),
+            Self::TooManyRequests(r) => (*r).respond_to(req),
+            Self::Typed(content_type, body) => (*content_type, body).respond_to(req),
+            Self::TypedStr(content_type, body) => (*content_type, body).respond_to(req),
+            Self::Unauthorized(r) => r.respond_to(req),
+            Self::Vec(r) => r.respond_to(req),
+            Self::WithCache(r) => r.respond_to(req),
+            Self::WithCookies(r) => r.respond_to(req),
+            Self::WithCors(r) => (*r).respond_to(req),
+            Self::WithCost(r) => r.respond_to(req),
+            Self::Xml(r) => r.respond_to(req),
+        };
+        if let Some(stats) = req.rocket().state::<UsageStats>() {
+            stats.record(variant);
+        }
+        #[cfg(feature = "tracing")]
+        trace::responded(variant, &res);
+        res
+    }
+}
+
+// Large variants are boxed, so returning a response from a route copies only a few bytes.
+const _: () = assert!(
+    std::mem::size_of::<RocketResponse>() <= 72
+        && std::mem::size_of::<RocketResponseGeneric<&'static str>>() <= 72
+        && std::mem::size_of::<RocketResponseGeneric2<&'static str, Redirect>>() <= 72
+);
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+const _: () = assert!(
+    std::mem::size_of::<RocketResponseGeneric3<&'static str, &'static str, Redirect>>() <= 72
+);
+
 #[cfg(test)]
+// the route attributes re-export a `uri!` macro, which the tests don't use
+#[allow(unused_imports)]
 mod tests {
     use super::{RocketResponse, RocketResponseGeneric, RocketResponseGeneric2};
     use rocket::{
@@ -362,11 +1759,25 @@ mod tests {
     pub(crate) fn route_response(id: usize) -> RocketResponse {
         match id {
             0 => RocketResponse::NoContent(response::status::NoContent),
-            1 => RocketResponse::Redirect(Redirect::to("/admin")),
+            1 => RocketResponse::redirect_to("/admin"),
             _ => RocketResponse::StaticStr("Hello world"),
         }
     }
 
+    #[get("/response_flash/<user>")]
+    fn route_response_flash(user: &str) -> RocketResponse {
+        RocketResponse::flash_error(
+            Redirect::to("/login"),
+            format!("Invalid password for {}", user),
+        )
+    }
+
+    #[cfg(feature = "json")]
+    #[get("/response_json")]
+    fn route_response_json() -> RocketResponse {
+        RocketResponse::SerdeJson(rocket::serde::json::Json("Hello world"))
+    }
+
     #[get("/response_generic/<id>")]
     pub(crate) fn route_response_generic(id: usize) -> RocketResponseGeneric<&'static str> {
         match id {
@@ -385,10 +1796,9 @@ mod tests {
         id: usize,
     ) -> RocketResponseGeneric2<&'static str, Redirect> {
         match id {
-            0 => RocketResponseGeneric2::Flash(response::Flash::error(
-                Redirect::to("/"),
-                format!("Invalid id {}", id),
-            )),
+            0 => {
+                RocketResponseGeneric2::flash_error(Redirect::to("/"), format!("Invalid id {}", id))
+            }
             1 => RocketResponseGeneric2::Unauthorized(status::Unauthorized(Some(
                 "admin need authentication",
             ))),
@@ -398,6 +1808,28 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "json")]
+    #[derive(rocket::serde::Serialize)]
+    #[serde(crate = "rocket::serde")]
+    struct Item {
+        id: usize,
+    }
+
+    #[cfg(feature = "json")]
+    use super::RocketResponseGeneric3;
+
+    #[cfg(feature = "json")]
+    #[get("/response_generic3/<id>")]
+    fn route_response_generic3(
+        id: usize,
+    ) -> RocketResponseGeneric3<response::content::RawHtml<String>, Item, Redirect> {
+        match id {
+            0 => RocketResponseGeneric3::flash_error(Redirect::to("/"), "Invalid id"),
+            1 => RocketResponseGeneric3::SerdeJson(rocket::serde::json::Json(Item { id })),
+            _ => RocketResponseGeneric3::String(format!("Item {}", id)),
+        }
+    }
+
     #[test]
     fn test_rocket_response() {
         let rocket = rocket::build().mount("/", routes![route_response]);
@@ -409,6 +1841,30 @@ mod tests {
         assert_eq!(ContentType::Plain, res.content_type().unwrap());
     }
 
+    #[test]
+    fn test_rocket_response_flash_msg() {
+        let rocket = rocket::build().mount("/", routes![route_response_flash]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+        let req = client.get("/response_flash/alice");
+        let res = req.dispatch();
+
+        assert_eq!(Status::SeeOther, res.status());
+        assert_eq!(Some("/login"), res.headers().get_one("Location"));
+        assert!(res.cookies().get("_flash").is_some());
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_rocket_response_json() {
+        let rocket = rocket::build().mount("/", routes![route_response_json]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+        let res = client.get("/response_json").dispatch();
+
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(ContentType::JSON, res.content_type().unwrap());
+        assert_eq!("\"Hello world\"", res.into_string().unwrap());
+    }
+
     #[test]
     fn test_rocket_response_generic() {
         let rocket = rocket::build().mount("/", routes![route_response_generic]);
@@ -428,4 +1884,21 @@ mod tests {
 
         assert_eq!(Status::SeeOther, res.status());
     }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_rocket_response_generic3() {
+        let rocket = rocket::build().mount("/", routes![route_response_generic3]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/response_generic3/0").dispatch();
+        assert_eq!(Status::SeeOther, res.status());
+
+        let res = client.get("/response_generic3/1").dispatch();
+        assert_eq!(ContentType::JSON, res.content_type().unwrap());
+        assert_eq!("{\"id\":1}", res.into_string().unwrap());
+
+        let res = client.get("/response_generic3/2").dispatch();
+        assert_eq!("Item 2", res.into_string().unwrap());
+    }
 }
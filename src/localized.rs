@@ -0,0 +1,328 @@
+use rocket::{
+    http::Header,
+    request::Request,
+    response::{self, Responder},
+};
+use std::fmt;
+
+/// Language tags of the `Accept-Language` header of `req` from the most preferred
+///
+/// Tags with `q=0` are left out.
+pub(crate) fn accept_languages<'a>(req: &'a Request<'_>) -> Vec<&'a str> {
+    let mut languages: Vec<(&str, f32)> = req
+        .headers()
+        .get("Accept-Language")
+        .flat_map(|header| header.split(','))
+        .filter_map(|language| {
+            let mut parts = language.split(';');
+            let tag = parts.next()?.trim();
+            let q = parts
+                .find_map(|param| param.trim().strip_prefix("q="))
+                .and_then(|q| q.parse().ok())
+                .unwrap_or(1.0);
+            Some((tag, q))
+        })
+        .filter(|(tag, q)| !tag.is_empty() && *q > 0.0)
+        .collect();
+    languages.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    languages.into_iter().map(|(tag, _)| tag).collect()
+}
+
+/// `true` if the language `range` of `Accept-Language` matches the language `tag`
+///
+/// `de` matches `de` and `de-AT`, `de-AT` matches `de-AT` and `de`.
+pub(crate) fn language_matches(range: &str, tag: &str) -> bool {
+    let prefix_of = |prefix: &str, tag: &str| {
+        tag.len() > prefix.len()
+            && tag.as_bytes()[prefix.len()] == b'-'
+            && tag[..prefix.len()].eq_ignore_ascii_case(prefix)
+    };
+    range == "*"
+        || range.eq_ignore_ascii_case(tag)
+        || prefix_of(range, tag)
+        || prefix_of(tag, range)
+}
+
+/// Language chosen by [Localized] for the response
+///
+/// The language is available in the request-local cache, e.g. for a logging fairing:
+///
+/// ```rust
+/// use rocket::fairing::AdHoc;
+/// use rocket_response::ChosenLanguage;
+///
+/// let fairing = AdHoc::on_response("Languages", |req, _| Box::pin(async move {
+///     if let Some(language) = req.local_cache(|| None::<ChosenLanguage>) {
+///         println!("{} in {}", req.uri(), language);
+///     }
+/// }));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ChosenLanguage(String);
+
+impl ChosenLanguage {
+    /// The language tag
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for ChosenLanguage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Wrapper with the language of its body
+///
+/// Sets `Content-Language` and adds `Vary: Accept-Language`. With alternatives added by
+/// [or](Self::or()) the candidate with the most preferred language of the `Accept-Language`
+/// header responds. Without a match the languages of the fallback chain are tried in order,
+/// and finally the first candidate responds. The chosen language is also stored as
+/// [ChosenLanguage] in the request-local cache. A response already carrying
+/// `Content-Language`, e.g. of a nested [Localized], is left unchanged.
+///
+/// The fallback chain is set per response with [fallback](Self::fallback()) or for all
+/// responses with `language_fallback` of the Rocket config, like
+/// `language_fallback = ["de", "en"]` for the site default `de` and then English. The
+/// default chain is `["en"]`.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::get;
+/// use rocket_response::{Localized, RocketResponse};
+///
+/// #[get("/")]
+/// fn index() -> RocketResponse {
+///     RocketResponse::Localized(Box::new(
+///         Localized::new("en", RocketResponse::StaticStr("Hello world"))
+///             .or("de", RocketResponse::StaticStr("Hallo Welt"))
+///             .or("fr", RocketResponse::StaticStr("Bonjour le monde")),
+///     ))
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Localized<R> {
+    candidates: Vec<(String, R)>,
+    fallback: Option<Vec<String>>,
+}
+
+impl<R> Localized<R> {
+    /// Wraps `responder` with the body in `language` like `en` or `de-AT`
+    pub fn new<L: Into<String>>(language: L, responder: R) -> Self {
+        Localized {
+            candidates: vec![(language.into(), responder)],
+            fallback: None,
+        }
+    }
+
+    /// Adds the alternative `responder` in `language`.
+    pub fn or<L: Into<String>>(mut self, language: L, responder: R) -> Self {
+        self.candidates.push((language.into(), responder));
+        self
+    }
+
+    /// Sets the fallback chain instead of the one of the Rocket config.
+    pub fn fallback(mut self, languages: &[&str]) -> Self {
+        self.fallback = Some(languages.iter().map(|l| l.to_string()).collect());
+        self
+    }
+
+    /// Languages of the candidates
+    pub fn languages(&self) -> impl Iterator<Item = &str> {
+        self.candidates
+            .iter()
+            .map(|(language, _)| language.as_str())
+    }
+
+    /// Consumes the wrapper and returns the first candidate.
+    pub fn into_inner(self) -> R {
+        self.candidates
+            .into_iter()
+            .next()
+            .map(|(_, responder)| responder)
+            .expect("Localized without candidate")
+    }
+
+    pub(crate) fn responder(&self) -> &R {
+        &self.candidates[0].1
+    }
+
+    /// Index of the candidate with the most preferred language of `req`
+    fn select(&self, req: &Request<'_>) -> usize {
+        let position = |range: &str| {
+            self.candidates
+                .iter()
+                .position(|(language, _)| language_matches(range, language))
+        };
+        if let Some(index) = accept_languages(req).into_iter().find_map(position) {
+            return index;
+        }
+
+        let configured;
+        let fallback = match &self.fallback {
+            Some(fallback) => fallback,
+            None => {
+                configured = req
+                    .rocket()
+                    .figment()
+                    .extract_inner::<Vec<String>>("language_fallback")
+                    .unwrap_or_else(|_| vec!["en".to_string()]);
+                &configured
+            }
+        };
+        fallback
+            .iter()
+            .find_map(|language| position(language))
+            .unwrap_or(0)
+    }
+}
+
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for Localized<R> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let index = self.select(req);
+        let (language, responder) = self
+            .candidates
+            .into_iter()
+            .nth(index)
+            .expect("Localized without candidate");
+        let mut res = responder.respond_to(req)?;
+        if res.headers().contains("Content-Language") {
+            return Ok(res);
+        }
+        req.local_cache(|| Some(ChosenLanguage(language.clone())));
+        res.set_header(Header::new("Content-Language", language));
+        res.adjoin_header(Header::new("Vary", "Accept-Language"));
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::{language_matches, ChosenLanguage, Localized};
+    use crate::RocketResponse;
+    use rocket::{
+        fairing::AdHoc,
+        figment::Figment,
+        get,
+        http::{Header, Status},
+        local::blocking::Client,
+        routes,
+    };
+
+    #[get("/localized")]
+    fn route_localized() -> RocketResponse {
+        RocketResponse::Localized(Box::new(
+            Localized::new("en", RocketResponse::StaticStr("Hello"))
+                .or("de-DE", RocketResponse::StaticStr("Hallo"))
+                .or("fr", RocketResponse::StaticStr("Bonjour")),
+        ))
+    }
+
+    #[get("/fallback")]
+    fn route_fallback() -> Localized<&'static str> {
+        Localized::new("de", "Hallo")
+            .or("fr", "Bonjour")
+            .or("en", "Hello")
+    }
+
+    #[get("/fallback_custom")]
+    fn route_fallback_custom() -> Localized<&'static str> {
+        route_fallback().fallback(&["it", "fr"])
+    }
+
+    #[test]
+    fn test_localized_fallback() {
+        let chosen = AdHoc::on_response("Chosen", |req, res| {
+            Box::pin(async move {
+                if let Some(language) = req.local_cache(|| None::<ChosenLanguage>) {
+                    res.set_header(Header::new("X-Chosen", language.to_string()));
+                }
+            })
+        });
+        let rocket = rocket::build()
+            .attach(chosen)
+            .mount("/", routes![route_fallback, route_fallback_custom]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+        let get = |path: &str| {
+            let res = client
+                .get(path.to_string())
+                .header(Header::new("Accept-Language", "ja"))
+                .dispatch();
+            (
+                res.headers()
+                    .get_one("Content-Language")
+                    .map(str::to_string),
+                res.headers().get_one("X-Chosen").map(str::to_string),
+            )
+        };
+        assert_eq!(
+            (Some("en".to_string()), Some("en".to_string())),
+            get("/fallback")
+        );
+        assert_eq!(Some("fr".to_string()), get("/fallback_custom").0);
+
+        let figment = Figment::from(rocket::Config::debug_default())
+            .merge(("language_fallback", ["fr", "en"]));
+        let rocket = rocket::custom(figment).mount("/", routes![route_fallback]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+        let res = client
+            .get("/fallback")
+            .header(Header::new("Accept-Language", "ja"))
+            .dispatch();
+        assert_eq!(Some("fr"), res.headers().get_one("Content-Language"));
+        let res = client
+            .get("/fallback")
+            .header(Header::new("Accept-Language", "de-CH"))
+            .dispatch();
+        assert_eq!("Hallo", res.into_string().unwrap());
+    }
+
+    #[test]
+    fn test_language_matches() {
+        assert!(language_matches("de", "de-DE"));
+        assert!(language_matches("de-AT", "de"));
+        assert!(language_matches("DE", "de"));
+        assert!(language_matches("*", "fr"));
+        assert!(!language_matches("de", "den"));
+        assert!(!language_matches("de-AT", "de-DE"));
+    }
+
+    #[test]
+    fn test_localized() {
+        let rocket = rocket::build().mount("/", routes![route_localized]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+        let get = |language: Option<&str>| {
+            let mut req = client.get("/localized");
+            if let Some(language) = language {
+                req.add_header(Header::new("Accept-Language", language.to_string()));
+            }
+            let res = req.dispatch();
+            assert_eq!(Status::Ok, res.status());
+            assert_eq!(Some("Accept-Language"), res.headers().get_one("Vary"));
+            (
+                res.headers()
+                    .get_one("Content-Language")
+                    .unwrap()
+                    .to_string(),
+                res.into_string().unwrap(),
+            )
+        };
+
+        assert_eq!(("en".to_string(), "Hello".to_string()), get(None));
+        assert_eq!(
+            ("de-DE".to_string(), "Hallo".to_string()),
+            get(Some("it, de;q=0.8, fr;q=0.5"))
+        );
+        assert_eq!(
+            ("fr".to_string(), "Bonjour".to_string()),
+            get(Some("fr-CH, en;q=0.9"))
+        );
+        assert_eq!(
+            ("en".to_string(), "Hello".to_string()),
+            get(Some("ja, fr;q=0"))
+        );
+    }
+}
@@ -0,0 +1,307 @@
+use crate::RetryAfter;
+use rocket::{
+    fairing::{self, Fairing, Info, Kind},
+    http::{Header, Status},
+    Build, Request, Response, Rocket,
+};
+use std::{
+    convert::TryFrom,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+#[derive(Debug)]
+struct State {
+    active: AtomicBool,
+    retry_after: AtomicU64,
+    /// Expected restart window in milliseconds
+    restart_window: AtomicU64,
+    /// Deadline of the draining in milliseconds since the Unix epoch, `0` if not draining
+    deadline: AtomicU64,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        State {
+            active: AtomicBool::new(false),
+            retry_after: AtomicU64::new(0),
+            restart_window: AtomicU64::new(30_000),
+            deadline: AtomicU64::new(0),
+        }
+    }
+}
+
+/// Maintenance mode, attached as fairing
+///
+/// While the mode is active, every `503 Service Unavailable` response, like of
+/// [RocketError::Unavailable](crate::RocketError::Unavailable) or a
+/// [Status](rocket::http::Status) variant, gets `X-Robots-Tag: noindex` and `Retry-After`.
+/// `noindex` keeps the temporary maintenance pages out of the index of search engines, and
+/// `Retry-After` tells crawlers when to come back. Headers already set by the responder are
+/// kept.
+/// After [clear](Self::clear()) the responses are left unchanged again.
+///
+/// Before a rolling restart the instance is [drained](Self::drain()) for its expected
+/// [restart window](Self::restart_window()). Then `Retry-After` is the time left until the
+/// deadline of the window, at least a second, and `X-Drain-Deadline` is the deadline as
+/// HTTP date, so load balancers and clients back off until the instance is back. Once the
+/// deadline has passed, the draining is over and the mode is inactive, unless it is
+/// [enabled](Self::enable()) or drained again.
+///
+/// The mode is managed by the fairing, so routes get it as `&State<Maintenance>`.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::{get, post, State};
+/// use rocket_response::{Maintenance, RocketError, RocketResponse};
+/// use std::time::Duration;
+///
+/// #[get("/")]
+/// fn index(maintenance: &State<Maintenance>) -> RocketResponse {
+///     if maintenance.is_active() {
+///         return RocketResponse::RocketError(RocketError::Unavailable(None));
+///     }
+///     RocketResponse::StaticStr("Hello world")
+/// }
+///
+/// #[post("/maintenance")]
+/// fn start(maintenance: &State<Maintenance>) {
+///     maintenance.enable(Duration::from_secs(600));
+/// }
+///
+/// #[post("/drain")]
+/// fn drain(maintenance: &State<Maintenance>) {
+///     maintenance.drain();
+/// }
+///
+/// let maintenance = Maintenance::new().restart_window(Duration::from_secs(45));
+/// let rocket = rocket::build().attach(maintenance);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Maintenance {
+    state: Arc<State>,
+}
+
+impl Maintenance {
+    /// Inactive maintenance mode
+    pub fn new() -> Self {
+        Maintenance::default()
+    }
+
+    /// Sets the expected restart window of [drain](Self::drain()), 30 seconds by default.
+    pub fn restart_window(self, window: Duration) -> Self {
+        self.state
+            .restart_window
+            .store(millis(window), Ordering::Relaxed);
+        self
+    }
+
+    /// Activates the mode with `retry_after` sent as `Retry-After`.
+    pub fn enable(&self, retry_after: Duration) {
+        self.state
+            .retry_after
+            .store(retry_after.as_secs(), Ordering::Relaxed);
+        self.state.deadline.store(0, Ordering::Relaxed);
+        self.state.active.store(true, Ordering::Release);
+    }
+
+    /// Activates the mode until the end of the restart window from now.
+    pub fn drain(&self) {
+        let window = self.state.restart_window.load(Ordering::Relaxed);
+        let now = millis(
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default(),
+        );
+        self.state
+            .deadline
+            .store(now.saturating_add(window).max(1), Ordering::Relaxed);
+        self.state.active.store(true, Ordering::Release);
+    }
+
+    /// Deactivates the mode.
+    pub fn clear(&self) {
+        self.state.active.store(false, Ordering::Release);
+        self.state.deadline.store(0, Ordering::Relaxed);
+    }
+
+    /// `true` if the mode is active
+    pub fn is_active(&self) -> bool {
+        self.state.active.load(Ordering::Acquire) && !self.drain_expired()
+    }
+
+    /// Deadline of the draining, `None` if the instance isn't drained or the deadline has
+    /// passed
+    pub fn drain_deadline(&self) -> Option<SystemTime> {
+        match self.state.deadline.load(Ordering::Relaxed) {
+            0 => None,
+            deadline => Some(UNIX_EPOCH + Duration::from_millis(deadline))
+                .filter(|deadline| *deadline > SystemTime::now()),
+        }
+    }
+
+    /// `true` if the instance was drained and the deadline has passed
+    fn drain_expired(&self) -> bool {
+        self.state.deadline.load(Ordering::Relaxed) != 0 && self.drain_deadline().is_none()
+    }
+
+    /// Time sent as `Retry-After` while the mode is active
+    pub fn retry_after(&self) -> Duration {
+        match self.drain_deadline() {
+            Some(deadline) => deadline
+                .duration_since(SystemTime::now())
+                .unwrap_or_default()
+                .max(Duration::from_secs(1)),
+            None => Duration::from_secs(self.state.retry_after.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+/// Milliseconds of `duration`, saturated
+fn millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+#[rocket::async_trait]
+impl Fairing for Maintenance {
+    fn info(&self) -> Info {
+        Info {
+            name: "Maintenance",
+            kind: Kind::Ignite | Kind::Response,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        Ok(rocket.manage(self.clone()))
+    }
+
+    async fn on_response<'r>(&self, _: &'r Request<'_>, res: &mut Response<'r>) {
+        if !self.is_active() || res.status() != Status::ServiceUnavailable {
+            return;
+        }
+        if !res.headers().contains("X-Robots-Tag") {
+            res.set_header(Header::new("X-Robots-Tag", "noindex"));
+        }
+        if !res.headers().contains("Retry-After") {
+            res.set_header(RetryAfter::from(self.retry_after()).header());
+        }
+        if let Some(deadline) = self.drain_deadline() {
+            if !res.headers().contains("X-Drain-Deadline") {
+                res.set_header(Header::new(
+                    "X-Drain-Deadline",
+                    httpdate::fmt_http_date(deadline),
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::Maintenance;
+    use crate::{RocketError, RocketResponse};
+    use rocket::{get, http::Status, local::blocking::Client, routes, State};
+    use std::time::Duration;
+
+    #[get("/maintenance")]
+    fn route_maintenance(maintenance: &State<Maintenance>) -> RocketResponse {
+        if maintenance.is_active() {
+            RocketResponse::RocketError(RocketError::Unavailable(None))
+        } else {
+            RocketResponse::Status(Status::ServiceUnavailable)
+        }
+    }
+
+    #[get("/ok")]
+    fn route_ok() -> RocketResponse {
+        RocketResponse::StaticStr("Hello world")
+    }
+
+    #[test]
+    fn test_maintenance() {
+        let maintenance = Maintenance::new();
+        let rocket = rocket::build()
+            .attach(maintenance.clone())
+            .mount("/", routes![route_maintenance, route_ok]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/maintenance").dispatch();
+        assert_eq!(Status::ServiceUnavailable, res.status());
+        assert!(!res.headers().contains("X-Robots-Tag"));
+        assert!(!res.headers().contains("Retry-After"));
+
+        maintenance.enable(Duration::from_secs(120));
+        let res = client.get("/maintenance").dispatch();
+        assert_eq!(Status::ServiceUnavailable, res.status());
+        assert_eq!(Some("noindex"), res.headers().get_one("X-Robots-Tag"));
+        assert_eq!(Some("120"), res.headers().get_one("Retry-After"));
+
+        let res = client.get("/ok").dispatch();
+        assert!(!res.headers().contains("X-Robots-Tag"));
+
+        maintenance.clear();
+        let res = client.get("/maintenance").dispatch();
+        assert_eq!(Status::ServiceUnavailable, res.status());
+        assert!(!res.headers().contains("X-Robots-Tag"));
+    }
+
+    #[test]
+    fn test_maintenance_drain() {
+        let maintenance = Maintenance::new().restart_window(Duration::from_secs(45));
+        let rocket = rocket::build()
+            .attach(maintenance.clone())
+            .mount("/", routes![route_maintenance]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+        assert_eq!(None, maintenance.drain_deadline());
+
+        maintenance.drain();
+        let deadline = maintenance.drain_deadline().unwrap();
+        let res = client.get("/maintenance").dispatch();
+        assert_eq!(Status::ServiceUnavailable, res.status());
+        let retry_after: u64 = res
+            .headers()
+            .get_one("Retry-After")
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!((44..=45).contains(&retry_after));
+        assert_eq!(
+            Some(httpdate::fmt_http_date(deadline)),
+            res.headers().get_one("X-Drain-Deadline").map(String::from)
+        );
+
+        maintenance.enable(Duration::from_secs(120));
+        let res = client.get("/maintenance").dispatch();
+        assert_eq!(Some("120"), res.headers().get_one("Retry-After"));
+        assert!(!res.headers().contains("X-Drain-Deadline"));
+    }
+
+    #[test]
+    fn test_maintenance_drain_expired() {
+        let maintenance = Maintenance::new().restart_window(Duration::from_millis(10));
+        let rocket = rocket::build()
+            .attach(maintenance.clone())
+            .mount("/", routes![route_maintenance]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        maintenance.drain();
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(!maintenance.is_active());
+        assert_eq!(None, maintenance.drain_deadline());
+        assert_eq!(Duration::ZERO, maintenance.retry_after());
+        let res = client.get("/maintenance").dispatch();
+        assert_eq!(Status::ServiceUnavailable, res.status());
+        assert!(!res.headers().contains("X-Robots-Tag"));
+        assert!(!res.headers().contains("Retry-After"));
+        assert!(!res.headers().contains("X-Drain-Deadline"));
+
+        maintenance.enable(Duration::from_secs(120));
+        assert!(maintenance.is_active());
+        assert_eq!(Duration::from_secs(120), maintenance.retry_after());
+    }
+}
@@ -0,0 +1,253 @@
+use crate::{ResponseSpec, RocketResponse};
+use rocket::{
+    http::{uri::Origin, Method, Status},
+    request::Request,
+    route::{Handler, Outcome, Route},
+    tokio, Build, Data, Rocket,
+};
+use std::{
+    convert::TryFrom,
+    fs,
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+/// Builder of a mock server answering with [ResponseSpec] files
+///
+/// The routes are read from a directory, in which the path of a spec file is the route:
+/// `<dir>/api/items/<id>/GET.json` answers `GET /api/items/<id>` and `<dir>/POST.toml`
+/// answers `POST /`. Files with other extensions than `.json` and `.toml` are ignored.
+///
+/// The spec files are read again for every request, so they can be edited while the
+/// server runs.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// use rocket_response::MockServer;
+/// use std::time::Duration;
+///
+/// #[rocket::launch]
+/// fn rocket() -> _ {
+///     MockServer::new("fixtures/api")
+///         .latency(Duration::from_millis(200))
+///         .fail_every(10, rocket::http::Status::ServiceUnavailable)
+///         .rocket()
+///         .expect("invalid fixtures")
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct MockServer {
+    dir: PathBuf,
+    options: MockOptions,
+}
+
+#[derive(Debug, Clone, Default)]
+struct MockOptions {
+    latency: Option<Duration>,
+    fail_every: Option<(usize, Status)>,
+}
+
+impl MockServer {
+    /// Mock server with the spec files in `dir`
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        MockServer {
+            dir: dir.into(),
+            options: MockOptions::default(),
+        }
+    }
+
+    /// Delays every response by `latency`.
+    pub fn latency(mut self, latency: Duration) -> Self {
+        self.options.latency = Some(latency);
+        self
+    }
+
+    /// Answers every `n`th request with `status` instead of the spec.
+    pub fn fail_every(mut self, n: usize, status: Status) -> Self {
+        self.options.fail_every = Some((n.max(1), status));
+        self
+    }
+
+    /// Reads the spec files and returns a [Route] for each of them.
+    pub fn routes(&self) -> io::Result<Vec<Route>> {
+        let options = Arc::new(self.options.clone());
+        let requests = Arc::new(AtomicUsize::new(0));
+
+        let mut files = Vec::new();
+        collect_files(&self.dir, &mut files)?;
+        files.sort();
+
+        files
+            .into_iter()
+            .map(|file| {
+                let (method, uri) = route_of(&self.dir, &file)?;
+                let handler = MockHandler {
+                    file,
+                    options: options.clone(),
+                    requests: requests.clone(),
+                };
+                Ok(Route::new(method, &uri, handler))
+            })
+            .collect()
+    }
+
+    /// Builds a [Rocket] with the routes mounted at `/`.
+    pub fn rocket(&self) -> io::Result<Rocket<Build>> {
+        Ok(rocket::build().mount("/", self.routes()?))
+    }
+}
+
+fn invalid(msg: String) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, msg)
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else if matches!(
+            path.extension().and_then(|ext| ext.to_str()),
+            Some("json") | Some("toml")
+        ) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Method and route URI of the spec `file` in `dir`
+fn route_of(dir: &Path, file: &Path) -> io::Result<(Method, String)> {
+    let stem = file
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("");
+    let method = Method::from_str(&stem.to_uppercase())
+        .map_err(|_| invalid(format!("no method in name of {}", file.display())))?;
+
+    let mut uri = String::new();
+    if let Some(parent) = file
+        .parent()
+        .and_then(|parent| parent.strip_prefix(dir).ok())
+    {
+        for segment in parent.iter() {
+            uri.push('/');
+            uri.push_str(&segment.to_string_lossy());
+        }
+    }
+    if uri.is_empty() {
+        uri.push('/');
+    }
+    Origin::parse_route(&uri).map_err(|e| {
+        invalid(format!(
+            "invalid route {} of {}: {}",
+            uri,
+            file.display(),
+            e
+        ))
+    })?;
+
+    Ok((method, uri))
+}
+
+#[derive(Clone)]
+struct MockHandler {
+    file: PathBuf,
+    options: Arc<MockOptions>,
+    requests: Arc<AtomicUsize>,
+}
+
+#[rocket::async_trait]
+impl Handler for MockHandler {
+    async fn handle<'r>(&self, req: &'r Request<'_>, _data: Data<'r>) -> Outcome<'r> {
+        if let Some(latency) = self.options.latency {
+            tokio::time::sleep(latency).await;
+        }
+
+        let count = self.requests.fetch_add(1, Ordering::Relaxed) + 1;
+        if let Some((n, status)) = self.options.fail_every {
+            if count % n == 0 {
+                return Outcome::from(req, status);
+            }
+        }
+
+        match ResponseSpec::from_file(&self.file).and_then(RocketResponse::try_from) {
+            Ok(res) => Outcome::from(req, res),
+            Err(e) => {
+                rocket::error_!("Mock spec {} failed: {}", self.file.display(), e);
+                Outcome::from(req, Status::InternalServerError)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MockServer;
+    use rocket::{
+        http::{ContentType, Status},
+        local::blocking::Client,
+    };
+    use std::{fs, path::PathBuf, time::Duration};
+
+    fn fixture_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("rocket-response-mock-{}", std::process::id()));
+        fs::create_dir_all(dir.join("api/items/<id>")).unwrap();
+        fs::write(dir.join("GET.toml"), "body = \"index\"\n").unwrap();
+        fs::write(
+            dir.join("api/items/<id>/get.json"),
+            r#"{"content_type": "application/json", "body": "{\"id\":1}"}"#,
+        )
+        .unwrap();
+        fs::write(dir.join("api/items/POST.json"), r#"{"status": 201}"#).unwrap();
+        fs::write(dir.join("api/README.md"), "ignored").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_mock_server() {
+        let dir = fixture_dir();
+        let rocket = MockServer::new(&dir)
+            .latency(Duration::from_millis(1))
+            .fail_every(4, Status::ServiceUnavailable)
+            .rocket()
+            .unwrap();
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/").dispatch();
+        assert_eq!("index", res.into_string().unwrap());
+
+        let res = client.get("/api/items/7").dispatch();
+        assert_eq!(ContentType::JSON, res.content_type().unwrap());
+        assert_eq!("{\"id\":1}", res.into_string().unwrap());
+
+        let res = client.post("/api/items").dispatch();
+        assert_eq!(Status::Created, res.status());
+
+        let res = client.get("/").dispatch();
+        assert_eq!(Status::ServiceUnavailable, res.status());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_mock_server_invalid() {
+        let dir = std::env::temp_dir().join(format!(
+            "rocket-response-mock-invalid-{}",
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("FETCH.json"), "{}").unwrap();
+
+        assert!(MockServer::new(&dir).routes().is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,93 @@
+use crate::body;
+use rocket::{
+    request::Request,
+    response::{self, Responder},
+};
+
+/// Serializes a dynamic [rmpv::Value] into MessagePack.
+///
+/// The counterpart of [Value](rocket::serde::json::Value) for payloads built at runtime.
+/// Returns a response with Content-Type `application/msgpack` and a fixed-size body. An
+/// already encoded payload is sent with [RawMsgPack](rocket::response::content::RawMsgPack).
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::get;
+/// use rocket_response::{MsgPackValue, RocketResponse};
+/// use rmpv::Value;
+///
+/// #[get("/sensor/<sensor>")]
+/// fn sensor(sensor: u16) -> RocketResponse {
+///     RocketResponse::SerdeMsgPackValue(MsgPackValue(Value::Map(vec![
+///         (Value::from("sensor"), Value::from(sensor)),
+///         (Value::from("value"), Value::from(21.5)),
+///     ])))
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MsgPackValue(pub rmpv::Value);
+
+impl MsgPackValue {
+    /// Consumes the wrapper and returns the wrapped value.
+    pub fn into_inner(self) -> rmpv::Value {
+        self.0
+    }
+}
+
+impl From<rmpv::Value> for MsgPackValue {
+    fn from(value: rmpv::Value) -> Self {
+        MsgPackValue(value)
+    }
+}
+
+impl<'r> Responder<'r, 'static> for MsgPackValue {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        body::respond_msgpack(&self.0, req)
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::MsgPackValue;
+    use crate::{RocketResponse, RocketResponseGeneric};
+    use rmpv::Value;
+    use rocket::{
+        get,
+        http::{ContentType, Status},
+        local::blocking::Client,
+        routes,
+    };
+
+    #[get("/msgpack_value")]
+    fn route_msgpack_value() -> RocketResponse {
+        RocketResponse::SerdeMsgPackValue(MsgPackValue(Value::Array(vec![
+            Value::from(1),
+            Value::from("a"),
+        ])))
+    }
+
+    #[get("/msgpack_value_generic")]
+    fn route_msgpack_value_generic() -> RocketResponseGeneric<&'static str> {
+        RocketResponseGeneric::SerdeMsgPackValue(Value::Nil.into())
+    }
+
+    #[test]
+    fn test_msgpack_value() {
+        let rocket = rocket::build().mount(
+            "/",
+            routes![route_msgpack_value, route_msgpack_value_generic],
+        );
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/msgpack_value").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(Some(ContentType::MsgPack), res.content_type());
+        // fixarray of 2, positive fixint 1, fixstr of 1
+        assert_eq!(b"\x92\x01\xa1a".to_vec(), res.into_bytes().unwrap());
+
+        let res = client.get("/msgpack_value_generic").dispatch();
+        assert_eq!(b"\xc0".to_vec(), res.into_bytes().unwrap());
+    }
+}
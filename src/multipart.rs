@@ -0,0 +1,534 @@
+use crate::Dyn;
+use rocket::{
+    http::{ContentType, Header},
+    request::Request,
+    response::{self, Responder, Response},
+    tokio::{
+        self,
+        io::{AsyncRead, ReadBuf},
+        sync::Semaphore,
+    },
+};
+use std::{
+    collections::hash_map::RandomState,
+    future::Future,
+    hash::{BuildHasher, Hasher},
+    io::{self, Cursor, Read},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
+
+/// Part of a [Multipart] body with its own headers
+#[derive(Debug, Clone, PartialEq)]
+pub struct Part {
+    headers: Vec<Header<'static>>,
+    body: Vec<u8>,
+}
+
+impl Part {
+    /// Part of `content_type` with `body`
+    pub fn new<B: Into<Vec<u8>>>(content_type: ContentType, body: B) -> Self {
+        Part {
+            headers: vec![content_type.into()],
+            body: body.into(),
+        }
+    }
+
+    /// Part of `multipart/byteranges` with the bytes `start` to `end` of `len` bytes
+    pub fn byterange<B: Into<Vec<u8>>>(
+        content_type: ContentType,
+        start: u64,
+        end: u64,
+        len: u64,
+        body: B,
+    ) -> Self {
+        Part::new(content_type, body).header(Header::new(
+            "Content-Range",
+            format!("bytes {}-{}/{}", start, end, len),
+        ))
+    }
+
+    /// Adds `header` to the part.
+    pub fn header<H: Into<Header<'static>>>(mut self, header: H) -> Self {
+        self.headers.push(header.into());
+        self
+    }
+
+    /// Body of the part
+    pub fn body(&self) -> &[u8] {
+        &self.body
+    }
+}
+
+/// Source of a part, either finished or a responder to finalize
+#[derive(Debug)]
+enum Source {
+    Ready(Part),
+    Pending(Dyn),
+}
+
+/// Body of several [Parts](Part) delimited by a boundary
+///
+/// Used for batch responses with `multipart/mixed` or for several ranges of a file with
+/// `multipart/byteranges` and `206 Partial Content`. The boundary is random, unless it is
+/// [set](Self::boundary()), and is chosen again, if it occurs in a part.
+///
+/// Parts can also be the responses of other responders, which are
+/// [finalized](Self::respond()) concurrently on the runtime, at most
+/// [parallelism](Self::parallelism()) bodies at a time. Their headers become the headers of
+/// the part, their status is dropped. If a responder fails, the whole response fails with its
+/// status. Because the bodies are only known after the response has started, the boundary
+/// can't be chosen again and the body is aborted, if it occurs in a part.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::{get, http::ContentType};
+/// use rocket_response::{Multipart, Part, RocketResponse};
+///
+/// #[get("/batch")]
+/// fn batch() -> RocketResponse {
+///     RocketResponse::Multipart(Box::new(
+///         Multipart::mixed()
+///             .part(Part::new(ContentType::JSON, r#"{"id":1}"#))
+///             .part(Part::new(ContentType::Plain, "done")),
+///     ))
+/// }
+///
+/// #[get("/reports")]
+/// fn reports() -> RocketResponse {
+///     RocketResponse::Multipart(Box::new(
+///         Multipart::mixed()
+///             .parallelism(2)
+///             .respond(RocketResponse::StaticStr("daily"))
+///             .respond(RocketResponse::StaticStr("weekly")),
+///     ))
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Multipart {
+    subtype: String,
+    boundary: Option<String>,
+    parts: Vec<Source>,
+    parallelism: usize,
+}
+
+impl Multipart {
+    /// Empty body of `multipart/<subtype>`
+    pub fn new<S: Into<String>>(subtype: S) -> Self {
+        Multipart {
+            subtype: subtype.into(),
+            boundary: None,
+            parts: Vec::new(),
+            parallelism: 4,
+        }
+    }
+
+    /// Empty body of `multipart/mixed`
+    pub fn mixed() -> Self {
+        Multipart::new("mixed")
+    }
+
+    /// Empty body of `multipart/byteranges`
+    pub fn byteranges() -> Self {
+        Multipart::new("byteranges")
+    }
+
+    /// Sets the boundary instead of a random one.
+    pub fn boundary<S: Into<String>>(mut self, boundary: S) -> Self {
+        self.boundary = Some(boundary.into());
+        self
+    }
+
+    /// Adds `part`.
+    pub fn part(mut self, part: Part) -> Self {
+        self.parts.push(Source::Ready(part));
+        self
+    }
+
+    /// Adds the response of `responder` as part, which is finalized concurrently with the
+    /// other responders.
+    pub fn respond<R>(mut self, responder: R) -> Self
+    where
+        R: for<'r> Responder<'r, 'static> + Send + 'static,
+    {
+        self.parts.push(Source::Pending(Dyn::new(responder)));
+        self
+    }
+
+    /// Sets the maximum number of responders whose bodies are read at a time, by default `4`.
+    pub fn parallelism(mut self, parallelism: usize) -> Self {
+        self.parallelism = parallelism.max(1);
+        self
+    }
+
+    /// Finished parts of the body, without the parts of responders
+    pub fn parts(&self) -> impl Iterator<Item = &Part> {
+        self.parts.iter().filter_map(|source| match source {
+            Source::Ready(part) => Some(part),
+            Source::Pending(_) => None,
+        })
+    }
+}
+
+/// `true` if `boundary` doesn't occur in any of `parts`
+fn unused(parts: &[Part], boundary: &str) -> bool {
+    let boundary = boundary.as_bytes();
+    parts.iter().all(|part| {
+        part.body
+            .windows(boundary.len())
+            .all(|window| window != boundary)
+    })
+}
+
+/// Body of `parts` delimited by `boundary`
+fn render(parts: &[Part], boundary: &str) -> Vec<u8> {
+    let mut body = Vec::new();
+    for part in parts {
+        body.extend_from_slice(b"--");
+        body.extend_from_slice(boundary.as_bytes());
+        body.extend_from_slice(b"\r\n");
+        for header in &part.headers {
+            body.extend_from_slice(header.name().as_str().as_bytes());
+            body.extend_from_slice(b": ");
+            body.extend_from_slice(header.value().as_bytes());
+            body.extend_from_slice(b"\r\n");
+        }
+        body.extend_from_slice(b"\r\n");
+        body.extend_from_slice(&part.body);
+        body.extend_from_slice(b"\r\n");
+    }
+    body.extend_from_slice(b"--");
+    body.extend_from_slice(boundary.as_bytes());
+    body.extend_from_slice(b"--\r\n");
+    body
+}
+
+/// New random boundary
+fn random_boundary() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+    format!("boundary-{:016x}", hasher.finish())
+}
+
+/// Part of a responder, whose body is read later
+enum Finalizing {
+    Ready(Part),
+    Pending(Vec<Header<'static>>, Response<'static>),
+}
+
+/// Reads the bodies of the `parts` of responders, at most `parallelism` at a time, and
+/// renders the body delimited by `boundary`.
+async fn finalize(
+    parts: Vec<Finalizing>,
+    parallelism: usize,
+    boundary: String,
+) -> io::Result<Vec<u8>> {
+    let permits = Arc::new(Semaphore::new(parallelism));
+    let tasks: Vec<_> = parts
+        .into_iter()
+        .map(|part| {
+            let permits = Arc::clone(&permits);
+            tokio::spawn(async move {
+                match part {
+                    Finalizing::Ready(part) => Ok::<_, io::Error>(part),
+                    Finalizing::Pending(headers, mut res) => {
+                        let _permit = permits.acquire_owned().await;
+                        let body = res.body_mut().to_bytes().await?;
+                        Ok(Part { headers, body })
+                    }
+                }
+            })
+        })
+        .collect();
+
+    let mut parts = Vec::with_capacity(tasks.len());
+    for task in tasks {
+        parts.push(
+            task.await
+                .map_err(|e| io::Error::new(io::ErrorKind::Other, e))??,
+        );
+    }
+    if !unused(&parts, &boundary) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "multipart boundary occurs in a part",
+        ));
+    }
+    Ok(render(&parts, &boundary))
+}
+
+/// Body rendered after the parts of responders are finalized
+struct Finalized {
+    body: Pin<Box<dyn Future<Output = io::Result<Vec<u8>>> + Send>>,
+    buf: Option<Cursor<Vec<u8>>>,
+}
+
+impl AsyncRead for Finalized {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = &mut *self;
+        if this.buf.is_none() {
+            match this.body.as_mut().poll(cx) {
+                Poll::Ready(Ok(body)) => this.buf = Some(Cursor::new(body)),
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+        if let Some(body) = &mut this.buf {
+            let n = body.read(buf.initialize_unfilled())?;
+            buf.advance(n);
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<'r> Responder<'r, 'static> for Multipart {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let mut parts = Vec::with_capacity(self.parts.len());
+        let mut pending = false;
+        for source in self.parts {
+            parts.push(match source {
+                Source::Ready(part) => Finalizing::Ready(part),
+                Source::Pending(responder) => {
+                    let res = responder.respond_to(req)?;
+                    let headers = res
+                        .headers()
+                        .iter()
+                        .map(|h| Header::new(h.name().to_string(), h.value().to_string()))
+                        .collect();
+                    pending = true;
+                    Finalizing::Pending(headers, res)
+                }
+            });
+        }
+
+        let mut builder = Response::build();
+        if pending {
+            let boundary = self.boundary.unwrap_or_else(random_boundary);
+            let content_type = ContentType::new("multipart", self.subtype)
+                .with_params(("boundary", boundary.clone()));
+            builder.header(content_type).streamed_body(Finalized {
+                body: Box::pin(finalize(parts, self.parallelism, boundary)),
+                buf: None,
+            });
+        } else {
+            let parts: Vec<Part> = parts
+                .into_iter()
+                .filter_map(|part| match part {
+                    Finalizing::Ready(part) => Some(part),
+                    Finalizing::Pending(..) => None,
+                })
+                .collect();
+            let boundary = match self.boundary {
+                Some(boundary) => boundary,
+                None => loop {
+                    let boundary = random_boundary();
+                    if unused(&parts, &boundary) {
+                        break boundary;
+                    }
+                },
+            };
+            let body = render(&parts, &boundary);
+            let content_type =
+                ContentType::new("multipart", self.subtype).with_params(("boundary", boundary));
+            builder
+                .header(content_type)
+                .sized_body(body.len(), Cursor::new(body));
+        }
+        builder.ok()
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::{Multipart, Part};
+    use crate::RocketResponse;
+    use rocket::{
+        get,
+        http::{ContentType, Header, Status},
+        local::blocking::Client,
+        request::Request,
+        response::{self, Responder, Response},
+        routes,
+        tokio::{
+            io::{AsyncRead, ReadBuf},
+            time::{self, Sleep},
+        },
+        State,
+    };
+    use std::{
+        future::Future,
+        io::{self, Cursor},
+        pin::Pin,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        task::{Context, Poll},
+        time::Duration,
+    };
+
+    /// Bodies read at a time and the maximum of them
+    #[derive(Default)]
+    struct Reads {
+        current: AtomicUsize,
+        max: AtomicUsize,
+    }
+
+    /// Responder whose body takes a while to read
+    struct Slow(&'static str, Arc<Reads>);
+
+    struct SlowBody {
+        body: Cursor<&'static str>,
+        reads: Arc<Reads>,
+        sleep: Option<Pin<Box<Sleep>>>,
+    }
+
+    impl AsyncRead for SlowBody {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<io::Result<()>> {
+            let this = &mut *self;
+            let reads = &this.reads;
+            let sleep = this.sleep.get_or_insert_with(|| {
+                let current = reads.current.fetch_add(1, Ordering::SeqCst) + 1;
+                reads.max.fetch_max(current, Ordering::SeqCst);
+                Box::pin(time::sleep(Duration::from_millis(50)))
+            });
+            if sleep.as_mut().poll(cx).is_pending() {
+                return Poll::Pending;
+            }
+            if this.body.position() == 0 {
+                reads.current.fetch_sub(1, Ordering::SeqCst);
+            }
+            Pin::new(&mut this.body).poll_read(cx, buf)
+        }
+    }
+
+    impl<'r> Responder<'r, 'static> for Slow {
+        fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+            Response::build()
+                .header(ContentType::Plain)
+                .streamed_body(SlowBody {
+                    body: Cursor::new(self.0),
+                    reads: self.1,
+                    sleep: None,
+                })
+                .ok()
+        }
+    }
+
+    #[get("/batch")]
+    fn route_batch() -> RocketResponse {
+        RocketResponse::Multipart(Box::new(
+            Multipart::mixed()
+                .boundary("b1")
+                .part(Part::new(ContentType::JSON, r#"{"id":1}"#).header(Header::new("X-Id", "1")))
+                .part(Part::new(ContentType::Plain, "done")),
+        ))
+    }
+
+    #[get("/ranges")]
+    fn route_ranges() -> Multipart {
+        Multipart::byteranges()
+            .part(Part::byterange(ContentType::Plain, 0, 3, 100, "boun"))
+            .part(Part::byterange(ContentType::Plain, 10, 13, 100, "dary"))
+    }
+
+    #[get("/slow?<fail>")]
+    fn route_slow(fail: bool, reads: &State<Arc<Reads>>) -> RocketResponse {
+        let mut multipart = Multipart::mixed()
+            .boundary("b2")
+            .parallelism(2)
+            .part(Part::new(ContentType::Plain, "ready"));
+        for body in ["p1", "p2", "p3", "p4"] {
+            multipart = multipart.respond(Slow(body, Arc::clone(reads)));
+        }
+        if fail {
+            multipart = multipart.respond(Status::NotFound);
+        }
+        RocketResponse::Multipart(Box::new(multipart))
+    }
+
+    #[test]
+    fn test_multipart() {
+        let rocket = rocket::build().mount("/", routes![route_batch, route_ranges]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/batch").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(
+            Some("multipart/mixed; boundary=b1"),
+            res.headers().get_one("Content-Type")
+        );
+        assert_eq!(
+            "--b1\r\nContent-Type: application/json\r\nX-Id: 1\r\n\r\n{\"id\":1}\r\n\
+            --b1\r\nContent-Type: text/plain; charset=utf-8\r\n\r\ndone\r\n--b1--\r\n",
+            res.into_string().unwrap()
+        );
+
+        let res = client.get("/ranges").dispatch();
+        let content_type = res.content_type().unwrap();
+        assert_eq!("byteranges", content_type.sub());
+        let boundary = content_type.param("boundary").unwrap().to_string();
+        assert!(boundary.starts_with("boundary-"));
+        let body = res.into_string().unwrap();
+        assert!(body.starts_with(&format!(
+            "--{}\r\nContent-Type: text/plain; charset=utf-8\r\n\
+            Content-Range: bytes 0-3/100\r\n",
+            boundary
+        )));
+        assert!(body.ends_with(&format!("\r\ndary\r\n--{}--\r\n", boundary)));
+    }
+
+    #[test]
+    fn test_multipart_respond() {
+        let reads = Arc::new(Reads::default());
+        let rocket = rocket::build()
+            .manage(Arc::clone(&reads))
+            .mount("/", routes![route_slow]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/slow?fail=false").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(
+            Some("multipart/mixed; boundary=b2"),
+            res.headers().get_one("Content-Type")
+        );
+        let part = |body| {
+            format!(
+                "--b2\r\nContent-Type: text/plain; charset=utf-8\r\n\r\n{}\r\n",
+                body
+            )
+        };
+        assert_eq!(
+            format!(
+                "{}{}{}{}{}--b2--\r\n",
+                part("ready"),
+                part("p1"),
+                part("p2"),
+                part("p3"),
+                part("p4")
+            ),
+            res.into_string().unwrap()
+        );
+        assert_eq!(2, reads.max.load(Ordering::SeqCst));
+        assert_eq!(0, reads.current.load(Ordering::SeqCst));
+
+        let res = client.get("/slow?fail=true").dispatch();
+        assert_eq!(Status::NotFound, res.status());
+    }
+}
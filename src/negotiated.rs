@@ -0,0 +1,569 @@
+use crate::{body, AcceptList, BuildError, ContentKind};
+use rocket::{
+    http::{ContentType, MediaType, Status},
+    request::Request,
+    response::{self, Responder},
+    serde::{
+        json::{serde_json, Value},
+        Serialize,
+    },
+};
+use std::{
+    fmt::{self, Write},
+    sync::Arc,
+};
+
+/// Serialization format of [Negotiated] and [ExtensionNegotiated]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Format {
+    /// `application/json`, extension `json`
+    Json,
+    /// `text/csv`, extension `csv`
+    ///
+    /// An array of objects is written with a header row of the keys of the first object,
+    /// anything else as a single row. Nested arrays and objects are written as JSON.
+    Csv,
+    /// `application/xml` or `text/xml`, extension `xml`
+    #[cfg(feature = "xml")]
+    Xml,
+    /// `application/msgpack`, extension `msgpack` or `mpk`
+    #[cfg(feature = "msgpack")]
+    MsgPack,
+}
+
+impl Format {
+    /// Format of the file extension `ext` without the dot
+    pub fn from_extension(ext: &str) -> Option<Format> {
+        match ext.to_ascii_lowercase().as_str() {
+            "json" => Some(Format::Json),
+            "csv" => Some(Format::Csv),
+            #[cfg(feature = "xml")]
+            "xml" => Some(Format::Xml),
+            #[cfg(feature = "msgpack")]
+            "msgpack" | "mpk" => Some(Format::MsgPack),
+            _ => None,
+        }
+    }
+
+    /// Format of `media_type`
+    pub fn from_media_type(media_type: &MediaType) -> Option<Format> {
+        let top = media_type.top().as_str().to_ascii_lowercase();
+        let sub = media_type.sub().as_str().to_ascii_lowercase();
+        match (top.as_str(), sub.as_str()) {
+            ("application", "json") => Some(Format::Json),
+            ("text", "csv") => Some(Format::Csv),
+            #[cfg(feature = "xml")]
+            ("application", "xml") | ("text", "xml") => Some(Format::Xml),
+            #[cfg(feature = "msgpack")]
+            ("application", "msgpack") | ("application", "x-msgpack") => Some(Format::MsgPack),
+            _ => None,
+        }
+    }
+
+    /// Most preferred format of the `Accept` header of `req`
+    ///
+    /// Without an `Accept` header or with `*/*` it is [Json](Format::Json).
+    pub fn from_accept(req: &Request<'_>) -> Option<Format> {
+        let available = Format::media_types();
+        AcceptList::from_request(req)
+            .preferred_index(available.iter().map(|(media_type, _)| media_type))
+            .map(|index| available[index].1)
+    }
+
+    /// Media types of the formats, the preferred one of a format first
+    fn media_types() -> Vec<(MediaType, Format)> {
+        vec![
+            (MediaType::JSON, Format::Json),
+            (MediaType::CSV, Format::Csv),
+            #[cfg(feature = "xml")]
+            (MediaType::new("application", "xml"), Format::Xml),
+            #[cfg(feature = "xml")]
+            (MediaType::new("text", "xml"), Format::Xml),
+            #[cfg(feature = "msgpack")]
+            (MediaType::MsgPack, Format::MsgPack),
+            #[cfg(feature = "msgpack")]
+            (MediaType::new("application", "x-msgpack"), Format::MsgPack),
+        ]
+    }
+
+    /// Content type of the format
+    pub fn content_type(self) -> ContentType {
+        match self {
+            Format::Json => ContentType::JSON,
+            Format::Csv => ContentType::CSV,
+            #[cfg(feature = "xml")]
+            Format::Xml => ContentType::new("application", "xml"),
+            #[cfg(feature = "msgpack")]
+            Format::MsgPack => ContentType::MsgPack,
+        }
+    }
+
+    pub(crate) fn content_kind(self) -> ContentKind {
+        match self {
+            #[cfg(feature = "msgpack")]
+            Format::MsgPack => ContentKind::Binary,
+            _ => ContentKind::Text,
+        }
+    }
+
+    /// Serializes `value` into the format.
+    fn respond<T: Serialize>(self, value: &T, req: &Request<'_>) -> response::Result<'static> {
+        match self {
+            Format::Json => body::respond_json(value, req),
+            Format::Csv => respond_csv(value, req),
+            #[cfg(feature = "xml")]
+            Format::Xml => crate::SerdeXml(value).respond_to(req),
+            #[cfg(feature = "msgpack")]
+            Format::MsgPack => body::respond_msgpack(value, req),
+        }
+    }
+}
+
+type Serializer = Arc<dyn Fn(&Value) -> Result<Vec<u8>, String> + Send + Sync>;
+
+struct Entry {
+    content_type: ContentType,
+    extensions: Vec<String>,
+    serialize: Serializer,
+}
+
+impl Entry {
+    /// Serializes `value` with the serializer of the entry.
+    fn respond<T: Serialize>(&self, value: &T, req: &Request<'_>) -> response::Result<'static> {
+        let bytes = match serde_json::to_value(value)
+            .map_err(|e| e.to_string())
+            .and_then(|value| (self.serialize)(&value))
+        {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                return BuildError::Serialize(self.content_type.to_string(), e).respond_to(req)
+            }
+        };
+        let mut buf = body::BodyBuf::take();
+        buf.as_mut_vec().extend_from_slice(&bytes);
+        buf.respond_to(self.content_type.clone(), req)
+    }
+}
+
+/// Registry of custom formats of [Negotiated] and [ExtensionNegotiated]
+///
+/// A format is a media type with file extensions and a serializer of the value as
+/// [Value]. The registry is consulted before the built-in [Formats](Format), if it is
+/// managed by Rocket, so a built-in format can also be replaced.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::http::ContentType;
+/// use rocket::serde::json::Value;
+/// use rocket_response::SerializerRegistry;
+///
+/// let rocket = rocket::build().manage(SerializerRegistry::new().register(
+///     ContentType::new("application", "vnd.example+text"),
+///     &["txt"],
+///     |value: &Value| Ok(value.to_string().into_bytes()),
+/// ));
+/// ```
+#[derive(Default)]
+pub struct SerializerRegistry {
+    entries: Vec<Entry>,
+}
+
+impl SerializerRegistry {
+    /// Registry without any format
+    pub fn new() -> Self {
+        SerializerRegistry::default()
+    }
+
+    /// Registers `serialize` for `content_type` and the file `extensions` without the dot.
+    pub fn register<F>(
+        mut self,
+        content_type: ContentType,
+        extensions: &[&str],
+        serialize: F,
+    ) -> Self
+    where
+        F: Fn(&Value) -> Result<Vec<u8>, String> + Send + Sync + 'static,
+    {
+        self.entries.push(Entry {
+            content_type,
+            extensions: extensions
+                .iter()
+                .map(|ext| ext.to_ascii_lowercase())
+                .collect(),
+            serialize: Arc::new(serialize),
+        });
+        self
+    }
+
+    /// Entry of `media_type`
+    fn by_media_type(&self, media_type: &MediaType) -> Option<&Entry> {
+        self.entries.iter().find(|entry| {
+            let content_type = entry.content_type.media_type();
+            content_type.top() == media_type.top() && content_type.sub() == media_type.sub()
+        })
+    }
+
+    /// Entry of the file extension `ext`
+    fn by_extension(&self, ext: &str) -> Option<&Entry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.extensions.iter().any(|e| e.eq_ignore_ascii_case(ext)))
+    }
+}
+
+impl fmt::Debug for SerializerRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(
+                self.entries
+                    .iter()
+                    .map(|entry| (entry.content_type.to_string(), &entry.extensions)),
+            )
+            .finish()
+    }
+}
+
+/// Custom or built-in format chosen for a response
+enum Choice<'a> {
+    Custom(&'a Entry),
+    Format(Format),
+}
+
+impl Choice<'_> {
+    fn respond<T: Serialize>(self, value: &T, req: &Request<'_>) -> response::Result<'static> {
+        match self {
+            Choice::Custom(entry) => entry.respond(value, req),
+            Choice::Format(format) => format.respond(value, req),
+        }
+    }
+}
+
+/// Format preferred by the `Accept` header of `req` with the managed [SerializerRegistry]
+///
+/// A registered format replaces the built-in one of the same media type, the other
+/// registered formats rank after the built-in ones.
+fn choose_accepted<'a>(req: &'a Request<'_>) -> Option<Choice<'a>> {
+    let registry = req.rocket().state::<SerializerRegistry>();
+    let mut available: Vec<(MediaType, Choice<'a>)> = Format::media_types()
+        .into_iter()
+        .map(|(media_type, format)| {
+            let choice = registry
+                .and_then(|registry| registry.by_media_type(&media_type))
+                .map_or(Choice::Format(format), Choice::Custom);
+            (media_type, choice)
+        })
+        .collect();
+    for entry in registry.iter().flat_map(|registry| registry.entries.iter()) {
+        let media_type = entry.content_type.media_type();
+        if Format::from_media_type(media_type).is_none() {
+            available.push((media_type.clone(), Choice::Custom(entry)));
+        }
+    }
+
+    let index = AcceptList::from_request(req)
+        .preferred_index(available.iter().map(|(media_type, _)| media_type))?;
+    Some(available.swap_remove(index).1)
+}
+
+/// Serializes `value` into CSV.
+fn respond_csv<T: Serialize + ?Sized>(value: &T, req: &Request<'_>) -> response::Result<'static> {
+    let value = serde_json::to_value(value).map_err(|e| {
+        rocket::error_!("CSV failed to serialize: {:?}", e);
+        Status::InternalServerError
+    })?;
+    let rows = match value {
+        Value::Array(rows) => rows,
+        value => vec![value],
+    };
+
+    let mut csv = String::new();
+    if let Some(Value::Object(first)) = rows.first() {
+        let keys: Vec<&String> = first.keys().collect();
+        write_record(&mut csv, keys.iter().map(|key| key.as_str().into()));
+        for row in &rows {
+            match row {
+                Value::Object(row) => write_record(
+                    &mut csv,
+                    keys.iter()
+                        .map(|key| row.get(key.as_str()).map_or("".into(), field)),
+                ),
+                row => write_record(&mut csv, std::iter::once(field(row))),
+            }
+        }
+    } else {
+        for row in &rows {
+            match row {
+                Value::Array(fields) => write_record(&mut csv, fields.iter().map(field)),
+                row => write_record(&mut csv, std::iter::once(field(row))),
+            }
+        }
+    }
+    let mut buf = body::BodyBuf::take();
+    buf.as_mut_vec().extend_from_slice(csv.as_bytes());
+
+    buf.respond_to(ContentType::CSV, req)
+}
+
+/// Text of a CSV field of `value`
+fn field(value: &Value) -> std::borrow::Cow<'_, str> {
+    match value {
+        Value::Null => "".into(),
+        Value::String(s) => s.as_str().into(),
+        value => value.to_string().into(),
+    }
+}
+
+/// Appends a record of `fields` quoted like in [RFC 4180](https://www.rfc-editor.org/rfc/rfc4180).
+fn write_record<'a, I: Iterator<Item = std::borrow::Cow<'a, str>>>(csv: &mut String, fields: I) {
+    for (i, field) in fields.enumerate() {
+        if i > 0 {
+            csv.push(',');
+        }
+        if field.contains(&[',', '"', '\r', '\n'][..]) {
+            let _ = write!(csv, "\"{}\"", field.replace('"', "\"\""));
+        } else {
+            csv.push_str(&field);
+        }
+    }
+    csv.push_str("\r\n");
+}
+
+/// Serializes the wrapped value into the [Format] preferred by the `Accept` header.
+///
+/// Without an `Accept` header the value is sent as JSON. If no accepted format is
+/// supported, the response is `406 Not Acceptable`. Custom formats are added with a
+/// [SerializerRegistry].
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::get;
+/// use rocket_response::Negotiated;
+///
+/// #[get("/report")]
+/// fn report() -> Negotiated<Vec<(&'static str, u32)>> {
+///     Negotiated(vec![("a", 1), ("b", 2)])
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Negotiated<T>(pub T);
+
+impl<T> Negotiated<T> {
+    /// Consumes the wrapper and returns the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<'r, T: Serialize> Responder<'r, 'static> for Negotiated<T> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        choose_accepted(req)
+            .ok_or(Status::NotAcceptable)?
+            .respond(&self.0, req)
+    }
+}
+
+/// Serializes the wrapped value into the [Format] of a file extension.
+///
+/// The extension is usually captured by the route, like `json` of `/report.json`. An
+/// unsupported extension responds with `404 Not Found`. Custom formats are added with a
+/// [SerializerRegistry].
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::get;
+/// use rocket_response::ExtensionNegotiated;
+///
+/// #[get("/report/<file>")]
+/// fn report(file: &str) -> Option<ExtensionNegotiated<Vec<(&'static str, u32)>>> {
+///     let (name, ext) = file.rsplit_once('.')?;
+///     (name == "report").then(|| ExtensionNegotiated::new(vec![("a", 1), ("b", 2)], ext))
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtensionNegotiated<T> {
+    value: T,
+    ext: String,
+    format: Option<Format>,
+}
+
+impl<T> ExtensionNegotiated<T> {
+    /// Wraps `value` to be serialized into the format of the extension `ext` without the dot
+    pub fn new(value: T, ext: &str) -> Self {
+        ExtensionNegotiated {
+            value,
+            ext: ext.to_string(),
+            format: Format::from_extension(ext),
+        }
+    }
+
+    /// Built-in format of the extension, if it is supported
+    pub fn format(&self) -> Option<Format> {
+        self.format
+    }
+
+    /// Consumes the wrapper and returns the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    pub(crate) fn content_kind(&self) -> ContentKind {
+        self.format.map_or(ContentKind::Other, Format::content_kind)
+    }
+}
+
+impl<'r, T: Serialize> Responder<'r, 'static> for ExtensionNegotiated<T> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let registry = req.rocket().state::<SerializerRegistry>();
+        registry
+            .and_then(|registry| registry.by_extension(&self.ext))
+            .map(Choice::Custom)
+            .or_else(|| self.format.map(Choice::Format))
+            .ok_or(Status::NotFound)?
+            .respond(&self.value, req)
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::{ExtensionNegotiated, Format, Negotiated, SerializerRegistry};
+    use crate::RocketResponseGeneric;
+    use rocket::{
+        get,
+        http::{Accept, ContentType, MediaType, Status},
+        local::blocking::Client,
+        routes,
+        serde::{
+            json::{json, Value},
+            Serialize,
+        },
+    };
+
+    #[derive(Serialize)]
+    #[serde(crate = "rocket::serde")]
+    struct Row {
+        name: &'static str,
+        total: u32,
+    }
+
+    fn rows() -> Vec<Row> {
+        vec![
+            Row {
+                name: "a, b",
+                total: 1,
+            },
+            Row {
+                name: "\"c\"",
+                total: 2,
+            },
+        ]
+    }
+
+    #[get("/report/<ext>")]
+    fn route_extension(ext: &str) -> RocketResponseGeneric<Value> {
+        RocketResponseGeneric::ExtensionNegotiated(Box::new(ExtensionNegotiated::new(
+            json!([{ "name": "a, b", "total": 1 }, { "name": "\"c\"", "total": 2 }]),
+            ext,
+        )))
+    }
+
+    #[get("/report")]
+    fn route_negotiated() -> Negotiated<Vec<Row>> {
+        Negotiated(rows())
+    }
+
+    #[test]
+    fn test_extension_negotiated() {
+        let rocket = rocket::build().mount("/", routes![route_extension]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/report/json").dispatch();
+        assert_eq!(Some(ContentType::JSON), res.content_type());
+        assert_eq!(
+            json!([{ "name": "a, b", "total": 1 }, { "name": "\"c\"", "total": 2 }]),
+            res.into_json::<Value>().unwrap()
+        );
+
+        let res = client.get("/report/CSV").dispatch();
+        assert_eq!(Some(ContentType::CSV), res.content_type());
+        assert_eq!(
+            "name,total\r\n\"a, b\",1\r\n\"\"\"c\"\"\",2\r\n",
+            res.into_string().unwrap()
+        );
+
+        let res = client.get("/report/pdf").dispatch();
+        assert_eq!(Status::NotFound, res.status());
+    }
+
+    #[test]
+    fn test_negotiated() {
+        let rocket = rocket::build().mount("/", routes![route_negotiated]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/report").dispatch();
+        assert_eq!(Some(ContentType::JSON), res.content_type());
+
+        let res = client
+            .get("/report")
+            .header(
+                "application/json; q=0.5, text/csv"
+                    .parse::<Accept>()
+                    .unwrap(),
+            )
+            .dispatch();
+        assert_eq!(Some(ContentType::CSV), res.content_type());
+
+        let res = client.get("/report").header(Accept::HTML).dispatch();
+        assert_eq!(Status::NotAcceptable, res.status());
+
+        assert_eq!(Some(Format::Json), Format::from_extension("json"));
+        assert_eq!(None, Format::from_extension("pdf"));
+    }
+
+    #[test]
+    fn test_serializer_registry() {
+        let vendor = ContentType::new("application", "vnd.example+text");
+        let registry = SerializerRegistry::new()
+            .register(vendor.clone(), &["ex"], |value: &Value| {
+                Ok(format!("ex:{}", value).into_bytes())
+            })
+            .register(ContentType::new("application", "x-broken"), &[], |_| {
+                Err("broken".to_string())
+            });
+        let rocket = rocket::build()
+            .manage(registry)
+            .mount("/", routes![route_extension, route_negotiated]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client
+            .get("/report")
+            .header(Accept::new(vec![MediaType::new(
+                "application",
+                "vnd.example+text",
+            )
+            .into()]))
+            .dispatch();
+        assert_eq!(Some(vendor.clone()), res.content_type());
+        assert_eq!(
+            "ex:[{\"name\":\"a, b\",\"total\":1},{\"name\":\"\\\"c\\\"\",\"total\":2}]",
+            res.into_string().unwrap()
+        );
+
+        let res = client.get("/report").header(Accept::CSV).dispatch();
+        assert_eq!(Some(ContentType::CSV), res.content_type());
+
+        let res = client
+            .get("/report")
+            .header("application/x-broken".parse::<Accept>().unwrap())
+            .dispatch();
+        assert_eq!(Status::InternalServerError, res.status());
+
+        let res = client.get("/report/EX").dispatch();
+        assert_eq!(Some(vendor), res.content_type());
+        assert!(res.into_string().unwrap().starts_with("ex:[{"));
+    }
+}
@@ -0,0 +1,365 @@
+use crate::cookies::comply;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use rocket::{
+    http::{Cookie, CookieJar, SameSite, Status},
+    request::Request,
+    response::{self, Redirect, Responder},
+    time::Duration,
+};
+use sha2::{Digest, Sha256};
+
+/// Redirect to the authorization endpoint of an OAuth 2.0 authorization code flow
+///
+/// Responds with `302 Found` to the authorization endpoint with the percent-encoded query
+/// parameters `response_type=code`, `client_id`, `redirect_uri`, `scope`, `state` and with
+/// [pkce](Self::pkce()) `code_challenge` and `code_challenge_method=S256`.
+///
+/// A random `state`, if not [set](Self::state()), and the PKCE code verifier are stored in the
+/// cookies [STATE_COOKIE](Self::STATE_COOKIE) and [VERIFIER_COOKIE](Self::VERIFIER_COOKIE).
+/// They are always `HttpOnly`, `Secure` and `SameSite=Lax`, so they are sent with the redirect
+/// back from the provider, and expire after 10 minutes. A managed
+/// [CookiePolicy](crate::CookiePolicy) requiring `SameSite=Strict` has to exempt them. The
+/// callback route checks them with [verify_state](Self::verify_state()) and
+/// [take_verifier](Self::take_verifier()).
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::{get, http::CookieJar};
+/// use rocket_response::{AuthRedirect, RocketResponse};
+///
+/// #[get("/login")]
+/// fn login() -> RocketResponse {
+///     RocketResponse::AuthRedirect(Box::new(
+///         AuthRedirect::new(
+///             "https://auth.example.com/authorize",
+///             "client",
+///             "https://app.example.com/callback",
+///         )
+///         .scope("openid")
+///         .scope("profile")
+///         .pkce(),
+///     ))
+/// }
+///
+/// #[get("/callback?<code>&<state>")]
+/// fn callback(code: &str, state: &str, jar: &CookieJar<'_>) -> Option<String> {
+///     if !AuthRedirect::verify_state(jar, state) {
+///         return None;
+///     }
+///     let verifier = AuthRedirect::take_verifier(jar)?;
+///     // exchange `code` and `verifier` for the tokens
+///     Some(format!("{} {}", code, verifier))
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthRedirect {
+    authorize_url: String,
+    client_id: String,
+    redirect_uri: String,
+    scopes: Vec<String>,
+    params: Vec<(String, String)>,
+    state: Option<String>,
+    pkce: bool,
+}
+
+impl AuthRedirect {
+    /// Name of the cookie with the `state`
+    pub const STATE_COOKIE: &'static str = "oauth_state";
+
+    /// Name of the cookie with the PKCE code verifier
+    pub const VERIFIER_COOKIE: &'static str = "oauth_pkce";
+
+    /// Redirect to `authorize_url` for `client_id` returning to `redirect_uri`
+    pub fn new<A, C, U>(authorize_url: A, client_id: C, redirect_uri: U) -> Self
+    where
+        A: Into<String>,
+        C: Into<String>,
+        U: Into<String>,
+    {
+        AuthRedirect {
+            authorize_url: authorize_url.into(),
+            client_id: client_id.into(),
+            redirect_uri: redirect_uri.into(),
+            scopes: Vec::new(),
+            params: Vec::new(),
+            state: None,
+            pkce: false,
+        }
+    }
+
+    /// Adds `scope` to the space separated `scope` parameter.
+    pub fn scope<S: Into<String>>(mut self, scope: S) -> Self {
+        self.scopes.push(scope.into());
+        self
+    }
+
+    /// Adds the query parameter `name`, like `prompt` or `nonce`.
+    pub fn param<N: Into<String>, V: Into<String>>(mut self, name: N, value: V) -> Self {
+        self.params.push((name.into(), value.into()));
+        self
+    }
+
+    /// Sets the `state` instead of a random one.
+    pub fn state<S: Into<String>>(mut self, state: S) -> Self {
+        self.state = Some(state.into());
+        self
+    }
+
+    /// Adds a PKCE code challenge of a random code verifier.
+    pub fn pkce(mut self) -> Self {
+        self.pkce = true;
+        self
+    }
+
+    /// `true` if `state` is the one of the [STATE_COOKIE](Self::STATE_COOKIE), which is
+    /// removed
+    pub fn verify_state(jar: &CookieJar<'_>, state: &str) -> bool {
+        let expected = match jar.get(Self::STATE_COOKIE) {
+            Some(cookie) => cookie.value().to_string(),
+            None => return false,
+        };
+        jar.remove(removal(Self::STATE_COOKIE));
+        expected.len() == state.len()
+            && expected
+                .bytes()
+                .zip(state.bytes())
+                .fold(0, |diff, (a, b)| diff | (a ^ b))
+                == 0
+    }
+
+    /// Code verifier of the [VERIFIER_COOKIE](Self::VERIFIER_COOKIE), which is removed
+    pub fn take_verifier(jar: &CookieJar<'_>) -> Option<String> {
+        let verifier = jar.get(Self::VERIFIER_COOKIE)?.value().to_string();
+        jar.remove(removal(Self::VERIFIER_COOKIE));
+        Some(verifier)
+    }
+
+    /// Location of the authorization endpoint with the query parameters
+    fn location(&self, state: &str, verifier: Option<&str>) -> String {
+        let mut params: Vec<(&str, String)> = vec![
+            ("response_type", "code".to_string()),
+            ("client_id", self.client_id.clone()),
+            ("redirect_uri", self.redirect_uri.clone()),
+        ];
+        if !self.scopes.is_empty() {
+            params.push(("scope", self.scopes.join(" ")));
+        }
+        params.push(("state", state.to_string()));
+        if let Some(verifier) = verifier {
+            params.push(("code_challenge", challenge(verifier)));
+            params.push(("code_challenge_method", "S256".to_string()));
+        }
+        params.extend(
+            self.params
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.clone())),
+        );
+
+        let mut location = self.authorize_url.clone();
+        let mut separator = if location.contains('?') { '&' } else { '?' };
+        for (name, value) in params {
+            location.push(separator);
+            location.push_str(&encode(name));
+            location.push('=');
+            location.push_str(&encode(&value));
+            separator = '&';
+        }
+        location
+    }
+}
+
+impl<'r> Responder<'r, 'static> for AuthRedirect {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let state = match self.state.clone() {
+            Some(state) => state,
+            None => random(16)?,
+        };
+        let verifier = if self.pkce { Some(random(32)?) } else { None };
+
+        let location = self.location(&state, verifier.as_deref());
+        let jar = req.cookies();
+        let mut state = cookie(Self::STATE_COOKIE, state);
+        if comply(req, &mut state) {
+            jar.add(state);
+        }
+        match verifier {
+            Some(verifier) => {
+                let mut verifier = cookie(Self::VERIFIER_COOKIE, verifier);
+                if comply(req, &mut verifier) {
+                    jar.add(verifier);
+                }
+            }
+            None => jar.remove(removal(Self::VERIFIER_COOKIE)),
+        }
+        Redirect::found(location).respond_to(req)
+    }
+}
+
+/// Flow cookie `name` with the enforced attributes
+fn cookie(name: &'static str, value: String) -> Cookie<'static> {
+    Cookie::build(name, value)
+        .path("/")
+        .http_only(true)
+        .secure(true)
+        .same_site(SameSite::Lax)
+        .max_age(Duration::minutes(10))
+        .finish()
+}
+
+/// Cookie removing the flow cookie `name`
+fn removal(name: &'static str) -> Cookie<'static> {
+    Cookie::build(name, "").path("/").finish()
+}
+
+/// `len` random bytes encoded as base64url
+fn random(len: usize) -> Result<String, Status> {
+    let mut bytes = vec![0; len];
+    getrandom::getrandom(&mut bytes).map_err(|e| {
+        rocket::error_!("AuthRedirect without random bytes: {}", e);
+        Status::InternalServerError
+    })?;
+    Ok(URL_SAFE_NO_PAD.encode(bytes))
+}
+
+/// PKCE `S256` code challenge of `verifier`
+fn challenge(verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+}
+
+/// Percent-encodes all but the unreserved characters of `value`.
+fn encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for b in value.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(char::from(b))
+            }
+            b => encoded.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::{challenge, AuthRedirect};
+    use crate::RocketResponse;
+    use rocket::{
+        get,
+        http::{CookieJar, SameSite, Status},
+        local::blocking::Client,
+        routes,
+    };
+
+    #[get("/login")]
+    fn route_login() -> RocketResponse {
+        let redirect = AuthRedirect::new(
+            "https://auth.example.com/authorize?audience=api",
+            "client id",
+            "https://app.example.com/callback?a=1&b=2",
+        )
+        .scope("openid")
+        .scope("profile")
+        .param("prompt", "consent")
+        .state("s+t/ä")
+        .pkce();
+        RocketResponse::AuthRedirect(Box::new(redirect))
+    }
+
+    #[get("/random")]
+    fn route_random() -> AuthRedirect {
+        AuthRedirect::new("https://auth.example.com/authorize", "client", "/callback").pkce()
+    }
+
+    #[get("/callback?<state>")]
+    fn route_callback(state: &str, jar: &CookieJar<'_>) -> String {
+        if AuthRedirect::verify_state(jar, state) {
+            AuthRedirect::take_verifier(jar).unwrap_or_default()
+        } else {
+            "invalid state".to_string()
+        }
+    }
+
+    #[test]
+    fn test_auth_redirect() {
+        assert_eq!(
+            "VYFANLqdx_HDV6BqEhluZJ63rtrIPROSSFdB3P6G83I",
+            challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWjOEjXk")
+        );
+
+        let rocket = rocket::build().mount("/", routes![route_login, route_random, route_callback]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/login").dispatch();
+        assert_eq!(Status::Found, res.status());
+        let verifier = res
+            .cookies()
+            .get(AuthRedirect::VERIFIER_COOKIE)
+            .unwrap()
+            .value()
+            .to_string();
+        assert_eq!(
+            Some(format!(
+                "https://auth.example.com/authorize?audience=api&response_type=code\
+                &client_id=client%20id\
+                &redirect_uri=https%3A%2F%2Fapp.example.com%2Fcallback%3Fa%3D1%26b%3D2\
+                &scope=openid%20profile&state=s%2Bt%2F%C3%A4\
+                &code_challenge={}\
+                &code_challenge_method=S256&prompt=consent",
+                challenge(&verifier)
+            )),
+            res.headers().get_one("Location").map(String::from)
+        );
+        for name in [AuthRedirect::STATE_COOKIE, AuthRedirect::VERIFIER_COOKIE].iter() {
+            let cookie = res.cookies().get(name).unwrap();
+            assert_eq!(Some(true), cookie.http_only());
+            assert_eq!(Some(true), cookie.secure());
+            assert_eq!(Some(SameSite::Lax), cookie.same_site());
+            assert_eq!(Some("/"), cookie.path());
+        }
+
+        let res = client.get("/callback?state=wrong").dispatch();
+        assert!(res
+            .headers()
+            .get("Set-Cookie")
+            .any(|cookie| cookie.starts_with("oauth_state=;") && cookie.contains("Max-Age=0")));
+        assert_eq!("invalid state", res.into_string().unwrap());
+        assert!(client.cookies().get(AuthRedirect::STATE_COOKIE).is_none());
+
+        let res = client.get("/login").dispatch();
+        let verifier = res
+            .cookies()
+            .get(AuthRedirect::VERIFIER_COOKIE)
+            .unwrap()
+            .value()
+            .to_string();
+        let res = client.get("/callback?state=s%2Bt%2F%C3%A4").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(verifier, res.into_string().unwrap());
+        assert!(client
+            .cookies()
+            .get(AuthRedirect::VERIFIER_COOKIE)
+            .is_none());
+
+        let res = client.get("/random").dispatch();
+        let location = res.headers().get_one("Location").unwrap();
+        let state = res
+            .cookies()
+            .get(AuthRedirect::STATE_COOKIE)
+            .unwrap()
+            .value()
+            .to_string();
+        let verifier = res
+            .cookies()
+            .get(AuthRedirect::VERIFIER_COOKIE)
+            .unwrap()
+            .value()
+            .to_string();
+        assert_eq!(22, state.len());
+        assert_eq!(43, verifier.len());
+        assert!(location.contains(&format!("&state={}&", state)));
+        assert!(location.contains(&format!("code_challenge={}&", challenge(&verifier))));
+    }
+}
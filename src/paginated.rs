@@ -0,0 +1,354 @@
+use crate::body;
+#[cfg(feature = "checksum")]
+use crate::ChecksumChain;
+use rocket::{
+    http::{ContentType, Header},
+    request::Request,
+    response::{self, Responder, Response},
+    serde::Serialize,
+};
+use std::fmt::Write;
+
+/// Page of a list serialized into JSON with
+/// [RFC 8288](https://www.rfc-editor.org/rfc/rfc8288) `Link` headers
+///
+/// The links to the `first`, `prev`, `next` and `last` page are built from the URI of the
+/// request by replacing the query parameters `page` and `per_page`. Pages start at `1`.
+/// `last` and `next` are only known together with the [total](Self::total()) count of items,
+/// which is sent as `X-Total-Count`.
+///
+/// With the `checksum` feature an export can be [chained](Self::checksum()), so a client
+/// can verify it received a consistent sequence of pages.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::get;
+/// use rocket_response::Paginated;
+///
+/// #[get("/items?<page>&<per_page>")]
+/// fn items(page: Option<u64>, per_page: Option<u64>) -> Paginated<Vec<u64>> {
+///     let page = page.unwrap_or(1).max(1);
+///     let per_page = per_page.unwrap_or(10);
+///     let start = (page - 1).saturating_mul(per_page);
+///     let items = (start..page.saturating_mul(per_page).min(95)).collect();
+///
+///     Paginated::new(items).page(page).per_page(per_page).total(95)
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Paginated<T> {
+    items: T,
+    page: u64,
+    per_page: u64,
+    total: Option<u64>,
+    params: (&'static str, &'static str),
+    #[cfg(feature = "checksum")]
+    checksum: bool,
+}
+
+/// Query parameter of the checksum of the previous page
+const CHECKSUM_PARAM: &str = "checksum";
+
+impl<T> Paginated<T> {
+    /// First page of `items` with 20 items per page
+    pub fn new(items: T) -> Self {
+        Paginated {
+            items,
+            page: 1,
+            per_page: 20,
+            total: None,
+            params: ("page", "per_page"),
+            #[cfg(feature = "checksum")]
+            checksum: false,
+        }
+    }
+
+    /// Sets the number of the page starting at `1`.
+    pub fn page(mut self, page: u64) -> Self {
+        self.page = page.max(1);
+        self
+    }
+
+    /// Sets the number of items per page.
+    pub fn per_page(mut self, per_page: u64) -> Self {
+        self.per_page = per_page.max(1);
+        self
+    }
+
+    /// Sets the total count of items of all pages.
+    pub fn total(mut self, total: u64) -> Self {
+        self.total = Some(total);
+        self
+    }
+
+    /// Sets the names of the query parameters of the page number and the items per page.
+    pub fn params(mut self, page: &'static str, per_page: &'static str) -> Self {
+        self.params = (page, per_page);
+        self
+    }
+
+    /// Sends the [ChecksumChain] of the pages up to this one as `X-Checksum-Chain` and, on
+    /// the last page, its final checksum as `X-Checksum-Final`.
+    ///
+    /// The checksum of the previous page is read from the query parameter `checksum`,
+    /// which the `next` link contains. The `last` page is only known with the
+    /// [total](Self::total()) count.
+    ///
+    /// Requires the `checksum` feature.
+    ///
+    /// ## Example usage
+    ///
+    /// ```rust
+    /// use rocket::get;
+    /// use rocket_response::Paginated;
+    ///
+    /// #[get("/export?<page>")]
+    /// fn export(page: Option<u64>) -> Paginated<Vec<u64>> {
+    ///     let page = page.unwrap_or(1).max(1);
+    ///     let start = (page - 1).saturating_mul(100);
+    ///     let items = (start..page.saturating_mul(100).min(950)).collect();
+    ///
+    ///     Paginated::new(items).page(page).per_page(100).total(950).checksum()
+    /// }
+    /// ```
+    #[cfg(feature = "checksum")]
+    pub fn checksum(mut self) -> Self {
+        self.checksum = true;
+        self
+    }
+
+    /// Consumes the wrapper and returns the wrapped items.
+    pub fn into_inner(self) -> T {
+        self.items
+    }
+
+    /// Number of the last page, if the total count is known
+    fn last_page(&self) -> Option<u64> {
+        self.total
+            .map(|total| ((total + self.per_page - 1) / self.per_page).max(1))
+    }
+
+    /// Value of the `Link` header for the request `req`, the `next` link with `checksum`
+    fn links(&self, req: &Request<'_>, checksum: Option<&str>) -> String {
+        let uri = req.uri();
+        let mut base = format!("{}?", uri.path());
+        if let Some(query) = uri.query() {
+            for segment in query.raw_segments() {
+                let name = segment.as_str().split('=').next().unwrap_or("");
+                #[cfg(feature = "checksum")]
+                if self.checksum && name == CHECKSUM_PARAM {
+                    continue;
+                }
+                if name != self.params.0 && name != self.params.1 {
+                    base.push_str(segment.as_str());
+                    base.push('&');
+                }
+            }
+        }
+
+        let last = self.last_page();
+        let mut links = vec![("first", 1)];
+        if self.page > 1 {
+            links.push((
+                "prev",
+                last.map_or(self.page - 1, |last| last.min(self.page - 1)),
+            ));
+        }
+        if let Some(last) = last {
+            if self.page < last {
+                links.push(("next", self.page + 1));
+            }
+            links.push(("last", last));
+        }
+
+        let mut value = String::new();
+        for (rel, page) in links {
+            if !value.is_empty() {
+                value.push_str(", ");
+            }
+            let _ = write!(
+                value,
+                "<{}{}={}&{}={}",
+                base, self.params.0, page, self.params.1, self.per_page
+            );
+            if let (Some(checksum), "next") = (checksum, rel) {
+                let _ = write!(value, "&{}={}", CHECKSUM_PARAM, checksum);
+            }
+            let _ = write!(value, ">; rel=\"{}\"", rel);
+        }
+        value
+    }
+
+    /// Chain of the previous pages of `req` continued by the `body` of this page
+    #[cfg(feature = "checksum")]
+    fn chain(&self, req: &Request<'_>, body: &[u8]) -> ChecksumChain {
+        let previous = match self.page {
+            1 => "",
+            _ => req
+                .query_value::<&str>(CHECKSUM_PARAM)
+                .and_then(Result::ok)
+                .unwrap_or(""),
+        };
+        let mut chain = ChecksumChain::resume(previous, self.page - 1);
+        chain.update(body);
+        chain
+    }
+}
+
+impl<'r, T: Serialize> Responder<'r, 'static> for Paginated<T> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let buf = match body::to_json(&self.items) {
+            Ok(buf) => buf,
+            Err(e) => return e.respond_to(req),
+        };
+        #[cfg(feature = "checksum")]
+        let chain = self.checksum.then(|| self.chain(req, buf.as_ref()));
+        #[cfg(feature = "checksum")]
+        let checksum = chain.as_ref().map(ChecksumChain::value);
+        #[cfg(not(feature = "checksum"))]
+        let checksum = None;
+
+        let mut builder = Response::build_from(buf.respond_to(ContentType::JSON, req)?);
+        builder.header(Header::new("Link", self.links(req, checksum)));
+        #[cfg(feature = "checksum")]
+        if let Some(chain) = &chain {
+            builder.header(Header::new("X-Checksum-Chain", chain.value().to_string()));
+            if Some(self.page) == self.last_page() {
+                builder.header(Header::new("X-Checksum-Final", chain.finalize()));
+            }
+        }
+        if let Some(total) = self.total {
+            builder.header(Header::new("X-Total-Count", total.to_string()));
+        }
+        builder.ok()
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::Paginated;
+    #[cfg(feature = "checksum")]
+    use crate::ChecksumChain;
+    use crate::RocketResponse;
+    use rocket::{
+        get,
+        http::{ContentType, Status},
+        local::blocking::Client,
+        routes,
+        serde::json::json,
+    };
+
+    #[get("/items?<page>&<per_page>")]
+    fn route_items(page: u64, per_page: u64) -> RocketResponse {
+        let items: Vec<u64> = ((page - 1) * per_page..(page * per_page).min(25)).collect();
+        RocketResponse::Paginated(Box::new(
+            Paginated::new(json!(items))
+                .page(page)
+                .per_page(per_page)
+                .total(25),
+        ))
+    }
+
+    #[get("/unknown")]
+    fn route_unknown() -> Paginated<&'static str> {
+        Paginated::new("a").page(3).params("p", "n")
+    }
+
+    #[cfg(feature = "checksum")]
+    #[get("/export?<page>")]
+    fn route_export(page: u64) -> Paginated<Vec<u64>> {
+        let items = ((page - 1) * 10..(page * 10).min(25)).collect();
+        Paginated::new(items)
+            .page(page)
+            .per_page(10)
+            .total(25)
+            .checksum()
+    }
+
+    #[test]
+    fn test_paginated() {
+        let rocket = rocket::build().mount("/", routes![route_items, route_unknown]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/items?page=2&per_page=10&q=x").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(ContentType::JSON, res.content_type().unwrap());
+        assert_eq!(Some("25"), res.headers().get_one("X-Total-Count"));
+        assert_eq!(
+            Some(
+                "</items?q=x&page=1&per_page=10>; rel=\"first\", \
+                </items?q=x&page=1&per_page=10>; rel=\"prev\", \
+                </items?q=x&page=3&per_page=10>; rel=\"next\", \
+                </items?q=x&page=3&per_page=10>; rel=\"last\""
+            ),
+            res.headers().get_one("Link")
+        );
+        assert_eq!(
+            "[10,11,12,13,14,15,16,17,18,19]",
+            res.into_string().unwrap()
+        );
+
+        let res = client.get("/items?page=3&per_page=10").dispatch();
+        assert_eq!(
+            Some(
+                "</items?page=1&per_page=10>; rel=\"first\", \
+                </items?page=2&per_page=10>; rel=\"prev\", \
+                </items?page=3&per_page=10>; rel=\"last\""
+            ),
+            res.headers().get_one("Link")
+        );
+
+        let res = client.get("/unknown").dispatch();
+        assert_eq!(None, res.headers().get_one("X-Total-Count"));
+        assert_eq!(
+            Some("</unknown?p=1&n=20>; rel=\"first\", </unknown?p=2&n=20>; rel=\"prev\""),
+            res.headers().get_one("Link")
+        );
+    }
+
+    #[cfg(feature = "checksum")]
+    #[test]
+    fn test_paginated_checksum() {
+        let rocket = rocket::build().mount("/", routes![route_export]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let mut chain = ChecksumChain::new();
+        let mut uri = Some("/export?page=1".to_string());
+        let mut last = None;
+        while let Some(next) = uri.take() {
+            let res = client.get(next).dispatch();
+            assert_eq!(Status::Ok, res.status());
+            let checksum = res
+                .headers()
+                .get_one("X-Checksum-Chain")
+                .unwrap()
+                .to_string();
+            last = res.headers().get_one("X-Checksum-Final").map(String::from);
+            uri = res
+                .headers()
+                .get_one("Link")
+                .unwrap()
+                .split(", ")
+                .find(|link| link.ends_with("rel=\"next\""))
+                .map(|link| link[1..link.find('>').unwrap()].to_string());
+            assert!(chain.verify(&res.into_bytes().unwrap(), &checksum));
+            if let Some(uri) = &uri {
+                assert!(uri.ends_with(&format!("&checksum={}", checksum)));
+            }
+        }
+        assert_eq!(3, chain.pages());
+        assert_eq!(Some(chain.finalize()), last);
+
+        let res = client.get("/export?page=2&checksum=0").dispatch();
+        let checksum = res
+            .headers()
+            .get_one("X-Checksum-Chain")
+            .unwrap()
+            .to_string();
+        let mut chain = ChecksumChain::new();
+        chain.update(b"[0,1,2,3,4,5,6,7,8,9]");
+        assert!(!chain.verify(&res.into_bytes().unwrap(), &checksum));
+    }
+}
@@ -0,0 +1,407 @@
+#[cfg(feature = "compress")]
+use crate::Compressed;
+use crate::{
+    CachePolicy, Cached, CorsPolicy, ETag, Hardened, SecurityHeaders, WithCache, WithCookies,
+    WithCors,
+};
+use rocket::{
+    http::Cookie,
+    request::Request,
+    response::{self, Responder},
+};
+use std::{marker::PhantomData, time::SystemTime};
+
+/// Stages of a [Pipeline]
+///
+/// The stages are ordered [Body](stage::Body) → [Validators](stage::Validators) →
+/// `Compression` → [Caching](stage::Caching) →
+/// [Cookies](stage::Cookies) → [Security](stage::Security) → [Cors](stage::Cors).
+/// A wrapper can only be added in its own stage or after a stage in front of it.
+///
+/// The validators are evaluated before anything is compressed, so a `304 Not Modified` is
+/// answered without compressing the body. The compression stage adds the content coding to
+/// the `ETag`, like `"v1-gzip"`, and `Vary: Accept-Encoding` to the `200 OK` and the
+/// `304 Not Modified`. The security and CORS headers are added last, so they are sent
+/// with every response of the wrappers.
+///
+/// The `Compression` stage requires the `compress` feature.
+pub mod stage {
+    mod sealed {
+        pub trait Sealed {}
+    }
+
+    /// Marker of a [Pipeline](crate::Pipeline) stage
+    pub trait Stage: sealed::Sealed {}
+
+    /// Stages in front of [Validators]
+    pub trait BeforeValidators: Stage {}
+    #[cfg(feature = "compress")]
+    /// Stages in front of [Compression]
+    pub trait BeforeCompression: Stage {}
+    /// Stages in front of [Caching]
+    pub trait BeforeCaching: Stage {}
+    /// Stages in front of [Cookies]
+    pub trait BeforeCookies: Stage {}
+    /// Stages in front of [Security]
+    pub trait BeforeSecurity: Stage {}
+    /// Stages in front of [Cors]
+    pub trait BeforeCors: Stage {}
+
+    /// The plain responder without any wrapper
+    #[derive(Debug)]
+    pub struct Body;
+    /// `ETag` and `Last-Modified` by [Cached](crate::Cached)
+    #[derive(Debug)]
+    pub struct Validators;
+    #[cfg(feature = "compress")]
+    /// Content coding by [Compressed](crate::Compressed)
+    #[derive(Debug)]
+    pub struct Compression;
+    /// `Cache-Control` by [WithCache](crate::WithCache)
+    #[derive(Debug)]
+    pub struct Caching;
+    /// Cookie changes by [WithCookies](crate::WithCookies)
+    #[derive(Debug)]
+    pub struct Cookies;
+    /// Security headers by [Hardened](crate::Hardened)
+    #[derive(Debug)]
+    pub struct Security;
+    /// CORS headers by [WithCors](crate::WithCors)
+    #[derive(Debug)]
+    pub struct Cors;
+
+    impl sealed::Sealed for Body {}
+    impl sealed::Sealed for Validators {}
+    #[cfg(feature = "compress")]
+    impl sealed::Sealed for Compression {}
+    impl sealed::Sealed for Caching {}
+    impl sealed::Sealed for Cookies {}
+    impl sealed::Sealed for Security {}
+    impl sealed::Sealed for Cors {}
+
+    impl Stage for Body {}
+    impl Stage for Validators {}
+    #[cfg(feature = "compress")]
+    impl Stage for Compression {}
+    impl Stage for Caching {}
+    impl Stage for Cookies {}
+    impl Stage for Security {}
+    impl Stage for Cors {}
+
+    impl BeforeValidators for Body {}
+
+    #[cfg(feature = "compress")]
+    impl BeforeCompression for Body {}
+    #[cfg(feature = "compress")]
+    impl BeforeCompression for Validators {}
+
+    impl BeforeCaching for Body {}
+    impl BeforeCaching for Validators {}
+    #[cfg(feature = "compress")]
+    impl BeforeCaching for Compression {}
+
+    impl BeforeCookies for Body {}
+    impl BeforeCookies for Validators {}
+    #[cfg(feature = "compress")]
+    impl BeforeCookies for Compression {}
+    impl BeforeCookies for Caching {}
+
+    impl BeforeSecurity for Body {}
+    impl BeforeSecurity for Validators {}
+    #[cfg(feature = "compress")]
+    impl BeforeSecurity for Compression {}
+    impl BeforeSecurity for Caching {}
+    impl BeforeSecurity for Cookies {}
+
+    impl BeforeCors for Body {}
+    impl BeforeCors for Validators {}
+    #[cfg(feature = "compress")]
+    impl BeforeCors for Compression {}
+    impl BeforeCors for Caching {}
+    impl BeforeCors for Cookies {}
+    impl BeforeCors for Security {}
+}
+
+/// Builder composing the wrappers of this crate in a fixed order
+///
+/// Instead of nesting `WithCookies::new(WithCache::new(Cached::new(...), ...))` by hand,
+/// the wrappers are added by builder calls. The order of the [stages](stage) is checked
+/// at compile time, so e.g. the validators of [Cached] are always evaluated
+/// before the `Cache-Control` header and cookies are set.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::{get, http::Cookie};
+/// use rocket_response::{CachePolicy, CorsPolicy, ETag, Pipeline, RocketResponse};
+/// use std::time::Duration;
+///
+/// #[get("/profile")]
+/// fn profile<'r>() -> impl rocket::response::Responder<'r, 'static> {
+///     Pipeline::new(RocketResponse::StaticStr("Profile"))
+///         .etag(ETag::strong("v1"))
+///         .cache_policy(CachePolicy::new().private().max_age(Duration::from_secs(60)))
+///         .add_cookie(Cookie::new("visited", "1"))
+///         .harden()
+///         .cors(CorsPolicy::new().allow_origin("https://example.com"))
+/// }
+/// ```
+///
+/// Adding a wrapper of an earlier stage doesn't compile:
+///
+/// ```rust,compile_fail
+/// use rocket_response::{CachePolicy, ETag, Pipeline, RocketResponse};
+///
+/// let _ = Pipeline::new(RocketResponse::StaticStr("Profile"))
+///     .cache_policy(CachePolicy::no_store_policy())
+///     .etag(ETag::strong("v1"));
+/// ```
+#[derive(Debug)]
+pub struct Pipeline<R, S = stage::Body> {
+    responder: R,
+    stage: PhantomData<S>,
+}
+
+impl<R> Pipeline<R, stage::Body> {
+    /// Starts a pipeline for `responder`
+    pub fn new(responder: R) -> Self {
+        Pipeline {
+            responder,
+            stage: PhantomData,
+        }
+    }
+}
+
+impl<R, S: stage::Stage> Pipeline<R, S> {
+    fn next<N, T>(self, f: impl FnOnce(R) -> T) -> Pipeline<T, N> {
+        Pipeline {
+            responder: f(self.responder),
+            stage: PhantomData,
+        }
+    }
+
+    /// Consumes the pipeline and returns the composed wrappers.
+    pub fn into_inner(self) -> R {
+        self.responder
+    }
+}
+
+impl<R, S: stage::BeforeValidators> Pipeline<R, S> {
+    /// Wraps in [Cached] with the `ETag` validator.
+    pub fn etag(self, etag: ETag) -> Pipeline<Cached<R>, stage::Validators> {
+        self.next(|r| Cached::new(r).etag(etag))
+    }
+
+    /// Wraps in [Cached] with the `Last-Modified` validator.
+    pub fn last_modified(
+        self,
+        last_modified: SystemTime,
+    ) -> Pipeline<Cached<R>, stage::Validators> {
+        self.next(|r| Cached::new(r).last_modified(last_modified))
+    }
+}
+
+impl<R> Pipeline<Cached<R>, stage::Validators> {
+    /// Sets the `ETag` validator, see [Cached::etag].
+    pub fn etag(self, etag: ETag) -> Self {
+        self.next(|r| r.etag(etag))
+    }
+
+    /// Sets the `Last-Modified` validator, see [Cached::last_modified].
+    pub fn last_modified(self, last_modified: SystemTime) -> Self {
+        self.next(|r| r.last_modified(last_modified))
+    }
+}
+
+#[cfg(feature = "compress")]
+impl<R, S: stage::BeforeCompression> Pipeline<R, S> {
+    /// Wraps in [Compressed] with its defaults.
+    ///
+    /// Requires the `compress` feature.
+    ///
+    /// The validators have to be added before, so compressing and then adding an `ETag`
+    /// doesn't compile:
+    ///
+    /// ```rust,compile_fail
+    /// use rocket_response::{ETag, Pipeline, RocketResponse};
+    ///
+    /// let _ = Pipeline::new(RocketResponse::StaticStr("Profile"))
+    ///     .compress()
+    ///     .etag(ETag::strong("v1"));
+    /// ```
+    pub fn compress(self) -> Pipeline<Compressed<R>, stage::Compression> {
+        self.next(Compressed::new)
+    }
+}
+
+#[cfg(feature = "compress")]
+impl<R> Pipeline<Compressed<R>, stage::Compression> {
+    /// Sets the minimum size of compressed bodies, see [Compressed::min_size].
+    pub fn min_size(self, min_size: usize) -> Self {
+        self.next(|r| r.min_size(min_size))
+    }
+}
+
+impl<R, S: stage::BeforeCaching> Pipeline<R, S> {
+    /// Wraps in [WithCache] with `policy`.
+    pub fn cache_policy(self, policy: CachePolicy) -> Pipeline<WithCache<R>, stage::Caching> {
+        self.next(|r| WithCache::new(r, policy))
+    }
+}
+
+impl<R, S: stage::BeforeCookies> Pipeline<R, S> {
+    /// Wraps in [WithCookies] adding `cookie`.
+    pub fn add_cookie(self, cookie: Cookie<'static>) -> Pipeline<WithCookies<R>, stage::Cookies> {
+        self.next(|r| WithCookies::new(r).add_cookie(cookie))
+    }
+
+    /// Wraps in [WithCookies] removing `cookie`.
+    pub fn remove_cookie(
+        self,
+        cookie: Cookie<'static>,
+    ) -> Pipeline<WithCookies<R>, stage::Cookies> {
+        self.next(|r| WithCookies::new(r).remove_cookie(cookie))
+    }
+}
+
+impl<R> Pipeline<WithCookies<R>, stage::Cookies> {
+    /// Adds `cookie`, see [WithCookies::add_cookie].
+    pub fn add_cookie(self, cookie: Cookie<'static>) -> Self {
+        self.next(|r| r.add_cookie(cookie))
+    }
+
+    /// Removes `cookie`, see [WithCookies::remove_cookie].
+    pub fn remove_cookie(self, cookie: Cookie<'static>) -> Self {
+        self.next(|r| r.remove_cookie(cookie))
+    }
+}
+
+impl<R, S: stage::BeforeSecurity> Pipeline<R, S> {
+    /// Wraps in [Hardened] with the default [SecurityHeaders].
+    pub fn harden(self) -> Pipeline<Hardened<R>, stage::Security> {
+        self.next(Hardened::new)
+    }
+
+    /// Wraps in [Hardened] with `headers`.
+    pub fn security_headers(
+        self,
+        headers: SecurityHeaders,
+    ) -> Pipeline<Hardened<R>, stage::Security> {
+        self.next(|r| Hardened::with_headers(r, headers))
+    }
+}
+
+impl<R, S: stage::BeforeCors> Pipeline<R, S> {
+    /// Wraps in [WithCors] with `policy`.
+    pub fn cors(self, policy: CorsPolicy) -> Pipeline<WithCors<R>, stage::Cors> {
+        self.next(|r| WithCors::new(r, policy))
+    }
+}
+
+impl<'r, 'o: 'r, R: Responder<'r, 'o>, S> Responder<'r, 'o> for Pipeline<R, S> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        self.responder.respond_to(req)
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::Pipeline;
+    use crate::{CachePolicy, CorsPolicy, ETag, RocketResponse};
+    use rocket::{
+        get,
+        http::{Cookie, Header, Status},
+        local::blocking::Client,
+        response::Responder,
+        routes,
+    };
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[get("/pipeline")]
+    fn route_pipeline<'r>() -> impl Responder<'r, 'static> {
+        Pipeline::new(RocketResponse::StaticStr("Hello world"))
+            .etag(ETag::strong("v1"))
+            .last_modified(UNIX_EPOCH + Duration::from_secs(1_000_000_000))
+            .cache_policy(CachePolicy::new().public().max_age(Duration::from_secs(60)))
+            .add_cookie(Cookie::new("a", "1"))
+            .add_cookie(Cookie::new("b", "2"))
+            .harden()
+            .cors(CorsPolicy::new().allow_origin("https://example.com"))
+    }
+
+    #[cfg(feature = "compress")]
+    #[get("/pipeline/compressed")]
+    fn route_pipeline_compressed<'r>() -> impl Responder<'r, 'static> {
+        Pipeline::new(RocketResponse::StaticStr("Hello world"))
+            .etag(ETag::strong("v1"))
+            .compress()
+            .min_size(0)
+            .cache_policy(CachePolicy::new().public().max_age(Duration::from_secs(60)))
+    }
+
+    #[test]
+    fn test_pipeline() {
+        let rocket = rocket::build().mount("/", routes![route_pipeline]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/pipeline").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(Some("\"v1\""), res.headers().get_one("ETag"));
+        assert_eq!(
+            Some("public, max-age=60"),
+            res.headers().get_one("Cache-Control")
+        );
+        assert_eq!(2, res.headers().get("Set-Cookie").count());
+        assert_eq!(
+            Some("nosniff"),
+            res.headers().get_one("X-Content-Type-Options")
+        );
+        assert_eq!(None, res.headers().get_one("Access-Control-Allow-Origin"));
+
+        let res = client
+            .get("/pipeline")
+            .header(Header::new("Origin", "https://example.com"))
+            .dispatch();
+        assert_eq!(
+            Some("https://example.com"),
+            res.headers().get_one("Access-Control-Allow-Origin")
+        );
+
+        let res = client
+            .get("/pipeline")
+            .header(Header::new("If-None-Match", "\"v1\""))
+            .dispatch();
+        assert_eq!(Status::NotModified, res.status());
+    }
+
+    #[cfg(feature = "compress")]
+    #[test]
+    fn test_pipeline_compressed() {
+        let rocket = rocket::build().mount("/", routes![route_pipeline_compressed]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client
+            .get("/pipeline/compressed")
+            .header(Header::new("Accept-Encoding", "gzip"))
+            .dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(Some("gzip"), res.headers().get_one("Content-Encoding"));
+        assert_eq!(Some("\"v1-gzip\""), res.headers().get_one("ETag"));
+        assert_eq!(Some("Accept-Encoding"), res.headers().get_one("Vary"));
+        assert_eq!(
+            Some("public, max-age=60"),
+            res.headers().get_one("Cache-Control")
+        );
+
+        let res = client
+            .get("/pipeline/compressed")
+            .header(Header::new("Accept-Encoding", "gzip"))
+            .header(Header::new("If-None-Match", "\"v1-gzip\""))
+            .dispatch();
+        assert_eq!(Status::NotModified, res.status());
+        assert_eq!(None, res.headers().get_one("Content-Encoding"));
+        assert_eq!(Some("\"v1-gzip\""), res.headers().get_one("ETag"));
+        assert_eq!(Some("Accept-Encoding"), res.headers().get_one("Vary"));
+    }
+}
@@ -0,0 +1,266 @@
+//! Re-exports of the enums, the most-used wrappers and Rocket responders, and the
+//! construction macros
+//!
+//! A single glob import is enough for the routes of a usual application.
+//!
+//! ## Example usage
+//!
+//! ```rust
+//! use rocket::get;
+//! use rocket_response::prelude::*;
+//!
+//! #[get("/<id>")]
+//! fn item(id: usize) -> RocketResponse {
+//!     match id {
+//!         0 => html!("<h1>Start</h1>"),
+//!         1 => redirect!("/"),
+//!         _ => not_found!("no item"),
+//!     }
+//! }
+//! ```
+
+#[cfg(feature = "json")]
+pub use crate::json_ok;
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+pub use crate::RocketResponseGeneric3;
+pub use crate::{
+    bad_request, html, not_found, redirect, text, BuildError, CachePolicy, Cached, Download, Dyn,
+    ETag, Either2, Either3, Hardened, IntoRocketResponse, Lazy, RocketError, RocketResponse,
+    RocketResponseGeneric, RocketResponseGeneric2, SecurityHeaders, StreamedFile, TryRespond,
+    WithCache,
+};
+#[cfg(feature = "json")]
+pub use rocket::serde::json::Json;
+pub use rocket::{
+    fs::NamedFile,
+    http::{ContentType, Status},
+    response::{
+        content::{RawHtml, RawJson, RawText},
+        status::{BadRequest, Created, Custom, NoContent, NotFound},
+        Flash, Redirect,
+    },
+};
+
+/// `Html` variant of the HTML `body`
+///
+/// The variant is of [RocketResponse](crate::RocketResponse), or of the enum named before
+/// the body.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket_response::{html, RocketResponse, RocketResponseGeneric};
+///
+/// let res: RocketResponse = html!("<p>Hello world</p>");
+/// let res: RocketResponseGeneric<String> = html!(RocketResponseGeneric, format!("<p>{}</p>", 1));
+/// ```
+#[macro_export]
+macro_rules! html {
+    ($enum:ident, $body:expr) => {
+        $crate::$enum::Html(::rocket::response::content::RawHtml($body))
+    };
+    ($body:expr) => {
+        $crate::html!(RocketResponse, $body)
+    };
+}
+
+/// `Plain` variant of the text `body`
+///
+/// The variant is of [RocketResponse](crate::RocketResponse), or of the enum named before
+/// the body.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket_response::{text, RocketResponse};
+///
+/// let res: RocketResponse = text!("Hello world");
+/// ```
+#[macro_export]
+macro_rules! text {
+    ($enum:ident, $body:expr) => {
+        $crate::$enum::Plain(::rocket::response::content::RawText($body))
+    };
+    ($body:expr) => {
+        $crate::text!(RocketResponse, $body)
+    };
+}
+
+/// `SerdeJson` variant serializing `value`
+///
+/// The variant is of `RocketResponseGeneric3`, whose serde type is independent of the
+/// content type, or of the enum named before the value.
+///
+/// Requires the `json` feature.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::serde::Serialize;
+/// use rocket_response::{json_ok, RocketResponseGeneric, RocketResponseGeneric3};
+///
+/// #[derive(Serialize)]
+/// #[serde(crate = "rocket::serde")]
+/// struct Item {
+///     id: usize,
+/// }
+///
+/// let res: RocketResponseGeneric3<String, Item, ()> = json_ok!(Item { id: 1 });
+/// let res: RocketResponseGeneric<&str> = json_ok!(RocketResponseGeneric, "Hello world");
+/// ```
+#[cfg(feature = "json")]
+#[macro_export]
+macro_rules! json_ok {
+    ($enum:ident, $value:expr) => {
+        $crate::$enum::SerdeJson(::rocket::serde::json::Json($value))
+    };
+    ($value:expr) => {
+        $crate::json_ok!(RocketResponseGeneric3, $value)
+    };
+}
+
+/// `NotFound` variant with the body `msg`
+///
+/// The variant is of [RocketResponse](crate::RocketResponse), or of the enum named before
+/// the message.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket_response::{not_found, RocketResponse};
+///
+/// let res: RocketResponse = not_found!("no item");
+/// ```
+#[macro_export]
+macro_rules! not_found {
+    ($enum:ident, $msg:expr) => {
+        $crate::$enum::NotFound(::rocket::response::status::NotFound($msg))
+    };
+    ($msg:expr) => {
+        $crate::not_found!(RocketResponse, $msg)
+    };
+}
+
+/// `BadRequest` variant with the body `msg`
+///
+/// The variant is of [RocketResponse](crate::RocketResponse), or of the enum named before
+/// the message.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket_response::{bad_request, RocketResponse};
+///
+/// let res: RocketResponse = bad_request!("invalid id");
+/// ```
+#[macro_export]
+macro_rules! bad_request {
+    ($enum:ident, $msg:expr) => {
+        $crate::$enum::BadRequest(::rocket::response::status::BadRequest(Some($msg)))
+    };
+    ($msg:expr) => {
+        $crate::bad_request!(RocketResponse, $msg)
+    };
+}
+
+/// `Redirect` variant of a `303 See Other` to `uri`
+///
+/// The variant is of [RocketResponse](crate::RocketResponse), or of the enum named before
+/// the URI.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket_response::{redirect, RocketResponse};
+///
+/// let res: RocketResponse = redirect!("/login");
+/// ```
+#[macro_export]
+macro_rules! redirect {
+    ($enum:ident, $uri:expr) => {
+        $crate::$enum::Redirect(::std::boxed::Box::new(::rocket::response::Redirect::to(
+            $uri,
+        )))
+    };
+    ($uri:expr) => {
+        $crate::redirect!(RocketResponse, $uri)
+    };
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use crate::prelude::*;
+    use rocket::{get, local::blocking::Client, routes};
+
+    #[get("/<id>")]
+    fn route_prelude(id: usize) -> RocketResponse {
+        match id {
+            0 => html!("<p>Hello world</p>"),
+            1 => text!("Hello world"),
+            2 => bad_request!("invalid id"),
+            3 => redirect!("/0"),
+            _ => not_found!("no item"),
+        }
+    }
+
+    #[get("/generic/<id>")]
+    fn route_prelude_generic(id: usize) -> RocketResponseGeneric<String> {
+        match id {
+            0 => html!(RocketResponseGeneric, format!("<p>{}</p>", id)),
+            _ => not_found!(RocketResponseGeneric, format!("no item {}", id)),
+        }
+    }
+
+    #[cfg(feature = "json")]
+    #[get("/json/<id>")]
+    fn route_prelude_json(id: usize) -> RocketResponseGeneric3<String, Vec<usize>, ()> {
+        json_ok!(vec![id])
+    }
+
+    #[test]
+    fn test_prelude() {
+        let rocket = rocket::build().mount("/", routes![route_prelude, route_prelude_generic]);
+        #[cfg(feature = "json")]
+        let rocket = rocket.mount("/", routes![route_prelude_json]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/0").dispatch();
+        assert_eq!(Some(ContentType::HTML), res.content_type());
+        assert_eq!("<p>Hello world</p>", res.into_string().unwrap());
+
+        let res = client.get("/1").dispatch();
+        assert_eq!(Some(ContentType::Plain), res.content_type());
+
+        let res = client.get("/2").dispatch();
+        assert_eq!(Status::BadRequest, res.status());
+        assert_eq!("invalid id", res.into_string().unwrap());
+
+        let res = client.get("/3").dispatch();
+        assert_eq!(Status::SeeOther, res.status());
+        assert_eq!(Some("/0"), res.headers().get_one("Location"));
+
+        let res = client.get("/4").dispatch();
+        assert_eq!(Status::NotFound, res.status());
+        assert_eq!("no item", res.into_string().unwrap());
+
+        let res = client.get("/generic/0").dispatch();
+        assert_eq!("<p>0</p>", res.into_string().unwrap());
+        let res = client.get("/generic/7").dispatch();
+        assert_eq!(Status::NotFound, res.status());
+        assert_eq!("no item 7", res.into_string().unwrap());
+
+        #[cfg(feature = "json")]
+        {
+            let res = client.get("/json/5").dispatch();
+            assert_eq!(Some(ContentType::JSON), res.content_type());
+            assert_eq!("[5]", res.into_string().unwrap());
+        }
+    }
+}
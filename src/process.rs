@@ -0,0 +1,370 @@
+use rocket::{
+    http::{ContentType, Status},
+    request::Request,
+    response::{self, Responder, Response},
+    tokio::{
+        io::{AsyncRead, AsyncReadExt, ReadBuf},
+        process::{ChildStdout, Command},
+        time::{self, Instant, Sleep},
+    },
+};
+use std::{
+    fmt,
+    future::Future,
+    io::{self, Cursor},
+    pin::Pin,
+    process::{ExitStatus, Stdio},
+    task::{Context, Poll},
+    time::Duration,
+};
+
+/// Size of the output read before responding
+const BUFFERED: usize = 8192;
+
+type Exit = Pin<Box<dyn Future<Output = io::Result<ExitStatus>> + Send>>;
+
+/// State of the command
+enum Run {
+    /// Not spawned yet
+    Pending(Box<Command>),
+    /// Running with the output read so far
+    Running(Vec<u8>, ChildStdout, Exit),
+    /// Exited with the complete output
+    Exited(ExitStatus, Vec<u8>),
+    /// Not finished in time
+    TimedOut,
+    /// Spawning or reading failed.
+    Failed(io::Error),
+}
+
+/// Output of a command streamed as body
+///
+/// The command runs with `stdin` closed and its `stdout` as body, `stderr` is inherited.
+/// The output is limited to [max_bytes](Self::max_bytes()) and the run to the
+/// [timeout](Self::timeout()), then the command is killed and the connection aborted, so the
+/// client doesn't take the truncated body as complete.
+///
+/// The status depends on the exit code, if it is known before responding: after
+/// [spawn](Self::spawn()) the first 8 KiB of the output are read, so a command exiting with
+/// a short output responds with the status mapped by [exit_status](Self::exit_status()),
+/// `500 Internal Server Error` for any unmapped failure and `504 Gateway Timeout` if no
+/// output arrives in time. A command still running, or only spawned when responding, is
+/// streamed with `200 OK` and a failing exit code aborts the connection.
+///
+/// Requires the `process` feature.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::{get, http::Status, tokio::process::Command};
+/// use rocket_response::{CommandOutput, RocketResponse};
+/// use std::time::Duration;
+///
+/// #[get("/report")]
+/// async fn report() -> RocketResponse {
+///     let mut command = Command::new("report-gen");
+///     command.arg("--csv");
+///     let output = CommandOutput::new(command)
+///         .max_bytes(10 * 1024 * 1024)
+///         .timeout(Duration::from_secs(30))
+///         .exit_status(2, Status::BadRequest);
+///     RocketResponse::CommandOutput(Box::new(output.spawn().await))
+/// }
+/// ```
+pub struct CommandOutput {
+    run: Run,
+    max_bytes: u64,
+    timeout: Duration,
+    deadline: Option<Instant>,
+    exit_statuses: Vec<(i32, Status)>,
+    content_type: ContentType,
+}
+
+impl CommandOutput {
+    /// Output of `command` as plain text, limited to 1 MiB and 60 seconds
+    pub fn new<C: Into<Command>>(command: C) -> Self {
+        CommandOutput {
+            run: Run::Pending(Box::new(command.into())),
+            max_bytes: 1024 * 1024,
+            timeout: Duration::from_secs(60),
+            deadline: None,
+            exit_statuses: Vec::new(),
+            content_type: ContentType::Plain,
+        }
+    }
+
+    /// Sets the maximum size of the output.
+    pub fn max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Sets the maximum time from spawning to the end of the output.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Responds the exit `code` with `status`, instead of `500 Internal Server Error` for a
+    /// failure or `200 OK` for `0`.
+    pub fn exit_status(mut self, code: i32, status: Status) -> Self {
+        self.exit_statuses.push((code, status));
+        self
+    }
+
+    /// Sets the content type.
+    pub fn content_type(mut self, content_type: ContentType) -> Self {
+        self.content_type = content_type;
+        self
+    }
+
+    /// Spawns the command and reads the start of its output, so the status can depend on the
+    /// exit code of a command exiting early.
+    pub async fn spawn(mut self) -> Self {
+        let (mut output, mut stdout, exit) = match self.started() {
+            Run::Running(output, stdout, exit) => (output, stdout, exit),
+            run => {
+                self.run = run;
+                return self;
+            }
+        };
+
+        let deadline = self.deadline.unwrap_or_else(Instant::now);
+        let limit = (BUFFERED as u64).min(self.max_bytes + 1);
+        let read = time::timeout_at(deadline, async {
+            let mut limited = (&mut stdout).take(limit);
+            limited.read_to_end(&mut output).await
+        });
+        self.run = match read.await {
+            Err(_) => Run::TimedOut,
+            Ok(Err(e)) => Run::Failed(e),
+            Ok(Ok(_)) if (output.len() as u64) < limit => {
+                match time::timeout_at(deadline, exit).await {
+                    Ok(Ok(status)) => Run::Exited(status, output),
+                    Ok(Err(e)) => Run::Failed(e),
+                    Err(_) => Run::TimedOut,
+                }
+            }
+            Ok(Ok(_)) => Run::Running(output, stdout, exit),
+        };
+        self
+    }
+
+    /// Takes the state, spawning the command if it is pending.
+    fn started(&mut self) -> Run {
+        match std::mem::replace(&mut self.run, Run::TimedOut) {
+            Run::Pending(command) => self.start(*command),
+            run => run,
+        }
+    }
+
+    /// Spawns `command` with the output piped.
+    fn start(&mut self, mut command: Command) -> Run {
+        command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .kill_on_drop(true);
+        let mut child = match command.spawn() {
+            Ok(child) => child,
+            Err(e) => return Run::Failed(e),
+        };
+        self.deadline = Some(Instant::now() + self.timeout);
+        match child.stdout.take() {
+            Some(stdout) => Run::Running(
+                Vec::new(),
+                stdout,
+                Box::pin(async move { child.wait().await }),
+            ),
+            None => Run::Failed(io::Error::new(io::ErrorKind::Other, "no stdout")),
+        }
+    }
+
+    /// Status of the exit `status`
+    fn status(&self, status: ExitStatus) -> Status {
+        let mapped = status.code().and_then(|code| {
+            self.exit_statuses
+                .iter()
+                .find(|(c, _)| *c == code)
+                .map(|(_, status)| *status)
+        });
+        match mapped {
+            Some(mapped) => mapped,
+            None if status.success() => Status::Ok,
+            None => Status::InternalServerError,
+        }
+    }
+}
+
+impl fmt::Debug for CommandOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let run = match &self.run {
+            Run::Pending(command) => format!("Pending({:?})", command),
+            Run::Running(output, ..) => format!("Running({} bytes)", output.len()),
+            Run::Exited(status, output) => format!("Exited({}, {} bytes)", status, output.len()),
+            Run::TimedOut => "TimedOut".to_string(),
+            Run::Failed(e) => format!("Failed({})", e),
+        };
+        f.debug_struct("CommandOutput")
+            .field("run", &run)
+            .field("max_bytes", &self.max_bytes)
+            .field("timeout", &self.timeout)
+            .field("exit_statuses", &self.exit_statuses)
+            .field("content_type", &self.content_type)
+            .finish()
+    }
+}
+
+impl<'r> Responder<'r, 'static> for CommandOutput {
+    fn respond_to(mut self, req: &'r Request<'_>) -> response::Result<'static> {
+        let mut res = Response::build();
+        res.header(self.content_type.clone());
+        match self.started() {
+            Run::Running(output, stdout, exit) => {
+                let deadline = self.deadline.unwrap_or_else(Instant::now);
+                res.streamed_body(Output {
+                    output: Cursor::new(output),
+                    stdout,
+                    exit,
+                    remaining: self.max_bytes,
+                    deadline: Box::pin(time::sleep_until(deadline)),
+                });
+            }
+            Run::Exited(status, output) => {
+                res.status(self.status(status))
+                    .sized_body(output.len(), Cursor::new(output));
+            }
+            Run::TimedOut => {
+                rocket::error_!("{}: command timed out", req.uri());
+                return Err(Status::GatewayTimeout);
+            }
+            Run::Failed(e) => {
+                rocket::error_!("{}: command failed: {}", req.uri(), e);
+                return Err(Status::InternalServerError);
+            }
+            Run::Pending(_) => unreachable!(),
+        }
+        res.ok()
+    }
+}
+
+/// Body of the output read so far and the remaining output of the running command
+struct Output {
+    output: Cursor<Vec<u8>>,
+    stdout: ChildStdout,
+    exit: Exit,
+    remaining: u64,
+    deadline: Pin<Box<Sleep>>,
+}
+
+impl AsyncRead for Output {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = &mut *self;
+        if this.deadline.as_mut().poll(cx).is_ready() {
+            return Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "command timed out",
+            )));
+        }
+
+        let filled = buf.filled().len();
+        let poll = if (this.output.position() as usize) < this.output.get_ref().len() {
+            Pin::new(&mut this.output).poll_read(cx, buf)
+        } else {
+            Pin::new(&mut this.stdout).poll_read(cx, buf)
+        };
+        let read = match poll {
+            Poll::Ready(Ok(())) => (buf.filled().len() - filled) as u64,
+            other => return other,
+        };
+        if read > this.remaining {
+            buf.set_filled(filled);
+            return Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::Other,
+                "command output exceeds the limit",
+            )));
+        }
+        this.remaining -= read;
+        if read > 0 {
+            return Poll::Ready(Ok(()));
+        }
+
+        match this.exit.as_mut().poll(cx) {
+            Poll::Ready(Ok(status)) if status.success() => Poll::Ready(Ok(())),
+            Poll::Ready(Ok(status)) => Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::Other,
+                format!("command failed with {}", status),
+            ))),
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::CommandOutput;
+    use crate::RocketResponse;
+    use rocket::{
+        get,
+        http::{ContentType, Status},
+        local::blocking::Client,
+        routes,
+        tokio::process::Command,
+    };
+    use std::time::Duration;
+
+    fn sh(script: &str) -> Command {
+        let mut command = Command::new("sh");
+        command.arg("-c").arg(script);
+        command
+    }
+
+    #[get("/command?<script>&<spawn>")]
+    async fn route_command(script: &str, spawn: bool) -> RocketResponse {
+        let output = CommandOutput::new(sh(script))
+            .max_bytes(100_000)
+            .timeout(Duration::from_millis(500))
+            .exit_status(3, Status::BadRequest);
+        RocketResponse::CommandOutput(Box::new(if spawn { output.spawn().await } else { output }))
+    }
+
+    #[test]
+    fn test_command_output() {
+        let rocket = rocket::build().mount("/", routes![route_command]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+        let get = |script: &str, spawn: bool| {
+            client
+                .get(format!("/command?script={}&spawn={}", script, spawn))
+                .dispatch()
+        };
+
+        for spawn in [true, false].iter() {
+            let res = get("printf%20hello", *spawn);
+            assert_eq!(Status::Ok, res.status());
+            assert_eq!(Some(ContentType::Plain), res.content_type());
+            assert_eq!("hello", res.into_string().unwrap());
+
+            let res = get("head%20-c%2050000%20/dev/zero", *spawn);
+            assert_eq!(vec![0; 50000], res.into_bytes().unwrap());
+        }
+
+        let res = get("printf%20usage;exit%203", true);
+        assert_eq!(Status::BadRequest, res.status());
+        assert_eq!("usage", res.into_string().unwrap());
+        let res = get("exit%201", true);
+        assert_eq!(Status::InternalServerError, res.status());
+        let res = get("sleep%205", true);
+        assert_eq!(Status::GatewayTimeout, res.status());
+        let res = get("printf%20usage;exit%203", false);
+        assert_eq!(Status::Ok, res.status());
+
+        let res = get("head%20-c%20200000%20/dev/zero", true);
+        assert_eq!(Status::Ok, res.status());
+        assert!(res.into_bytes().map_or(true, |body| body.len() <= 100_000));
+    }
+}
@@ -0,0 +1,222 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use hmac::{Hmac, Mac};
+use rocket::{
+    fs::NamedFile,
+    http::Status,
+    request::Request,
+    response::{self, Responder},
+};
+use sha2::Sha256;
+use std::{
+    fmt,
+    io::{self, ErrorKind},
+    path::Path,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Signer of time-limited URLs for [ProtectedFile] responses, managed by Rocket
+///
+/// A signed URL has the query parameters `expires`, the expiry in seconds since the Unix
+/// epoch, and `signature`, the URL-safe base64 HMAC-SHA256 of the path and the expiry. The
+/// path is signed as in the URI, percent-encoded, other query parameters aren't signed.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket_response::UrlSigner;
+/// use std::time::Duration;
+///
+/// let signer = UrlSigner::new("a secret key of the server");
+/// let url = signer.sign("/downloads/report.pdf", Duration::from_secs(3600));
+/// assert!(url.starts_with("/downloads/report.pdf?expires="));
+///
+/// let rocket = rocket::build().manage(signer);
+/// ```
+#[derive(Clone)]
+pub struct UrlSigner {
+    key: Arc<[u8]>,
+}
+
+impl UrlSigner {
+    /// Signer with the secret `key`
+    pub fn new<K: AsRef<[u8]>>(key: K) -> Self {
+        UrlSigner {
+            key: key.as_ref().into(),
+        }
+    }
+
+    /// `path` with the query of a signature valid for `valid_for` from now
+    pub fn sign(&self, path: &str, valid_for: Duration) -> String {
+        self.sign_until(path, SystemTime::now() + valid_for)
+    }
+
+    /// `path` with the query of a signature valid until `expires`
+    pub fn sign_until(&self, path: &str, expires: SystemTime) -> String {
+        let expires = expires
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |expires| expires.as_secs());
+        let signature = URL_SAFE_NO_PAD.encode(self.mac(path, expires).finalize().into_bytes());
+        format!("{}?expires={}&signature={}", path, expires, signature)
+    }
+
+    /// Checks the signature of `req`, `403 Forbidden` if it is missing or invalid,
+    /// `410 Gone` if it is expired.
+    pub fn verify(&self, req: &Request<'_>) -> Result<(), Status> {
+        let (mut expires, mut signature) = (None, None);
+        for (name, value) in req.uri().query().into_iter().flat_map(|q| q.segments()) {
+            match name {
+                "expires" => expires = value.parse::<u64>().ok(),
+                "signature" => signature = URL_SAFE_NO_PAD.decode(value).ok(),
+                _ => {}
+            }
+        }
+        let (expires, signature) = expires.zip(signature).ok_or(Status::Forbidden)?;
+
+        let path = req.uri().path();
+        self.mac(path.as_str(), expires)
+            .verify_slice(&signature)
+            .map_err(|_| Status::Forbidden)?;
+        if UNIX_EPOCH + Duration::from_secs(expires) < SystemTime::now() {
+            return Err(Status::Gone);
+        }
+        Ok(())
+    }
+
+    /// MAC of `path` and `expires`
+    fn mac(&self, path: &str, expires: u64) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(&self.key).expect("HMAC takes any key size");
+        mac.update(path.as_bytes());
+        mac.update(b"\n");
+        mac.update(expires.to_string().as_bytes());
+        mac
+    }
+}
+
+impl fmt::Debug for UrlSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("UrlSigner")
+    }
+}
+
+/// File responded only to a request with a valid signature of the managed [UrlSigner]
+///
+/// A request without a valid signature is forwarded to the `403 Forbidden` catcher, one
+/// with an expired signature to the `410 Gone` catcher, before the existence of the file
+/// is revealed by a `404 Not Found`. Without a managed [UrlSigner] the response is a logged
+/// `500 Internal Server Error`.
+///
+/// Requires the `signed-files` feature.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::get;
+/// use rocket_response::{ProtectedFile, RocketResponse, UrlSigner};
+/// use std::path::{Path, PathBuf};
+///
+/// #[get("/downloads/<name>")]
+/// async fn download(name: PathBuf) -> RocketResponse {
+///     let file = ProtectedFile::open(Path::new("downloads").join(name)).await;
+///     RocketResponse::ProtectedFile(Box::new(file))
+/// }
+///
+/// let rocket = rocket::build().manage(UrlSigner::new("a secret key of the server"));
+/// ```
+#[derive(Debug)]
+pub struct ProtectedFile {
+    file: io::Result<NamedFile>,
+}
+
+impl ProtectedFile {
+    /// Opens the file at `path`, an error is only responded to a signed request.
+    pub async fn open<P: AsRef<Path>>(path: P) -> Self {
+        ProtectedFile {
+            file: NamedFile::open(path).await,
+        }
+    }
+
+    /// Protects the opened `file`.
+    pub fn new(file: NamedFile) -> Self {
+        ProtectedFile { file: Ok(file) }
+    }
+}
+
+impl<'r> Responder<'r, 'static> for ProtectedFile {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        match req.rocket().state::<UrlSigner>() {
+            Some(signer) => signer.verify(req)?,
+            None => {
+                rocket::error_!("{}: no UrlSigner managed", req.uri());
+                return Err(Status::InternalServerError);
+            }
+        }
+        match self.file {
+            Ok(file) => file.respond_to(req),
+            Err(e) if e.kind() == ErrorKind::NotFound => Err(Status::NotFound),
+            Err(e) => {
+                rocket::error_!("{}: opening the file failed: {}", req.uri(), e);
+                Err(Status::InternalServerError)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::{ProtectedFile, UrlSigner};
+    use crate::RocketResponse;
+    use rocket::{get, http::Status, local::blocking::Client, routes};
+    use std::time::{Duration, SystemTime};
+
+    #[get("/files/<name>")]
+    async fn route_protected_file(name: &str) -> RocketResponse {
+        RocketResponse::ProtectedFile(Box::new(ProtectedFile::open(name).await))
+    }
+
+    #[test]
+    fn test_protected_file() {
+        let signer = UrlSigner::new("secret");
+        let rocket = rocket::build()
+            .manage(signer.clone())
+            .mount("/", routes![route_protected_file]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+        let status = |uri: &str| client.get(uri.to_string()).dispatch().status();
+
+        let url = signer.sign("/files/Cargo.toml", Duration::from_secs(60));
+        let res = client.get(url.clone()).dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(std::fs::read("Cargo.toml").ok(), res.into_bytes());
+
+        assert_eq!(Status::Forbidden, status("/files/Cargo.toml"));
+        assert_eq!(
+            Status::Forbidden,
+            status(&url.replace("Cargo.toml", "Cargo.lock"))
+        );
+        assert_eq!(
+            Status::Forbidden,
+            status(&url.replace("expires=", "expires=1"))
+        );
+        let other = UrlSigner::new("other").sign("/files/Cargo.toml", Duration::from_secs(60));
+        assert_eq!(Status::Forbidden, status(&other));
+
+        let expired = SystemTime::now() - Duration::from_secs(1);
+        assert_eq!(
+            Status::Gone,
+            status(&signer.sign_until("/files/Cargo.toml", expired))
+        );
+        assert_eq!(Status::Forbidden, status("/files/missing"));
+        assert_eq!(
+            Status::NotFound,
+            status(&signer.sign("/files/missing", Duration::from_secs(60)))
+        );
+
+        let rocket = rocket::build().mount("/", routes![route_protected_file]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+        let res = client.get(url).dispatch();
+        assert_eq!(Status::InternalServerError, res.status());
+    }
+}
@@ -0,0 +1,219 @@
+use rocket::{
+    http::Header,
+    request::Request,
+    response::{self, Responder},
+};
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Quota of an API key in the current window
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quota {
+    /// Units per window
+    pub limit: u64,
+    /// Units left in the current window
+    pub remaining: u64,
+    /// Time until the window resets
+    pub reset: Duration,
+}
+
+#[derive(Debug)]
+struct Usage {
+    used: u64,
+    start: Instant,
+}
+
+/// Tracker of the used units per API key in fixed windows, to be managed by Rocket
+///
+/// [QuotaHeaders] reads it from the managed state.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket_response::QuotaTracker;
+/// use std::time::Duration;
+///
+/// let rocket = rocket::build().manage(QuotaTracker::new(1000, Duration::from_secs(3600)));
+/// ```
+#[derive(Debug)]
+pub struct QuotaTracker {
+    limit: u64,
+    window: Duration,
+    usage: Mutex<HashMap<String, Usage>>,
+}
+
+impl QuotaTracker {
+    /// Tracker allowing `limit` units per `window` for every API key
+    pub fn new(limit: u64, window: Duration) -> Self {
+        QuotaTracker {
+            limit,
+            window,
+            usage: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Uses `units` of the quota of `key` and returns the quota afterwards.
+    ///
+    /// The units are counted also beyond the limit, so the remaining units stay `0`
+    /// until the window resets.
+    pub fn consume(&self, key: &str, units: u64) -> Quota {
+        let now = Instant::now();
+        let mut usage = self.usage.lock().unwrap_or_else(|e| e.into_inner());
+        let entry = usage.entry(key.to_string()).or_insert(Usage {
+            used: 0,
+            start: now,
+        });
+        if now.duration_since(entry.start) >= self.window {
+            entry.used = 0;
+            entry.start = now;
+        }
+        entry.used = entry.used.saturating_add(units);
+
+        Quota {
+            limit: self.limit,
+            remaining: self.limit.saturating_sub(entry.used),
+            reset: (entry.start + self.window).saturating_duration_since(now),
+        }
+    }
+
+    /// Current quota of `key`
+    pub fn quota(&self, key: &str) -> Quota {
+        self.consume(key, 0)
+    }
+}
+
+/// Wrapper setting the `X-Quota-Limit`, `X-Quota-Remaining` and `X-Quota-Reset` headers
+///
+/// The quota of the API key is read from the [QuotaTracker] in the managed state. If the
+/// wrapped response has an `X-Request-Cost` header, e.g. of [WithCost](crate::WithCost),
+/// these units are consumed first. `X-Quota-Reset` is the number of seconds until the
+/// window resets.
+///
+/// Without a managed [QuotaTracker] the response is left unchanged. A response already
+/// carrying `X-Quota-Limit`, e.g. of a nested [QuotaHeaders], is left unchanged too, and no
+/// units are consumed again.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::get;
+/// use rocket_response::{QuotaHeaders, RocketResponse};
+///
+/// #[get("/search?<key>")]
+/// fn search(key: String) -> RocketResponse {
+///     RocketResponse::QuotaHeaders(QuotaHeaders::new(RocketResponse::StaticStr("[]"), key))
+/// }
+/// ```
+#[derive(Debug)]
+pub struct QuotaHeaders<R> {
+    responder: Box<R>,
+    key: String,
+}
+
+impl<R> QuotaHeaders<R> {
+    /// Wraps `responder` to respond with the quota of the API key `key`
+    pub fn new<K: Into<String>>(responder: R, key: K) -> Self {
+        QuotaHeaders {
+            responder: Box::new(responder),
+            key: key.into(),
+        }
+    }
+
+    /// Consumes the wrapper and returns the wrapped responder.
+    pub fn into_inner(self) -> R {
+        *self.responder
+    }
+
+    pub(crate) fn responder(&self) -> &R {
+        &self.responder
+    }
+}
+
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for QuotaHeaders<R> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let mut res = self.responder.respond_to(req)?;
+        if res.headers().contains("X-Quota-Limit") {
+            return Ok(res);
+        }
+        let tracker = match req.rocket().state::<QuotaTracker>() {
+            Some(tracker) => tracker,
+            None => {
+                rocket::warn_!("QuotaHeaders without managed QuotaTracker");
+                return Ok(res);
+            }
+        };
+
+        let cost = res
+            .headers()
+            .get_one("X-Request-Cost")
+            .and_then(|cost| cost.parse().ok())
+            .unwrap_or(0);
+        let quota = tracker.consume(&self.key, cost);
+
+        res.set_header(Header::new("X-Quota-Limit", quota.limit.to_string()));
+        res.set_header(Header::new(
+            "X-Quota-Remaining",
+            quota.remaining.to_string(),
+        ));
+        res.set_header(Header::new(
+            "X-Quota-Reset",
+            quota.reset.as_secs().to_string(),
+        ));
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::{QuotaHeaders, QuotaTracker};
+    use crate::{Cost, RocketResponse, WithCost};
+    use rocket::{get, local::blocking::Client, routes};
+    use std::time::Duration;
+
+    fn units(cost: &Cost) -> u64 {
+        cost.items.unwrap_or(0)
+    }
+
+    #[get("/quota/<key>")]
+    fn route_quota(key: &str) -> RocketResponse {
+        RocketResponse::QuotaHeaders(QuotaHeaders::new(
+            RocketResponse::WithCost(
+                WithCost::new(RocketResponse::StaticStr("Hello world"), units).items(4),
+            ),
+            key,
+        ))
+    }
+
+    #[test]
+    fn test_quota_tracker() {
+        let tracker = QuotaTracker::new(10, Duration::from_millis(20));
+        assert_eq!(7, tracker.consume("a", 3).remaining);
+        assert_eq!(0, tracker.consume("a", 30).remaining);
+        assert_eq!(10, tracker.quota("b").remaining);
+
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(10, tracker.quota("a").remaining);
+    }
+
+    #[test]
+    fn test_quota_headers() {
+        let rocket = rocket::build()
+            .manage(QuotaTracker::new(10, Duration::from_secs(60)))
+            .mount("/", routes![route_quota]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        for remaining in ["6", "2", "0"].iter() {
+            let res = client.get("/quota/abc").dispatch();
+            assert_eq!(Some("10"), res.headers().get_one("X-Quota-Limit"));
+            assert_eq!(Some(*remaining), res.headers().get_one("X-Quota-Remaining"));
+            assert!(res.headers().get_one("X-Quota-Reset").is_some());
+        }
+
+        let res = client.get("/quota/other").dispatch();
+        assert_eq!(Some("6"), res.headers().get_one("X-Quota-Remaining"));
+    }
+}
@@ -0,0 +1,378 @@
+use rocket::{
+    fs::NamedFile,
+    http::{ContentType, Header, Status},
+    request::Request,
+    response::{self, Responder, Response},
+    tokio::{
+        fs::File,
+        io::{AsyncRead, AsyncSeek, ReadBuf},
+    },
+};
+use std::{
+    convert::TryFrom,
+    io::{self, SeekFrom},
+    ops::RangeInclusive,
+    path::Path,
+    pin::Pin,
+    task::{Context, Poll},
+    time::SystemTime,
+};
+
+/// File honoring the `Range` and `If-Range` request headers
+///
+/// A single byte range of the `Range` header is answered with `206 Partial Content` and
+/// `Content-Range`, an unsatisfiable range with `416 Range Not Satisfiable`. Without a
+/// `Range` header, with several ranges or with an `If-Range` not matching the
+/// `Last-Modified` date the whole file is sent. `Accept-Ranges: bytes` is always sent.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::get;
+/// use rocket_response::RangedFile;
+/// use std::io;
+///
+/// #[get("/video")]
+/// async fn video() -> io::Result<RangedFile> {
+///     RangedFile::open("static/video.mp4").await
+/// }
+/// ```
+#[derive(Debug)]
+pub struct RangedFile {
+    file: File,
+    len: u64,
+    modified: Option<SystemTime>,
+    content_type: Option<ContentType>,
+}
+
+impl RangedFile {
+    /// Opens the file at `path` with the content type of its extension.
+    pub async fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref();
+        let content_type = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(ContentType::from_extension);
+        Ok(RangedFile {
+            content_type,
+            ..RangedFile::from_file(File::open(path).await?).await?
+        })
+    }
+
+    /// File of `file` without content type
+    pub async fn from_file(file: File) -> io::Result<Self> {
+        let metadata = file.metadata().await?;
+        Ok(RangedFile {
+            file,
+            len: metadata.len(),
+            modified: metadata.modified().ok(),
+            content_type: None,
+        })
+    }
+
+    /// File of `file` with the content type of its extension
+    pub async fn from_named_file(file: NamedFile) -> io::Result<Self> {
+        let content_type = file
+            .path()
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(ContentType::from_extension);
+        Ok(RangedFile {
+            content_type,
+            ..RangedFile::from_file(file.take_file()).await?
+        })
+    }
+
+    /// Sets the content type.
+    pub fn content_type(mut self, content_type: ContentType) -> Self {
+        self.content_type = Some(content_type);
+        self
+    }
+
+    /// Size of the file
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// `true` if the file is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Range of the `Range` header of `req` to be sent
+    ///
+    /// `None` sends the whole file, `Some(Err(()))` is unsatisfiable.
+    fn range(&self, req: &Request<'_>) -> Option<Result<RangeInclusive<u64>, ()>> {
+        let range = req.headers().get_one("Range")?;
+        if let (Some(if_range), Some(modified)) = (req.headers().get_one("If-Range"), self.modified)
+        {
+            let unchanged = httpdate::parse_http_date(if_range).map_or(false, |date| {
+                httpdate::fmt_http_date(date) == httpdate::fmt_http_date(modified)
+            });
+            if !unchanged {
+                return None;
+            }
+        }
+
+        let spec = range.trim().strip_prefix("bytes=")?;
+        if spec.contains(',') {
+            return None;
+        }
+        let (start, end) = spec.split_once('-')?;
+        let (start, end) = (start.trim(), end.trim());
+        let last = self.len.checked_sub(1);
+
+        let range = if start.is_empty() {
+            let suffix: u64 = end.parse().ok()?;
+            match last {
+                Some(last) if suffix > 0 => Ok(self.len.saturating_sub(suffix)..=last),
+                _ => Err(()),
+            }
+        } else {
+            let start: u64 = start.parse().ok()?;
+            let end: Option<u64> = if end.is_empty() {
+                None
+            } else {
+                Some(end.parse().ok()?)
+            };
+            if end.map_or(false, |end| end < start) {
+                return None;
+            }
+            match last {
+                Some(last) if start <= last => Ok(start..=end.map_or(last, |end| end.min(last))),
+                _ => Err(()),
+            }
+        };
+        Some(range)
+    }
+}
+
+impl<'r> Responder<'r, 'static> for RangedFile {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let mut builder = Response::build();
+        builder.header(Header::new("Accept-Ranges", "bytes"));
+        if let Some(content_type) = self.content_type.clone() {
+            builder.header(content_type);
+        }
+        if let Some(modified) = self.modified {
+            builder.header(Header::new(
+                "Last-Modified",
+                httpdate::fmt_http_date(modified),
+            ));
+        }
+
+        match self.range(req) {
+            None => builder.sized_body(
+                usize::try_from(self.len).ok(),
+                RangeReader::new(self.file, 0, self.len),
+            ),
+            Some(Ok(range)) => {
+                let (start, end) = (*range.start(), *range.end());
+                let len = end - start + 1;
+                builder
+                    .status(Status::PartialContent)
+                    .header(Header::new(
+                        "Content-Range",
+                        format!("bytes {}-{}/{}", start, end, self.len),
+                    ))
+                    .sized_body(
+                        usize::try_from(len).ok(),
+                        RangeReader::new(self.file, start, len),
+                    )
+            }
+            Some(Err(())) => builder
+                .status(Status::RangeNotSatisfiable)
+                .header(Header::new(
+                    "Content-Range",
+                    format!("bytes */{}", self.len),
+                )),
+        };
+        builder.ok()
+    }
+}
+
+/// Body reading `len` bytes of a file from `start`
+struct RangeReader {
+    file: File,
+    start: u64,
+    len: u64,
+    pos: u64,
+    seek: Seek,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Seek {
+    Pending,
+    Started,
+    Done,
+}
+
+impl RangeReader {
+    fn new(file: File, start: u64, len: u64) -> Self {
+        RangeReader {
+            file,
+            start,
+            len,
+            pos: 0,
+            seek: Seek::Pending,
+        }
+    }
+}
+
+impl AsyncRead for RangeReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = &mut *self;
+        if this.seek == Seek::Pending {
+            Pin::new(&mut this.file).start_seek(SeekFrom::Start(this.start + this.pos))?;
+            this.seek = Seek::Started;
+        }
+        if this.seek == Seek::Started {
+            match Pin::new(&mut this.file).poll_complete(cx) {
+                Poll::Ready(Ok(_)) => this.seek = Seek::Done,
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        let remaining = this.len.saturating_sub(this.pos);
+        if remaining == 0 {
+            return Poll::Ready(Ok(()));
+        }
+        let max = usize::try_from(remaining).unwrap_or(usize::MAX);
+        if buf.remaining() <= max {
+            let filled = buf.filled().len();
+            let result = Pin::new(&mut this.file).poll_read(cx, buf);
+            this.pos += (buf.filled().len() - filled) as u64;
+            result
+        } else {
+            let mut chunk = vec![0; max];
+            let mut chunk = ReadBuf::new(&mut chunk);
+            let result = Pin::new(&mut this.file).poll_read(cx, &mut chunk);
+            buf.put_slice(chunk.filled());
+            this.pos += chunk.filled().len() as u64;
+            result
+        }
+    }
+}
+
+impl AsyncSeek for RangeReader {
+    fn start_seek(mut self: Pin<&mut Self>, position: SeekFrom) -> io::Result<()> {
+        let pos = match position {
+            SeekFrom::Start(pos) => Some(pos),
+            SeekFrom::End(offset) => offset_by(self.len, offset),
+            SeekFrom::Current(offset) => offset_by(self.pos, offset),
+        }
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "invalid seek"))?;
+        self.pos = pos;
+        self.seek = Seek::Pending;
+        Ok(())
+    }
+
+    fn poll_complete(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<u64>> {
+        Poll::Ready(Ok(self.pos))
+    }
+}
+
+fn offset_by(base: u64, offset: i64) -> Option<u64> {
+    if offset >= 0 {
+        base.checked_add(offset as u64)
+    } else {
+        base.checked_sub(offset.unsigned_abs())
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::RangedFile;
+    use crate::RocketResponse;
+    use rocket::{
+        get,
+        http::{ContentType, Header, Status},
+        local::blocking::Client,
+        routes,
+    };
+    use std::io;
+
+    #[get("/ranged")]
+    async fn route_ranged() -> io::Result<RocketResponse> {
+        Ok(RocketResponse::RangedFile(Box::new(
+            RangedFile::open("Cargo.toml").await?,
+        )))
+    }
+
+    #[test]
+    fn test_ranged_file() {
+        let content = std::fs::read("Cargo.toml").unwrap();
+        let len = content.len();
+        let rocket = rocket::build().mount("/", routes![route_ranged]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/ranged").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(Some("bytes"), res.headers().get_one("Accept-Ranges"));
+        assert_eq!(ContentType::from_extension("toml"), res.content_type());
+        let modified = res.headers().get_one("Last-Modified").unwrap().to_string();
+        assert_eq!(content, res.into_bytes().unwrap());
+
+        for (range, start, end) in [
+            ("bytes=0-9", 0, 9),
+            ("bytes=10-", 10, len - 1),
+            ("bytes=-5", len - 5, len - 1),
+            ("bytes=5-100000", 5, len - 1),
+        ]
+        .iter()
+        {
+            let res = client
+                .get("/ranged")
+                .header(Header::new("Range", *range))
+                .dispatch();
+            assert_eq!(Status::PartialContent, res.status(), "{}", range);
+            assert_eq!(
+                Some(format!("bytes {}-{}/{}", start, end, len).as_str()),
+                res.headers().get_one("Content-Range")
+            );
+            assert_eq!(
+                content[*start..=*end].to_vec(),
+                res.into_bytes().unwrap(),
+                "{}",
+                range
+            );
+        }
+
+        let res = client
+            .get("/ranged")
+            .header(Header::new("Range", format!("bytes={}-", len)))
+            .dispatch();
+        assert_eq!(Status::RangeNotSatisfiable, res.status());
+        assert_eq!(
+            Some(format!("bytes */{}", len).as_str()),
+            res.headers().get_one("Content-Range")
+        );
+
+        let res = client
+            .get("/ranged")
+            .header(Header::new("Range", "bytes=0-9"))
+            .header(Header::new("If-Range", modified))
+            .dispatch();
+        assert_eq!(Status::PartialContent, res.status());
+
+        let res = client
+            .get("/ranged")
+            .header(Header::new("Range", "bytes=0-9"))
+            .header(Header::new("If-Range", "Thu, 01 Jan 1970 00:00:00 GMT"))
+            .dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(len, res.into_bytes().unwrap().len());
+
+        let res = client
+            .get("/ranged")
+            .header(Header::new("Range", "bytes=0-1,4-5"))
+            .dispatch();
+        assert_eq!(Status::Ok, res.status());
+    }
+}
@@ -0,0 +1,105 @@
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+use crate::RocketResponseGeneric3;
+use crate::{RocketResponse, RocketResponseGeneric, RocketResponseGeneric2};
+use rocket::{http::uri::Reference, response::Redirect, serde::Serialize};
+use std::convert::TryInto;
+
+/// Implements the `redirect_*` constructors, which map on the constructors of [Redirect]
+/// and make the chosen status code explicit.
+macro_rules! impl_redirect_constructors {
+    ([$($gen:tt)*] $name:ty $(where $($bound:tt)+)?) => {
+        impl<$($gen)*> $name $(where $($bound)+)? {
+            /// `303 See Other` redirect, see [Redirect::to]
+            pub fn redirect_to<L: TryInto<Reference<'static>>>(uri: L) -> Self {
+                Self::Redirect(Box::new(Redirect::to(uri)))
+            }
+
+            /// `307 Temporary Redirect`, see [Redirect::temporary]
+            pub fn redirect_temporary<L: TryInto<Reference<'static>>>(uri: L) -> Self {
+                Self::Redirect(Box::new(Redirect::temporary(uri)))
+            }
+
+            /// `308 Permanent Redirect`, see [Redirect::permanent]
+            pub fn redirect_permanent<L: TryInto<Reference<'static>>>(uri: L) -> Self {
+                Self::Redirect(Box::new(Redirect::permanent(uri)))
+            }
+
+            /// `302 Found` redirect, see [Redirect::found]
+            pub fn redirect_found<L: TryInto<Reference<'static>>>(uri: L) -> Self {
+                Self::Redirect(Box::new(Redirect::found(uri)))
+            }
+
+            /// `301 Moved Permanently` redirect, see [Redirect::moved]
+            pub fn redirect_moved<L: TryInto<Reference<'static>>>(uri: L) -> Self {
+                Self::Redirect(Box::new(Redirect::moved(uri)))
+            }
+        }
+    };
+}
+
+impl_redirect_constructors!([] RocketResponse);
+impl_redirect_constructors!([T] RocketResponseGeneric<T> where T: Serialize);
+impl_redirect_constructors!([T, U] RocketResponseGeneric2<T, U> where T: Serialize);
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+impl_redirect_constructors!([T, S, U] RocketResponseGeneric3<T, S, U> where S: Serialize);
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use crate::{RocketResponse, RocketResponseGeneric2};
+    use rocket::{get, http::Status, local::blocking::Client, response::Redirect, routes};
+
+    #[get("/redirect/<id>")]
+    fn route_redirect(id: usize) -> RocketResponse {
+        match id {
+            0 => RocketResponse::redirect_to("/0"),
+            1 => RocketResponse::redirect_temporary("/1"),
+            2 => RocketResponse::redirect_permanent("/2"),
+            3 => RocketResponse::redirect_found("/3"),
+            _ => RocketResponse::redirect_moved("/4"),
+        }
+    }
+
+    #[get("/redirect_generic2")]
+    fn route_redirect_generic2() -> RocketResponseGeneric2<&'static str, Redirect> {
+        RocketResponseGeneric2::redirect_permanent("/new")
+    }
+
+    #[test]
+    fn test_redirect() {
+        let rocket = rocket::build().mount("/", routes![route_redirect, route_redirect_generic2]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let expected = [
+            Status::SeeOther,
+            Status::TemporaryRedirect,
+            Status::PermanentRedirect,
+            Status::Found,
+            Status::MovedPermanently,
+        ];
+        for (id, status) in expected.iter().enumerate() {
+            let res = client.get(format!("/redirect/{}", id)).dispatch();
+            assert_eq!(*status, res.status());
+            assert_eq!(
+                Some(format!("/{}", id).as_str()),
+                res.headers().get_one("Location")
+            );
+        }
+
+        let res = client.get("/redirect_generic2").dispatch();
+        assert_eq!(Status::PermanentRedirect, res.status());
+        assert_eq!(Some("/new"), res.headers().get_one("Location"));
+    }
+}
@@ -0,0 +1,719 @@
+use crate::error::escape_json;
+use rocket::{
+    http::{ContentType, Method, Status},
+    request::Request,
+    response::{self, Responder, Response},
+    route::{Handler, Outcome, Route},
+    Data,
+};
+use std::{
+    collections::HashMap,
+    fmt,
+    io::{self, Cursor},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+/// Storage of the entries of a [ResponseCache]
+///
+/// The entries are opaque bytes, which expire after their time to live. The default
+/// backend is the [MemoryBackend] of a single instance, instances sharing their cache use
+/// a distributed backend, like the `RedisBackend` of the `redis` feature.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket_response::{CacheBackend, MemoryBackend};
+/// use std::{io, time::Duration};
+///
+/// /// Backend counting the hits of another backend
+/// struct Counting(MemoryBackend);
+///
+/// #[rocket::async_trait]
+/// impl CacheBackend for Counting {
+///     async fn get(&self, key: &str) -> io::Result<Option<Vec<u8>>> {
+///         let value = self.0.get(key).await?;
+///         println!("{}: {}", key, if value.is_some() { "hit" } else { "miss" });
+///         Ok(value)
+///     }
+///
+///     async fn set(&self, key: &str, value: Vec<u8>, ttl: Duration) -> io::Result<()> {
+///         self.0.set(key, value, ttl).await
+///     }
+///
+///     async fn purge(&self, prefix: &str) -> io::Result<usize> {
+///         self.0.purge(prefix).await
+///     }
+/// }
+/// ```
+#[rocket::async_trait]
+pub trait CacheBackend: Send + Sync + 'static {
+    /// Value of `key`, `None` if it is missing or expired
+    async fn get(&self, key: &str) -> io::Result<Option<Vec<u8>>>;
+
+    /// Stores `value` as `key` for `ttl`.
+    async fn set(&self, key: &str, value: Vec<u8>, ttl: Duration) -> io::Result<()>;
+
+    /// Removes the keys starting with `prefix` and returns their number.
+    async fn purge(&self, prefix: &str) -> io::Result<usize>;
+}
+
+/// [CacheBackend] in the memory of the instance
+#[derive(Debug, Default)]
+pub struct MemoryBackend {
+    entries: Mutex<HashMap<String, (Instant, Vec<u8>)>>,
+}
+
+impl MemoryBackend {
+    /// Empty backend
+    pub fn new() -> Self {
+        MemoryBackend::default()
+    }
+
+    fn entries(&self) -> std::sync::MutexGuard<'_, HashMap<String, (Instant, Vec<u8>)>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[rocket::async_trait]
+impl CacheBackend for MemoryBackend {
+    async fn get(&self, key: &str) -> io::Result<Option<Vec<u8>>> {
+        let mut entries = self.entries();
+        match entries.get(key) {
+            Some((expires, value)) if *expires > Instant::now() => Ok(Some(value.clone())),
+            Some(_) => {
+                entries.remove(key);
+                Ok(None)
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn set(&self, key: &str, value: Vec<u8>, ttl: Duration) -> io::Result<()> {
+        let now = Instant::now();
+        let mut entries = self.entries();
+        entries.retain(|_, (expires, _)| *expires > now);
+        entries.insert(key.to_string(), (now + ttl, value));
+        Ok(())
+    }
+
+    async fn purge(&self, prefix: &str) -> io::Result<usize> {
+        let mut entries = self.entries();
+        let len = entries.len();
+        entries.retain(|key, _| !key.starts_with(prefix));
+        Ok(len - entries.len())
+    }
+}
+
+/// [CacheBackend] in Redis, shared by all instances using the same server
+///
+/// The keys are prefixed by a namespace, `rocket-response:` by default. A purge scans the
+/// keys of the namespace starting with the prefix.
+///
+/// Requires the `redis` feature.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// use redis::{aio::ConnectionManager, Client};
+/// use rocket_response::{RedisBackend, ResponseCache};
+///
+/// # rocket::async_test(async {
+/// let client = Client::open("redis://127.0.0.1/").unwrap();
+/// let manager = ConnectionManager::new(client).await.unwrap();
+/// let cache = ResponseCache::with_backend(RedisBackend::new(manager).namespace("app:"));
+///
+/// let rocket = rocket::build().manage(cache);
+/// # });
+/// ```
+#[cfg(feature = "redis")]
+#[derive(Clone)]
+pub struct RedisBackend {
+    connection: redis::aio::ConnectionManager,
+    namespace: String,
+}
+
+#[cfg(feature = "redis")]
+impl RedisBackend {
+    /// Backend of the server of `connection`
+    pub fn new(connection: redis::aio::ConnectionManager) -> Self {
+        RedisBackend {
+            connection,
+            namespace: "rocket-response:".to_string(),
+        }
+    }
+
+    /// Sets the `namespace` prefixing the keys.
+    pub fn namespace<N: Into<String>>(mut self, namespace: N) -> Self {
+        self.namespace = namespace.into();
+        self
+    }
+}
+
+#[cfg(feature = "redis")]
+impl fmt::Debug for RedisBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RedisBackend")
+            .field("namespace", &self.namespace)
+            .finish()
+    }
+}
+
+#[cfg(feature = "redis")]
+fn redis_error(error: redis::RedisError) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, error)
+}
+
+#[cfg(feature = "redis")]
+#[rocket::async_trait]
+impl CacheBackend for RedisBackend {
+    async fn get(&self, key: &str) -> io::Result<Option<Vec<u8>>> {
+        redis::cmd("GET")
+            .arg(format!("{}{}", self.namespace, key))
+            .query_async(&mut self.connection.clone())
+            .await
+            .map_err(redis_error)
+    }
+
+    async fn set(&self, key: &str, value: Vec<u8>, ttl: Duration) -> io::Result<()> {
+        redis::cmd("SET")
+            .arg(format!("{}{}", self.namespace, key))
+            .arg(value)
+            .arg("PX")
+            .arg(ttl.as_millis().max(1) as u64)
+            .query_async(&mut self.connection.clone())
+            .await
+            .map_err(redis_error)
+    }
+
+    async fn purge(&self, prefix: &str) -> io::Result<usize> {
+        let mut pattern = String::new();
+        for c in self.namespace.chars().chain(prefix.chars()) {
+            if matches!(c, '*' | '?' | '[' | ']' | '\\') {
+                pattern.push('\\');
+            }
+            pattern.push(c);
+        }
+        pattern.push('*');
+
+        let mut connection = self.connection.clone();
+        let (mut cursor, mut purged) = (0u64, 0);
+        loop {
+            let (next, keys): (u64, Vec<String>) = redis::cmd("SCAN")
+                .arg(cursor)
+                .arg("MATCH")
+                .arg(&pattern)
+                .arg("COUNT")
+                .arg(100)
+                .query_async(&mut connection)
+                .await
+                .map_err(redis_error)?;
+            if !keys.is_empty() {
+                purged += redis::cmd("DEL")
+                    .arg(&keys)
+                    .query_async::<_, usize>(&mut connection)
+                    .await
+                    .map_err(redis_error)?;
+            }
+            if next == 0 {
+                return Ok(purged);
+            }
+            cursor = next;
+        }
+    }
+}
+
+/// Rendered response stored in a [ResponseCache]
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::http::ContentType;
+/// use rocket_response::CacheEntry;
+///
+/// let entry = CacheEntry::new(ContentType::HTML, "<p>Hello world</p>");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheEntry {
+    pub(crate) status: Status,
+    pub(crate) content_type: ContentType,
+    body: Vec<u8>,
+}
+
+impl CacheEntry {
+    /// Entry of a `200 OK` response of `content_type` with `body`
+    pub fn new<B: Into<Vec<u8>>>(content_type: ContentType, body: B) -> Self {
+        CacheEntry {
+            status: Status::Ok,
+            content_type,
+            body: body.into(),
+        }
+    }
+
+    /// Sets the status.
+    pub fn status(mut self, status: Status) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Body of the response
+    pub fn body(&self) -> &[u8] {
+        &self.body
+    }
+
+    /// Status code, content type and body separated by newlines
+    fn encode(&self) -> Vec<u8> {
+        let mut buf = format!("{}\n{}\n", self.status.code, self.content_type).into_bytes();
+        buf.extend_from_slice(&self.body);
+        buf
+    }
+
+    /// Entry of encoded `buf`, `None` if it is invalid
+    fn decode(mut buf: Vec<u8>) -> Option<Self> {
+        let status_end = buf.iter().position(|b| *b == b'\n')?;
+        let type_end = status_end + 1 + buf[status_end + 1..].iter().position(|b| *b == b'\n')?;
+        let status = std::str::from_utf8(&buf[..status_end]).ok()?.parse().ok()?;
+        let content_type = std::str::from_utf8(&buf[status_end + 1..type_end])
+            .ok()?
+            .parse()
+            .ok()?;
+        buf.drain(..=type_end);
+        Some(CacheEntry {
+            status: Status::from_code(status)?,
+            content_type,
+            body: buf,
+        })
+    }
+}
+
+impl<'r> Responder<'r, 'static> for CacheEntry {
+    fn respond_to(self, _req: &'r Request<'_>) -> response::Result<'static> {
+        Response::build()
+            .status(self.status)
+            .header(self.content_type)
+            .sized_body(self.body.len(), Cursor::new(self.body))
+            .ok()
+    }
+}
+
+/// Cache of rendered responses, managed by Rocket and shared by the routes
+///
+/// A route looks up its response by a key, like of a [CacheKey](crate::CacheKey), and
+/// renders and [inserts](Self::insert()) it on a miss. The entries expire after the time to
+/// live and are removed by a [purge](Self::purge()) of a key prefix, e.g. after an update.
+/// A failing backend is logged and treated like a miss, so the routes still respond.
+///
+/// The entries are stored by a [CacheBackend], the [MemoryBackend] by default, so
+/// multiple instances share their entries with a distributed backend.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::{get, http::ContentType, post, State};
+/// use rocket_response::{CacheEntry, ResponseCache, RocketResponse};
+/// use std::time::Duration;
+///
+/// #[get("/articles")]
+/// async fn articles(cache: &State<ResponseCache>) -> RocketResponse {
+///     if let Some(entry) = cache.get("/articles").await {
+///         return RocketResponse::CacheEntry(Box::new(entry));
+///     }
+///     let entry = CacheEntry::new(ContentType::HTML, "<ul><li>Hello world</li></ul>");
+///     cache.insert("/articles", entry.clone()).await;
+///     RocketResponse::CacheEntry(Box::new(entry))
+/// }
+///
+/// #[post("/articles")]
+/// async fn publish(cache: &State<ResponseCache>) -> RocketResponse {
+///     cache.purge("/articles").await.ok();
+///     RocketResponse::StaticStr("published")
+/// }
+///
+/// let rocket = rocket::build().manage(ResponseCache::new().ttl(Duration::from_secs(300)));
+/// ```
+#[derive(Clone)]
+pub struct ResponseCache {
+    backend: Arc<dyn CacheBackend>,
+    ttl: Duration,
+    counters: Arc<(AtomicU64, AtomicU64)>,
+}
+
+/// Counters of the lookups of a [ResponseCache] since its creation
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket_response::ResponseCache;
+///
+/// let stats = ResponseCache::new().stats();
+/// println!("response cache hit rate {:.2}", stats.hit_rate());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CacheStats {
+    /// Count of lookups finding an entry
+    pub hits: u64,
+    /// Count of lookups of missing or expired entries, or failed by the backend
+    pub misses: u64,
+}
+
+impl CacheStats {
+    /// Ratio of hits to all lookups, `0.0` if there was no lookup yet.
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+impl ResponseCache {
+    /// Cache in memory with a time to live of 60 seconds
+    pub fn new() -> Self {
+        ResponseCache::with_backend(MemoryBackend::new())
+    }
+
+    /// Cache in `backend` with a time to live of 60 seconds
+    pub fn with_backend<B: CacheBackend>(backend: B) -> Self {
+        ResponseCache {
+            backend: Arc::new(backend),
+            ttl: Duration::from_secs(60),
+            counters: Arc::default(),
+        }
+    }
+
+    /// Sets the time to live of the entries.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Entry of `key`, `None` if it is missing or expired
+    pub async fn get(&self, key: &str) -> Option<CacheEntry> {
+        let entry = match self.backend.get(key).await {
+            Ok(value) => value.and_then(CacheEntry::decode),
+            Err(e) => {
+                rocket::warn_!("response cache lookup of {} failed: {}", key, e);
+                None
+            }
+        };
+        let counter = match entry {
+            Some(_) => &self.counters.0,
+            None => &self.counters.1,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        entry
+    }
+
+    /// Current [CacheStats], shared by the clones of the cache
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.counters.0.load(Ordering::Relaxed),
+            misses: self.counters.1.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Stores `entry` as `key`.
+    pub async fn insert(&self, key: &str, entry: CacheEntry) {
+        if let Err(e) = self.backend.set(key, entry.encode(), self.ttl).await {
+            rocket::warn_!("response cache insert of {} failed: {}", key, e);
+        }
+    }
+
+    /// Removes the entries with keys starting with `prefix` and returns their number.
+    pub async fn purge(&self, prefix: &str) -> io::Result<usize> {
+        self.backend.purge(prefix).await
+    }
+
+    /// Routes purging the managed cache, e.g. called by a webhook after a deployment
+    ///
+    /// `DELETE /__response/cache/<prefix>` purges the entries with keys starting with the
+    /// percent-decoded `prefix`, so `%2Farticles` purges the keys starting with `/articles`,
+    /// and `DELETE /__response/cache` purges all entries. The request has to authorize by
+    /// the header `Authorization: Bearer <token>`, otherwise the response is
+    /// `401 Unauthorized`. The purged entries are reported by a [PurgeResult].
+    ///
+    /// ```rust
+    /// use rocket_response::ResponseCache;
+    ///
+    /// let rocket = rocket::build()
+    ///     .manage(ResponseCache::new())
+    ///     .mount("/", ResponseCache::purge_routes("a secret token"));
+    /// ```
+    pub fn purge_routes<T: Into<String>>(token: T) -> Vec<Route> {
+        let handler = PurgeHandler {
+            token: token.into().into(),
+        };
+        vec![
+            Route::new(Method::Delete, "/__response/cache", handler.clone()),
+            Route::new(Method::Delete, "/__response/cache/<prefix>", handler),
+        ]
+    }
+}
+
+impl Default for ResponseCache {
+    fn default() -> Self {
+        ResponseCache::new()
+    }
+}
+
+impl fmt::Debug for ResponseCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResponseCache")
+            .field("ttl", &self.ttl)
+            .finish()
+    }
+}
+
+/// Number of entries removed by a purge of the [ResponseCache], responded as JSON
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::{delete, State};
+/// use rocket_response::{PurgeResult, ResponseCache, RocketResponse};
+///
+/// #[delete("/articles/cache")]
+/// async fn purge_articles(cache: &State<ResponseCache>) -> std::io::Result<RocketResponse> {
+///     let purged = cache.purge("/articles").await?;
+///     Ok(RocketResponse::PurgeResult(Box::new(PurgeResult::new("/articles", purged))))
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PurgeResult {
+    prefix: String,
+    purged: usize,
+}
+
+impl PurgeResult {
+    /// Result of `purged` entries removed by the purge of `prefix`
+    pub fn new<P: Into<String>>(prefix: P, purged: usize) -> Self {
+        PurgeResult {
+            prefix: prefix.into(),
+            purged,
+        }
+    }
+
+    /// Prefix of the purged keys
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Number of the purged entries
+    pub fn purged(&self) -> usize {
+        self.purged
+    }
+}
+
+impl<'r> Responder<'r, 'static> for PurgeResult {
+    fn respond_to(self, _req: &'r Request<'_>) -> response::Result<'static> {
+        let body = format!(
+            r#"{{"prefix":"{}","purged":{}}}"#,
+            escape_json(&self.prefix),
+            self.purged
+        );
+        Response::build()
+            .header(ContentType::JSON)
+            .sized_body(body.len(), Cursor::new(body))
+            .ok()
+    }
+}
+
+/// Handler of the [purge routes](ResponseCache::purge_routes())
+#[derive(Clone)]
+struct PurgeHandler {
+    token: Arc<str>,
+}
+
+impl PurgeHandler {
+    /// `true` if `req` has the bearer token, compared in constant time
+    fn authorized(&self, req: &Request<'_>) -> bool {
+        let given = req
+            .headers()
+            .get_one("Authorization")
+            .and_then(|auth| auth.strip_prefix("Bearer "))
+            .unwrap_or("");
+        let expected = self.token.as_bytes();
+        given.len() == expected.len()
+            && given
+                .bytes()
+                .zip(expected)
+                .fold(0, |diff, (a, b)| diff | (a ^ b))
+                == 0
+    }
+}
+
+#[rocket::async_trait]
+impl Handler for PurgeHandler {
+    async fn handle<'r>(&self, req: &'r Request<'_>, _data: Data<'r>) -> Outcome<'r> {
+        if self.token.is_empty() || !self.authorized(req) {
+            return Outcome::Failure(Status::Unauthorized);
+        }
+        let cache = match req.rocket().state::<ResponseCache>() {
+            Some(cache) => cache,
+            None => {
+                rocket::error_!("{}: no ResponseCache managed", req.uri());
+                return Outcome::Failure(Status::InternalServerError);
+            }
+        };
+        // the segments after the mount point are `__response`, `cache` and the prefix
+        let prefix = match req.param::<&str>(2) {
+            Some(Ok(prefix)) => prefix,
+            Some(Err(_)) => return Outcome::Failure(Status::BadRequest),
+            None => "",
+        };
+        match cache.purge(prefix).await {
+            Ok(purged) => Outcome::from(req, PurgeResult::new(prefix, purged)),
+            Err(e) => {
+                rocket::error_!("{}: response cache purge failed: {}", req.uri(), e);
+                Outcome::Failure(Status::InternalServerError)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::{CacheBackend, CacheEntry, CacheStats, MemoryBackend, ResponseCache};
+    use crate::RocketResponse;
+    use rocket::{
+        get,
+        http::{ContentType, Header, Status},
+        local::blocking::Client,
+        routes, State,
+    };
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+
+    static RENDERED: AtomicUsize = AtomicUsize::new(0);
+
+    #[get("/cached/<name>")]
+    async fn route_cached(name: &str, cache: &State<ResponseCache>) -> RocketResponse {
+        let key = format!("/cached/{}", name);
+        if let Some(entry) = cache.get(&key).await {
+            return RocketResponse::CacheEntry(Box::new(entry));
+        }
+        RENDERED.fetch_add(1, Ordering::SeqCst);
+        let entry =
+            CacheEntry::new(ContentType::HTML, format!("<p>{}</p>", name)).status(Status::Accepted);
+        cache.insert(&key, entry.clone()).await;
+        RocketResponse::CacheEntry(Box::new(entry))
+    }
+
+    #[get("/purge/<prefix>")]
+    async fn route_purge(prefix: &str, cache: &State<ResponseCache>) -> String {
+        cache.purge(prefix).await.unwrap().to_string()
+    }
+
+    #[test]
+    fn test_response_cache() {
+        let rocket = rocket::build()
+            .manage(ResponseCache::new())
+            .mount("/", routes![route_cached, route_purge]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        for _ in 0..2 {
+            let res = client.get("/cached/a").dispatch();
+            assert_eq!(Status::Accepted, res.status());
+            assert_eq!(Some(ContentType::HTML), res.content_type());
+            assert_eq!("<p>a</p>", res.into_string().unwrap());
+        }
+        assert_eq!(1, RENDERED.load(Ordering::SeqCst));
+        client.get("/cached/b").dispatch();
+        assert_eq!(2, RENDERED.load(Ordering::SeqCst));
+
+        let res = client.get("/purge/%2Fcached%2Fa").dispatch();
+        assert_eq!("1", res.into_string().unwrap());
+        client.get("/cached/a").dispatch();
+        client.get("/cached/b").dispatch();
+        assert_eq!(3, RENDERED.load(Ordering::SeqCst));
+
+        let stats = client.rocket().state::<ResponseCache>().unwrap().stats();
+        assert_eq!(CacheStats { hits: 2, misses: 3 }, stats);
+        assert_eq!(0.4, stats.hit_rate());
+    }
+
+    #[test]
+    fn test_memory_backend() {
+        rocket::async_test(async {
+            let backend = MemoryBackend::new();
+            backend
+                .set("a", b"1".to_vec(), Duration::from_secs(60))
+                .await
+                .unwrap();
+            backend
+                .set("ab", b"2".to_vec(), Duration::from_secs(60))
+                .await
+                .unwrap();
+            backend
+                .set("b", b"3".to_vec(), Duration::from_millis(0))
+                .await
+                .unwrap();
+            assert_eq!(Some(b"1".to_vec()), backend.get("a").await.unwrap());
+            assert_eq!(None, backend.get("b").await.unwrap());
+            assert_eq!(2, backend.purge("a").await.unwrap());
+            assert_eq!(None, backend.get("ab").await.unwrap());
+        });
+
+        let entry = CacheEntry::new(ContentType::JSON, "{\n}").status(Status::NotFound);
+        assert_eq!(Some(entry.clone()), CacheEntry::decode(entry.encode()));
+        assert_eq!(None, CacheEntry::decode(b"200".to_vec()));
+    }
+
+    #[test]
+    fn test_purge_routes() {
+        let cache = ResponseCache::new();
+        let rocket = rocket::build()
+            .manage(cache.clone())
+            .mount("/", ResponseCache::purge_routes("token"));
+        let client = Client::tracked(rocket).expect("no rocket instance");
+        let purge = |uri: &str, token: &'static str| {
+            client
+                .delete(uri.to_string())
+                .header(Header::new("Authorization", token))
+                .dispatch()
+        };
+        let fill = || {
+            rocket::async_test(async {
+                for key in ["/articles/1", "/articles/2", "/users/1"].iter() {
+                    cache
+                        .insert(key, CacheEntry::new(ContentType::Plain, ""))
+                        .await;
+                }
+            })
+        };
+
+        fill();
+        let res = purge("/__response/cache/%2Farticles", "Bearer token");
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(Some(ContentType::JSON), res.content_type());
+        assert_eq!(
+            r#"{"prefix":"/articles","purged":2}"#,
+            res.into_string().unwrap()
+        );
+
+        let res = purge("/__response/cache", "Bearer token");
+        assert_eq!(r#"{"prefix":"","purged":1}"#, res.into_string().unwrap());
+
+        fill();
+        assert_eq!(
+            Status::Unauthorized,
+            purge("/__response/cache", "Bearer tok").status()
+        );
+        assert_eq!(
+            Status::Unauthorized,
+            purge("/__response/cache", "token").status()
+        );
+        let res = client.delete("/__response/cache").dispatch();
+        assert_eq!(Status::Unauthorized, res.status());
+        let res = purge("/__response/cache", "Bearer token");
+        assert_eq!(r#"{"prefix":"","purged":3}"#, res.into_string().unwrap());
+    }
+}
@@ -0,0 +1,264 @@
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+use crate::RocketResponseGeneric3;
+use crate::{RocketResponse, RocketResponseGeneric, RocketResponseGeneric2};
+use rocket::{
+    http::{Header, Status},
+    request::Request,
+    response::{self, Responder},
+    serde::Serialize,
+};
+use std::time::{Duration, SystemTime};
+
+/// Value of the `Retry-After` header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryAfter {
+    /// Delay, sent in whole seconds rounded up
+    Delay(Duration),
+    /// Point in time, sent as HTTP date
+    Date(SystemTime),
+}
+
+impl RetryAfter {
+    /// `Retry-After` header of the value
+    pub fn header(&self) -> Header<'static> {
+        let value = match self {
+            RetryAfter::Delay(delay) => {
+                let secs = delay.as_secs() + u64::from(delay.subsec_nanos() > 0);
+                secs.to_string()
+            }
+            RetryAfter::Date(date) => httpdate::fmt_http_date(*date),
+        };
+        Header::new("Retry-After", value)
+    }
+}
+
+impl From<Duration> for RetryAfter {
+    fn from(delay: Duration) -> Self {
+        RetryAfter::Delay(delay)
+    }
+}
+
+impl From<SystemTime> for RetryAfter {
+    fn from(date: SystemTime) -> Self {
+        RetryAfter::Date(date)
+    }
+}
+
+/// Responds with `responder` and overrides the status by `status`.
+fn respond<'r, 'o: 'r, R: Responder<'r, 'o>>(
+    status: Status,
+    retry_after: Option<RetryAfter>,
+    responder: R,
+    req: &'r Request<'_>,
+) -> response::Result<'o> {
+    let mut res = responder.respond_to(req)?;
+    res.set_status(status);
+    if let Some(retry_after) = retry_after {
+        res.set_header(retry_after.header());
+    }
+    Ok(res)
+}
+
+/// `429 Too Many Requests` with an optional `Retry-After` and a body
+///
+/// The status of the wrapped responder is replaced, so any responder can be the body.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::get;
+/// use rocket_response::{RocketResponse, TooManyRequests};
+/// use std::time::Duration;
+///
+/// #[get("/search")]
+/// fn search() -> RocketResponse {
+///     RocketResponse::rate_limited(
+///         Duration::from_secs(30),
+///         RocketResponse::StaticStr("Slow down"),
+///     )
+/// }
+///
+/// #[get("/search_plain")]
+/// fn search_plain() -> TooManyRequests<&'static str> {
+///     TooManyRequests::new("Slow down")
+/// }
+/// ```
+#[derive(Debug)]
+pub struct TooManyRequests<R> {
+    responder: Box<R>,
+    retry_after: Option<RetryAfter>,
+}
+
+/// `503 Service Unavailable` with an optional `Retry-After` and a body
+///
+/// The status of the wrapped responder is replaced, so any responder can be the body. The
+/// `Retry-After` of the response is kept by [Maintenance](crate::Maintenance).
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::get;
+/// use rocket_response::{RocketResponse, ServiceUnavailable};
+/// use std::time::{Duration, SystemTime};
+///
+/// #[get("/")]
+/// fn index() -> RocketResponse {
+///     RocketResponse::unavailable(
+///         SystemTime::now() + Duration::from_secs(3600),
+///         RocketResponse::StaticStr("Back soon"),
+///     )
+/// }
+///
+/// #[get("/plain")]
+/// fn plain() -> ServiceUnavailable<&'static str> {
+///     ServiceUnavailable::new("Back soon").retry_after(Duration::from_secs(60))
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ServiceUnavailable<R> {
+    responder: Box<R>,
+    retry_after: Option<RetryAfter>,
+}
+
+/// Implements the wrapper methods and the [Responder] of a `Retry-After` status.
+macro_rules! impl_retry_status {
+    ($name:ident, $status:expr) => {
+        impl<R> $name<R> {
+            /// Wraps `responder` as body without `Retry-After`
+            pub fn new(responder: R) -> Self {
+                $name {
+                    responder: Box::new(responder),
+                    retry_after: None,
+                }
+            }
+
+            /// Sets `Retry-After` to a [Duration] or a [SystemTime].
+            pub fn retry_after<A: Into<RetryAfter>>(mut self, retry_after: A) -> Self {
+                self.retry_after = Some(retry_after.into());
+                self
+            }
+
+            /// Consumes the wrapper and returns the wrapped body.
+            pub fn into_inner(self) -> R {
+                *self.responder
+            }
+
+            pub(crate) fn responder(&self) -> &R {
+                &self.responder
+            }
+        }
+
+        impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for $name<R> {
+            fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+                respond($status, self.retry_after, *self.responder, req)
+            }
+        }
+    };
+}
+
+impl_retry_status!(TooManyRequests, Status::TooManyRequests);
+impl_retry_status!(ServiceUnavailable, Status::ServiceUnavailable);
+
+/// Implements the `rate_limited` and `unavailable` constructors.
+macro_rules! impl_retry_constructors {
+    ([$($gen:tt)*] $name:ty $(where $($bound:tt)+)?) => {
+        impl<$($gen)*> $name $(where $($bound)+)? {
+            /// `429 Too Many Requests` with `Retry-After` and `body`,
+            /// see [TooManyRequests]
+            pub fn rate_limited<A: Into<RetryAfter>>(retry_after: A, body: Self) -> Self {
+                Self::TooManyRequests(Box::new(TooManyRequests::new(body).retry_after(retry_after)))
+            }
+
+            /// `503 Service Unavailable` with `Retry-After` and `body`,
+            /// see [ServiceUnavailable]
+            pub fn unavailable<A: Into<RetryAfter>>(retry_after: A, body: Self) -> Self {
+                Self::ServiceUnavailable(Box::new(
+                    ServiceUnavailable::new(body).retry_after(retry_after),
+                ))
+            }
+        }
+    };
+}
+
+impl_retry_constructors!([] RocketResponse);
+impl_retry_constructors!([T] RocketResponseGeneric<T> where T: Serialize);
+impl_retry_constructors!([T, U] RocketResponseGeneric2<T, U> where T: Serialize);
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+impl_retry_constructors!([T, S, U] RocketResponseGeneric3<T, S, U> where S: Serialize);
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::{RetryAfter, TooManyRequests};
+    use crate::{RocketResponse, RocketResponseGeneric};
+    use rocket::{get, http::Status, local::blocking::Client, routes};
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    #[get("/limited")]
+    fn route_limited() -> RocketResponse {
+        RocketResponse::rate_limited(
+            Duration::from_millis(1500),
+            RocketResponse::StaticStr("Slow down"),
+        )
+    }
+
+    #[get("/unavailable")]
+    fn route_unavailable() -> RocketResponseGeneric<&'static str> {
+        RocketResponseGeneric::unavailable(
+            UNIX_EPOCH + Duration::from_secs(784_111_777),
+            RocketResponseGeneric::Plain(rocket::response::content::RawText("Back soon")),
+        )
+    }
+
+    #[get("/plain")]
+    fn route_plain() -> TooManyRequests<&'static str> {
+        TooManyRequests::new("Slow down")
+    }
+
+    #[test]
+    fn test_retry_after() {
+        let rocket =
+            rocket::build().mount("/", routes![route_limited, route_unavailable, route_plain]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/limited").dispatch();
+        assert_eq!(Status::TooManyRequests, res.status());
+        assert_eq!(Some("2"), res.headers().get_one("Retry-After"));
+        assert_eq!("Slow down", res.into_string().unwrap());
+
+        let res = client.get("/unavailable").dispatch();
+        assert_eq!(Status::ServiceUnavailable, res.status());
+        assert_eq!(
+            Some("Sun, 06 Nov 1994 08:49:37 GMT"),
+            res.headers().get_one("Retry-After")
+        );
+        assert_eq!("Back soon", res.into_string().unwrap());
+
+        let res = client.get("/plain").dispatch();
+        assert_eq!(Status::TooManyRequests, res.status());
+        assert!(!res.headers().contains("Retry-After"));
+    }
+
+    #[test]
+    fn test_retry_after_value() {
+        assert_eq!(
+            "60",
+            RetryAfter::from(Duration::from_secs(60)).header().value()
+        );
+        assert_eq!("0", RetryAfter::from(Duration::ZERO).header().value());
+        let date = RetryAfter::from(SystemTime::UNIX_EPOCH);
+        assert_eq!("Thu, 01 Jan 1970 00:00:00 GMT", date.header().value());
+    }
+}
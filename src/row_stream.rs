@@ -0,0 +1,512 @@
+use crate::{DbError, DbErrorKind};
+use rocket::{
+    futures::stream::{Stream, StreamExt},
+    http::ContentType,
+    request::Request,
+    response::{self, Responder, Response},
+    serde::{
+        de::{Deserialize, Deserializer, MapAccess, SeqAccess, Visitor},
+        json::{json, serde_json, Value},
+        Serialize,
+    },
+    tokio::io::{AsyncRead, ReadBuf},
+};
+use std::{
+    fmt,
+    io::{self, Cursor, Read},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+type Rows = Pin<Box<dyn Stream<Item = Result<Vec<u8>, DbError>> + Send>>;
+
+/// Format of the rows of a [RowStream]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum RowFormat {
+    NdJson,
+    JsonArray,
+    Csv,
+}
+
+/// Behavior of a [RowStream] on an error of a row in the middle of the body
+///
+/// The error is logged in any case.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OnRowError {
+    /// Aborts the connection without the end of the body, so an HTTP client reports the
+    /// truncated body as an error
+    Abort,
+    /// Ends the body with an error record of the [DbErrorKind] and the number of rows
+    /// streamed before, so a client can tell the truncated export from a complete one
+    ///
+    /// NDJSON ends with the line `{"error":{"kind":"other","rows":1}}`, a JSON array with this
+    /// object as last element and CSV with the sentinel row `#error,other,1`. The kinds are
+    /// `not_found`, `unique_violation`, `foreign_key_violation` and `other`. The message of
+    /// the error isn't sent.
+    Record,
+}
+
+impl Default for OnRowError {
+    fn default() -> Self {
+        OnRowError::Abort
+    }
+}
+
+/// Rows of a database query streamed as NDJSON, JSON array or CSV body
+///
+/// Each row is mapped to a serializable value, which is encoded as soon as the row is
+/// fetched, so the result isn't collected in memory. The stream owns the cursor, and with
+/// it the connection, until the body is complete: if the client disconnects, Rocket drops
+/// the body and with it the stream, which closes the cursor and returns the connection to
+/// the pool.
+///
+/// The response is `200 OK`, so an error of a row or of its serialization can't change the
+/// status anymore. By default it is logged and aborts the connection, so the client doesn't
+/// take the truncated body as complete. With [on_error](Self::on_error()) the body ends with
+/// an error record instead, see [OnRowError].
+///
+/// A [csv](Self::csv()) row has a column per field of a struct or map, named by the header
+/// of the first row, or per element of a tuple or sequence without header. Nested values
+/// are written as JSON.
+///
+/// Requires the `db-sqlx` and `json` features.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::{futures::stream, get};
+/// use rocket_response::{RocketResponse, RowStream};
+///
+/// #[get("/users.csv")]
+/// fn users() -> RocketResponse {
+///     // e.g. rocket::response::stream::stream! {
+///     //     let mut rows = sqlx::query_as("...").fetch(&mut *db);
+///     //     while let Some(row) = rows.next().await { yield row; }
+///     // }
+///     let rows = stream::iter(vec![Ok::<_, sqlx::Error>((1, "alice")), Ok((2, "bob"))]);
+///     RocketResponse::RowStream(Box::new(RowStream::csv(rows, |(id, name)| (id, name))))
+/// }
+/// ```
+pub struct RowStream {
+    rows: Rows,
+    format: RowFormat,
+    on_error: OnRowError,
+}
+
+impl RowStream {
+    /// Streams `rows` mapped by `mapper` as newline delimited JSON, one value per line
+    pub fn ndjson<S, T, E, F, U>(rows: S, mut mapper: F) -> Self
+    where
+        S: Stream<Item = Result<T, E>> + Send + 'static,
+        E: Into<DbError>,
+        F: FnMut(T) -> U + Send + 'static,
+        U: Serialize,
+    {
+        Self::encoded(rows, RowFormat::NdJson, move |row| {
+            let mut buf = serde_json::to_vec(&mapper(row))?;
+            buf.push(b'\n');
+            Ok(buf)
+        })
+    }
+
+    /// Streams `rows` mapped by `mapper` as JSON array
+    pub fn json_array<S, T, E, F, U>(rows: S, mut mapper: F) -> Self
+    where
+        S: Stream<Item = Result<T, E>> + Send + 'static,
+        E: Into<DbError>,
+        F: FnMut(T) -> U + Send + 'static,
+        U: Serialize,
+    {
+        Self::encoded(rows, RowFormat::JsonArray, move |row| {
+            serde_json::to_vec(&mapper(row))
+        })
+    }
+
+    /// Streams `rows` mapped by `mapper` as CSV with a line per row
+    pub fn csv<S, T, E, F, U>(rows: S, mut mapper: F) -> Self
+    where
+        S: Stream<Item = Result<T, E>> + Send + 'static,
+        E: Into<DbError>,
+        F: FnMut(T) -> U + Send + 'static,
+        U: Serialize,
+    {
+        let mut first = true;
+        Self::encoded(rows, RowFormat::Csv, move |row| {
+            let record: Record = serde_json::from_slice(&serde_json::to_vec(&mapper(row))?)?;
+            let mut buf = Vec::new();
+            if std::mem::replace(&mut first, false) && !record.names.is_empty() {
+                write_csv_line(&mut buf, record.names.iter().map(String::as_str));
+            }
+            let fields: Vec<String> = record.values.iter().map(csv_field).collect();
+            write_csv_line(&mut buf, fields.iter().map(String::as_str));
+            Ok(buf)
+        })
+    }
+
+    /// Stream of `rows` encoded by `encode`
+    fn encoded<S, T, E, F>(rows: S, format: RowFormat, mut encode: F) -> Self
+    where
+        S: Stream<Item = Result<T, E>> + Send + 'static,
+        E: Into<DbError>,
+        F: FnMut(T) -> serde_json::Result<Vec<u8>> + Send + 'static,
+    {
+        let rows = rows.map(move |row| {
+            encode(row.map_err(Into::<DbError>::into)?)
+                .map_err(|e| DbError::new(DbErrorKind::Other, e))
+        });
+        RowStream {
+            rows: Box::pin(rows),
+            format,
+            on_error: OnRowError::default(),
+        }
+    }
+
+    /// Sets the behavior on an error in the middle of the body.
+    pub fn on_error(mut self, on_error: OnRowError) -> Self {
+        self.on_error = on_error;
+        self
+    }
+
+    pub(crate) fn content_type(&self) -> ContentType {
+        match self.format {
+            RowFormat::NdJson => ContentType::new("application", "x-ndjson"),
+            RowFormat::JsonArray => ContentType::JSON,
+            RowFormat::Csv => ContentType::CSV,
+        }
+    }
+}
+
+impl fmt::Debug for RowStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RowStream")
+            .field("format", &self.format)
+            .field("on_error", &self.on_error)
+            .finish()
+    }
+}
+
+impl<'r> Responder<'r, 'static> for RowStream {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        Response::build()
+            .header(self.content_type())
+            .streamed_body(Body {
+                rows: self.rows,
+                format: self.format,
+                on_error: self.on_error,
+                uri: req.uri().to_string(),
+                buf: Cursor::new(Vec::new()),
+                count: 0,
+                done: false,
+            })
+            .ok()
+    }
+}
+
+/// Body of the encoded rows
+struct Body {
+    rows: Rows,
+    format: RowFormat,
+    on_error: OnRowError,
+    uri: String,
+    buf: Cursor<Vec<u8>>,
+    count: usize,
+    done: bool,
+}
+
+impl AsyncRead for Body {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = &mut *self;
+        loop {
+            if (this.buf.position() as usize) < this.buf.get_ref().len() {
+                let n = this.buf.read(buf.initialize_unfilled())?;
+                buf.advance(n);
+                return Poll::Ready(Ok(()));
+            }
+            if this.done {
+                return Poll::Ready(Ok(()));
+            }
+
+            let array = this.format == RowFormat::JsonArray;
+            let mut next = Vec::new();
+            match this.rows.as_mut().poll_next(cx) {
+                Poll::Ready(Some(Ok(row))) => {
+                    if array {
+                        next.push(if this.count == 0 { b'[' } else { b',' });
+                    }
+                    next.extend(row);
+                    this.count += 1;
+                }
+                Poll::Ready(Some(Err(e))) => {
+                    rocket::error_!("{}: streaming rows failed: {}", this.uri, e);
+                    this.done = true;
+                    if this.on_error == OnRowError::Abort {
+                        return Poll::Ready(Err(io::Error::new(io::ErrorKind::Other, e)));
+                    }
+                    let kind = kind_name(e.kind());
+                    if this.format == RowFormat::Csv {
+                        let rows = this.count.to_string();
+                        write_csv_line(&mut next, ["#error", kind, &rows].iter().copied());
+                    } else {
+                        let record = json!({ "error": { "kind": kind, "rows": this.count } });
+                        if array {
+                            next.push(if this.count == 0 { b'[' } else { b',' });
+                        }
+                        next.extend(record.to_string().into_bytes());
+                        next.push(if array { b']' } else { b'\n' });
+                    }
+                }
+                Poll::Ready(None) => {
+                    if array {
+                        next.extend_from_slice(if this.count == 0 { b"[]" } else { b"]" });
+                    }
+                    this.done = true;
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+            this.buf = Cursor::new(next);
+        }
+    }
+}
+
+/// Name of `kind` in error records
+fn kind_name(kind: DbErrorKind) -> &'static str {
+    match kind {
+        DbErrorKind::NotFound => "not_found",
+        DbErrorKind::UniqueViolation => "unique_violation",
+        DbErrorKind::ForeignKeyViolation => "foreign_key_violation",
+        DbErrorKind::Other => "other",
+    }
+}
+
+/// Fields of a row in the order of serialization, named if it is a struct or map
+#[derive(Default)]
+struct Record {
+    names: Vec<String>,
+    values: Vec<Value>,
+}
+
+impl<'de> Deserialize<'de> for Record {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(RecordVisitor)
+    }
+}
+
+struct RecordVisitor;
+
+impl<'de> Visitor<'de> for RecordVisitor {
+    type Value = Record;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a struct, map, tuple or sequence as CSV row")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Record, A::Error> {
+        let mut record = Record::default();
+        while let Some((name, value)) = map.next_entry()? {
+            record.names.push(name);
+            record.values.push(value);
+        }
+        Ok(record)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Record, A::Error> {
+        let mut record = Record::default();
+        while let Some(value) = seq.next_element()? {
+            record.values.push(value);
+        }
+        Ok(record)
+    }
+}
+
+/// CSV field of `value`, JSON for arrays and objects
+fn csv_field(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        value => value.to_string(),
+    }
+}
+
+/// Writes `fields` quoted as needed and terminated by CRLF.
+fn write_csv_line<'a, I: Iterator<Item = &'a str>>(buf: &mut Vec<u8>, fields: I) {
+    for (i, field) in fields.enumerate() {
+        if i > 0 {
+            buf.push(b',');
+        }
+        if field.contains(&[',', '"', '\r', '\n'][..]) {
+            buf.push(b'"');
+            buf.extend_from_slice(field.replace('"', "\"\"").as_bytes());
+            buf.push(b'"');
+        } else {
+            buf.extend_from_slice(field.as_bytes());
+        }
+    }
+    buf.extend_from_slice(b"\r\n");
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::{OnRowError, RowStream};
+    use crate::RocketResponse;
+    use rocket::{
+        futures::stream::{self, StreamExt},
+        get,
+        http::{ContentType, Status},
+        local::blocking::Client,
+        routes,
+        serde::Serialize,
+    };
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    static CLOSED: AtomicBool = AtomicBool::new(false);
+
+    /// Cursor of a query, closed on drop
+    struct Cursor;
+
+    impl Drop for Cursor {
+        fn drop(&mut self) {
+            CLOSED.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[derive(Serialize)]
+    #[serde(crate = "rocket::serde")]
+    struct User {
+        id: u32,
+        name: &'static str,
+        tags: Vec<&'static str>,
+    }
+
+    fn users(fail: bool) -> impl stream::Stream<Item = Result<(u32, &'static str), sqlx::Error>> {
+        let mut rows = vec![Ok((1, "alice")), Ok((2, "bob, \"jr\""))];
+        if fail {
+            rows.insert(1, Err(sqlx::Error::PoolTimedOut));
+        }
+        stream::iter(rows)
+    }
+
+    fn user((id, name): (u32, &'static str)) -> User {
+        User {
+            id,
+            name,
+            tags: vec!["a"],
+        }
+    }
+
+    #[get("/rows/<format>?<fail>&<record>")]
+    fn route_rows(format: &str, fail: bool, record: bool) -> RocketResponse {
+        let rows = users(fail);
+        let rows = match format {
+            "ndjson" => RowStream::ndjson(rows, user),
+            "array" => RowStream::json_array(rows, user),
+            "empty" => RowStream::json_array(users(false).take(0), user),
+            "tuple" => RowStream::csv(rows, |row| row),
+            _ => RowStream::csv(rows, user),
+        };
+        RocketResponse::RowStream(Box::new(if record {
+            rows.on_error(OnRowError::Record)
+        } else {
+            rows
+        }))
+    }
+
+    #[test]
+    fn test_row_stream() {
+        let rocket = rocket::build().mount("/", routes![route_rows]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/rows/ndjson").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(
+            Some(ContentType::new("application", "x-ndjson")),
+            res.content_type()
+        );
+        assert_eq!(
+            "{\"id\":1,\"name\":\"alice\",\"tags\":[\"a\"]}\n\
+             {\"id\":2,\"name\":\"bob, \\\"jr\\\"\",\"tags\":[\"a\"]}\n",
+            res.into_string().unwrap()
+        );
+
+        let res = client.get("/rows/array").dispatch();
+        assert_eq!(Some(ContentType::JSON), res.content_type());
+        let body = res.into_string().unwrap();
+        assert!(body.starts_with("[{\"id\":1,") && body.ends_with("]}]"));
+        assert_eq!(
+            "[]",
+            client.get("/rows/empty").dispatch().into_string().unwrap()
+        );
+
+        let res = client.get("/rows/csv").dispatch();
+        assert_eq!(Some(ContentType::CSV), res.content_type());
+        assert_eq!(
+            "id,name,tags\r\n1,alice,\"[\"\"a\"\"]\"\r\n2,\"bob, \"\"jr\"\"\",\"[\"\"a\"\"]\"\r\n",
+            res.into_string().unwrap()
+        );
+        let res = client.get("/rows/tuple").dispatch();
+        assert_eq!(
+            "1,alice\r\n2,\"bob, \"\"jr\"\"\"\r\n",
+            res.into_string().unwrap()
+        );
+
+        let res = client.get("/rows/ndjson?fail=true").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert!(res.into_string().map_or(true, |body| !body.contains("bob")));
+    }
+
+    #[test]
+    fn test_row_stream_error_record() {
+        let rocket = rocket::build().mount("/", routes![route_rows]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/rows/ndjson?fail=true&record=true").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(
+            "{\"id\":1,\"name\":\"alice\",\"tags\":[\"a\"]}\n\
+             {\"error\":{\"kind\":\"other\",\"rows\":1}}\n",
+            res.into_string().unwrap()
+        );
+
+        let res = client.get("/rows/array?fail=true&record=true").dispatch();
+        assert_eq!(
+            "[{\"id\":1,\"name\":\"alice\",\"tags\":[\"a\"]},\
+             {\"error\":{\"kind\":\"other\",\"rows\":1}}]",
+            res.into_string().unwrap()
+        );
+
+        let res = client.get("/rows/csv?fail=true&record=true").dispatch();
+        assert_eq!(
+            "id,name,tags\r\n1,alice,\"[\"\"a\"\"]\"\r\n#error,other,1\r\n",
+            res.into_string().unwrap()
+        );
+
+        let res = client.get("/rows/ndjson?record=true").dispatch();
+        assert!(!res.into_string().unwrap().contains("error"));
+    }
+
+    #[get("/rows_endless")]
+    fn route_rows_endless() -> RocketResponse {
+        let cursor = Cursor;
+        let rows = stream::repeat(()).map(move |_| {
+            let _ = &cursor;
+            Ok::<_, sqlx::Error>(1)
+        });
+        RocketResponse::RowStream(Box::new(RowStream::ndjson(rows, |row| row)))
+    }
+
+    #[test]
+    fn test_row_stream_disconnect() {
+        let rocket = rocket::build().mount("/", routes![route_rows_endless]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/rows_endless").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert!(!CLOSED.load(Ordering::SeqCst));
+        drop(res);
+        assert!(CLOSED.load(Ordering::SeqCst));
+    }
+}
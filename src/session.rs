@@ -0,0 +1,165 @@
+use crate::{cookies::comply, FlashKind};
+use rocket::{
+    http::{uri::Reference, Cookie},
+    request::Request,
+    response::{self, Flash, Redirect, Responder},
+};
+use std::convert::TryInto;
+
+/// Change of the session cookie
+#[derive(Debug)]
+enum SessionOp {
+    Login(Cookie<'static>),
+    Logout(Cookie<'static>),
+}
+
+/// `303 See Other` redirect setting or removing a private session cookie with an optional
+/// flash message, the login and logout of a session
+///
+/// The session cookie is a private cookie of the
+/// [CookieJar](rocket::http::CookieJar), encrypted with the `secret_key` of Rocket, and checked
+/// by a managed [CookiePolicy](crate::CookiePolicy) like the cookies of
+/// [WithCookies](crate::WithCookies). The flash message is set like by [Flash], so the page
+/// redirected to reads it with a [FlashMessage](rocket::request::FlashMessage). An OAuth
+/// authorize redirect is an [AuthRedirect](crate::AuthRedirect) of the `oauth` feature.
+///
+/// Requires the `secrets` feature.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::{http::Cookie, post};
+/// use rocket_response::{FlashKind, RocketResponse, SessionRedirect};
+///
+/// #[post("/login")]
+/// fn login() -> RocketResponse {
+///     // after checking the credentials
+///     RocketResponse::SessionRedirect(Box::new(
+///         SessionRedirect::login("/", Cookie::new("session", "alice"))
+///             .flash(FlashKind::Success, "Welcome back"),
+///     ))
+/// }
+///
+/// #[post("/logout")]
+/// fn logout() -> RocketResponse {
+///     RocketResponse::SessionRedirect(Box::new(
+///         SessionRedirect::logout("/login", "session").flash(FlashKind::Success, "Logged out"),
+///     ))
+/// }
+/// ```
+#[derive(Debug)]
+pub struct SessionRedirect {
+    redirect: Redirect,
+    op: SessionOp,
+    flash: Option<(FlashKind, String)>,
+}
+
+impl SessionRedirect {
+    /// Redirect to `uri` adding the private session `cookie`
+    pub fn login<L: TryInto<Reference<'static>>>(uri: L, cookie: Cookie<'static>) -> Self {
+        SessionRedirect {
+            redirect: Redirect::to(uri),
+            op: SessionOp::Login(cookie),
+            flash: None,
+        }
+    }
+
+    /// Redirect to `uri` removing the private session cookie `name`
+    pub fn logout<L, N>(uri: L, name: N) -> Self
+    where
+        L: TryInto<Reference<'static>>,
+        N: Into<String>,
+    {
+        SessionRedirect {
+            redirect: Redirect::to(uri),
+            op: SessionOp::Logout(Cookie::named(name.into())),
+            flash: None,
+        }
+    }
+
+    /// Attaches the flash message `msg` of `kind`.
+    pub fn flash<M: Into<String>>(mut self, kind: FlashKind, msg: M) -> Self {
+        self.flash = Some((kind, msg.into()));
+        self
+    }
+}
+
+impl<'r> Responder<'r, 'static> for SessionRedirect {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let jar = req.cookies();
+        match self.op {
+            SessionOp::Login(mut cookie) => {
+                if comply(req, &mut cookie) {
+                    jar.add_private(cookie);
+                }
+            }
+            SessionOp::Logout(cookie) => jar.remove_private(cookie),
+        }
+        match self.flash {
+            Some((kind, msg)) => Flash::new(self.redirect, kind.as_str(), msg).respond_to(req),
+            None => self.redirect.respond_to(req),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::SessionRedirect;
+    use crate::{FlashKind, RocketResponse};
+    use rocket::{
+        get,
+        http::{Cookie, Status},
+        local::blocking::Client,
+        request::FlashMessage,
+        routes,
+    };
+
+    #[get("/login")]
+    fn route_login() -> RocketResponse {
+        RocketResponse::SessionRedirect(Box::new(
+            SessionRedirect::login("/", Cookie::new("session", "alice"))
+                .flash(FlashKind::Success, "welcome"),
+        ))
+    }
+
+    #[get("/logout")]
+    fn route_logout() -> RocketResponse {
+        RocketResponse::SessionRedirect(Box::new(SessionRedirect::logout("/", "session")))
+    }
+
+    #[get("/")]
+    fn route_index(flash: Option<FlashMessage<'_>>) -> String {
+        flash
+            .map(|flash| format!("{}:{}", flash.kind(), flash.message()))
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn test_session_redirect() {
+        let rocket = rocket::build().mount("/", routes![route_login, route_logout, route_index]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/login").dispatch();
+        assert_eq!(Status::SeeOther, res.status());
+        assert_eq!(Some("/"), res.headers().get_one("Location"));
+        assert_eq!(
+            Some("alice"),
+            res.cookies()
+                .get_private("session")
+                .map(|cookie| cookie.value().to_string())
+                .as_deref()
+        );
+        assert!(res.cookies().get("session").unwrap().value() != "alice");
+        assert_eq!(
+            "success:welcome",
+            client.get("/").dispatch().into_string().unwrap()
+        );
+        assert!(client.cookies().get_private("session").is_some());
+
+        let res = client.get("/logout").dispatch();
+        assert_eq!(Status::SeeOther, res.status());
+        assert!(client.cookies().get_private("session").is_none());
+        assert_eq!("", client.get("/").dispatch().into_string().unwrap());
+    }
+}
@@ -0,0 +1,308 @@
+use crate::UrlContext;
+use rocket::{
+    fairing::{self, Fairing, Info, Kind},
+    http::{ContentType, Method, Status},
+    request::Request,
+    response::{self, Responder, Response},
+    Build, Rocket,
+};
+use std::{fmt::Write, io::Cursor};
+
+/// How frequently a page of a [SitemapRegistry] is likely to change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChangeFreq {
+    /// `always`
+    Always,
+    /// `hourly`
+    Hourly,
+    /// `daily`
+    Daily,
+    /// `weekly`
+    Weekly,
+    /// `monthly`
+    Monthly,
+    /// `yearly`
+    Yearly,
+    /// `never`
+    Never,
+}
+
+impl ChangeFreq {
+    /// Value of the `changefreq` element
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ChangeFreq::Always => "always",
+            ChangeFreq::Hourly => "hourly",
+            ChangeFreq::Daily => "daily",
+            ChangeFreq::Weekly => "weekly",
+            ChangeFreq::Monthly => "monthly",
+            ChangeFreq::Yearly => "yearly",
+            ChangeFreq::Never => "never",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Entry {
+    path: String,
+    changefreq: Option<ChangeFreq>,
+    priority: Option<f32>,
+}
+
+/// Registry of the pages rendered by [Sitemap], attached as fairing
+///
+/// Routes are declared with [route](Self::route()). With
+/// [mounted_routes](Self::mounted_routes()) every mounted `GET` route without dynamic
+/// segments is added at ignition, so the sitemap stays in sync with the routes. Declared
+/// routes keep their `changefreq` and `priority`.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::{get, routes};
+/// use rocket_response::{ChangeFreq, Sitemap, SitemapRegistry};
+///
+/// #[get("/")]
+/// fn index() -> &'static str {
+///     "Hello world"
+/// }
+///
+/// #[get("/sitemap.xml")]
+/// fn sitemap() -> Sitemap {
+///     Sitemap
+/// }
+///
+/// let rocket = rocket::build()
+///     .mount("/", routes![index, sitemap])
+///     .attach(
+///         SitemapRegistry::new()
+///             .route("/", Some(ChangeFreq::Daily), Some(1.0))
+///             .mounted_routes()
+///             .exclude("/sitemap.xml"),
+///     );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SitemapRegistry {
+    entries: Vec<Entry>,
+    excluded: Vec<String>,
+    mounted_routes: bool,
+}
+
+impl SitemapRegistry {
+    /// Empty registry
+    pub fn new() -> Self {
+        SitemapRegistry::default()
+    }
+
+    /// Declares the page `path` with an optional `changefreq` and `priority` from `0.0` to
+    /// `1.0`.
+    pub fn route<P: Into<String>>(
+        mut self,
+        path: P,
+        changefreq: Option<ChangeFreq>,
+        priority: Option<f32>,
+    ) -> Self {
+        let path = path.into();
+        self.entries.retain(|entry| entry.path != path);
+        self.entries.push(Entry {
+            path,
+            changefreq,
+            priority: priority.map(|priority| priority.clamp(0.0, 1.0)),
+        });
+        self
+    }
+
+    /// Adds the mounted `GET` routes without dynamic segments at ignition.
+    pub fn mounted_routes(mut self) -> Self {
+        self.mounted_routes = true;
+        self
+    }
+
+    /// Excludes the mounted route `path`, like the route of the sitemap.
+    pub fn exclude<P: Into<String>>(mut self, path: P) -> Self {
+        self.excluded.push(path.into());
+        self
+    }
+
+    /// Paths of the registered pages
+    pub fn paths(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|entry| entry.path.as_str())
+    }
+
+    /// Sitemap XML with the locations relative to `url`
+    fn render(&self, url: &UrlContext) -> String {
+        let mut xml = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+            <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+        );
+        for entry in &self.entries {
+            let _ = write!(
+                xml,
+                "<url><loc>{}</loc>",
+                escape(&url.absolute(&entry.path))
+            );
+            if let Some(changefreq) = entry.changefreq {
+                let _ = write!(xml, "<changefreq>{}</changefreq>", changefreq.as_str());
+            }
+            if let Some(priority) = entry.priority {
+                let _ = write!(xml, "<priority>{:.1}</priority>", priority);
+            }
+            xml.push_str("</url>\n");
+        }
+        xml.push_str("</urlset>\n");
+        xml
+    }
+}
+
+/// Escapes the XML special characters of `text`.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[rocket::async_trait]
+impl Fairing for SitemapRegistry {
+    fn info(&self) -> Info {
+        Info {
+            name: "Sitemap registry",
+            kind: Kind::Ignite,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        let mut registry = self.clone();
+        if self.mounted_routes {
+            let mut paths: Vec<&str> = rocket
+                .routes()
+                .filter(|route| route.method == Method::Get)
+                .map(|route| route.uri.path())
+                .filter(|path| !path.contains('<'))
+                .collect();
+            paths.sort_unstable();
+            paths.dedup();
+            for path in paths {
+                let known = registry.entries.iter().any(|entry| entry.path == path);
+                if !known && !self.excluded.iter().any(|excluded| excluded == path) {
+                    registry.entries.push(Entry {
+                        path: path.to_string(),
+                        changefreq: None,
+                        priority: None,
+                    });
+                }
+            }
+        }
+        Ok(rocket.manage(registry))
+    }
+}
+
+/// [Sitemap](https://www.sitemaps.org/protocol.html) of the pages of the managed
+/// [SitemapRegistry]
+///
+/// The locations are made absolute with the [UrlContext] of the request. Without an
+/// attached [SitemapRegistry] the response is `404 Not Found`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Sitemap;
+
+impl<'r> Responder<'r, 'static> for Sitemap {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let registry = req.rocket().state::<SitemapRegistry>().ok_or_else(|| {
+            rocket::warn_!("Sitemap without attached SitemapRegistry");
+            Status::NotFound
+        })?;
+        let xml = registry.render(&UrlContext::from_request(req));
+        Response::build()
+            .header(ContentType::XML)
+            .sized_body(xml.len(), Cursor::new(xml))
+            .ok()
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::{ChangeFreq, Sitemap, SitemapRegistry};
+    use crate::RocketResponse;
+    use rocket::{
+        get,
+        http::{ContentType, Header, Status},
+        local::blocking::Client,
+        post, routes,
+    };
+
+    #[get("/")]
+    fn route_index() -> &'static str {
+        "index"
+    }
+
+    #[get("/about?<lang>")]
+    fn route_about(lang: Option<&str>) -> &str {
+        lang.unwrap_or("about")
+    }
+
+    #[get("/items/<id>")]
+    fn route_item(id: usize) -> String {
+        id.to_string()
+    }
+
+    #[post("/items")]
+    fn route_create() -> &'static str {
+        "created"
+    }
+
+    #[get("/sitemap.xml")]
+    fn route_sitemap() -> RocketResponse {
+        RocketResponse::Sitemap(Sitemap)
+    }
+
+    #[test]
+    fn test_sitemap() {
+        let rocket = rocket::build()
+            .mount(
+                "/",
+                routes![route_index, route_about, route_item, route_create],
+            )
+            .mount("/", routes![route_sitemap])
+            .attach(
+                SitemapRegistry::new()
+                    .route("/", Some(ChangeFreq::Daily), Some(1.0))
+                    .route("/items/1&2", None, Some(0.3))
+                    .mounted_routes()
+                    .exclude("/sitemap.xml"),
+            );
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client
+            .get("/sitemap.xml")
+            .header(Header::new("Host", "example.com"))
+            .dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(Some(ContentType::XML), res.content_type());
+        assert_eq!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+            <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n\
+            <url><loc>http://example.com/</loc><changefreq>daily</changefreq>\
+            <priority>1.0</priority></url>\n\
+            <url><loc>http://example.com/items/1&amp;2</loc><priority>0.3</priority></url>\n\
+            <url><loc>http://example.com/about</loc></url>\n\
+            </urlset>\n",
+            res.into_string().unwrap()
+        );
+
+        let rocket = rocket::build().mount("/", routes![route_sitemap]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+        assert_eq!(
+            Status::NotFound,
+            client.get("/sitemap.xml").dispatch().status()
+        );
+    }
+}
@@ -0,0 +1,288 @@
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+use crate::RocketResponseGeneric3;
+use crate::{
+    AcceptList, RocketError, RocketResponse, RocketResponseGeneric, RocketResponseGeneric2,
+};
+use rocket::{
+    http::{ContentType, Status, StatusClass},
+    request::Request,
+    response::{self, Responder, Response},
+    serde::Serialize,
+};
+use std::{fmt, io::Cursor, sync::Arc};
+
+type Render = Arc<dyn Fn(Status) -> String + Send + Sync>;
+
+struct Page {
+    status: Option<Status>,
+    content_type: ContentType,
+    render: Render,
+}
+
+/// Registry of the error pages rendered by [StatusPage]
+///
+/// A page is rendered for one content type, either for every error status with
+/// [page](Self::page()) or for a single status with [status_page](Self::status_page()),
+/// which is preferred. The content type is selected by the `Accept` header of the request.
+/// Without any acceptable page the status is rendered like [RocketError].
+///
+/// The registry is consulted, if it is managed by Rocket.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::http::{ContentType, Status};
+/// use rocket_response::ErrorPages;
+///
+/// let rocket = rocket::build().manage(
+///     ErrorPages::new()
+///         .page(ContentType::HTML, |status| {
+///             format!("<h1>{}</h1><p>Please try again later.</p>", status)
+///         })
+///         .status_page(Status::NotFound, ContentType::HTML, |_| {
+///             "<h1>Page not found</h1>".to_string()
+///         })
+///         .page(ContentType::JSON, |status| {
+///             format!("{{\"error\":{}}}", status.code)
+///         }),
+/// );
+/// ```
+#[derive(Default)]
+pub struct ErrorPages {
+    pages: Vec<Page>,
+}
+
+impl ErrorPages {
+    /// Registry without any page
+    pub fn new() -> Self {
+        ErrorPages::default()
+    }
+
+    /// Renders every error status with `render` for `content_type`.
+    pub fn page<F>(mut self, content_type: ContentType, render: F) -> Self
+    where
+        F: Fn(Status) -> String + Send + Sync + 'static,
+    {
+        self.pages.push(Page {
+            status: None,
+            content_type,
+            render: Arc::new(render),
+        });
+        self
+    }
+
+    /// Renders `status` with `render` for `content_type`.
+    pub fn status_page<F>(mut self, status: Status, content_type: ContentType, render: F) -> Self
+    where
+        F: Fn(Status) -> String + Send + Sync + 'static,
+    {
+        self.pages.push(Page {
+            status: Some(status),
+            content_type,
+            render: Arc::new(render),
+        });
+        self
+    }
+
+    /// Page of `status` with the most preferred content type of `req`
+    fn select(&self, status: Status, req: &Request<'_>) -> Option<&Page> {
+        let pages: Vec<&Page> = {
+            let exact: Vec<&Page> = self
+                .pages
+                .iter()
+                .filter(|page| page.status == Some(status))
+                .collect();
+            if exact.is_empty() {
+                self.pages
+                    .iter()
+                    .filter(|page| page.status.is_none())
+                    .collect()
+            } else {
+                exact
+            }
+        };
+        AcceptList::from_request(req)
+            .preferred_index(pages.iter().map(|page| page.content_type.media_type()))
+            .map(|index| pages[index])
+    }
+}
+
+impl fmt::Debug for ErrorPages {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(
+                self.pages
+                    .iter()
+                    .map(|page| (page.status, page.content_type.to_string())),
+            )
+            .finish()
+    }
+}
+
+/// Status with a body for the error statuses
+///
+/// An error status is rendered with the page of the managed [ErrorPages] or, without one,
+/// like [RocketError] as JSON object or minimal HTML. Other statuses respond without body
+/// like [Status].
+///
+/// The enums convert a [Status] into this variant.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::{get, http::Status};
+/// use rocket_response::RocketResponse;
+///
+/// #[get("/<id>")]
+/// fn item(id: usize) -> RocketResponse {
+///     match id {
+///         0 => Status::NotFound.into(),
+///         _ => RocketResponse::StaticStr("Hello world"),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatusPage(pub Status);
+
+impl<'r> Responder<'r, 'static> for StatusPage {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let status = self.0;
+        match status.class() {
+            StatusClass::ClientError | StatusClass::ServerError => {}
+            _ => return status.respond_to(req),
+        }
+
+        let page = req
+            .rocket()
+            .state::<ErrorPages>()
+            .and_then(|pages| pages.select(status, req));
+        match page {
+            Some(page) => {
+                let body = (page.render)(status);
+                Response::build()
+                    .status(status)
+                    .header(page.content_type.clone())
+                    .sized_body(body.len(), Cursor::new(body))
+                    .ok()
+            }
+            None => RocketError::Other(status, None).respond_to(req),
+        }
+    }
+}
+
+/// Implements `From<Status>` mapping on the [StatusPage] variant.
+macro_rules! impl_from_status {
+    ([$($gen:tt)*] $name:ty $(where $($bound:tt)+)?) => {
+        impl<$($gen)*> From<Status> for $name $(where $($bound)+)? {
+            fn from(status: Status) -> Self {
+                Self::StatusPage(StatusPage(status))
+            }
+        }
+    };
+}
+
+impl_from_status!([] RocketResponse);
+impl_from_status!([T] RocketResponseGeneric<T> where T: Serialize);
+impl_from_status!([T, U] RocketResponseGeneric2<T, U> where T: Serialize);
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+impl_from_status!([T, S, U] RocketResponseGeneric3<T, S, U> where S: Serialize);
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::ErrorPages;
+    use crate::{RocketResponse, RocketResponseGeneric};
+    use rocket::{
+        get,
+        http::{Accept, ContentType, Status},
+        local::blocking::Client,
+        routes,
+    };
+
+    #[get("/status/<code>")]
+    fn route_status(code: u16) -> RocketResponse {
+        Status::from_code(code)
+            .unwrap_or(Status::InternalServerError)
+            .into()
+    }
+
+    #[get("/status_generic")]
+    fn route_status_generic() -> RocketResponseGeneric<&'static str> {
+        Status::Conflict.into()
+    }
+
+    #[test]
+    fn test_status_page() {
+        let rocket = rocket::build().mount("/", routes![route_status, route_status_generic]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/status/204").dispatch();
+        assert_eq!(Status::NoContent, res.status());
+        assert!(res.into_string().is_none());
+
+        let res = client.get("/status/404").header(Accept::JSON).dispatch();
+        assert_eq!(Status::NotFound, res.status());
+        assert_eq!(
+            "{\"status\":404,\"message\":\"Not Found\"}",
+            res.into_string().unwrap()
+        );
+
+        let res = client.get("/status_generic").dispatch();
+        assert_eq!(Status::Conflict, res.status());
+        assert_eq!(Some(ContentType::HTML), res.content_type());
+
+        let rocket = rocket::build()
+            .manage(
+                ErrorPages::new()
+                    .page(ContentType::HTML, |status| {
+                        format!("<h1>{}</h1>", status.code)
+                    })
+                    .status_page(Status::NotFound, ContentType::HTML, |_| {
+                        "<h1>Gone fishing</h1>".to_string()
+                    })
+                    .page(ContentType::JSON, |status| {
+                        format!("{{\"error\":{}}}", status.code)
+                    }),
+            )
+            .mount("/", routes![route_status]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/status/500").dispatch();
+        assert_eq!(Status::InternalServerError, res.status());
+        assert_eq!(Some(ContentType::HTML), res.content_type());
+        assert_eq!("<h1>500</h1>", res.into_string().unwrap());
+
+        let res = client.get("/status/500").header(Accept::JSON).dispatch();
+        assert_eq!(Some(ContentType::JSON), res.content_type());
+        assert_eq!("{\"error\":500}", res.into_string().unwrap());
+
+        let res = client.get("/status/404").header(Accept::HTML).dispatch();
+        assert_eq!("<h1>Gone fishing</h1>", res.into_string().unwrap());
+
+        let res = client.get("/status/404").header(Accept::JSON).dispatch();
+        assert_eq!(
+            "{\"status\":404,\"message\":\"Not Found\"}",
+            res.into_string().unwrap()
+        );
+
+        let res = client.get("/status/503").header(Accept::XML).dispatch();
+        assert_eq!(Status::ServiceUnavailable, res.status());
+        assert_eq!(Some(ContentType::HTML), res.content_type());
+        assert!(res
+            .into_string()
+            .unwrap()
+            .contains("<h1>503 Service Unavailable</h1>"));
+    }
+}
@@ -0,0 +1,218 @@
+use rocket::{
+    http::ContentType,
+    request::Request,
+    response::{self, Responder, Response},
+    tokio::{
+        fs::File,
+        io::{AsyncRead, ReadBuf},
+        time::{self, Sleep},
+    },
+};
+use std::{
+    future::Future,
+    io,
+    path::Path,
+    pin::Pin,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+/// File streamed in chunks of a configurable size with an optional bandwidth limit
+///
+/// Unlike the `TokioFile` variant with the defaults of Rocket, the file is read in chunks of
+/// [chunk_size](Self::chunk_size()), 64 KiB by default, so a multi-GB download holds at most
+/// one chunk in memory. With [throttle](Self::throttle()) the body is sent with at most the
+/// given bytes per second, so a single download doesn't monopolize the bandwidth. Like a
+/// [ReaderStream](rocket::response::stream::ReaderStream), the body is streamed without
+/// `Content-Length`.
+///
+/// An I/O error in the middle of the body aborts the connection without the terminating
+/// chunk, so an HTTP client reports the truncated body as an error instead of a complete
+/// download.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::get;
+/// use rocket_response::{RocketResponse, StreamedFile};
+/// use std::io;
+///
+/// #[get("/video")]
+/// async fn video() -> io::Result<RocketResponse> {
+///     let file = StreamedFile::open("static/video.mp4").await?;
+///     Ok(RocketResponse::StreamedFile(Box::new(
+///         file.chunk_size(256 * 1024).throttle(10 * 1024 * 1024),
+///     )))
+/// }
+/// ```
+#[derive(Debug)]
+pub struct StreamedFile {
+    file: File,
+    chunk_size: usize,
+    throttle: Option<u64>,
+    content_type: Option<ContentType>,
+}
+
+impl StreamedFile {
+    /// Opens the file at `path` with the content type of its extension.
+    pub async fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref();
+        let content_type = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(ContentType::from_extension);
+        Ok(StreamedFile {
+            content_type,
+            ..StreamedFile::from_file(File::open(path).await?)
+        })
+    }
+
+    /// File of `file` without content type
+    pub fn from_file(file: File) -> Self {
+        StreamedFile {
+            file,
+            chunk_size: 64 * 1024,
+            throttle: None,
+            content_type: None,
+        }
+    }
+
+    /// Sets the size of the chunks read and sent, at least 1 byte.
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Limits the body to `bytes_per_sec`, at least 1 byte per second.
+    pub fn throttle(mut self, bytes_per_sec: u64) -> Self {
+        self.throttle = Some(bytes_per_sec.max(1));
+        self
+    }
+
+    /// Sets the content type.
+    pub fn content_type(mut self, content_type: ContentType) -> Self {
+        self.content_type = Some(content_type);
+        self
+    }
+}
+
+impl<'r> Responder<'r, 'static> for StreamedFile {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+        let body = Throttled::new(self.file, self.chunk_size, self.throttle);
+        let mut res = Response::build();
+        res.streamed_body(body);
+        if let Some(content_type) = self.content_type {
+            res.header(content_type);
+        }
+        let mut res = res.ok()?;
+        res.set_max_chunk_size(self.chunk_size);
+        Ok(res)
+    }
+}
+
+/// Body reading chunks of the wrapped body with at most `rate` bytes per second
+struct Throttled<B> {
+    body: B,
+    buf: Vec<u8>,
+    rate: Option<u64>,
+    start: Option<Instant>,
+    sent: u64,
+    sleep: Option<Pin<Box<Sleep>>>,
+}
+
+impl<B> Throttled<B> {
+    fn new(body: B, chunk: usize, rate: Option<u64>) -> Self {
+        Throttled {
+            body,
+            buf: vec![0; chunk],
+            rate,
+            start: None,
+            sent: 0,
+            sleep: None,
+        }
+    }
+}
+
+impl<B: AsyncRead + Unpin> AsyncRead for Throttled<B> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = &mut *self;
+        if let Some(rate) = this.rate {
+            let start = *this.start.get_or_insert_with(Instant::now);
+            let due = start + Duration::from_secs_f64(this.sent as f64 / rate as f64);
+            if this.sleep.is_none() && due > Instant::now() {
+                this.sleep = Some(Box::pin(time::sleep_until(due.into())));
+            }
+            if let Some(sleep) = &mut this.sleep {
+                if sleep.as_mut().poll(cx).is_pending() {
+                    return Poll::Pending;
+                }
+                this.sleep = None;
+            }
+        }
+
+        let len = this.buf.len().min(buf.remaining());
+        let mut chunk = ReadBuf::new(&mut this.buf[..len]);
+        match Pin::new(&mut this.body).poll_read(cx, &mut chunk) {
+            Poll::Ready(Ok(())) => {
+                buf.put_slice(chunk.filled());
+                this.sent += chunk.filled().len() as u64;
+                Poll::Ready(Ok(()))
+            }
+            other => other,
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::StreamedFile;
+    use crate::RocketResponse;
+    use rocket::{
+        get,
+        http::{ContentType, Status},
+        local::blocking::Client,
+        routes,
+    };
+    use std::{
+        io,
+        time::{Duration, Instant},
+    };
+
+    #[get("/streamed?<throttle>")]
+    async fn route_streamed(throttle: Option<u64>) -> io::Result<RocketResponse> {
+        let file = StreamedFile::open("Cargo.toml")
+            .await?
+            .content_type(ContentType::Plain)
+            .chunk_size(100);
+        Ok(RocketResponse::StreamedFile(Box::new(match throttle {
+            Some(throttle) => file.throttle(throttle),
+            None => file,
+        })))
+    }
+
+    #[test]
+    fn test_streamed_file() {
+        let content = std::fs::read("Cargo.toml").unwrap();
+        let rocket = rocket::build().mount("/", routes![route_streamed]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/streamed").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(Some(ContentType::Plain), res.content_type());
+        assert_eq!(content, res.into_bytes().unwrap());
+
+        // the last chunk is due after at least 200 ms
+        let rate = (content.len() as u64 - 1) / 100 * 100 * 5;
+        let start = Instant::now();
+        let res = client
+            .get(format!("/streamed?throttle={}", rate))
+            .dispatch();
+        assert_eq!(content, res.into_bytes().unwrap());
+        assert!(start.elapsed() >= Duration::from_millis(190));
+    }
+}
@@ -0,0 +1,149 @@
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+use crate::RocketResponseGeneric3;
+use crate::{BuildError, RocketResponse, RocketResponseGeneric, RocketResponseGeneric2};
+use rocket::{
+    figment::value::Value,
+    http::{ContentType, Status},
+    response::status::Custom,
+    serde::Serialize,
+};
+use rocket_dyn_templates::Template;
+use std::borrow::Cow;
+
+/// Renders the template `name` with `context` by [Template::show].
+///
+/// A missing template or a failed rendering is a [BuildError::Render].
+fn render(
+    rocket: &rocket::Rocket<rocket::Orbit>,
+    name: &str,
+    context: Result<Value, BuildError>,
+) -> Result<String, BuildError> {
+    Template::show(rocket, name.to_string(), context?).ok_or_else(|| {
+        BuildError::Render(format!(
+            "template `{}` is missing or failed to render",
+            name
+        ))
+    })
+}
+
+/// Implements the `template*` constructors rendering a [Template] with a serializable context.
+macro_rules! impl_template_constructors {
+    ([$($gen:tt)*] $name:ty, [$($bound:tt)*]) => {
+        impl<$($gen)*> $name where $($bound)* {
+            /// Renders the template `name` with `context`, see [Template::show]
+            ///
+            /// The template is rendered when the response is sent, before its body exists, so
+            /// a missing template or a failed rendering responds as [BuildError::Render]. The
+            /// body is sent as `text/html`, other content types need the `Template` variant.
+            pub fn template<N, C>(name: N, context: C) -> Self
+            where
+                N: Into<Cow<'static, str>>,
+                C: Serialize,
+            {
+                let name = name.into();
+                let context = Value::serialize(context)
+                    .map_err(|e| BuildError::Render(format!("context of `{}`: {}", name, e)));
+                Self::lazy(move |req| match render(req.rocket(), &name, context) {
+                    Ok(html) => Self::TypedStr(Box::new(ContentType::HTML), Cow::Owned(html)),
+                    Err(e) => Self::BuildError(Box::new(e)),
+                })
+            }
+
+            /// Renders the template `name` with `context` and responds with `status`
+            /// instead of `200 OK`, e.g. for an error page
+            pub fn template_status<N, C>(status: Status, name: N, context: C) -> Self
+            where
+                N: Into<Cow<'static, str>>,
+                C: Serialize,
+            {
+                Self::Custom(Box::new(Custom(status, Self::template(name, context))))
+            }
+        }
+    };
+}
+
+impl_template_constructors!([] RocketResponse, []);
+impl_template_constructors!([T] RocketResponseGeneric<T>, [T: Serialize]);
+impl_template_constructors!([T, U] RocketResponseGeneric2<T, U>, [T: Serialize]);
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+impl_template_constructors!([T, S, U] RocketResponseGeneric3<T, S, U>, [S: Serialize]);
+
+#[cfg(all(test, feature = "templates-tera"))]
+#[allow(unused_imports)]
+mod tests {
+    use crate::RocketResponse;
+    use rocket::{
+        figment::Figment,
+        get,
+        http::{ContentType, Status},
+        local::blocking::Client,
+        routes,
+        serde::Serialize,
+    };
+    use rocket_dyn_templates::Template;
+    use std::fs;
+
+    #[derive(Serialize)]
+    #[serde(crate = "rocket::serde")]
+    struct Context {
+        name: &'static str,
+    }
+
+    #[get("/template/<ok>")]
+    fn route_template(ok: bool) -> RocketResponse {
+        if ok {
+            RocketResponse::template("hello", Context { name: "world" })
+        } else {
+            RocketResponse::template_status(
+                Status::InternalServerError,
+                "hello",
+                Context { name: "error" },
+            )
+        }
+    }
+
+    #[get("/template_missing")]
+    fn route_template_missing() -> RocketResponse {
+        RocketResponse::template("missing", Context { name: "world" })
+    }
+
+    #[test]
+    fn test_template() {
+        let dir =
+            std::env::temp_dir().join(format!("rocket-response-templates-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("hello.html.tera"), "Hello {{ name }}").unwrap();
+
+        let figment = Figment::from(rocket::Config::default()).merge(("template_dir", &dir));
+        let rocket = rocket::custom(figment)
+            .attach(Template::fairing())
+            .mount("/", routes![route_template, route_template_missing]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/template/true").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(ContentType::HTML, res.content_type().unwrap());
+        assert_eq!("Hello world", res.into_string().unwrap());
+
+        let res = client.get("/template/false").dispatch();
+        assert_eq!(Status::InternalServerError, res.status());
+        assert_eq!("Hello error", res.into_string().unwrap());
+
+        let res = client.get("/template_missing").dispatch();
+        assert_eq!(Status::InternalServerError, res.status());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
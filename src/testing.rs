@@ -0,0 +1,387 @@
+//! Utilities to test response-producing code without mounting routes.
+//!
+//! Available with the `testing` feature.
+//!
+//! Besides rendering a response for a synthetic request, the module provides
+//! [assert_variant](crate::assert_variant), [dispatch_enum] and the `expected_status()` and
+//! `expected_content_type()` of the enums, so tests don't repeat which status and content
+//! type a variant responds with.
+
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+use crate::RocketResponseGeneric3;
+use crate::{RocketResponse, RocketResponseGeneric, RocketResponseGeneric2};
+use rocket::{
+    config::LogLevel,
+    http::{ContentType, Header, HeaderMap, Method, Status},
+    local::blocking::Client,
+    request::Request,
+    response::Responder,
+    route::{self, Handler, Route},
+    serde::Serialize,
+    tokio, Build, Config, Data, Rocket,
+};
+use std::sync::Arc;
+
+/// Calls `f` with a synthetic [Request] for `method` and `uri`.
+///
+/// The request belongs to an ignited but not launched [Rocket](rocket::Rocket) instance
+/// without any routes, so no server is started and no route is dispatched.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::http::{Method, Status};
+/// use rocket_response::{testing, RocketResponse};
+///
+/// let (status, headers, body) = testing::with_request(Method::Get, "/", |req| {
+///     testing::render_to_bytes(RocketResponse::StaticStr("Hello world"), req)
+/// })
+/// .unwrap();
+///
+/// assert_eq!(Status::Ok, status);
+/// assert_eq!(Some("text/plain; charset=utf-8"), headers.get_one("Content-Type"));
+/// assert_eq!(b"Hello world".to_vec(), body);
+/// ```
+pub fn with_request<T, F>(method: Method, uri: &str, f: F) -> T
+where
+    F: FnOnce(&Request<'_>) -> T,
+{
+    let config = Config {
+        log_level: LogLevel::Off,
+        ..Config::debug_default()
+    };
+    let client = Client::untracked(rocket::custom(config)).expect("no rocket instance");
+    let req = client.req(method, uri.to_string());
+    f(req.inner())
+}
+
+/// Finalizes `resp` for `req` into its [Status], [HeaderMap] and body bytes.
+///
+/// The body is read to its end on a dedicated runtime, so this must not be called from
+/// within an async context.
+///
+/// Returns the `Err` of the [Responder] if there is one.
+///
+/// See [with_request] for an example.
+pub fn render_to_bytes<'r, R>(
+    resp: R,
+    req: &'r Request<'_>,
+) -> Result<(Status, HeaderMap<'static>, Vec<u8>), Status>
+where
+    R: Responder<'r, 'static>,
+{
+    let mut res = resp.respond_to(req)?;
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("no runtime");
+    let body = runtime
+        .block_on(res.body_mut().to_bytes())
+        .map_err(|_| Status::InternalServerError)?;
+
+    Ok((res.status(), res.headers().clone(), body))
+}
+
+/// Handler responding with the responder returned by its function
+struct FnHandler<F>(Arc<F>);
+
+impl<F> Clone for FnHandler<F> {
+    fn clone(&self) -> Self {
+        FnHandler(Arc::clone(&self.0))
+    }
+}
+
+#[rocket::async_trait]
+impl<F, R> Handler for FnHandler<F>
+where
+    F: Fn() -> R + Send + Sync + 'static,
+    R: for<'r> Responder<'r, 'static>,
+{
+    async fn handle<'r>(&self, req: &'r Request<'_>, _: Data<'r>) -> route::Outcome<'r> {
+        route::Outcome::from(req, (self.0)())
+    }
+}
+
+/// Dispatches `method` and `uri` to `rocket` with a route responding with the result of `f`.
+///
+/// The route matches every path, so `uri` only matters for responders reading the request.
+/// Managed state and fairings of `rocket` apply like in production. Returns the [Status],
+/// [HeaderMap] and body bytes of the response, also of the catcher of an error status.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::http::{Method, Status};
+/// use rocket_response::{assert_variant, testing, RocketResponse};
+///
+/// let response = || RocketResponse::StaticStr("Hello world");
+/// assert_variant!(response(), RocketResponse::StaticStr);
+///
+/// let (status, headers, body) =
+///     testing::dispatch_enum(rocket::build(), Method::Get, "/", response);
+/// assert_eq!(response().expected_status(), Some(status));
+/// assert_eq!(
+///     response().expected_content_type().map(|ct| ct.to_string()).as_deref(),
+///     headers.get_one("Content-Type")
+/// );
+/// assert_eq!(b"Hello world".to_vec(), body);
+/// ```
+pub fn dispatch_enum<F, R>(
+    rocket: Rocket<Build>,
+    method: Method,
+    uri: &str,
+    f: F,
+) -> (Status, HeaderMap<'static>, Vec<u8>)
+where
+    F: Fn() -> R + Send + Sync + 'static,
+    R: for<'r> Responder<'r, 'static>,
+{
+    let route = Route::new(method, "/<_..>", FnHandler(Arc::new(f)));
+    let client = Client::untracked(rocket.mount("/", vec![route])).expect("no rocket instance");
+    let res = client.req(method, uri.to_string()).dispatch();
+    let status = res.status();
+    let mut headers = HeaderMap::new();
+    for header in res.headers().iter() {
+        headers.add(Header::new(
+            header.name().as_str().to_string(),
+            header.value().to_string(),
+        ));
+    }
+    (status, headers, res.into_bytes().unwrap_or_default())
+}
+
+/// Asserts that a response is the given variant.
+///
+/// Panics with the name of the actual variant otherwise.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket_response::{assert_variant, RocketResponse};
+///
+/// let response = RocketResponse::redirect_to("/");
+/// assert_variant!(response, RocketResponse::Redirect);
+/// ```
+#[macro_export]
+macro_rules! assert_variant {
+    ($response:expr, $variant:path) => {
+        match &$response {
+            $variant(..) => {}
+            other => panic!(
+                "expected variant {}, got {}",
+                stringify!($variant),
+                other.variant_name()
+            ),
+        }
+    };
+}
+
+/// Implements `expected_status()` and `expected_content_type()` of the variants.
+macro_rules! impl_expectations {
+    ([$($gen:tt)*] $name:ty $(where $($bound:tt)+)?) => {
+        impl<$($gen)*> $name $(where $($bound)+)? {
+            /// Status of the response, if the variant determines it
+            ///
+            /// Wrappers, which don't change the status, return the one of the wrapped
+            /// response.
+            pub fn expected_status(&self) -> Option<Status> {
+                match self {
+                    Self::Accepted(_) | Self::AcceptedJob(_) => Some(Status::Accepted),
+                    Self::BadRequest(_) => Some(Status::BadRequest),
+                    Self::Conflict(_) => Some(Status::Conflict),
+                    Self::Created(_) | Self::CreatedResource(_) => Some(Status::Created),
+                    Self::Custom(r) => Some(r.0),
+                    Self::Forbidden(_) => Some(Status::Forbidden),
+                    Self::NotFound(_) => Some(Status::NotFound),
+                    Self::NoContent(_) => Some(Status::NoContent),
+                    Self::CacheEntry(r) => Some(r.status),
+                    Self::PurgeResult(_) => Some(Status::Ok),
+                    Self::RocketError(r) => Some(r.status()),
+                    Self::ServiceUnavailable(_) => Some(Status::ServiceUnavailable),
+                    Self::Status(r) => Some(*r),
+                    Self::StatusPage(r) => Some(r.0),
+                    Self::TooManyRequests(_) => Some(Status::TooManyRequests),
+                    Self::Unauthorized(_) => Some(Status::Unauthorized),
+                    Self::Css(_)
+                    | Self::Html(_)
+                    | Self::JavaScript(_)
+                    | Self::Json(_)
+                    | Self::MsgPack(_)
+                    | Self::Plain(_)
+                    | Self::StaticSlice(_)
+                    | Self::StaticStr(_)
+                    | Self::String(_)
+                    | Self::Typed(..)
+                    | Self::TypedStr(..)
+                    | Self::Vec(_)
+                    | Self::Xml(_) => Some(Status::Ok),
+                    #[cfg(feature = "graphql")]
+                    Self::GraphQL(_) => Some(Status::Ok),
+                    #[cfg(all(feature = "db-sqlx", feature = "json"))]
+                    Self::RowStream(_) => Some(Status::Ok),
+                    Self::Hardened(r) => r.responder().expected_status(),
+                    Self::HeadAware(r) => r.responder().expected_status(),
+                    Self::WithCache(r) => r.responder().expected_status(),
+                    Self::WithCookies(r) => r.responder().expected_status(),
+                    Self::WithCost(r) => r.responder().expected_status(),
+                    _ => None,
+                }
+            }
+
+            /// Content type of the response, if the variant determines it
+            ///
+            /// Wrappers, which don't change the body, return the one of the wrapped response.
+            pub fn expected_content_type(&self) -> Option<ContentType> {
+                match self {
+                    Self::Css(_) => Some(ContentType::CSS),
+                    Self::Html(_) => Some(ContentType::HTML),
+                    Self::JavaScript(_) => Some(ContentType::JavaScript),
+                    Self::Json(_) => Some(ContentType::JSON),
+                    Self::MsgPack(_) => Some(ContentType::MsgPack),
+                    Self::Plain(_) | Self::StaticStr(_) | Self::String(_) => {
+                        Some(ContentType::Plain)
+                    }
+                    Self::StaticSlice(_) | Self::Vec(_) => Some(ContentType::Binary),
+                    Self::Typed(r, _) | Self::TypedStr(r, _) => Some((**r).clone()),
+                    Self::CacheEntry(r) => Some(r.content_type.clone()),
+                    Self::PurgeResult(_) => Some(ContentType::JSON),
+                    #[cfg(feature = "json")]
+                    Self::HealthReport(_) => Some(ContentType::JSON),
+                    Self::Xml(_) => Some(ContentType::XML),
+                    #[cfg(feature = "bincode")]
+                    Self::SerdeBincode(_) => Some(ContentType::Binary),
+                    #[cfg(feature = "cbor")]
+                    Self::SerdeCbor(_) => Some(ContentType::new("application", "cbor")),
+                    #[cfg(feature = "graphql")]
+                    Self::GraphQL(_) => Some(ContentType::JSON),
+                    #[cfg(all(feature = "db-sqlx", feature = "json"))]
+                    Self::RowStream(r) => Some(r.content_type()),
+                    #[cfg(feature = "json")]
+                    Self::SerdeJson(_) | Self::SerdeValue(_) => Some(ContentType::JSON),
+                    #[cfg(feature = "msgpack")]
+                    Self::SerdeMsgPack(_) | Self::SerdeMsgPackValue(_) => {
+                        Some(ContentType::MsgPack)
+                    }
+                    #[cfg(feature = "xml")]
+                    Self::SerdeXml(_) => Some(ContentType::new("application", "xml")),
+                    Self::AcceptedJob(r) => r.responder().expected_content_type(),
+                    Self::Custom(r) => r.1.expected_content_type(),
+                    Self::Hardened(r) => r.responder().expected_content_type(),
+                    Self::HeadAware(r) => r.responder().expected_content_type(),
+                    Self::WithCache(r) => r.responder().expected_content_type(),
+                    Self::WithCookies(r) => r.responder().expected_content_type(),
+                    Self::WithCost(r) => r.responder().expected_content_type(),
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+impl_expectations!([] RocketResponse);
+impl_expectations!([T] RocketResponseGeneric<T> where T: Serialize);
+impl_expectations!([T, U] RocketResponseGeneric2<T, U> where T: Serialize);
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+impl_expectations!([T, S, U] RocketResponseGeneric3<T, S, U> where S: Serialize);
+
+#[cfg(test)]
+mod tests {
+    use super::{dispatch_enum, render_to_bytes, with_request};
+    use crate::{CachePolicy, RocketError, RocketResponse, RocketResponseGeneric, WithCache};
+    use rocket::{
+        http::{ContentType, Method, Status},
+        response::status,
+    };
+    use std::time::Duration;
+
+    #[test]
+    fn test_render_to_bytes() {
+        let (status, headers, body) = with_request(Method::Get, "/", |req| {
+            render_to_bytes(RocketResponse::redirect_to("/admin"), req)
+        })
+        .unwrap();
+
+        assert_eq!(Status::SeeOther, status);
+        assert_eq!(Some("/admin"), headers.get_one("Location"));
+        assert!(body.is_empty());
+
+        let (status, _, body) = with_request(Method::Get, "/", |req| {
+            render_to_bytes(
+                RocketResponseGeneric::NotFound(status::NotFound("not here")),
+                req,
+            )
+        })
+        .unwrap();
+
+        assert_eq!(Status::NotFound, status);
+        assert_eq!(b"not here".to_vec(), body);
+    }
+
+    #[test]
+    fn test_render_to_bytes_err() {
+        let res = with_request(Method::Get, "/", |req| {
+            render_to_bytes(RocketResponse::Status(Status::Forbidden), req)
+        });
+
+        assert_eq!(Some(Status::Forbidden), res.err());
+    }
+
+    #[test]
+    fn test_assert_variant() {
+        assert_variant!(RocketResponse::redirect_to("/"), RocketResponse::Redirect);
+        let res = std::panic::catch_unwind(|| {
+            assert_variant!(RocketResponse::StaticStr(""), RocketResponse::Redirect);
+        });
+        let msg = res.unwrap_err().downcast::<String>().unwrap();
+        assert_eq!(
+            "expected variant RocketResponse::Redirect, got StaticStr",
+            msg.as_str()
+        );
+    }
+
+    #[test]
+    fn test_dispatch_enum() {
+        let responses: Vec<fn() -> RocketResponse> = vec![
+            || RocketResponse::StaticStr("Hello world"),
+            || RocketResponse::NotFound(status::NotFound("not here")),
+            || RocketResponse::RocketError(RocketError::Conflict(None)),
+            || RocketResponse::rate_limited(Duration::from_secs(1), RocketResponse::Vec(vec![1])),
+        ];
+        for response in responses {
+            let expected = response().expected_status().unwrap();
+            let (status, _, _) = dispatch_enum(rocket::build(), Method::Get, "/", response);
+            assert_eq!(expected, status);
+        }
+
+        let response = || RocketResponseGeneric::Html(rocket::response::content::RawHtml("<p>"));
+        let (status, headers, body) =
+            dispatch_enum(rocket::build(), Method::Post, "/any/path?q=1", response);
+        assert_eq!(Some(status), response().expected_status());
+        assert_eq!(
+            response().expected_content_type(),
+            headers
+                .get_one("Content-Type")
+                .and_then(ContentType::parse_flexible)
+        );
+        assert_eq!(b"<p>".to_vec(), body);
+
+        let wrapped = RocketResponse::WithCache(WithCache::new(
+            RocketResponse::Vec(vec![]),
+            CachePolicy::new(),
+        ));
+        assert_eq!(Some(ContentType::Binary), wrapped.expected_content_type());
+        assert_eq!(None, RocketResponse::redirect_to("/").expected_status());
+    }
+}
@@ -0,0 +1,148 @@
+use rocket::{
+    http::ContentType,
+    request::Request,
+    response::{self, Responder, Response},
+};
+use std::io::Cursor;
+
+/// UTF-8 byte order mark
+const BOM: &str = "\u{feff}";
+
+/// Text body with an optional UTF-8 BOM and CRLF line endings
+///
+/// Excel and other Windows consumers of CSV or plain text exports need both, which are
+/// applied when the response is built.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::get;
+/// use rocket_response::{RocketResponse, TextBody};
+///
+/// #[get("/export.csv")]
+/// fn export() -> RocketResponse {
+///     RocketResponse::TextBody(Box::new(TextBody::csv("id;name\n1;Zoë\n").windows()))
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextBody {
+    text: String,
+    content_type: ContentType,
+    bom: bool,
+    crlf: bool,
+}
+
+impl TextBody {
+    /// Text of `content_type`
+    pub fn new<S: Into<String>>(text: S, content_type: ContentType) -> Self {
+        TextBody {
+            text: text.into(),
+            content_type,
+            bom: false,
+            crlf: false,
+        }
+    }
+
+    /// Text of `text/plain`
+    pub fn plain<S: Into<String>>(text: S) -> Self {
+        TextBody::new(text, ContentType::Plain)
+    }
+
+    /// Text of `text/csv`
+    pub fn csv<S: Into<String>>(text: S) -> Self {
+        TextBody::new(text, ContentType::CSV)
+    }
+
+    /// Text of `text/xml`
+    pub fn xml<S: Into<String>>(text: S) -> Self {
+        TextBody::new(text, ContentType::XML)
+    }
+
+    /// Prepends a UTF-8 BOM, if the text doesn't start with one.
+    pub fn bom(mut self) -> Self {
+        self.bom = true;
+        self
+    }
+
+    /// Normalizes the line endings to CRLF.
+    pub fn crlf(mut self) -> Self {
+        self.crlf = true;
+        self
+    }
+
+    /// Prepends a UTF-8 BOM and normalizes the line endings to CRLF.
+    pub fn windows(self) -> Self {
+        self.bom().crlf()
+    }
+
+    /// The text like it is sent
+    pub fn into_string(self) -> String {
+        let mut text = String::with_capacity(self.text.len() + BOM.len());
+        if self.bom && !self.text.starts_with(BOM) {
+            text.push_str(BOM);
+        }
+
+        if self.crlf {
+            let mut lines = self.text.split('\n').peekable();
+            while let Some(line) = lines.next() {
+                text.push_str(line.strip_suffix('\r').unwrap_or(line));
+                if lines.peek().is_some() {
+                    text.push_str("\r\n");
+                }
+            }
+        } else {
+            text.push_str(&self.text);
+        }
+        text
+    }
+}
+
+impl<'r> Responder<'r, 'static> for TextBody {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+        let content_type = self.content_type.clone();
+        let text = self.into_string();
+        Response::build()
+            .header(content_type)
+            .sized_body(text.len(), Cursor::new(text))
+            .ok()
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::TextBody;
+    use crate::RocketResponse;
+    use rocket::{
+        get,
+        http::{ContentType, Status},
+        local::blocking::Client,
+        routes,
+    };
+
+    #[get("/text_body")]
+    fn route_text_body() -> RocketResponse {
+        RocketResponse::TextBody(Box::new(TextBody::csv("a;b\n1;2\r\n").windows()))
+    }
+
+    #[test]
+    fn test_text_body() {
+        assert_eq!("a\nb", TextBody::plain("a\nb").into_string());
+        assert_eq!("a\r\nb", TextBody::plain("a\nb").crlf().into_string());
+        assert_eq!(
+            "\u{feff}a",
+            TextBody::plain("\u{feff}a").bom().into_string()
+        );
+
+        let rocket = rocket::build().mount("/", routes![route_text_body]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/text_body").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(ContentType::CSV, res.content_type().unwrap());
+        assert_eq!(
+            b"\xef\xbb\xbfa;b\r\n1;2\r\n".to_vec(),
+            res.into_bytes().unwrap()
+        );
+    }
+}
@@ -0,0 +1,206 @@
+use rocket::{
+    http::Header,
+    request::Request,
+    response::{self, Responder},
+};
+use std::{
+    fmt::Write,
+    time::{Duration, Instant},
+};
+
+/// Named metric of a `Server-Timing` header
+#[derive(Debug, Clone, PartialEq)]
+struct Metric {
+    name: String,
+    duration: Option<Duration>,
+    desc: Option<String>,
+}
+
+impl Metric {
+    fn write_to(&self, header: &mut String) {
+        if !header.is_empty() {
+            header.push_str(", ");
+        }
+        header.push_str(&self.name);
+        if let Some(duration) = self.duration {
+            let _ = write!(header, ";dur={:.3}", duration.as_secs_f64() * 1000.0);
+        }
+        if let Some(desc) = &self.desc {
+            let _ = write!(
+                header,
+                ";desc=\"{}\"",
+                desc.replace('\\', "\\\\").replace('"', "\\\"")
+            );
+        }
+    }
+}
+
+/// Wrapper emitting a [`Server-Timing`](https://www.w3.org/TR/server-timing/) header
+///
+/// The time spent responding by the wrapped responder, e.g. serializing its body, is sent
+/// as metric `respond`. With [started](Self::started()) the time since the handler started
+/// is sent as metric `handler`. Further metrics are added with [metric](Self::metric()).
+///
+/// The durations are in milliseconds. A `Server-Timing` header of the wrapped responder is
+/// kept.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::get;
+/// use rocket_response::{RocketResponse, Timed};
+/// use std::time::Instant;
+///
+/// #[get("/report")]
+/// fn report() -> RocketResponse {
+///     let start = Instant::now();
+///     let query = Instant::now();
+///     // querying the database
+///     let query = query.elapsed();
+///
+///     RocketResponse::Timed(Box::new(
+///         Timed::new(RocketResponse::StaticStr("[]"))
+///             .started(start)
+///             .metric("db", query)
+///             .describe("cache", "miss"),
+///     ))
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Timed<R> {
+    responder: Box<R>,
+    started: Option<Instant>,
+    metrics: Vec<Metric>,
+}
+
+impl<R> Timed<R> {
+    /// Wraps `responder` to time its response
+    pub fn new(responder: R) -> Self {
+        Timed {
+            responder: Box::new(responder),
+            started: None,
+            metrics: Vec::new(),
+        }
+    }
+
+    /// Sets the start of the handler to send the metric `handler`.
+    pub fn started(mut self, started: Instant) -> Self {
+        self.started = Some(started);
+        self
+    }
+
+    /// Adds the metric `name` with `duration`.
+    pub fn metric<N: Into<String>>(mut self, name: N, duration: Duration) -> Self {
+        self.metrics.push(Metric {
+            name: name.into(),
+            duration: Some(duration),
+            desc: None,
+        });
+        self
+    }
+
+    /// Adds the metric `name` with the description `desc` and without duration.
+    pub fn describe<N: Into<String>, D: Into<String>>(mut self, name: N, desc: D) -> Self {
+        self.metrics.push(Metric {
+            name: name.into(),
+            duration: None,
+            desc: Some(desc.into()),
+        });
+        self
+    }
+
+    /// Consumes the wrapper and returns the wrapped responder.
+    pub fn into_inner(self) -> R {
+        *self.responder
+    }
+
+    pub(crate) fn responder(&self) -> &R {
+        &self.responder
+    }
+}
+
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for Timed<R> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let start = Instant::now();
+        let mut res = self.responder.respond_to(req)?;
+        let respond = start.elapsed();
+
+        let mut header = String::new();
+        if let Some(started) = self.started {
+            Metric {
+                name: "handler".to_string(),
+                duration: Some(start.saturating_duration_since(started)),
+                desc: None,
+            }
+            .write_to(&mut header);
+        }
+        Metric {
+            name: "respond".to_string(),
+            duration: Some(respond),
+            desc: None,
+        }
+        .write_to(&mut header);
+        for metric in &self.metrics {
+            metric.write_to(&mut header);
+        }
+
+        res.adjoin_header(Header::new("Server-Timing", header));
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::Timed;
+    use crate::RocketResponse;
+    use rocket::{get, http::Header, local::blocking::Client, routes};
+    use std::time::{Duration, Instant};
+
+    #[derive(rocket::Responder)]
+    struct Timing {
+        body: &'static str,
+        timing: Header<'static>,
+    }
+
+    #[get("/timed")]
+    fn route_timed() -> RocketResponse {
+        RocketResponse::Timed(Box::new(
+            Timed::new(RocketResponse::StaticStr("Hello world"))
+                .started(Instant::now())
+                .metric("db", Duration::from_micros(1500))
+                .describe("cache", "miss \"cold\""),
+        ))
+    }
+
+    #[get("/timed_inner")]
+    fn route_timed_inner() -> Timed<Timing> {
+        Timed::new(Timing {
+            body: "Hello world",
+            timing: Header::new("Server-Timing", "inner;dur=1"),
+        })
+    }
+
+    #[test]
+    fn test_timed() {
+        let rocket = rocket::build().mount("/", routes![route_timed, route_timed_inner]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/timed").dispatch();
+        let timing = res.headers().get_one("Server-Timing").unwrap();
+        assert!(timing.starts_with("handler;dur="), "{}", timing);
+        assert!(timing.contains(", respond;dur="), "{}", timing);
+        assert!(
+            timing.ends_with(", db;dur=1.500, cache;desc=\"miss \\\"cold\\\"\""),
+            "{}",
+            timing
+        );
+        assert_eq!("Hello world", res.into_string().unwrap());
+
+        let res = client.get("/timed_inner").dispatch();
+        let timings: Vec<&str> = res.headers().get("Server-Timing").collect();
+        assert_eq!(2, timings.len());
+        assert_eq!("inner;dur=1", timings[0]);
+        assert!(timings[1].starts_with("respond;dur="));
+    }
+}
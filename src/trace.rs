@@ -0,0 +1,116 @@
+use rocket::response;
+
+/// Emits an event of the responded `variant` with status, content type and body size.
+///
+/// Server errors are emitted at level `ERROR`, everything else at `DEBUG`.
+pub(crate) fn responded(variant: &'static str, res: &response::Result<'_>) {
+    match res {
+        Ok(res) => {
+            let status = res.status().code;
+            let content_type = res.content_type().map(|ct| ct.to_string());
+            let content_type = content_type.as_deref().unwrap_or("");
+            let body_size = res.body().preset_size();
+            if status >= 500 {
+                tracing::error!(variant, status, content_type, body_size = ?body_size, "responded");
+            } else {
+                tracing::debug!(variant, status, content_type, body_size = ?body_size, "responded");
+            }
+        }
+        Err(status) if status.code >= 500 => {
+            tracing::error!(variant, status = status.code, "failed to respond");
+        }
+        Err(status) => {
+            tracing::debug!(variant, status = status.code, "failed to respond");
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use crate::RocketResponse;
+    use rocket::{get, http::Status, local::blocking::Client, routes};
+    use std::{
+        fmt::Debug,
+        sync::{Arc, Mutex},
+    };
+    use tracing::{
+        field::{Field, Visit},
+        span, Event, Level, Metadata, Subscriber,
+    };
+
+    /// Subscriber recording the fields of all events
+    #[derive(Clone, Default)]
+    struct Recorder(Arc<Mutex<Vec<(Level, String)>>>);
+
+    struct Fields(String);
+
+    impl Visit for Fields {
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            self.0.push_str(&format!("{}={:?} ", field.name(), value));
+        }
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+            span::Id::from_u64(1)
+        }
+
+        fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+        fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut fields = Fields(String::new());
+            event.record(&mut fields);
+            self.0
+                .lock()
+                .unwrap()
+                .push((*event.metadata().level(), fields.0));
+        }
+
+        fn enter(&self, _: &span::Id) {}
+
+        fn exit(&self, _: &span::Id) {}
+    }
+
+    #[get("/traced/<id>")]
+    fn route_traced(id: usize) -> RocketResponse {
+        match id {
+            0 => RocketResponse::Status(Status::InternalServerError),
+            _ => RocketResponse::StaticStr("Hello world"),
+        }
+    }
+
+    #[test]
+    fn test_responded() {
+        let recorder = Recorder::default();
+        tracing::subscriber::with_default(recorder.clone(), || {
+            let rocket = rocket::build().mount("/", routes![route_traced]);
+            let client = Client::tracked(rocket).expect("no rocket instance");
+            client.get("/traced/1").dispatch();
+            client.get("/traced/0").dispatch();
+        });
+
+        let events = recorder.0.lock().unwrap();
+        let responded: Vec<_> = events
+            .iter()
+            .filter(|(_, fields)| fields.contains("variant="))
+            .collect();
+        assert_eq!(2, responded.len());
+        assert_eq!(Level::DEBUG, responded[0].0);
+        assert!(responded[0].1.contains("variant=\"StaticStr\""));
+        assert!(responded[0].1.contains("status=200"));
+        assert!(responded[0]
+            .1
+            .contains("content_type=\"text/plain; charset=utf-8\""));
+        assert!(responded[0].1.contains("body_size=Some(11)"));
+        assert_eq!(Level::ERROR, responded[1].0);
+        assert!(responded[1].1.contains("variant=\"Status\""));
+        assert!(responded[1].1.contains("status=500"));
+    }
+}
@@ -0,0 +1,110 @@
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+use crate::RocketResponseGeneric3;
+use crate::{RocketResponse, RocketResponseGeneric, RocketResponseGeneric2};
+use rocket::{http::ContentType, serde::Serialize};
+use std::borrow::Cow;
+
+/// Implements the `typed*` constructors of the `Typed` and `TypedStr` variants.
+macro_rules! impl_typed_constructors {
+    ([$($gen:tt)*] $name:ty $(where $($bound:tt)+)?) => {
+        impl<$($gen)*> $name $(where $($bound)+)? {
+            /// `body` with `content_type`, for content types without a `Raw*` responder like
+            /// `image/png` or `application/wasm`
+            ///
+            /// ## Example usage
+            ///
+            /// ```rust
+            /// use rocket::{get, http::ContentType};
+            /// use rocket_response::RocketResponse;
+            ///
+            /// #[get("/module.wasm")]
+            /// fn module() -> RocketResponse {
+            ///     RocketResponse::typed(ContentType::WASM, &b"\0asm\x01\0\0\0"[..])
+            /// }
+            /// ```
+            pub fn typed<B: Into<Vec<u8>>>(content_type: ContentType, body: B) -> Self {
+                Self::Typed(Box::new(content_type), body.into())
+            }
+
+            /// Text `body` with `content_type`, like `text/calendar`
+            pub fn typed_str<B>(content_type: ContentType, body: B) -> Self
+            where
+                B: Into<Cow<'static, str>>,
+            {
+                Self::TypedStr(Box::new(content_type), body.into())
+            }
+        }
+    };
+}
+
+impl_typed_constructors!([] RocketResponse);
+impl_typed_constructors!([T] RocketResponseGeneric<T> where T: Serialize);
+impl_typed_constructors!([T, U] RocketResponseGeneric2<T, U> where T: Serialize);
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "json",
+    feature = "msgpack",
+    feature = "xml"
+))]
+impl_typed_constructors!([T, S, U] RocketResponseGeneric3<T, S, U> where S: Serialize);
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use crate::{ContentKind, RocketResponse, RocketResponseGeneric};
+    use rocket::{
+        get,
+        http::{ContentType, Status},
+        local::blocking::Client,
+        routes,
+    };
+
+    #[get("/typed")]
+    fn route_typed() -> RocketResponse {
+        RocketResponse::typed(ContentType::PNG, vec![0x89, b'P', b'N', b'G'])
+    }
+
+    #[get("/typed_str")]
+    fn route_typed_str() -> RocketResponseGeneric<&'static str> {
+        RocketResponseGeneric::typed_str(
+            ContentType::new("text", "calendar"),
+            "BEGIN:VCALENDAR\r\nEND:VCALENDAR\r\n".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_typed() {
+        let rocket = rocket::build().mount("/", routes![route_typed, route_typed_str]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/typed").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(Some(ContentType::PNG), res.content_type());
+        assert_eq!(vec![0x89, b'P', b'N', b'G'], res.into_bytes().unwrap());
+
+        let res = client.get("/typed_str").dispatch();
+        assert_eq!(
+            Some(ContentType::new("text", "calendar")),
+            res.content_type()
+        );
+        assert_eq!(
+            "BEGIN:VCALENDAR\r\nEND:VCALENDAR\r\n",
+            res.into_string().unwrap()
+        );
+
+        let res = RocketResponse::typed_str(ContentType::Plain, "text");
+        assert_eq!("TypedStr", res.variant_name());
+        assert_eq!(ContentKind::Text, res.content_kind());
+        assert_eq!(
+            ContentKind::Binary,
+            RocketResponse::typed(ContentType::PNG, Vec::new()).content_kind()
+        );
+    }
+}
@@ -0,0 +1,308 @@
+use rocket::{
+    http::Header,
+    request::{self, FromRequest, Request},
+    response::{self, Responder},
+};
+use std::convert::Infallible;
+
+/// Scheme and host of the public URL of a request
+///
+/// Behind a reverse proxy the scheme and host of the internal listener differ from the
+/// ones used by the client. The context is taken from, in this order,
+///
+/// 1. the `public_url` of the Rocket config, like `public_url = "https://api.example.com"`
+/// 2. the `proto` and `host` of the first `Forwarded` element
+/// 3. the `X-Forwarded-Proto` and `X-Forwarded-Host` headers
+/// 4. the `Host` header
+/// 5. the address and port of the Rocket config
+///
+/// The scheme defaults to `http`. The forwarding headers can be set by any client, so
+/// without a proxy overwriting them `public_url` should be configured.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::{get, response::status::Created};
+/// use rocket_response::UrlContext;
+///
+/// #[get("/items/new")]
+/// fn new_item(url: UrlContext) -> Created<&'static str> {
+///     Created::new(url.absolute("/items/1"))
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UrlContext {
+    scheme: String,
+    host: String,
+}
+
+impl UrlContext {
+    /// Context of `scheme` and `host` with an optional port
+    pub fn new<S: Into<String>, H: Into<String>>(scheme: S, host: H) -> Self {
+        UrlContext {
+            scheme: scheme.into(),
+            host: host.into(),
+        }
+    }
+
+    /// Context of a URL like `https://api.example.com`, if it has a scheme and host
+    pub fn parse(url: &str) -> Option<Self> {
+        let (scheme, rest) = url.split_once("://")?;
+        let host = rest.split('/').next().unwrap_or("");
+        if scheme.is_empty() || host.is_empty() {
+            return None;
+        }
+        Some(UrlContext::new(scheme, host))
+    }
+
+    /// Context of the public URL of `req`
+    pub fn from_request(req: &Request<'_>) -> Self {
+        if let Some(context) = req
+            .rocket()
+            .figment()
+            .extract_inner::<String>("public_url")
+            .ok()
+            .and_then(|url| UrlContext::parse(&url))
+        {
+            return context;
+        }
+
+        let headers = req.headers();
+        let (mut scheme, mut host) = (None, None);
+        if let Some(forwarded) = headers.get_one("Forwarded") {
+            let first = forwarded.split(',').next().unwrap_or("");
+            for pair in first.split(';') {
+                let (key, value) = match pair.split_once('=') {
+                    Some(pair) => pair,
+                    None => continue,
+                };
+                let value = value.trim().trim_matches('"');
+                match key.trim().to_ascii_lowercase().as_str() {
+                    "proto" => scheme = Some(value.to_string()),
+                    "host" => host = Some(value.to_string()),
+                    _ => {}
+                }
+            }
+        }
+        let first_of = |name| {
+            headers
+                .get_one(name)
+                .and_then(|value| value.split(',').next())
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        let scheme = scheme
+            .or_else(|| first_of("X-Forwarded-Proto"))
+            .unwrap_or_else(|| "http".to_string());
+        let host = host
+            .or_else(|| first_of("X-Forwarded-Host"))
+            .or_else(|| first_of("Host"))
+            .unwrap_or_else(|| {
+                let config = req.rocket().config();
+                format!("{}:{}", config.address, config.port)
+            });
+
+        UrlContext { scheme, host }
+    }
+
+    /// Scheme like `https`
+    pub fn scheme(&self) -> &str {
+        &self.scheme
+    }
+
+    /// Host with an optional port like `api.example.com`
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// Absolute URL of `uri`
+    ///
+    /// An origin like `/items/1` is prefixed with the scheme and host, any other URI is
+    /// returned unchanged.
+    pub fn absolute(&self, uri: &str) -> String {
+        if uri.starts_with('/') && !uri.starts_with("//") {
+            format!("{}://{}{}", self.scheme, self.host, uri)
+        } else {
+            uri.to_string()
+        }
+    }
+
+    /// `Link` header value with the targets made absolute
+    fn absolute_links(&self, links: &str) -> String {
+        let mut value = String::with_capacity(links.len());
+        let mut rest = links;
+        while let Some(start) = rest.find('<') {
+            let end = match rest[start..].find('>') {
+                Some(end) => start + end,
+                None => break,
+            };
+            value.push_str(&rest[..=start]);
+            value.push_str(&self.absolute(&rest[start + 1..end]));
+            rest = &rest[end..];
+        }
+        value.push_str(rest);
+        value
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for UrlContext {
+    type Error = Infallible;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        request::Outcome::Success(UrlContext::from_request(req))
+    }
+}
+
+/// Wrapper making the `Location` and `Link` headers absolute
+///
+/// Relative targets like of [Created](rocket::response::status::Created),
+/// [Redirect](rocket::response::Redirect) or [Paginated](crate::Paginated) are prefixed
+/// with the public scheme and host of the [UrlContext] of the request.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::get;
+/// use rocket_response::{Absolute, RocketResponse};
+///
+/// #[get("/old")]
+/// fn old() -> RocketResponse {
+///     RocketResponse::Absolute(Box::new(Absolute::new(RocketResponse::redirect_to("/new"))))
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Absolute<R> {
+    responder: Box<R>,
+}
+
+impl<R> Absolute<R> {
+    /// Wraps `responder` to make its `Location` and `Link` headers absolute
+    pub fn new(responder: R) -> Self {
+        Absolute {
+            responder: Box::new(responder),
+        }
+    }
+
+    /// Consumes the wrapper and returns the wrapped responder.
+    pub fn into_inner(self) -> R {
+        *self.responder
+    }
+
+    pub(crate) fn responder(&self) -> &R {
+        &self.responder
+    }
+}
+
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for Absolute<R> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let mut res = self.responder.respond_to(req)?;
+        let context = UrlContext::from_request(req);
+
+        if let Some(location) = res.headers().get_one("Location") {
+            let location = context.absolute(location);
+            res.set_header(Header::new("Location", location));
+        }
+        let links: Vec<String> = res
+            .headers()
+            .get("Link")
+            .map(|links| context.absolute_links(links))
+            .collect();
+        if !links.is_empty() {
+            res.remove_header("Link");
+            for links in links {
+                res.adjoin_header(Header::new("Link", links));
+            }
+        }
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::{Absolute, UrlContext};
+    use crate::RocketResponse;
+    use rocket::{
+        figment::Figment,
+        get,
+        http::{Header, Status},
+        local::blocking::Client,
+        response::status::Created,
+        routes,
+    };
+
+    #[get("/created")]
+    fn route_created() -> RocketResponse {
+        RocketResponse::Absolute(Box::new(Absolute::new(RocketResponse::Created(
+            Created::new("/items/1"),
+        ))))
+    }
+
+    #[get("/context")]
+    fn route_context(url: UrlContext) -> String {
+        url.absolute("/x")
+    }
+
+    #[test]
+    fn test_url_context() {
+        let context = UrlContext::parse("https://api.example.com/base").unwrap();
+        assert_eq!("https", context.scheme());
+        assert_eq!("api.example.com", context.host());
+        assert_eq!("https://api.example.com/a", context.absolute("/a"));
+        assert_eq!(
+            "//cdn.example.com/a",
+            context.absolute("//cdn.example.com/a")
+        );
+        assert_eq!(
+            "<https://api.example.com/a?page=1>; rel=\"first\", <http://other/b>; rel=\"last\"",
+            context.absolute_links("</a?page=1>; rel=\"first\", <http://other/b>; rel=\"last\"")
+        );
+        assert_eq!(None, UrlContext::parse("/relative"));
+    }
+
+    #[test]
+    fn test_absolute() {
+        let rocket = rocket::build().mount("/", routes![route_created, route_context]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client
+            .get("/created")
+            .header(Header::new(
+                "Forwarded",
+                "for=1.2.3.4;proto=https;host=\"api.example.com\"",
+            ))
+            .dispatch();
+        assert_eq!(Status::Created, res.status());
+        assert_eq!(
+            Some("https://api.example.com/items/1"),
+            res.headers().get_one("Location")
+        );
+
+        let res = client
+            .get("/context")
+            .header(Header::new("X-Forwarded-Proto", "https"))
+            .header(Header::new(
+                "X-Forwarded-Host",
+                "proxy.example.com, internal",
+            ))
+            .dispatch();
+        assert_eq!("https://proxy.example.com/x", res.into_string().unwrap());
+
+        let res = client
+            .get("/context")
+            .header(Header::new("Host", "localhost:8000"))
+            .dispatch();
+        assert_eq!("http://localhost:8000/x", res.into_string().unwrap());
+
+        let figment = Figment::from(rocket::Config::debug_default())
+            .merge(("public_url", "https://public.example.com"));
+        let rocket = rocket::custom(figment).mount("/", routes![route_context]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+        let res = client
+            .get("/context")
+            .header(Header::new("X-Forwarded-Host", "proxy.example.com"))
+            .dispatch();
+        assert_eq!("https://public.example.com/x", res.into_string().unwrap());
+    }
+}
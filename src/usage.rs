@@ -0,0 +1,133 @@
+use rocket::{
+    http::{ContentType, Status},
+    request::Request,
+    response::{self, Responder, Response},
+};
+use std::{collections::HashMap, fmt::Write, io::Cursor, sync::Mutex};
+
+/// Collector of the variants responded by the enums
+///
+/// If the collector is managed by Rocket, every responded variant is counted, wrapper
+/// variants like [Cached](crate::Cached) and the variant they wrap alike. The report shows
+/// which variants a deployment actually uses, and [unused](Self::unused()) of `VARIANTS`
+/// hints at the features, which can be disabled. The report is served by [UsageReport].
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::{get, routes};
+/// use rocket_response::{UsageReport, UsageStats};
+///
+/// #[get("/usage")]
+/// fn usage() -> UsageReport {
+///     UsageReport
+/// }
+///
+/// let rocket = rocket::build()
+///     .manage(UsageStats::new())
+///     .mount("/debug", routes![usage]);
+/// ```
+#[derive(Debug, Default)]
+pub struct UsageStats {
+    counts: Mutex<HashMap<&'static str, u64>>,
+}
+
+impl UsageStats {
+    /// Collector without any count
+    pub fn new() -> Self {
+        UsageStats::default()
+    }
+
+    /// Counts a response of `variant`.
+    pub(crate) fn record(&self, variant: &'static str) {
+        let mut counts = self.counts.lock().unwrap_or_else(|e| e.into_inner());
+        *counts.entry(variant).or_insert(0) += 1;
+    }
+
+    /// Responded variants with their count, the most used first
+    pub fn report(&self) -> Vec<(&'static str, u64)> {
+        let counts = self.counts.lock().unwrap_or_else(|e| e.into_inner());
+        let mut report: Vec<_> = counts.iter().map(|(v, c)| (*v, *c)).collect();
+        report.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        report
+    }
+
+    /// Names of `variants`, like `RocketResponse::VARIANTS`, which never responded
+    pub fn unused(&self, variants: &[&'static str]) -> Vec<&'static str> {
+        let counts = self.counts.lock().unwrap_or_else(|e| e.into_inner());
+        variants
+            .iter()
+            .copied()
+            .filter(|variant| !counts.contains_key(variant))
+            .collect()
+    }
+}
+
+/// Plain text report of the managed [UsageStats]
+///
+/// Lists a line with variant and count for every responded variant, the most used first.
+/// Without managed [UsageStats] the response is `404 Not Found`, so the route of the report
+/// can stay mounted, while the collector is opt-in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UsageReport;
+
+impl<'r> Responder<'r, 'static> for UsageReport {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let stats = req.rocket().state::<UsageStats>().ok_or(Status::NotFound)?;
+        let mut body = String::new();
+        for (variant, count) in stats.report() {
+            let _ = writeln!(body, "{} {}", variant, count);
+        }
+        Response::build()
+            .header(ContentType::Plain)
+            .sized_body(body.len(), Cursor::new(body))
+            .ok()
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::{UsageReport, UsageStats};
+    use crate::{Cached, ETag, RocketResponse};
+    use rocket::{get, http::Status, local::blocking::Client, routes};
+
+    #[get("/used/<id>")]
+    fn route_used(id: usize) -> RocketResponse {
+        match id {
+            0 => RocketResponse::StaticStr("Hello world"),
+            _ => RocketResponse::Cached(
+                Cached::new(RocketResponse::StaticStr("cached")).etag(ETag::strong("v1")),
+            ),
+        }
+    }
+
+    #[get("/usage")]
+    fn route_usage() -> UsageReport {
+        UsageReport
+    }
+
+    #[test]
+    fn test_usage_stats() {
+        let rocket = rocket::build().mount("/", routes![route_usage]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+        assert_eq!(Status::NotFound, client.get("/usage").dispatch().status());
+
+        let rocket = rocket::build()
+            .manage(UsageStats::new())
+            .mount("/", routes![route_used, route_usage]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+        client.get("/used/0").dispatch();
+        client.get("/used/1").dispatch();
+
+        let res = client.get("/usage").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!("StaticStr 2\nCached 1\n", res.into_string().unwrap());
+
+        let stats = client.rocket().state::<UsageStats>().unwrap();
+        assert_eq!(vec![("StaticStr", 2), ("Cached", 1)], stats.report());
+        let unused = stats.unused(RocketResponse::VARIANTS);
+        assert!(unused.contains(&"Redirect"));
+        assert!(!unused.contains(&"Cached"));
+    }
+}
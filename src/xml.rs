@@ -0,0 +1,103 @@
+use crate::{body::BodyBuf, BuildError};
+use rocket::{
+    http::ContentType,
+    request::Request,
+    response::{self, Responder},
+    serde::Serialize,
+};
+
+/// Serializes the wrapped value into XML.
+///
+/// Returns a response with Content-Type `application/xml` and a fixed-size body with the
+/// serialized value. If serialization fails, it responds as [BuildError::Serialize].
+///
+/// ## Example usage
+///
+/// ```rust
+/// use rocket::{get, serde::Serialize};
+/// use rocket_response::SerdeXml;
+///
+/// #[derive(Serialize)]
+/// #[serde(crate = "rocket::serde")]
+/// struct Item {
+///     id: usize,
+/// }
+///
+/// #[get("/item/<id>")]
+/// fn item(id: usize) -> SerdeXml<Item> {
+///     SerdeXml(Item { id })
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SerdeXml<T>(pub T);
+
+impl<T> SerdeXml<T> {
+    /// Consumes the wrapper and returns the wrapped item.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for SerdeXml<T> {
+    fn from(value: T) -> Self {
+        SerdeXml(value)
+    }
+}
+
+impl<'r, T: Serialize> Responder<'r, 'static> for SerdeXml<T> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let mut buf = BodyBuf::take();
+        match quick_xml::se::to_writer(buf.as_mut_vec(), &self.0) {
+            Ok(()) => buf.respond_to(ContentType::new("application", "xml"), req),
+            Err(e) => BuildError::Serialize("XML".to_string(), e.to_string()).respond_to(req),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::SerdeXml;
+    use crate::RocketResponseGeneric;
+    use rocket::{get, http::Status, local::blocking::Client, routes, serde::Serialize};
+
+    #[derive(Serialize)]
+    #[serde(crate = "rocket::serde")]
+    struct Item {
+        id: usize,
+    }
+
+    #[get("/xml/<id>")]
+    fn route_xml(id: usize) -> RocketResponseGeneric<&'static str> {
+        match id {
+            0 => RocketResponseGeneric::Status(Status::NotFound),
+            _ => RocketResponseGeneric::SerdeXml(SerdeXml("hello")),
+        }
+    }
+
+    #[get("/item/<id>")]
+    fn route_item(id: usize) -> SerdeXml<Item> {
+        SerdeXml(Item { id })
+    }
+
+    #[test]
+    fn test_serde_xml() {
+        let rocket = rocket::build().mount("/", routes![route_xml, route_item]);
+        let client = Client::tracked(rocket).expect("no rocket instance");
+
+        let res = client.get("/item/7").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(
+            Some("application/xml"),
+            res.headers().get_one("Content-Type")
+        );
+        assert_eq!("<Item id=\"7\"/>", res.into_string().unwrap());
+
+        let res = client.get("/xml/1").dispatch();
+        assert_eq!(Status::Ok, res.status());
+        assert_eq!(
+            Some("application/xml"),
+            res.headers().get_one("Content-Type")
+        );
+    }
+}
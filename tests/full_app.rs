@@ -0,0 +1,176 @@
+//! End-to-end test of the example application across the features
+
+#[allow(dead_code, unused_imports)]
+#[path = "../examples/full_app.rs"]
+mod full_app;
+
+use rocket::{
+    http::{Accept, ContentType, Header, MediaType, Status},
+    local::blocking::Client,
+    serde::json::{serde_json, Value},
+};
+
+fn client() -> Client {
+    Client::tracked(full_app::app()).expect("no rocket instance")
+}
+
+#[test]
+fn test_content() {
+    let client = client();
+
+    let res = client.get("/").dispatch();
+    assert_eq!(Status::Ok, res.status());
+    assert_eq!(Some(ContentType::HTML), res.content_type());
+    assert_eq!("<h1>Notes</h1>", res.into_string().unwrap());
+
+    let res = client.get("/notes/3").dispatch();
+    assert_eq!(Status::Ok, res.status());
+    assert_eq!(Some(ContentType::HTML), res.content_type());
+    assert_eq!(
+        "<h1>Note 3</h1>\n<p>Note number 3</p>\n",
+        res.into_string().unwrap()
+    );
+
+    let res = client.get("/notes/99").dispatch();
+    assert_eq!(Status::NotFound, res.status());
+    assert_eq!("no such note", res.into_string().unwrap());
+
+    let res = client.get("/download").dispatch();
+    assert_eq!(Some(ContentType::Binary), res.content_type());
+    assert_eq!(Some(vec![0, 1, 2, 3]), res.into_bytes());
+
+    let res = client.get("/old").dispatch();
+    assert_eq!(Status::SeeOther, res.status());
+    assert_eq!(Some("/"), res.headers().get_one("Location"));
+
+    let res = client.get("/fail").dispatch();
+    assert_eq!(Status::BadRequest, res.status());
+
+    let res = client.get("/hooked").dispatch();
+    assert_eq!(Some(ContentType::Plain), res.content_type());
+    assert_eq!(Some("yes"), res.headers().get_one("X-Hooked"));
+}
+
+#[test]
+fn test_serde_formats() {
+    let client = client();
+    let expected = serde_json::to_value(full_app::notes()).unwrap();
+
+    let res = client.get("/notes").dispatch();
+    assert_eq!(Some(ContentType::JSON), res.content_type());
+    assert_eq!(Some(expected), res.into_json::<Value>());
+
+    let res = client.get("/notes.msgpack").dispatch();
+    assert_eq!(Some(ContentType::MsgPack), res.content_type());
+    assert_eq!(25, res.into_msgpack::<Vec<Value>>().unwrap().len());
+
+    let res = client.get("/notes/negotiated").dispatch();
+    assert_eq!(Some(ContentType::JSON), res.content_type());
+
+    let res = client
+        .get("/notes/negotiated")
+        .header(Accept::new(vec![MediaType::MsgPack.into()]))
+        .dispatch();
+    assert_eq!(Some(ContentType::MsgPack), res.content_type());
+    assert_eq!(25, res.into_msgpack::<Vec<Value>>().unwrap().len());
+}
+
+#[test]
+fn test_caching() {
+    let client = client();
+
+    let res = client.get("/hello").dispatch();
+    assert_eq!(Status::Ok, res.status());
+    assert_eq!(Some("\"v1\""), res.headers().get_one("ETag"));
+    assert_eq!(
+        Some("public, max-age=60"),
+        res.headers().get_one("Cache-Control")
+    );
+    assert_eq!(
+        Some("nosniff"),
+        res.headers().get_one("X-Content-Type-Options")
+    );
+    assert_eq!("Hello world", res.into_string().unwrap());
+
+    let res = client
+        .get("/hello")
+        .header(Header::new("If-None-Match", "\"v1\""))
+        .dispatch();
+    assert_eq!(Status::NotModified, res.status());
+
+    for _ in 0..2 {
+        let res = client.get("/cached").dispatch();
+        assert_eq!(Some(ContentType::Plain), res.content_type());
+        assert_eq!("rendered once", res.into_string().unwrap());
+    }
+
+    let res = client.get("/sync").dispatch();
+    let etag = res.headers().get_one("ETag").unwrap().to_string();
+    let body = res.into_json::<Value>().unwrap();
+    assert_eq!(Value::Bool(true), body["full"]);
+    assert_eq!(25, body["changed"].as_array().unwrap().len());
+
+    let res = client
+        .get("/sync")
+        .header(Header::new("If-None-Match", etag))
+        .dispatch();
+    assert_eq!(Status::NotModified, res.status());
+}
+
+#[test]
+fn test_pagination() {
+    let client = client();
+
+    let res = client.get("/export?page=3").dispatch();
+    assert_eq!(Some("25"), res.headers().get_one("X-Total-Count"));
+    let links = res.headers().get_one("Link").unwrap().to_string();
+    assert!(links.contains("</export?page=2&per_page=10>; rel=\"prev\""));
+    assert!(!links.contains("rel=\"next\""));
+    let page = res.into_json::<Vec<Value>>().unwrap();
+    assert_eq!(5, page.len());
+    assert_eq!(Value::from(21), page[0]["id"]);
+}
+
+#[test]
+fn test_compression() {
+    let client = client();
+
+    let res = client
+        .get("/report")
+        .header(Header::new("Accept-Encoding", "gzip"))
+        .dispatch();
+    assert_eq!(Some("gzip"), res.headers().get_one("Content-Encoding"));
+    assert_eq!(Some(ContentType::Plain), res.content_type());
+    let compressed = res.into_bytes().unwrap();
+
+    let res = client.get("/report").dispatch();
+    assert_eq!(None, res.headers().get_one("Content-Encoding"));
+    let plain = res.into_string().unwrap();
+    assert!(plain.starts_with("Note number 1\n"));
+    assert!(compressed.len() < plain.len());
+}
+
+#[test]
+fn test_health() {
+    let client = client();
+
+    let res = client.get("/health").dispatch();
+    assert_eq!(Status::Ok, res.status());
+    let body = res.into_json::<Value>().unwrap();
+    assert_eq!("up", body["status"]);
+    assert_eq!(25, body["checks"]["notes"]["count"]);
+    assert_eq!(0, body["checks"]["response_cache"]["hits"]);
+    assert_eq!("inactive", body["checks"]["maintenance"]["mode"]);
+    assert_eq!("up", body["checks"]["response_cache"]["status"]);
+
+    let res = client.post("/drain").dispatch();
+    assert_eq!(Status::Accepted, res.status());
+
+    let res = client.get("/health").dispatch();
+    assert_eq!(Status::ServiceUnavailable, res.status());
+    assert!(res.headers().contains("Retry-After"));
+    assert!(res.headers().contains("X-Drain-Deadline"));
+    let body = res.into_json::<Value>().unwrap();
+    assert_eq!("down", body["status"]);
+    assert_eq!("draining", body["checks"]["maintenance"]["mode"]);
+}